@@ -0,0 +1,51 @@
+//! Reading and writing XML documents, with JSON for comparison. The numbers
+//! for earlier versions of the XML backend are in DESIGN.md.
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use gedcomx::Gedcomx;
+
+// How many copies of the example documents go into the one that's read.
+const COPIES: usize = 100;
+
+// A document of 1.5 MB of XML, made from copies of the entities of the
+// example documents.
+fn document() -> Gedcomx {
+    let mut document = Gedcomx::default();
+    for _ in 0..COPIES {
+        for name in ["birth", "death", "marriage", "religious", "spec"] {
+            let xml = std::fs::read_to_string(format!("../data/{name}.xml")).unwrap();
+            let gx = Gedcomx::from_xml_str(&xml).unwrap();
+            document.persons.extend(gx.persons);
+            document.relationships.extend(gx.relationships);
+            document.source_descriptions.extend(gx.source_descriptions);
+            document.agents.extend(gx.agents);
+            document.events.extend(gx.events);
+            document.documents.extend(gx.documents);
+            document.places.extend(gx.places);
+        }
+    }
+    document
+}
+
+fn xml(c: &mut Criterion) {
+    let document = document();
+    let xml = document.to_xml_string().unwrap();
+    let json = document.to_json_string().unwrap();
+
+    let mut group = c.benchmark_group("xml");
+    group.throughput(Throughput::Bytes(xml.len() as u64));
+    group.bench_function("read", |b| b.iter(|| Gedcomx::from_xml_str(&xml).unwrap()));
+    group.bench_function("write", |b| b.iter(|| document.to_xml_string().unwrap()));
+    group.finish();
+
+    let mut group = c.benchmark_group("json");
+    group.throughput(Throughput::Bytes(json.len() as u64));
+    group.bench_function("read", |b| {
+        b.iter(|| Gedcomx::from_json_str(&json).unwrap());
+    });
+    group.bench_function("write", |b| b.iter(|| document.to_json_string().unwrap()));
+    group.finish();
+}
+
+criterion_group!(benches, xml);
+criterion_main!(benches);
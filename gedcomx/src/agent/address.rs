@@ -1,65 +1,70 @@
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
+
+use crate::Extensions;
 
 /// A street or postal address of a person or organization.
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, YaSerialize, YaDeserialize, Deserialize, PartialEq, Eq, Clone, Default,
-)]
-#[yaserde(
-    rename = "address",
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct Address {
     /// A full representation of the complete address.
-    #[yaserde(prefix = "gx")]
     pub value: Option<String>,
 
     /// The city.
-    #[yaserde(prefix = "gx")]
     pub city: Option<String>,
 
     /// The country.
-    #[yaserde(prefix = "gx")]
     pub country: Option<String>,
 
     /// The postal code.
-    #[yaserde(rename = "postalCode", prefix = "gx")]
     pub postal_code: Option<String>,
 
     /// The state or province.
-    #[yaserde(rename = "stateOrProvince", prefix = "gx")]
     pub state_or_province: Option<String>,
 
     /// The street.
-    #[yaserde(prefix = "gx")]
     pub street: Option<String>,
 
     /// The street (second line).
-    #[yaserde(prefix = "gx")]
     pub street2: Option<String>,
 
     /// The street (third line).
-    #[yaserde(prefix = "gx")]
     pub street3: Option<String>,
 
     /// The street (fourth line).
-    #[yaserde(prefix = "gx")]
     pub street4: Option<String>,
 
     /// The street (fifth line).
-    #[yaserde(prefix = "gx")]
     pub street5: Option<String>,
 
     /// The street (sixth line).
-    #[yaserde(prefix = "gx")]
     pub street6: Option<String>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Address: "address" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        elements {
+            value: gx "value",
+            city: gx "city",
+            country: gx "country",
+            postal_code: gx "postalCode",
+            state_or_province: gx "stateOrProvince",
+            street: gx "street",
+            street2: gx "street2",
+            street3: gx "street3",
+            street4: gx "street4",
+            street5: gx "street5",
+            street6: gx "street6",
+        }
+        @extensions
+    }
 }
 
 impl Address {
@@ -89,6 +94,7 @@ impl Address {
             street4,
             street5,
             street6,
+            extensions: Extensions::default(),
         }
     }
 
@@ -197,9 +203,9 @@ impl AddressBuilder {
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
-    use yaserde::ser::Config;
 
     use super::*;
+    use crate::xmlcodec::Config;
 
     #[test]
     fn builder() {
@@ -215,6 +221,7 @@ mod test {
             street4: Some("street4".to_string()),
             street5: Some("street5".to_string()),
             street6: Some("street6".to_string()),
+            extensions: Extensions::default(),
         };
 
         let address_2 = Address::builder()
@@ -265,6 +272,7 @@ mod test {
                 street4: Some("street4".to_string()),
                 street5: Some("street5".to_string()),
                 street6: Some("street6".to_string()),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -291,6 +299,7 @@ mod test {
             street4: Some("street4".to_string()),
             street5: Some("street5".to_string()),
             street6: Some("street6".to_string()),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&address).unwrap();
@@ -327,7 +336,7 @@ mod test {
             .state_or_province("California")
             .street("2299 Poplar Ave")
             .build();
-        let address: Address = yaserde::de::from_str(xml).unwrap();
+        let address: Address = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(address, expected_address);
     }
@@ -346,7 +355,7 @@ mod test {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&address, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&address, config).unwrap();
 
         assert_eq!(
             xml,
@@ -363,8 +372,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Address) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Address = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Address = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -3,35 +3,27 @@ use std::convert::TryInto;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::{Address, Id, Identifier, OnlineAccount, Person, ResourceReference, Result, TextValue};
+use crate::{
+    Address, Extensions, Id, Identifier, Link, OnlineAccount, Person, ResourceReference, Result,
+    TextValue,
+};
 
 /// Someone or something that curates genealogical data, such as a genealogical
 /// researcher, user of software, organization, or group.
 ///
 /// In genealogical research, an agent often takes the role of a contributor.
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Default, Clone, Eq,
-)]
-#[yaserde(
-    rename = "agent",
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone, Eq)]
 #[non_exhaustive]
 pub struct Agent {
     /// An identifier for the data structure holding the agent data.
     /// The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
     /// As such, the constraints of the id are provided in the definition of the
     /// media type (e.g. XML, JSON) of the data structure.
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// A list of identifiers for the agent.
-    #[yaserde(rename = "identifier", prefix = "gx")]
     #[serde(
         skip_serializing_if = "Vec::is_empty",
         default,
@@ -42,47 +34,72 @@ pub struct Agent {
     /// The name(s) of the person or organization. If more than one name is
     /// provided, names are assumed to be given in order of preference, with
     /// the most preferred name in the first position in the list.
-    #[yaserde(rename = "name", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub names: Vec<TextValue>,
 
     /// The homepage of the person or organization. Note this is different from
     /// the homepage of the service where the person or organization has an
     /// account.
-    #[yaserde(prefix = "gx")]
     pub homepage: Option<ResourceReference>,
 
     /// The [openid](https://openid.net) of the person or organization.
-    #[yaserde(prefix = "gx")]
     pub openid: Option<ResourceReference>,
 
     /// The online account(s) of the person or organization.
-    #[yaserde(rename = "account", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub accounts: Vec<OnlineAccount>,
 
     /// The email address(es) of the person or organization. If provided, MUST
     /// resolve to a valid e-mail address (e.g. "mailto:someone@gedcomx.org").
-    #[yaserde(rename = "email", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub emails: Vec<ResourceReference>,
 
     /// The phone(s) (voice, fax, mobile) of the person or organization. If
     /// provided, MUST resolve to a valid phone number (e.g.
     /// "tel:+1-201-555-0123").
-    #[yaserde(rename = "phone", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub phones: Vec<ResourceReference>,
 
     /// The address(es) of the person or organization.
-    #[yaserde(rename = "address", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub addresses: Vec<Address>,
 
     /// A reference to the person that describes this agent. MUST resolve to an
     /// instance of [Person](crate::Person).
-    #[yaserde(prefix = "gx")]
     pub person: Option<ResourceReference>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Agent: "agent" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+        }
+        elements {
+            identifiers: gx "identifier",
+            names: gx "name",
+            homepage: gx "homepage",
+            openid: gx "openid",
+            accounts: gx "account",
+            emails: gx "email",
+            phones: gx "phone",
+            addresses: gx "address",
+            person: gx "person",
+            links: gx "link",
+        }
+        @extensions
+    }
 }
 
 impl Agent {
@@ -110,6 +127,8 @@ impl Agent {
             phones,
             addresses,
             person,
+            links: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -200,8 +219,13 @@ impl AgentBuilder {
         Ok(self)
     }
 
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
     pub fn build(&self) -> Agent {
-        Agent::new(
+        let mut agent = Agent::new(
             self.0.id.clone(),
             self.0.identifiers.clone(),
             self.0.names.clone(),
@@ -212,17 +236,18 @@ impl AgentBuilder {
             self.0.phones.clone(),
             self.0.addresses.clone(),
             self.0.person.clone(),
-        )
+        );
+        agent.links.clone_from(&self.0.links);
+        agent
     }
 }
 
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
-    use yaserde::ser::Config;
 
     use super::*;
-    use crate::{GedcomxError, IdentifierType};
+    use crate::{GedcomxError, IdentifierType, xmlcodec::Config};
 
     #[test]
     fn builder() {
@@ -249,6 +274,8 @@ mod test {
             phones: vec!["tel:+1-201-555-0123".into()],
             addresses: vec![Address::builder().country("United States").build()],
             person: Some((&person).try_into().unwrap()),
+            links: vec![],
+            extensions: Extensions::default(),
         };
 
         let agent_2 = Agent::builder()
@@ -372,7 +399,7 @@ mod test {
             .person(&person)
             .unwrap()
             .build();
-        let agent: Agent = yaserde::de::from_str(xml).unwrap();
+        let agent: Agent = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(agent, expected_agent);
     }
@@ -406,7 +433,7 @@ mod test {
             write_document_declaration: false,
             ..Config::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&agent, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&agent, config).unwrap();
         let expected_xml = r##"<agent xmlns="http://gedcomx.org/v1/" id="local_id"><identifier type="http://gedcomx.org/Primary">primaryIdentifier</identifier><name>Ephraim Kunz</name><name xml:lang="es">Ephraim Kunz Spanish</name><homepage resource="www.ephraimkunz.com" /><openid resource="some_openid_value" /><account><serviceHomepage resource="http://familysearch.org/" /><accountName>Family Search Account</accountName></account><email resource="mailto:someone@gedcomx.org" /><email resource="mailto:someone2@gedcomx.org" /><phone resource="tel:+1-201-555-0123" /><address><country>United States</country></address><person resource="#P-1" /></agent>"##;
         assert_eq!(xml, expected_xml);
     }
@@ -420,8 +447,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Agent) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Agent = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Agent = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
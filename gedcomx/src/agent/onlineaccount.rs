@@ -1,30 +1,33 @@
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::ResourceReference;
+use crate::{Extensions, ResourceReference};
 
 /// A description of an account for an online service provider.
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
-#[yaserde(
-    rename = "account",
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct OnlineAccount {
     /// The homepage of the service that provides this account.
-    #[yaserde(rename = "serviceHomepage", prefix = "gx")]
     pub service_homepage: ResourceReference,
 
     /// The name, label, or id that uniquely identifies the account maintained
     /// by the online service provider.
-    #[yaserde(rename = "accountName", prefix = "gx")]
     pub account_name: String,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    OnlineAccount: "account" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        elements {
+            service_homepage: gx "serviceHomepage",
+            account_name: gx "accountName",
+        }
+        @extensions
+    }
 }
 
 impl OnlineAccount {
@@ -36,6 +39,7 @@ impl OnlineAccount {
         Self {
             service_homepage: service_homepage.into(),
             account_name: account_name.into(),
+            extensions: Extensions::default(),
         }
     }
 }
@@ -48,9 +52,8 @@ impl Arbitrary for OnlineAccount {
 
 #[cfg(test)]
 mod test {
-    use yaserde::ser::Config;
-
     use super::*;
+    use crate::xmlcodec::Config;
 
     #[test]
     fn json_deserialize() {
@@ -67,6 +70,7 @@ mod test {
             OnlineAccount {
                 service_homepage: ResourceReference::from("http://familysearch.org/"),
                 account_name: "Family Search Account".to_string(),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -76,6 +80,7 @@ mod test {
         let online_account = OnlineAccount {
             service_homepage: ResourceReference::from("http://familysearch.org/"),
             account_name: "Family Search Account".to_string(),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&online_account).unwrap();
@@ -91,12 +96,13 @@ mod test {
         let xml = r#"<account xmlns="http://gedcomx.org/v1/"><serviceHomepage resource="http://familysearch.org/"/>
         <accountName>Family Search Account</accountName></account>"#;
 
-        let online_account: OnlineAccount = yaserde::de::from_str(xml).unwrap();
+        let online_account: OnlineAccount = crate::xmlcodec::from_str(xml).unwrap();
         assert_eq!(
             online_account,
             OnlineAccount {
                 service_homepage: ResourceReference::from("http://familysearch.org/"),
                 account_name: "Family Search Account".to_string(),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -106,13 +112,14 @@ mod test {
         let online_account = OnlineAccount {
             service_homepage: ResourceReference::from("http://familysearch.org/"),
             account_name: "Family Search Account".to_string(),
+            extensions: Extensions::default(),
         };
 
         let config = Config {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&online_account, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&online_account, config).unwrap();
 
         assert_eq!(
             xml,
@@ -129,8 +136,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: OnlineAccount) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: OnlineAccount = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: OnlineAccount = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
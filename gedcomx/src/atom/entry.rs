@@ -0,0 +1,426 @@
+use quickcheck::{Arbitrary, Gen};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use super::Person;
+use crate::{Extensions, Gedcomx, Link, Timestamp, Uri};
+
+/// An entry of a [`Feed`](crate::atom::Feed), such as a single search result.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct Entry {
+    /// The authors of the entry.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub authors: Vec<Person>,
+
+    /// Categories that the entry belongs to.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub categories: Vec<Category>,
+
+    /// How confident the service is that the entry matches the request, from
+    /// 1 (least confident) to 5 (most confident).
+    pub confidence: Option<u32>,
+
+    /// The content of the entry.
+    pub content: Option<Content>,
+
+    /// The contributors to the entry.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub contributors: Vec<Person>,
+
+    /// A permanent, universally unique identifier for the entry.
+    pub id: Option<String>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// When the entry was first made available.
+    pub published: Option<Timestamp>,
+
+    /// Information about rights held in and over the entry.
+    pub rights: Option<String>,
+
+    /// The relevance of the entry to the request, relative to the other
+    /// entries of the feed.
+    pub score: Option<f32>,
+
+    /// A human-readable title for the entry.
+    pub title: Option<String>,
+
+    /// When the entry was last modified.
+    pub updated: Option<Timestamp>,
+
+    /// Data that isn't part of the Atom or GEDCOM X models.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Entry: "entry" in atom ["" = crate::xmlcodec::ATOM_NAMESPACE, "gx" = crate::GEDCOMX_NAMESPACE] {
+        elements {
+            authors: atom "author",
+            categories: atom "category",
+            confidence: gx "confidence",
+            content: atom "content",
+            contributors: atom "contributor",
+            id: atom "id",
+            links: atom "link",
+            published: atom "published",
+            rights: atom "rights",
+            score: gx "score",
+            title: atom "title",
+            updated: atom "updated",
+        }
+        @extensions
+    }
+}
+
+impl Entry {
+    pub fn new(
+        authors: Vec<Person>,
+        categories: Vec<Category>,
+        confidence: Option<u32>,
+        content: Option<Content>,
+        contributors: Vec<Person>,
+        id: Option<String>,
+        links: Vec<Link>,
+        published: Option<Timestamp>,
+        rights: Option<String>,
+        score: Option<f32>,
+        title: Option<String>,
+        updated: Option<Timestamp>,
+    ) -> Self {
+        Self {
+            authors,
+            categories,
+            confidence,
+            content,
+            contributors,
+            id,
+            links,
+            published,
+            rights,
+            score,
+            title,
+            updated,
+            extensions: Extensions::default(),
+        }
+    }
+
+    pub fn builder() -> EntryBuilder {
+        EntryBuilder::new()
+    }
+
+    /// The GEDCOM X document carried by the entry, if any.
+    pub fn gedcomx(&self) -> Option<&Gedcomx> {
+        self.content.as_ref()?.gedcomx.as_ref()
+    }
+}
+
+impl Arbitrary for Entry {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::builder()
+            .author(Person::arbitrary(g))
+            .category(Category::arbitrary(g))
+            .confidence(u32::arbitrary(g))
+            .content(Content::arbitrary(g))
+            .contributor(Person::arbitrary(g))
+            .id(crate::arbitrary_trimmed(g))
+            .link(Link::arbitrary(g))
+            .published(Timestamp::arbitrary(g))
+            .rights(crate::arbitrary_trimmed(g))
+            .score(f32::from(u8::arbitrary(g)))
+            .title(crate::arbitrary_trimmed(g))
+            .updated(Timestamp::arbitrary(g))
+            .build()
+    }
+}
+
+pub struct EntryBuilder(Entry);
+
+impl EntryBuilder {
+    pub(crate) fn new() -> Self {
+        Self(Entry::default())
+    }
+
+    pub fn author(&mut self, author: Person) -> &mut Self {
+        self.0.authors.push(author);
+        self
+    }
+
+    pub fn category(&mut self, category: Category) -> &mut Self {
+        self.0.categories.push(category);
+        self
+    }
+
+    pub fn confidence(&mut self, confidence: u32) -> &mut Self {
+        self.0.confidence = Some(confidence);
+        self
+    }
+
+    pub fn content(&mut self, content: Content) -> &mut Self {
+        self.0.content = Some(content);
+        self
+    }
+
+    pub fn contributor(&mut self, contributor: Person) -> &mut Self {
+        self.0.contributors.push(contributor);
+        self
+    }
+
+    pub fn id<I: Into<String>>(&mut self, id: I) -> &mut Self {
+        self.0.id = Some(id.into());
+        self
+    }
+
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
+    pub fn published(&mut self, published: Timestamp) -> &mut Self {
+        self.0.published = Some(published);
+        self
+    }
+
+    pub fn rights<I: Into<String>>(&mut self, rights: I) -> &mut Self {
+        self.0.rights = Some(rights.into());
+        self
+    }
+
+    pub fn score(&mut self, score: f32) -> &mut Self {
+        self.0.score = Some(score);
+        self
+    }
+
+    pub fn title<I: Into<String>>(&mut self, title: I) -> &mut Self {
+        self.0.title = Some(title.into());
+        self
+    }
+
+    pub fn updated(&mut self, updated: Timestamp) -> &mut Self {
+        self.0.updated = Some(updated);
+        self
+    }
+
+    pub fn build(&self) -> Entry {
+        Entry::new(
+            self.0.authors.clone(),
+            self.0.categories.clone(),
+            self.0.confidence,
+            self.0.content.clone(),
+            self.0.contributors.clone(),
+            self.0.id.clone(),
+            self.0.links.clone(),
+            self.0.published.clone(),
+            self.0.rights.clone(),
+            self.0.score,
+            self.0.title.clone(),
+            self.0.updated.clone(),
+        )
+    }
+}
+
+/// The content of an [`Entry`].
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct Content {
+    /// The media type of the content, such as
+    /// `application/x-gedcomx-v1+xml`.
+    #[serde(rename = "type")]
+    pub content_type: Option<String>,
+
+    /// The GEDCOM X document carried by the entry.
+    pub gedcomx: Option<Gedcomx>,
+}
+
+impl_xml! {
+    Content: "Content" in atom ["" = crate::xmlcodec::ATOM_NAMESPACE, "gx" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            content_type: "type",
+        }
+        elements {
+            gedcomx: gx "gedcomx",
+        }
+    }
+}
+
+impl Content {
+    pub fn new(content_type: Option<String>, gedcomx: Option<Gedcomx>) -> Self {
+        Self {
+            content_type,
+            gedcomx,
+        }
+    }
+}
+
+impl From<Gedcomx> for Content {
+    fn from(gedcomx: Gedcomx) -> Self {
+        Self::new(None, Some(gedcomx))
+    }
+}
+
+impl Arbitrary for Content {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(
+            Some(crate::arbitrary_trimmed(g)),
+            Some(Gedcomx::arbitrary(g)),
+        )
+    }
+}
+
+/// A category that an [`Entry`] belongs to.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
+#[non_exhaustive]
+pub struct Category {
+    /// The scheme that the term belongs to.
+    pub scheme: Option<Uri>,
+
+    /// The category.
+    pub term: Option<String>,
+
+    /// A human-readable label for the category.
+    pub label: Option<String>,
+}
+
+impl_xml! {
+    Category: "Category" in atom ["" = crate::xmlcodec::ATOM_NAMESPACE] {
+        attributes {
+            scheme: "scheme",
+            term: "term",
+            label: "label",
+        }
+    }
+}
+
+impl Category {
+    pub fn new<I: Into<String>>(term: I, scheme: Option<Uri>, label: Option<String>) -> Self {
+        Self {
+            scheme,
+            term: Some(term.into()),
+            label,
+        }
+    }
+}
+
+impl Arbitrary for Category {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(
+            crate::arbitrary_trimmed(g),
+            Some(Uri::arbitrary(g)),
+            Some(crate::arbitrary_trimmed(g)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{Person as GedcomxPerson, xmlcodec::Config};
+
+    fn entry() -> Entry {
+        let person = GedcomxPerson::builder().id("P-1").build();
+        Entry::builder()
+            .id("P-1")
+            .title("Person P-1")
+            .confidence(4)
+            .score(2.5)
+            .link(Link::new("person", "https://example.org/persons/P-1"))
+            .category(Category::new(
+                "person",
+                Some("http://gedcomx.org/".into()),
+                None,
+            ))
+            .content(Content::new(
+                Some("application/x-gedcomx-v1+xml".into()),
+                Some(Gedcomx::builder().person(person).build()),
+            ))
+            .build()
+    }
+
+    #[test]
+    fn json_deserialize() {
+        let json = r#"{
+            "categories" : [ { "scheme" : "http://gedcomx.org/", "term" : "person" } ],
+            "confidence" : 4,
+            "content" : {
+                "type" : "application/x-gedcomx-v1+xml",
+                "gedcomx" : { "persons" : [ { "id" : "P-1" } ] }
+            },
+            "id" : "P-1",
+            "links" : { "person" : { "href" : "https://example.org/persons/P-1" } },
+            "score" : 2.5,
+            "title" : "Person P-1"
+        }"#;
+
+        let entry: Entry = serde_json::from_str(json).unwrap();
+        assert_eq!(entry, self::entry());
+        assert_eq!(entry.gedcomx().unwrap().persons.len(), 1);
+    }
+
+    #[test]
+    fn json_serialize() {
+        let json = serde_json::to_string(&entry()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"categories":[{"scheme":"http://gedcomx.org/","term":"person"}],"confidence":4,"content":{"type":"application/x-gedcomx-v1+xml","gedcomx":{"persons":[{"id":"P-1"}]}},"id":"P-1","links":{"person":{"href":"https://example.org/persons/P-1"}},"score":2.5,"title":"Person P-1"}"#
+        );
+    }
+
+    #[test]
+    fn xml_deserialize() {
+        let xml = r#"<entry xmlns="http://www.w3.org/2005/Atom" xmlns:gx="http://gedcomx.org/v1/">
+            <category scheme="http://gedcomx.org/" term="person" />
+            <gx:confidence>4</gx:confidence>
+            <content type="application/x-gedcomx-v1+xml">
+                <gx:gedcomx>
+                    <gx:person id="P-1" />
+                </gx:gedcomx>
+            </content>
+            <id>P-1</id>
+            <link rel="person" href="https://example.org/persons/P-1" />
+            <gx:score>2.5</gx:score>
+            <title>Person P-1</title>
+        </entry>"#;
+
+        let entry: Entry = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(entry, self::entry());
+    }
+
+    #[test]
+    fn xml_serialize() {
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        let xml = crate::xmlcodec::to_string(&entry(), config).unwrap();
+        assert_eq!(
+            xml,
+            r#"<entry xmlns="http://www.w3.org/2005/Atom" xmlns:gx="http://gedcomx.org/v1/"><category scheme="http://gedcomx.org/" term="person" /><gx:confidence>4</gx:confidence><content type="application/x-gedcomx-v1+xml"><gx:gedcomx xmlns="http://gedcomx.org/v1/"><person id="P-1" /></gx:gedcomx></content><id>P-1</id><link rel="person" href="https://example.org/persons/P-1" /><gx:score>2.5</gx:score><title>Person P-1</title></entry>"#
+        );
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_json(input: Entry) -> bool {
+        let json = serde_json::to_string(&input).unwrap();
+        let from_json: Entry = serde_json::from_str(&json).unwrap();
+        input == from_json
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_xml(input: Entry) -> bool {
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Entry = crate::xmlcodec::from_str(&xml).unwrap();
+        input == from_xml
+    }
+}
@@ -0,0 +1,463 @@
+use quickcheck::{Arbitrary, Gen};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use super::{Entry, Person};
+use crate::{
+    Extensions, GedcomxError, Link, Result, Timestamp, Uri, tracking,
+    xmlcodec::{self, Config},
+};
+
+/// A list of resources, such as a page of search results, as defined by the
+/// [GEDCOM X Atom Extensions](https://github.com/FamilySearch/gedcomx-rs/blob/master/specifications/atom-model-specification.md).
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct Feed {
+    /// The authors of the feed.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub authors: Vec<Person>,
+
+    /// The contributors to the feed.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub contributors: Vec<Person>,
+
+    /// The agent used to generate the feed.
+    pub generator: Option<Generator>,
+
+    /// An image that provides iconic visual identification for the feed.
+    pub icon: Option<String>,
+
+    /// A permanent, universally unique identifier for the feed.
+    pub id: Option<String>,
+
+    /// The total number of results available, if the feed is a page of
+    /// results.
+    pub results: Option<u32>,
+
+    /// The index of the first entry of the feed in the full list of results,
+    /// if the feed is a page of results.
+    pub index: Option<u32>,
+
+    /// Hypermedia links to related resources, such as the `next` and
+    /// `previous` pages of results.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// An image that provides visual identification for the feed.
+    pub logo: Option<String>,
+
+    /// Information about rights held in and over the feed.
+    pub rights: Option<String>,
+
+    /// A human-readable description or subtitle for the feed.
+    pub subtitle: Option<String>,
+
+    /// A human-readable title for the feed.
+    pub title: Option<String>,
+
+    /// When the feed was last modified.
+    pub updated: Option<Timestamp>,
+
+    /// The entries of the feed.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub entries: Vec<Entry>,
+
+    /// Data that isn't part of the Atom or GEDCOM X models.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Feed: "feed" in atom ["" = crate::xmlcodec::ATOM_NAMESPACE, "gx" = crate::GEDCOMX_NAMESPACE] {
+        elements {
+            authors: atom "author",
+            contributors: atom "contributor",
+            generator: atom "generator",
+            icon: atom "icon",
+            id: atom "id",
+            results: gx "results",
+            index: gx "index",
+            links: atom "link",
+            logo: atom "logo",
+            rights: atom "rights",
+            subtitle: atom "subtitle",
+            title: atom "title",
+            updated: atom "updated",
+            entries: atom "entry",
+        }
+        @extensions
+    }
+}
+
+impl Feed {
+    pub fn new(
+        authors: Vec<Person>,
+        contributors: Vec<Person>,
+        generator: Option<Generator>,
+        icon: Option<String>,
+        id: Option<String>,
+        results: Option<u32>,
+        index: Option<u32>,
+        links: Vec<Link>,
+        logo: Option<String>,
+        rights: Option<String>,
+        subtitle: Option<String>,
+        title: Option<String>,
+        updated: Option<Timestamp>,
+        entries: Vec<Entry>,
+    ) -> Self {
+        Self {
+            authors,
+            contributors,
+            generator,
+            icon,
+            id,
+            results,
+            index,
+            links,
+            logo,
+            rights,
+            subtitle,
+            title,
+            updated,
+            entries,
+            extensions: Extensions::default(),
+        }
+    }
+
+    pub fn builder() -> FeedBuilder {
+        FeedBuilder::new()
+    }
+
+    /// The first link of the feed with the given relation, such as `next`.
+    pub fn link(&self, rel: &str) -> Option<&Link> {
+        self.links.iter().find(|l| l.rel.as_deref() == Some(rel))
+    }
+
+    /// Serialize the instance as a string of JSON.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if serialization fails.
+    pub fn to_json_string(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(GedcomxError::JSONError)
+    }
+
+    /// Deserialize an instance of the type from a string of JSON text.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails.
+    pub fn from_json_str(s: &str) -> Result<Self> {
+        tracking::from_json_slice(s.as_bytes())
+    }
+
+    /// Deserialize an instance of the type from an IO stream of JSON.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails, and
+    /// `GedcomxError::IoError` if reading fails.
+    pub fn from_json_reader<R: std::io::Read>(rdr: R) -> Result<Self> {
+        tracking::from_json_reader(rdr)
+    }
+
+    /// Serialize the instance as a string of XML.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::XMLSerializeError` if serialization fails.
+    pub fn to_xml_string(&self) -> Result<String> {
+        xmlcodec::to_string(self, Config::default())
+    }
+
+    /// Deserialize an instance of the type from a string of XML text.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails.
+    pub fn from_xml_str(s: &str) -> Result<Self> {
+        xmlcodec::from_str(s)
+    }
+
+    /// Deserialize an instance of the type from an IO stream of XML.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails, and
+    /// `GedcomxError::IoError` if reading fails.
+    pub fn from_xml_reader<R: std::io::Read>(rdr: R) -> Result<Self> {
+        xmlcodec::from_reader(rdr)
+    }
+}
+
+impl Arbitrary for Feed {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::builder()
+            .author(Person::arbitrary(g))
+            .contributor(Person::arbitrary(g))
+            .generator(Generator::arbitrary(g))
+            .icon(crate::arbitrary_trimmed(g))
+            .id(crate::arbitrary_trimmed(g))
+            .results(u32::arbitrary(g))
+            .index(u32::arbitrary(g))
+            .link(Link::arbitrary(g))
+            .logo(crate::arbitrary_trimmed(g))
+            .rights(crate::arbitrary_trimmed(g))
+            .subtitle(crate::arbitrary_trimmed(g))
+            .title(crate::arbitrary_trimmed(g))
+            .updated(Timestamp::arbitrary(g))
+            .entry(Entry::arbitrary(g))
+            .build()
+    }
+}
+
+pub struct FeedBuilder(Feed);
+
+impl FeedBuilder {
+    pub(crate) fn new() -> Self {
+        Self(Feed::default())
+    }
+
+    pub fn author(&mut self, author: Person) -> &mut Self {
+        self.0.authors.push(author);
+        self
+    }
+
+    pub fn contributor(&mut self, contributor: Person) -> &mut Self {
+        self.0.contributors.push(contributor);
+        self
+    }
+
+    pub fn generator(&mut self, generator: Generator) -> &mut Self {
+        self.0.generator = Some(generator);
+        self
+    }
+
+    pub fn icon<I: Into<String>>(&mut self, icon: I) -> &mut Self {
+        self.0.icon = Some(icon.into());
+        self
+    }
+
+    pub fn id<I: Into<String>>(&mut self, id: I) -> &mut Self {
+        self.0.id = Some(id.into());
+        self
+    }
+
+    pub fn results(&mut self, results: u32) -> &mut Self {
+        self.0.results = Some(results);
+        self
+    }
+
+    pub fn index(&mut self, index: u32) -> &mut Self {
+        self.0.index = Some(index);
+        self
+    }
+
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
+    pub fn logo<I: Into<String>>(&mut self, logo: I) -> &mut Self {
+        self.0.logo = Some(logo.into());
+        self
+    }
+
+    pub fn rights<I: Into<String>>(&mut self, rights: I) -> &mut Self {
+        self.0.rights = Some(rights.into());
+        self
+    }
+
+    pub fn subtitle<I: Into<String>>(&mut self, subtitle: I) -> &mut Self {
+        self.0.subtitle = Some(subtitle.into());
+        self
+    }
+
+    pub fn title<I: Into<String>>(&mut self, title: I) -> &mut Self {
+        self.0.title = Some(title.into());
+        self
+    }
+
+    pub fn updated(&mut self, updated: Timestamp) -> &mut Self {
+        self.0.updated = Some(updated);
+        self
+    }
+
+    pub fn entry(&mut self, entry: Entry) -> &mut Self {
+        self.0.entries.push(entry);
+        self
+    }
+
+    pub fn build(&self) -> Feed {
+        Feed::new(
+            self.0.authors.clone(),
+            self.0.contributors.clone(),
+            self.0.generator.clone(),
+            self.0.icon.clone(),
+            self.0.id.clone(),
+            self.0.results,
+            self.0.index,
+            self.0.links.clone(),
+            self.0.logo.clone(),
+            self.0.rights.clone(),
+            self.0.subtitle.clone(),
+            self.0.title.clone(),
+            self.0.updated.clone(),
+            self.0.entries.clone(),
+        )
+    }
+}
+
+/// The agent used to generate a [`Feed`].
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
+#[non_exhaustive]
+pub struct Generator {
+    /// A URI for the generator.
+    pub uri: Option<Uri>,
+
+    /// The version of the generator.
+    pub version: Option<String>,
+
+    /// The name of the generator.
+    pub value: Option<String>,
+}
+
+impl_xml! {
+    Generator: "Generator" in atom ["" = crate::xmlcodec::ATOM_NAMESPACE] {
+        attributes {
+            uri: "uri",
+            version: "version",
+        }
+        text value
+    }
+}
+
+impl Generator {
+    pub fn new<I: Into<String>>(value: I, uri: Option<Uri>, version: Option<String>) -> Self {
+        Self {
+            uri,
+            version,
+            value: Some(value.into()),
+        }
+    }
+}
+
+impl Arbitrary for Generator {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(
+            crate::arbitrary_trimmed(g),
+            Some(Uri::arbitrary(g)),
+            Some(crate::arbitrary_trimmed(g)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn feed() -> Feed {
+        Feed::builder()
+            .id("search-results")
+            .title("Search Results")
+            .generator(Generator::new(
+                "Example",
+                Some("https://example.org".into()),
+                Some("1.0".into()),
+            ))
+            .results(65)
+            .index(20)
+            .link(Link::new(
+                "self",
+                "https://example.org/search?q=surname:Kunz&start=20",
+            ))
+            .link(Link::new(
+                "next",
+                "https://example.org/search?q=surname:Kunz&start=40",
+            ))
+            .entry(Entry::builder().id("P-1").score(2.5).build())
+            .build()
+    }
+
+    #[test]
+    fn json_deserialize() {
+        let json = r#"{
+            "generator" : { "uri" : "https://example.org", "version" : "1.0", "value" : "Example" },
+            "id" : "search-results",
+            "results" : 65,
+            "index" : 20,
+            "links" : {
+                "self" : { "href" : "https://example.org/search?q=surname:Kunz&start=20" },
+                "next" : { "href" : "https://example.org/search?q=surname:Kunz&start=40" }
+            },
+            "title" : "Search Results",
+            "entries" : [ { "id" : "P-1", "score" : 2.5 } ]
+        }"#;
+
+        let feed = Feed::from_json_str(json).unwrap();
+        assert_eq!(feed, self::feed());
+        assert_eq!(
+            feed.link("next").unwrap().href,
+            Some("https://example.org/search?q=surname:Kunz&start=40".into())
+        );
+    }
+
+    #[test]
+    fn json_serialize() {
+        let json = feed().to_json_string().unwrap();
+        assert_eq!(
+            json,
+            r#"{"generator":{"uri":"https://example.org","version":"1.0","value":"Example"},"id":"search-results","results":65,"index":20,"links":{"self":{"href":"https://example.org/search?q=surname:Kunz&start=20"},"next":{"href":"https://example.org/search?q=surname:Kunz&start=40"}},"title":"Search Results","entries":[{"id":"P-1","score":2.5}]}"#
+        );
+    }
+
+    #[test]
+    fn xml_deserialize() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:gx="http://gedcomx.org/v1/">
+            <generator uri="https://example.org" version="1.0">Example</generator>
+            <id>search-results</id>
+            <gx:results>65</gx:results>
+            <gx:index>20</gx:index>
+            <link rel="self" href="https://example.org/search?q=surname:Kunz&amp;start=20" />
+            <link rel="next" href="https://example.org/search?q=surname:Kunz&amp;start=40" />
+            <title>Search Results</title>
+            <entry>
+                <id>P-1</id>
+                <gx:score>2.5</gx:score>
+            </entry>
+        </feed>"#;
+
+        let feed = Feed::from_xml_str(xml).unwrap();
+        assert_eq!(feed, self::feed());
+    }
+
+    #[test]
+    fn xml_serialize() {
+        let config = crate::xmlcodec::Config {
+            write_document_declaration: false,
+            ..crate::xmlcodec::Config::default()
+        };
+        let xml = crate::xmlcodec::to_string(&feed(), config).unwrap();
+        assert_eq!(
+            xml,
+            r#"<feed xmlns="http://www.w3.org/2005/Atom" xmlns:gx="http://gedcomx.org/v1/"><generator uri="https://example.org" version="1.0">Example</generator><id>search-results</id><gx:results>65</gx:results><gx:index>20</gx:index><link rel="self" href="https://example.org/search?q=surname:Kunz&amp;start=20" /><link rel="next" href="https://example.org/search?q=surname:Kunz&amp;start=40" /><title>Search Results</title><entry><id>P-1</id><gx:score>2.5</gx:score></entry></feed>"#
+        );
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_json(input: Feed) -> bool {
+        let json = serde_json::to_string(&input).unwrap();
+        let from_json: Feed = serde_json::from_str(&json).unwrap();
+        input == from_json
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_xml(input: Feed) -> bool {
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Feed = crate::xmlcodec::from_str(&xml).unwrap();
+        input == from_xml
+    }
+}
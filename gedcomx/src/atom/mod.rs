@@ -0,0 +1,20 @@
+//! Models for the [GEDCOM X Atom Extensions](https://github.com/FamilySearch/gedcomx-rs/blob/master/specifications/atom-model-specification.md).
+//!
+//! Web services that implement [GEDCOM X RS](https://github.com/FamilySearch/gedcomx-rs/blob/master/specifications/rs-specification.md)
+//! use Atom feeds to return lists of resources, such as a page of search
+//! results. Each [`Entry`] of a [`Feed`] carries its resource as a
+//! [`Gedcomx`](crate::Gedcomx) document in its [`Content`], and the feed's
+//! [`Link`](crate::Link)s point to the other pages of results.
+//!
+//! In XML the feed is in the `http://www.w3.org/2005/Atom` namespace, with the
+//! GEDCOM X extensions in the `http://gedcomx.org/v1/` namespace. In JSON it
+//! follows the [GEDCOM X Atom JSON Media Type](https://github.com/FamilySearch/gedcomx-rs/blob/master/specifications/atom-json-media-type-specification.md).
+
+mod entry;
+pub use entry::{Category, Content, Entry, EntryBuilder};
+
+mod feed;
+pub use feed::{Feed, FeedBuilder, Generator};
+
+mod person;
+pub use person::Person;
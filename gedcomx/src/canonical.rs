@@ -0,0 +1,286 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    Entity, Gedcomx, GedcomxError, OwnedEntity, Result,
+    xmlcodec::{self, Config},
+};
+
+impl Gedcomx {
+    /// Serialize the instance as canonical JSON.
+    ///
+    /// Canonical JSON is pretty-printed, the members of every object are
+    /// sorted by name, and numbers with no fractional part are written as
+    /// integers, so documents with the same content always serialize to the
+    /// same string. This makes it suitable for hashing and for storing in
+    /// version control.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if serialization fails.
+    pub fn to_canonical_json_string(&self) -> Result<String> {
+        to_canonical_json(self)
+    }
+
+    /// Serialize the instance as canonical XML.
+    ///
+    /// Canonical XML is pretty-printed and writes timestamps in UTC. Elements
+    /// and attributes are always written in the same order, and identifiers
+    /// are sorted by type and then value, so documents with the same content
+    /// always serialize to the same string.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::XMLSerializeError` if serialization fails.
+    pub fn to_canonical_xml_string(&self) -> Result<String> {
+        let config = Config {
+            perform_indent: true,
+            canonical: true,
+            ..Config::default()
+        };
+        xmlcodec::to_string(self, config)
+    }
+
+    /// A hex-encoded SHA-256 hash of the canonical JSON of the document.
+    ///
+    /// Documents with the same content have the same hash no matter how they
+    /// were read or built.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if serialization fails.
+    pub fn content_hash(&self) -> Result<String> {
+        Ok(sha256_hex(self.to_canonical_json_string()?.as_bytes()))
+    }
+}
+
+impl Entity<'_> {
+    /// A hex-encoded SHA-256 hash of the kind and canonical JSON of the
+    /// entity.
+    ///
+    /// Entities of different kinds never share a hash, even if their JSON is
+    /// the same.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if serialization fails.
+    pub fn content_hash(&self) -> Result<String> {
+        let json = match self {
+            Self::Person(e) => to_canonical_json(e),
+            Self::Relationship(e) => to_canonical_json(e),
+            Self::SourceDescription(e) => to_canonical_json(e),
+            Self::Agent(e) => to_canonical_json(e),
+            Self::Event(e) => to_canonical_json(e),
+            Self::Document(e) => to_canonical_json(e),
+            Self::PlaceDescription(e) => to_canonical_json(e),
+            Self::Group(e) => to_canonical_json(e),
+        }?;
+        Ok(sha256_hex(format!("{}\n{json}", self.kind()).as_bytes()))
+    }
+}
+
+impl OwnedEntity {
+    /// A hex-encoded SHA-256 hash of the kind and canonical JSON of the
+    /// entity. The same as the hash of the borrowed [`Entity`].
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if serialization fails.
+    pub fn content_hash(&self) -> Result<String> {
+        self.as_entity().content_hash()
+    }
+}
+
+fn to_canonical_json<T: Serialize>(value: &T) -> Result<String> {
+    let value = serde_json::to_value(value).map_err(GedcomxError::JSONError)?;
+    serde_json::to_string_pretty(&canonicalize(value)).map_err(GedcomxError::JSONError)
+}
+
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut members: Vec<_> = map.into_iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(
+                members
+                    .into_iter()
+                    .map(|(k, v)| (k, canonicalize(v)))
+                    .collect::<Map<_, _>>(),
+            )
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(canonicalize).collect()),
+        Value::Number(n) => Value::Number(canonical_number(n)),
+        v => v,
+    }
+}
+
+// Floats that are whole numbers small enough to be exact are written as
+// integers, so 1.0 and 1 (and -0.0 and 0) are the same.
+#[allow(clippy::float_cmp, clippy::cast_possible_truncation)]
+fn canonical_number(n: Number) -> Number {
+    const MAX_EXACT: f64 = 9_007_199_254_740_992.0;
+
+    match n.as_f64() {
+        Some(f) if n.is_f64() && f.trunc() == f && f.abs() < MAX_EXACT => Number::from(f as i64),
+        _ => n,
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(64);
+    for byte in Sha256::digest(bytes) {
+        let _ = write!(hex, "{byte:02x}");
+    }
+    hex
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{Agent, Identifier, IdentifierType, Person, Timestamp};
+
+    #[test]
+    fn canonical_json_sorts_members_and_normalizes_numbers() {
+        let value = serde_json::json!({
+            "z": [1.0, -0.0, 1.5],
+            "a": { "y": 2, "b": true },
+        });
+
+        assert_eq!(
+            serde_json::to_string(&canonicalize(value)).unwrap(),
+            r#"{"a":{"b":true,"y":2},"z":[1,0,1.5]}"#
+        );
+    }
+
+    #[test]
+    fn same_content_has_same_hash() {
+        let json = r#"{
+            "persons": [{ "id": "P-1", "private": true }],
+            "agents": [{ "id": "A-1" }]
+        }"#;
+        let reordered = r#"{
+            "agents": [{ "id": "A-1" }],
+            "persons": [{ "private": true, "id": "P-1" }]
+        }"#;
+
+        let gx = Gedcomx::from_json_str(json).unwrap();
+        let reordered = Gedcomx::from_json_str(reordered).unwrap();
+
+        assert_eq!(
+            gx.to_canonical_json_string().unwrap(),
+            reordered.to_canonical_json_string().unwrap()
+        );
+        assert_eq!(
+            gx.content_hash().unwrap(),
+            reordered.content_hash().unwrap()
+        );
+        assert_eq!(gx.content_hash().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn changed_content_has_different_hash() {
+        let gx = Gedcomx::builder()
+            .person(Person::builder().id("P-1").build())
+            .build();
+        let changed = Gedcomx::builder()
+            .person(Person::builder().id("P-2").build())
+            .build();
+
+        assert_ne!(gx.content_hash().unwrap(), changed.content_hash().unwrap());
+    }
+
+    #[test]
+    fn entity_hash_includes_kind() {
+        let person = Person::builder().id("1").build();
+        let agent = Agent::builder().id("1").build();
+
+        assert_eq!(
+            to_canonical_json(&person).unwrap(),
+            to_canonical_json(&agent).unwrap()
+        );
+        assert_ne!(
+            Entity::Person(&person).content_hash().unwrap(),
+            Entity::Agent(&agent).content_hash().unwrap()
+        );
+        assert_eq!(
+            OwnedEntity::Person(person.clone()).content_hash().unwrap(),
+            Entity::Person(&person).content_hash().unwrap()
+        );
+    }
+
+    #[test]
+    fn canonical_xml_writes_timestamps_in_utc() {
+        let xml = r#"<gedcomx xmlns="http://gedcomx.org/v1/">
+            <attribution>
+                <modified>2014-03-07T07:00:00</modified>
+            </attribution>
+        </gedcomx>"#;
+
+        let gx = Gedcomx::from_xml_str(xml).unwrap();
+        let canonical = gx.to_canonical_xml_string().unwrap();
+
+        assert!(canonical.contains("<modified>2014-03-07T07:00:00Z</modified>"));
+        assert!(
+            gx.to_xml_string()
+                .unwrap()
+                .contains("<modified>2014-03-07T07:00:00</modified>")
+        );
+        assert_eq!(
+            Gedcomx::from_xml_str(&canonical)
+                .unwrap()
+                .to_canonical_xml_string()
+                .unwrap(),
+            canonical
+        );
+    }
+
+    #[test]
+    fn timestamp_with_offset_is_canonical_in_utc() {
+        let timestamp: Timestamp = "2014-03-07T09:00:00+02:00".parse().unwrap();
+        let gx = Gedcomx::builder()
+            .attribution(crate::Attribution::builder().modified(timestamp).build())
+            .build();
+
+        assert!(
+            gx.to_canonical_xml_string()
+                .unwrap()
+                .contains("<modified>2014-03-07T07:00:00Z</modified>")
+        );
+    }
+
+    #[test]
+    fn canonical_xml_sorts_identifiers() {
+        let identifiers = vec![
+            Identifier::new("b", Some(IdentifierType::Primary)),
+            Identifier::new("z", None),
+            Identifier::new("a", Some(IdentifierType::Primary)),
+            Identifier::new("c", Some(IdentifierType::Authority)),
+        ];
+        let mut shuffled = identifiers.clone();
+        shuffled.rotate_left(2);
+        shuffled.swap(0, 1);
+
+        let person = |identifiers: Vec<Identifier>| {
+            let mut person = Person::builder();
+            for identifier in identifiers {
+                person.identifier(identifier);
+            }
+            Gedcomx::builder().person(person.build()).build()
+        };
+        let gx = person(identifiers);
+        let shuffled = person(shuffled);
+
+        let canonical = gx.to_canonical_xml_string().unwrap();
+        assert_eq!(canonical, shuffled.to_canonical_xml_string().unwrap());
+
+        let values: Vec<_> = canonical
+            .match_indices("</identifier>")
+            .map(|(end, _)| &canonical[end - 1..end])
+            .collect();
+        assert_eq!(values, ["z", "c", "a", "b"]);
+        assert_ne!(
+            gx.to_xml_string().unwrap(),
+            shuffled.to_xml_string().unwrap()
+        );
+    }
+}
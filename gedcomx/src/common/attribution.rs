@@ -3,9 +3,8 @@ use std::convert::TryInto;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::{Agent, ResourceReference, Result, Timestamp};
+use crate::{Agent, Extensions, ResourceReference, Result, Timestamp};
 
 /// The data structure used to attribute who, when, and why to genealogical
 /// data.
@@ -13,40 +12,46 @@ use crate::{Agent, ResourceReference, Result, Timestamp};
 /// Data is attributed to the agent who made the latest significant change to
 /// the nature of the data being attributed.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    rename = "attribution",
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct Attribution {
     /// Reference to the agent to whom the attributed data is attributed. If
     /// provided, MUST resolve to an instance of [`Agent`](crate::Agent).
-    #[yaserde(prefix = "gx")]
     pub contributor: Option<ResourceReference>,
 
     /// Timestamp of when the attributed data was modified.
-    #[yaserde(prefix = "gx")]
     pub modified: Option<Timestamp>,
 
     /// A statement of why the attributed data is being provided by the
     /// contributor.
-    #[yaserde(rename = "changeMessage", prefix = "gx")]
     pub change_message: Option<String>,
 
     /// Reference to the agent that created the attributed data. The creator MAY
     /// be different from the contributor if changes were made to the
     /// attributed data. If provided, MUST resolve to an instance of
     /// [`Agent`](crate::Agent).
-    #[yaserde(prefix = "gx")]
     pub creator: Option<ResourceReference>,
 
     /// Timestamp of when the attributed data was contributed.
-    #[yaserde(prefix = "gx")]
     pub created: Option<Timestamp>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Attribution: "attribution" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        elements {
+            contributor: gx "contributor",
+            modified: gx "modified",
+            change_message: gx "changeMessage",
+            creator: gx "creator",
+            created: gx "created",
+        }
+        @extensions
+    }
 }
 
 impl Attribution {
@@ -63,6 +68,7 @@ impl Attribution {
             change_message,
             creator,
             created,
+            extensions: Extensions::default(),
         }
     }
 
@@ -142,10 +148,9 @@ impl AttributionBuilder {
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
-    use yaserde::ser::Config;
 
     use super::*;
-    use crate::GedcomxError;
+    use crate::{GedcomxError, xmlcodec::Config};
 
     #[test]
     fn builder() {
@@ -158,6 +163,7 @@ mod test {
             change_message: Some("change message".to_string()),
             creator: Some((&creator).try_into().unwrap()),
             created: Some(Timestamp::default()),
+            extensions: Extensions::default(),
         };
 
         let actual = Attribution::builder()
@@ -224,6 +230,7 @@ mod test {
                         .expect("Invalid date")
                         .into()
                 ),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -252,6 +259,7 @@ mod test {
                     .expect("Invalid date")
                     .into(),
             ),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&attribution).unwrap();
@@ -282,7 +290,7 @@ mod test {
             <created>2012-05-29T00:00:00</created>
           </attribution>"#;
 
-        let attribution: Attribution = yaserde::de::from_str(xml).unwrap();
+        let attribution: Attribution = crate::xmlcodec::from_str(xml).unwrap();
 
         let expected_attribution = Attribution {
             contributor: Some("http://identifier/for/contributor".into()),
@@ -290,6 +298,7 @@ mod test {
             change_message: Some("...change message here...".to_string()),
             creator: Some("http://identifier/for/creator".into()),
             created: Some("2012-05-29T00:00:00".parse().unwrap()),
+            extensions: Extensions::default(),
         };
 
         assert_eq!(attribution, expected_attribution);
@@ -301,7 +310,7 @@ mod test {
         <attribution>
         </attribution>"#;
 
-        let attribution: Attribution = yaserde::de::from_str(xml).unwrap();
+        let attribution: Attribution = crate::xmlcodec::from_str(xml).unwrap();
 
         let expected_attribution = Attribution::default();
         assert_eq!(attribution, expected_attribution);
@@ -315,13 +324,14 @@ mod test {
             change_message: Some("...change message here...".to_string()),
             creator: Some("http://identifier/for/creator".into()),
             created: Some("2012-05-29T00:00:00".parse().unwrap()),
+            extensions: Extensions::default(),
         };
 
         let config = Config {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&attribution, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&attribution, config).unwrap();
 
         let expected_xml = r#"<attribution xmlns="http://gedcomx.org/v1/"><contributor resource="http://identifier/for/contributor" /><modified>2012-06-29T00:00:00</modified><changeMessage>...change message here...</changeMessage><creator resource="http://identifier/for/creator" /><created>2012-05-29T00:00:00</created></attribution>"#;
 
@@ -336,7 +346,7 @@ mod test {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&attribution, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&attribution, config).unwrap();
 
         let expected_xml = r#"<attribution xmlns="http://gedcomx.org/v1/" />"#;
 
@@ -352,8 +362,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Attribution) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Attribution = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Attribution = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
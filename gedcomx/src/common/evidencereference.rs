@@ -3,9 +3,11 @@ use std::convert::TryFrom;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::{Attribution, Event, GedcomxError, Group, Person, PlaceDescription, Relationship, Uri};
+use crate::{
+    Attribution, Event, Extensions, GedcomxError, Group, Person, PlaceDescription, Relationship,
+    Uri,
+};
 
 /// A reference to data being used to derive the given instance of Subject.
 ///
@@ -25,24 +27,32 @@ use crate::{Attribution, Event, GedcomxError, Group, Person, PlaceDescription, R
 /// "fghij" are the same person, he will add two `EvidenceReference` instances
 /// to the working `Person`: one for "abcde" and one for "fghij".
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    rename = "evidence",
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct EvidenceReference {
     /// Reference to the supporting data.
-    #[yaserde(attribute)]
     pub resource: Uri,
 
     /// The attribution of this evidence reference. If not provided, the
     /// attribution of the containing resource of the source reference is
     /// assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    EvidenceReference: "evidence" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            resource: "resource",
+        }
+        elements {
+            attribution: gx "attribution",
+        }
+        @extensions
+    }
 }
 
 impl EvidenceReference {
@@ -50,6 +60,7 @@ impl EvidenceReference {
         Self {
             resource,
             attribution,
+            extensions: Extensions::default(),
         }
     }
 }
@@ -72,10 +83,9 @@ impl Arbitrary for EvidenceReference {
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
-    use yaserde::ser::Config;
 
     use super::*;
-    use crate::TestData;
+    use crate::{TestData, xmlcodec::Config};
 
     #[test]
     fn json_deserialize() {
@@ -139,11 +149,12 @@ mod test {
             </attribution>
         </evidence>"#;
 
-        let evidence_reference: EvidenceReference = yaserde::de::from_str(xml).unwrap();
+        let evidence_reference: EvidenceReference = crate::xmlcodec::from_str(xml).unwrap();
 
         let expected_evidence_reference = EvidenceReference {
             resource: "http://identifier/for/data/being/referenced".into(),
             attribution: Some(Attribution::default()),
+            extensions: Extensions::default(),
         };
 
         assert_eq!(evidence_reference, expected_evidence_reference);
@@ -155,11 +166,12 @@ mod test {
         <evidence resource="http://identifier/for/data/being/referenced">
         </evidence>"#;
 
-        let evidence_reference: EvidenceReference = yaserde::de::from_str(xml).unwrap();
+        let evidence_reference: EvidenceReference = crate::xmlcodec::from_str(xml).unwrap();
 
         let expected_evidence_reference = EvidenceReference {
             resource: "http://identifier/for/data/being/referenced".into(),
             attribution: None,
+            extensions: Extensions::default(),
         };
 
         assert_eq!(evidence_reference, expected_evidence_reference);
@@ -170,13 +182,14 @@ mod test {
         let evidence_reference = EvidenceReference {
             resource: "http://identifier/for/data/being/referenced".into(),
             attribution: Some(Attribution::default()),
+            extensions: Extensions::default(),
         };
 
         let config = Config {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&evidence_reference, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&evidence_reference, config).unwrap();
 
         let expected_xml = r#"<evidence xmlns="http://gedcomx.org/v1/" resource="http://identifier/for/data/being/referenced"><attribution /></evidence>"#;
 
@@ -188,6 +201,7 @@ mod test {
         let evidence_reference = EvidenceReference {
             resource: "http://identifier/for/data/being/referenced".into(),
             attribution: None,
+            extensions: Extensions::default(),
         };
 
         let config = Config {
@@ -195,7 +209,7 @@ mod test {
             ..Default::default()
         };
 
-        let xml = yaserde::ser::to_string_with_config(&evidence_reference, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&evidence_reference, config).unwrap();
 
         let expected_xml = r#"<evidence xmlns="http://gedcomx.org/v1/" resource="http://identifier/for/data/being/referenced" />"#;
 
@@ -211,8 +225,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: EvidenceReference) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: EvidenceReference = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: EvidenceReference = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -0,0 +1,288 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use xml::{
+    attribute::OwnedAttribute,
+    namespace::{NS_XML_URI, NS_XMLNS_URI},
+    reader::{EventReader, XmlEvent},
+};
+
+use crate::{
+    DeserializeError, GedcomxError,
+    xmlcodec::{self, Config, Content, StartElement, XmlReader, XmlWriter},
+};
+
+pub const GEDCOMX_NAMESPACE: &str = "http://gedcomx.org/v1/";
+
+/// Data that isn't part of the GEDCOM X model, such as the properties added by
+/// the FamilySearch API.
+///
+/// GEDCOM X is extensible, so a document can contain JSON members, or XML
+/// elements and attributes in other namespaces, that this crate doesn't know
+/// about. Every type that is serialized as a JSON object or XML element keeps
+/// what it didn't recognize in one of these, and writes it back out when it's
+/// serialized, so reading and then writing a document doesn't lose any data.
+///
+/// Extensions aren't converted between formats: JSON members are only written
+/// to JSON, and XML elements and attributes are only written to XML.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct Extensions {
+    /// Unrecognized JSON members, by name.
+    pub json: serde_json::Map<String, serde_json::Value>,
+
+    /// XML attributes in a namespace other than the GEDCOM X namespace.
+    pub xml_attributes: Vec<ExtensionAttribute>,
+
+    /// XML elements in a namespace other than the GEDCOM X namespace. Each is a
+    /// standalone XML fragment that declares the namespaces it uses.
+    pub xml_elements: Vec<String>,
+}
+
+impl Extensions {
+    /// Whether there's no extension data at all.
+    pub fn is_empty(&self) -> bool {
+        self.json.is_empty() && self.xml_attributes.is_empty() && self.xml_elements.is_empty()
+    }
+}
+
+/// An XML attribute in a namespace other than the GEDCOM X namespace.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct ExtensionAttribute {
+    /// The namespace URI of the attribute.
+    pub namespace: String,
+
+    /// The prefix the namespace is bound to.
+    pub prefix: String,
+
+    /// The local name of the attribute.
+    pub name: String,
+
+    /// The value of the attribute.
+    pub value: String,
+}
+
+impl ExtensionAttribute {
+    pub fn new<N: Into<String>, P: Into<String>, L: Into<String>, V: Into<String>>(
+        namespace: N,
+        prefix: P,
+        name: L,
+        value: V,
+    ) -> Self {
+        Self {
+            namespace: namespace.into(),
+            prefix: prefix.into(),
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl Serialize for Extensions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.json.serialize(serializer)
+    }
+}
+
+// Only ever deserialized flattened into a containing type, so it gets the
+// members the containing type didn't recognize.
+impl<'de> Deserialize<'de> for Extensions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self {
+            json: serde_json::Map::deserialize(deserializer)?,
+            ..Self::default()
+        })
+    }
+}
+
+fn is_extension_namespace(namespace: Option<&str>) -> bool {
+    !matches!(
+        namespace,
+        Some(GEDCOMX_NAMESPACE | NS_XML_URI | NS_XMLNS_URI)
+    )
+}
+
+// The XML extensions of an element are written and read along with the fields
+// of its type.
+impl Extensions {
+    pub(crate) fn write_xml_namespaces(&self, writer: &mut XmlWriter) {
+        for attribute in &self.xml_attributes {
+            writer.namespace(attribute.prefix.clone(), attribute.namespace.clone());
+        }
+    }
+
+    pub(crate) fn write_xml_attributes(&self, writer: &mut XmlWriter) {
+        for attribute in &self.xml_attributes {
+            writer.attribute(
+                &format!("{}:{}", attribute.prefix, attribute.name),
+                &attribute.value,
+            );
+        }
+    }
+
+    pub(crate) fn write_xml_elements(&self, writer: &mut XmlWriter) -> crate::Result<()> {
+        for element in &self.xml_elements {
+            for event in EventReader::new_with_config(element.as_bytes(), xmlcodec::parser_config())
+            {
+                match event.map_err(|e| GedcomxError::XMLSerializeError(e.to_string()))? {
+                    XmlEvent::StartElement {
+                        name,
+                        attributes,
+                        namespace,
+                    } => {
+                        writer.start(&name.borrow().repr_display().to_string());
+                        for (prefix, uri) in &namespace {
+                            writer.namespace(prefix.to_string(), uri.to_string());
+                        }
+                        for attribute in &attributes {
+                            writer.attribute(
+                                &attribute.name.borrow().repr_display().to_string(),
+                                &attribute.value,
+                            );
+                        }
+                    }
+                    // Whitespace between elements is only there for formatting.
+                    XmlEvent::Characters(text) if !text.trim().is_empty() => writer.text(&text),
+                    XmlEvent::EndElement { name } => {
+                        writer.end(&name.borrow().repr_display().to_string());
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn read_xml_attribute(&mut self, attribute: OwnedAttribute) {
+        let name = attribute.name;
+        if !is_extension_namespace(name.namespace.as_deref()) {
+            return;
+        }
+        if let (Some(namespace), Some(prefix)) = (name.namespace, name.prefix) {
+            self.xml_attributes.push(ExtensionAttribute::new(
+                namespace,
+                prefix,
+                name.local_name,
+                attribute.value,
+            ));
+        }
+    }
+
+    /// Read an element that isn't a field of the containing type. GEDCOM X
+    /// elements the type doesn't have are dropped.
+    pub(crate) fn read_xml_element<R: std::io::Read>(
+        &mut self,
+        reader: &mut XmlReader<R>,
+        start: &StartElement,
+    ) -> Result<(), DeserializeError> {
+        if is_extension_namespace(start.name.namespace.as_deref()) {
+            let mut writer = XmlWriter::new(fragment_config());
+            let mut open = vec![write_start(&mut writer, start)];
+            while let Some(name) = open.last() {
+                match reader.next_content()? {
+                    Content::Start(start) => open.push(write_start(&mut writer, &start)),
+                    Content::Text(text) if !text.trim().is_empty() => writer.text(&text),
+                    Content::Text(_) => (),
+                    Content::End => {
+                        writer.end(name);
+                        open.pop();
+                    }
+                }
+            }
+            self.xml_elements.push(writer.into_string());
+        } else {
+            reader.skip()?;
+        }
+        Ok(())
+    }
+}
+
+// Each extension element is a standalone fragment.
+const fn fragment_config() -> Config {
+    Config {
+        write_document_declaration: false,
+        perform_indent: false,
+        canonical: false,
+    }
+}
+
+// Start an element as it was named in the document, and give back the name.
+fn write_start(writer: &mut XmlWriter, start: &StartElement) -> String {
+    let name = start.name.borrow().repr_display().to_string();
+    writer.start(&name);
+    for (prefix, uri) in &start.namespace {
+        writer.namespace(prefix.to_string(), uri.to_string());
+    }
+    for attribute in &start.attributes {
+        writer.attribute(
+            &attribute.name.borrow().repr_display().to_string(),
+            &attribute.value,
+        );
+    }
+    name
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{Note, xmlcodec::Config};
+
+    #[test]
+    fn json_roundtrip() {
+        let json = r#"{"text":"This is a note","fs:level":3,"links":{"self":{"href":"https://example.com/notes/1"}}}"#;
+
+        let note: Note = serde_json::from_str(json).unwrap();
+        assert_eq!(note.text, "This is a note");
+        assert_eq!(note.extensions.json["fs:level"], 3);
+        assert_eq!(
+            note.extensions.json["links"]["self"]["href"],
+            "https://example.com/notes/1"
+        );
+        assert!(note.extensions.xml_elements.is_empty());
+
+        assert_eq!(serde_json::to_string(&note).unwrap(), json);
+    }
+
+    #[test]
+    fn json_roundtrip_no_extensions() {
+        let note: Note = serde_json::from_str(r#"{"text":"This is a note"}"#).unwrap();
+        assert!(note.extensions.is_empty());
+    }
+
+    #[test]
+    fn xml_roundtrip() {
+        let xml = r#"<note xmlns="http://gedcomx.org/v1/" xmlns:ex="http://example.com/" xml:lang="en" ex:level="3"><text>This is a note</text><ex:tag kind="a"><ex:value>Tagged</ex:value></ex:tag></note>"#;
+
+        let note: Note = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(note.text, "This is a note");
+        assert_eq!(
+            note.extensions.xml_attributes,
+            vec![ExtensionAttribute::new(
+                "http://example.com/",
+                "ex",
+                "level",
+                "3"
+            )]
+        );
+        assert_eq!(note.extensions.xml_elements.len(), 1);
+        assert!(note.extensions.json.is_empty());
+
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        assert_eq!(crate::xmlcodec::to_string(&note, config).unwrap(), xml);
+    }
+
+    #[test]
+    fn xml_extensions_not_written_to_json() {
+        let xml = r#"<note xmlns="http://gedcomx.org/v1/" xmlns:ex="http://example.com/" ex:level="3"><text>This is a note</text><ex:tag /></note>"#;
+
+        let note: Note = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(
+            serde_json::to_string(&note).unwrap(),
+            r#"{"text":"This is a note"}"#
+        );
+    }
+}
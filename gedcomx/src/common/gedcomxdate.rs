@@ -3,7 +3,10 @@ use std::{convert::TryFrom, fmt, fmt::Write, str};
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 
-use crate::GedcomxError;
+use crate::{
+    DeserializeError, GEDCOMX_NAMESPACE, GedcomxError,
+    xmlcodec::{FromXml, StartElement, ToXml, XmlReader, XmlWriter},
+};
 
 /// Newtype wrapping `GedcomxDate` from the `gedcomx_date` crate.
 ///
@@ -14,64 +17,31 @@ use crate::GedcomxError;
 #[serde(try_from = "String", into = "String")]
 pub struct GedcomxDate(pub gedcomx_date::GedcomxDate);
 
-impl yaserde::YaSerialize for GedcomxDate {
-    fn serialize<W: std::io::Write>(
-        &self,
-        writer: &mut yaserde::ser::Serializer<W>,
-    ) -> Result<(), String> {
-        let yaserde_label = writer
-            .get_start_event_name()
-            .unwrap_or_else(|| "GedcomxDate".to_string());
-        let struct_start_event = xml::writer::XmlEvent::start_element(yaserde_label.as_ref())
-            .default_ns("http://gedcomx.org/v1/");
-        let event: xml::writer::events::XmlEvent = struct_start_event.into();
-        let _ret = writer.write(event);
-
-        let _ret = writer.write(xml::writer::XmlEvent::characters(self.to_string().as_str()));
-
-        let _ret = writer.write(xml::writer::events::XmlEvent::end_element());
+// The formal form of a date is always in the GEDCOM X namespace.
+impl ToXml for GedcomxDate {
+    const NAME: &'static str = "formal";
 
+    fn write(&self, writer: &mut XmlWriter, name: &str) -> crate::Result<()> {
+        writer.start(name);
+        writer.namespace("", GEDCOMX_NAMESPACE);
+        writer.text(&self.to_string());
+        writer.end(name);
         Ok(())
     }
-
-    fn serialize_attributes(
-        &self,
-        attributes: Vec<xml::attribute::OwnedAttribute>,
-        namespace: xml::namespace::Namespace,
-    ) -> Result<
-        (
-            Vec<xml::attribute::OwnedAttribute>,
-            xml::namespace::Namespace,
-        ),
-        String,
-    > {
-        Ok((attributes, namespace))
-    }
 }
 
-impl yaserde::YaDeserialize for GedcomxDate {
-    fn deserialize<R: std::io::Read>(
-        reader: &mut yaserde::de::Deserializer<R>,
-    ) -> Result<Self, String> {
-        if let xml::reader::XmlEvent::StartElement { name, .. } = reader.peek()?.clone() {
-            let expected_name = "formal".to_owned();
-            if name.local_name != expected_name {
-                return Err(format!(
-                    "Wrong StartElement name: {name}, expected: {expected_name}"
-                ));
-            }
-            let _next = reader.next_event();
-        } else {
-            return Err("StartElement missing".to_string());
-        }
-
-        if let xml::reader::XmlEvent::Characters(text) = reader.peek()?.clone() {
-            text.parse::<Self>().map_err(|e| e.to_string())
-        } else {
-            Err("Characters missing".to_string())
-        }
+impl FromXml for GedcomxDate {
+    fn read<R: std::io::Read>(
+        reader: &mut XmlReader<R>,
+        _start: StartElement,
+    ) -> Result<Self, DeserializeError> {
+        let text = reader.read_text()?;
+        text.trim()
+            .parse()
+            .map_err(|e: GedcomxError| reader.value_error(e.to_string(), &text))
     }
 }
+
 impl str::FromStr for GedcomxDate {
     type Err = GedcomxError;
 
@@ -0,0 +1,331 @@
+use quickcheck::{Arbitrary, Gen};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::Extensions;
+
+/// A hypermedia link from a resource to a related resource, as defined by the
+/// [GEDCOM X RS](https://github.com/FamilySearch/gedcomx-rs/blob/master/specifications/rs-specification.md)
+/// specification.
+///
+/// In JSON, links are a map from the link relation to the link, so `rel` is
+/// the key of the map rather than a member of the link. In XML, each link is
+/// a `link` element with a `rel` attribute, in the namespace of the element
+/// that contains it: `gx:link` in GEDCOM X documents and `atom:link` in Atom
+/// feeds.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
+#[non_exhaustive]
+pub struct Link {
+    /// The link relation, such as `self`, `next` or `person`.
+    #[serde(skip)]
+    pub rel: Option<String>,
+
+    /// The target of the link.
+    // A string rather than a `Uri` for compatibility with earlier versions,
+    // which couldn't read a `Uri` attribute containing `&`, as is common in
+    // the query strings of links.
+    pub href: Option<String>,
+
+    /// A URI template, per [RFC 6570](https://tools.ietf.org/html/rfc6570),
+    /// used to build the target of the link.
+    pub template: Option<String>,
+
+    /// The media type of the target of the link.
+    #[serde(rename = "type")]
+    pub link_type: Option<String>,
+
+    /// The media types accepted by the target of the link.
+    pub accept: Option<String>,
+
+    /// The HTTP methods allowed on the target of the link.
+    pub allow: Option<String>,
+
+    /// The language of the target of the link.
+    pub hreflang: Option<String>,
+
+    /// A human-readable title for the link.
+    pub title: Option<String>,
+
+    /// The number of results in the page the link points to.
+    pub count: Option<u32>,
+
+    /// The index of the first result in the page the link points to.
+    pub offset: Option<u32>,
+
+    /// The total number of results available.
+    pub results: Option<u32>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Link: "link" in _ [] {
+        attributes {
+            rel: "rel",
+            href: "href",
+            template: "template",
+            link_type: "type",
+            accept: "accept",
+            allow: "allow",
+            hreflang: "hreflang",
+            title: "title",
+            count: "count",
+            offset: "offset",
+            results: "results",
+        }
+        @extensions
+    }
+}
+
+impl Link {
+    pub fn new<I: Into<String>, J: Into<String>>(rel: I, href: J) -> Self {
+        Self {
+            rel: Some(rel.into()),
+            href: Some(href.into()),
+            ..Self::default()
+        }
+    }
+
+    pub fn builder<I: Into<String>>(rel: I) -> LinkBuilder {
+        LinkBuilder::new(rel)
+    }
+}
+
+impl Arbitrary for Link {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::builder(crate::arbitrary_trimmed(g))
+            .href(crate::arbitrary_trimmed(g))
+            .template(crate::arbitrary_trimmed(g))
+            .link_type(crate::arbitrary_trimmed(g))
+            .title(crate::arbitrary_trimmed(g))
+            .count(u32::arbitrary(g))
+            .offset(u32::arbitrary(g))
+            .results(u32::arbitrary(g))
+            .build()
+    }
+}
+
+pub struct LinkBuilder(Link);
+
+impl LinkBuilder {
+    pub(crate) fn new<I: Into<String>>(rel: I) -> Self {
+        Self(Link {
+            rel: Some(rel.into()),
+            ..Link::default()
+        })
+    }
+
+    pub fn href<I: Into<String>>(&mut self, href: I) -> &mut Self {
+        self.0.href = Some(href.into());
+        self
+    }
+
+    pub fn template<I: Into<String>>(&mut self, template: I) -> &mut Self {
+        self.0.template = Some(template.into());
+        self
+    }
+
+    pub fn link_type<I: Into<String>>(&mut self, link_type: I) -> &mut Self {
+        self.0.link_type = Some(link_type.into());
+        self
+    }
+
+    pub fn accept<I: Into<String>>(&mut self, accept: I) -> &mut Self {
+        self.0.accept = Some(accept.into());
+        self
+    }
+
+    pub fn allow<I: Into<String>>(&mut self, allow: I) -> &mut Self {
+        self.0.allow = Some(allow.into());
+        self
+    }
+
+    pub fn hreflang<I: Into<String>>(&mut self, hreflang: I) -> &mut Self {
+        self.0.hreflang = Some(hreflang.into());
+        self
+    }
+
+    pub fn title<I: Into<String>>(&mut self, title: I) -> &mut Self {
+        self.0.title = Some(title.into());
+        self
+    }
+
+    pub fn count(&mut self, count: u32) -> &mut Self {
+        self.0.count = Some(count);
+        self
+    }
+
+    pub fn offset(&mut self, offset: u32) -> &mut Self {
+        self.0.offset = Some(offset);
+        self
+    }
+
+    pub fn results(&mut self, results: u32) -> &mut Self {
+        self.0.results = Some(results);
+        self
+    }
+
+    pub fn build(&self) -> Link {
+        self.0.clone()
+    }
+}
+
+pub mod serde_vec_link_to_map {
+    use std::fmt;
+
+    use serde::{
+        de::{Deserializer, MapAccess, Visitor},
+        ser::{SerializeMap, Serializer},
+    };
+
+    use crate::Link;
+
+    /// # Errors
+    /// Returns serde errors if serialization fails.
+    pub fn serialize<S>(links: &[Link], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(links.len()))?;
+        for link in links {
+            map.serialize_entry(link.rel.as_deref().unwrap_or_default(), link)?;
+        }
+        map.end()
+    }
+
+    struct LinkVisitor;
+
+    impl<'de> Visitor<'de> for LinkVisitor {
+        type Value = Vec<Link>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("A map of link relations to links")
+        }
+
+        fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+        where
+            M: MapAccess<'de>,
+        {
+            let mut links = Vec::with_capacity(access.size_hint().unwrap_or(0));
+
+            while let Some((rel, mut link)) = access.next_entry::<String, Link>()? {
+                link.rel = Some(rel);
+                links.push(link);
+            }
+
+            Ok(links)
+        }
+    }
+
+    /// # Errors
+    /// Returns serde errors if deserialization fails.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<Link>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(LinkVisitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::xmlcodec::Config;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Links {
+        #[serde(with = "serde_vec_link_to_map")]
+        links: Vec<Link>,
+    }
+
+    fn links() -> Vec<Link> {
+        vec![
+            Link::new("self", "https://example.org/persons/P-1"),
+            Link::builder("next")
+                .href("https://example.org/search?q=surname:Kunz&start=20")
+                .link_type("application/x-gedcomx-atom+json")
+                .count(20)
+                .offset(20)
+                .results(65)
+                .build(),
+            Link::builder("person-template")
+                .template("https://example.org/persons/{pid}")
+                .allow("GET,DELETE")
+                .build(),
+        ]
+    }
+
+    #[test]
+    fn json_deserialize() {
+        let json = r#"{
+            "links" : {
+                "self" : { "href" : "https://example.org/persons/P-1" },
+                "next" : {
+                    "href" : "https://example.org/search?q=surname:Kunz&start=20",
+                    "type" : "application/x-gedcomx-atom+json",
+                    "count" : 20,
+                    "offset" : 20,
+                    "results" : 65
+                },
+                "person-template" : {
+                    "template" : "https://example.org/persons/{pid}",
+                    "allow" : "GET,DELETE"
+                }
+            }
+        }"#;
+
+        let links: Links = serde_json::from_str(json).unwrap();
+        assert_eq!(links.links, self::links());
+    }
+
+    #[test]
+    fn json_serialize() {
+        let json = serde_json::to_string(&Links { links: links() }).unwrap();
+        assert_eq!(
+            json,
+            r#"{"links":{"self":{"href":"https://example.org/persons/P-1"},"next":{"href":"https://example.org/search?q=surname:Kunz&start=20","type":"application/x-gedcomx-atom+json","count":20,"offset":20,"results":65},"person-template":{"template":"https://example.org/persons/{pid}","allow":"GET,DELETE"}}}"#
+        );
+    }
+
+    #[test]
+    fn xml_deserialize() {
+        let xml = r#"<link rel="next" href="https://example.org/search?q=surname:Kunz&amp;start=20" type="application/x-gedcomx-atom+json" count="20" offset="20" results="65" />"#;
+
+        let link: Link = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(link, links()[1]);
+    }
+
+    #[test]
+    fn xml_serialize() {
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        let xml = crate::xmlcodec::to_string(&links()[1], config).unwrap();
+        assert_eq!(
+            xml,
+            r#"<link rel="next" href="https://example.org/search?q=surname:Kunz&amp;start=20" type="application/x-gedcomx-atom+json" count="20" offset="20" results="65" />"#
+        );
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_json(input: Vec<Link>) -> bool {
+        let input = Links { links: input };
+        let json = serde_json::to_string(&input).unwrap();
+        let from_json: Links = serde_json::from_str(&json).unwrap();
+        input == from_json
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_xml(input: Link) -> bool {
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Link = crate::xmlcodec::from_str(&xml).unwrap();
+        input == from_xml
+    }
+}
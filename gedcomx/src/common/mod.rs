@@ -4,6 +4,12 @@ pub use attribution::{Attribution, AttributionBuilder};
 mod evidencereference;
 pub use evidencereference::EvidenceReference;
 
+mod extensions;
+// Not public API, but every type with extensions needs it.
+#[allow(clippy::redundant_pub_crate)]
+pub(crate) use extensions::GEDCOMX_NAMESPACE;
+pub use extensions::{ExtensionAttribute, Extensions};
+
 mod gedcomxdate;
 pub use gedcomxdate::GedcomxDate;
 
@@ -13,6 +19,9 @@ pub use id::Id;
 mod lang;
 pub use lang::Lang;
 
+mod link;
+pub use link::{Link, LinkBuilder, serde_vec_link_to_map};
+
 mod note;
 pub use note::{Note, NoteBuilder};
 
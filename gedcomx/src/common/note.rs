@@ -1,40 +1,47 @@
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::{Attribution, Lang};
+use crate::{Attribution, Extensions, Lang};
 
 /// A note that was contributed from genealogical research.
 ///
 /// Notes are not intended to contain genealogical conclusions. Notes are only
 /// associated with a single genealogical resource.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    rename = "note",
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct Note {
     /// The locale identifier for the note.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// A subject or title for the note.
-    #[yaserde(prefix = "gx")]
     pub subject: Option<String>,
 
     /// The text of the note.
-    #[yaserde(prefix = "gx")]
     pub text: String,
 
     /// The attribution of this note. If not provided, the attribution of the
     /// containing resource of the note is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Note: "note" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            lang: "xml:lang",
+        }
+        elements {
+            subject: gx "subject",
+            text: gx "text",
+            attribution: gx "attribution",
+        }
+        @extensions
+    }
 }
 
 impl Note {
@@ -49,6 +56,7 @@ impl Note {
             subject,
             text,
             attribution,
+            extensions: Extensions::default(),
         }
     }
 
@@ -104,10 +112,8 @@ impl NoteBuilder {
 
 #[cfg(test)]
 mod test {
-    use yaserde::ser::Config;
-
     use super::*;
-    use crate::TestData;
+    use crate::{TestData, xmlcodec::Config};
 
     #[test]
     fn builder() {
@@ -116,6 +122,7 @@ mod test {
             subject: Some("subject".to_string()),
             text: "text".to_string(),
             attribution: Some(Attribution::default()),
+            extensions: Extensions::default(),
         };
 
         let actual = Note::builder("text")
@@ -151,6 +158,7 @@ mod test {
                 subject: Some("TestSubject".to_string()),
                 text: "This is a note".to_string(),
                 attribution: Some(data.attribution),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -174,6 +182,7 @@ mod test {
             subject: Some("TestSubject".to_string()),
             text: "This is a note".to_string(),
             attribution: Some(data.attribution),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&note).unwrap();
@@ -205,7 +214,7 @@ mod test {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&note, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&note, config).unwrap();
         let expected = r##"<note xmlns="http://gedcomx.org/v1/" xml:lang="en"><subject>...subject or title...</subject><text>...text of the note...</text><attribution /></note>"##;
 
         assert_eq!(xml, expected);
@@ -220,7 +229,7 @@ mod test {
         </attribution>    
         </note>"##;
 
-        let note: Note = yaserde::de::from_str(xml).unwrap();
+        let note: Note = crate::xmlcodec::from_str(xml).unwrap();
         let expected = Note::builder("...text of the note...")
             .lang("en")
             .subject("...subject or title...")
@@ -238,8 +247,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Note) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Note = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Note = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -1,28 +1,37 @@
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::Uri;
+use crate::{Extensions, Uri};
 
 /// Used to supply additional details, annotations, tags, or other qualifying
 /// data to a specific data element.
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
 #[non_exhaustive]
 pub struct Qualifier {
     /// The name of the qualifier. The name should be an element of a
     /// constrained vocabulary and is used to determine meaning of the
     /// qualifier.
-    #[yaserde(attribute)]
     pub name: Uri,
 
     /// The value of the qualifier. Some qualifiers may not have values,
     /// indicating that the qualifier is to be treated more like a "tag".
-    #[yaserde(text)]
     pub value: Option<String>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Qualifier: "Qualifier" in _ [] {
+        attributes {
+            name: "name",
+        }
+        text value
+        @extensions
+    }
 }
 
 impl Qualifier {
@@ -30,6 +39,7 @@ impl Qualifier {
         Self {
             name: name.into(),
             value: value.map(std::convert::Into::into),
+            extensions: Extensions::default(),
         }
     }
 }
@@ -45,9 +55,8 @@ impl Arbitrary for Qualifier {
 
 #[cfg(test)]
 mod test {
-    use yaserde::ser::Config;
-
     use super::*;
+    use crate::xmlcodec::Config;
 
     #[test]
     fn json_deserialize() {
@@ -102,7 +111,7 @@ mod test {
             ..Default::default()
         };
 
-        let xml = yaserde::ser::to_string_with_config(&qualifier, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&qualifier, config).unwrap();
 
         let expected = r##"<Qualifier name="http://gedcomx.org/QualifierName">...qualifier value...</Qualifier>"##;
 
@@ -113,7 +122,7 @@ mod test {
     fn xml_deserialize() {
         let xml = r##"<Qualifier name="http://gedcomx.org/QualifierName">...qualifier value...</Qualifier>"##;
 
-        let qualifier: Qualifier = yaserde::de::from_str(xml).unwrap();
+        let qualifier: Qualifier = crate::xmlcodec::from_str(xml).unwrap();
         let expected = Qualifier::new(
             "http://gedcomx.org/QualifierName",
             Some("...qualifier value..."),
@@ -130,8 +139,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Qualifier) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Qualifier = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Qualifier = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
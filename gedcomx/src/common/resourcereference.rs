@@ -2,36 +2,50 @@ use std::convert::TryFrom;
 
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::{Agent, Document, DocumentType, GedcomxError, Person, PlaceDescription, Uri};
+use crate::{
+    Agent, Document, DocumentType, Extensions, GedcomxError, Person, PlaceDescription, Uri,
+};
 
 /// A generic reference to a resource.
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
 #[non_exhaustive]
 pub struct ResourceReference {
     /// The URI to the resource being referenced.
-    #[yaserde(attribute)]
     pub resource: Uri,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    ResourceReference: "ResourceReference" in _ [] {
+        attributes {
+            resource: "resource",
+        }
+        @extensions
+    }
 }
 
 impl ResourceReference {
     pub fn new(uri: Uri) -> Self {
-        Self { resource: uri }
+        Self {
+            resource: uri,
+            extensions: Extensions::default(),
+        }
     }
 }
 
 impl From<&str> for ResourceReference {
     fn from(s: &str) -> Self {
-        Self { resource: s.into() }
+        Self::new(s.into())
     }
 }
 
 impl From<String> for ResourceReference {
     fn from(s: String) -> Self {
-        Self { resource: s.into() }
+        Self::new(s.into())
     }
 }
 
@@ -41,11 +55,7 @@ impl TryFrom<&Agent> for ResourceReference {
     fn try_from(agent: &Agent) -> Result<Self, Self::Error> {
         agent.id.as_ref().map_or_else(
             || Err(GedcomxError::no_id_error(&agent)),
-            |id| {
-                Ok(Self {
-                    resource: id.into(),
-                })
-            },
+            |id| Ok(Self::new(id.into())),
         )
     }
 }
@@ -56,11 +66,7 @@ impl TryFrom<&Person> for ResourceReference {
     fn try_from(person: &Person) -> Result<Self, Self::Error> {
         person.id.as_ref().map_or_else(
             || Err(GedcomxError::no_id_error(&person)),
-            |id| {
-                Ok(Self {
-                    resource: id.into(),
-                })
-            },
+            |id| Ok(Self::new(id.into())),
         )
     }
 }
@@ -71,11 +77,7 @@ impl TryFrom<&PlaceDescription> for ResourceReference {
     fn try_from(place_description: &PlaceDescription) -> Result<Self, Self::Error> {
         place_description.id.as_ref().map_or_else(
             || Err(GedcomxError::no_id_error(&place_description)),
-            |id| {
-                Ok(Self {
-                    resource: id.into(),
-                })
-            },
+            |id| Ok(Self::new(id.into())),
         )
     }
 }
@@ -92,9 +94,7 @@ impl TryFrom<&Document> for ResourceReference {
             document.document_type.is_none()
                 || document.document_type == Some(DocumentType::Analysis),
         ) {
-            (Some(id), true) => Ok(Self {
-                resource: id.into(),
-            }),
+            (Some(id), true) => Ok(Self::new(id.into())),
             (None, _) => Err(GedcomxError::no_id_error(&document)),
             (_, false) => Err(GedcomxError::WrongDocumentType {
                 expected: DocumentType::Analysis,
@@ -110,6 +110,7 @@ impl Arbitrary for ResourceReference {
     fn arbitrary(g: &mut Gen) -> Self {
         Self {
             resource: Uri::arbitrary(g),
+            extensions: Extensions::default(),
         }
     }
 }
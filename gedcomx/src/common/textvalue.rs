@@ -1,24 +1,33 @@
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::Lang;
+use crate::{Extensions, Lang};
 
 /// An element representing a text value that may be in a specific language.
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
 #[non_exhaustive]
 pub struct TextValue {
     /// The locale identifier for the value of the text.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The text value.
-    #[yaserde(text)]
     pub value: String,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    TextValue: "TextValue" in _ [] {
+        attributes {
+            lang: "xml:lang",
+        }
+        text value
+        @extensions
+    }
 }
 
 impl TextValue {
@@ -30,6 +39,7 @@ impl TextValue {
         Self {
             value: value.into(),
             lang: lang.map(std::convert::Into::into),
+            extensions: Extensions::default(),
         }
     }
 }
@@ -51,9 +61,8 @@ impl Arbitrary for TextValue {
 
 #[cfg(test)]
 mod test {
-    use yaserde::ser::Config;
-
     use super::*;
+    use crate::xmlcodec::Config;
 
     #[test]
     fn json_deserialize() {
@@ -68,6 +77,7 @@ mod test {
             TextValue {
                 lang: Some("en".into()),
                 value: "text of the value".to_string(),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -87,6 +97,7 @@ mod test {
         let text_value = TextValue {
             lang: Some("en".into()),
             value: "text of the value".to_string(),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&text_value).unwrap();
@@ -112,7 +123,7 @@ mod test {
             ..Default::default()
         };
 
-        let xml = yaserde::ser::to_string_with_config(&textvalue, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&textvalue, config).unwrap();
 
         let expected = r##"<TextValue xml:lang="en">...textual value...</TextValue>"##;
 
@@ -123,7 +134,7 @@ mod test {
     fn xml_deserialize() {
         let xml = r##"<TextValue xml:lang="en">...textual value...</TextValue>"##;
 
-        let textvalue: TextValue = yaserde::de::from_str(xml).unwrap();
+        let textvalue: TextValue = crate::xmlcodec::from_str(xml).unwrap();
         let expected = TextValue::new("...textual value...", Some("en"));
         assert_eq!(textvalue, expected);
     }
@@ -137,8 +148,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: TextValue) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: TextValue = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: TextValue = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
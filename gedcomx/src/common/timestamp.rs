@@ -3,7 +3,11 @@ use std::{fmt, str::FromStr};
 use chrono::{DateTime, NaiveDateTime, ParseError, TimeZone, Utc, serde::ts_milliseconds};
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
-use yaserde::{YaDeserialize, YaSerialize};
+
+use crate::{
+    DeserializeError,
+    xmlcodec::{FromXml, StartElement, ToXml, XmlReader, XmlWriter},
+};
 
 /// When an event something was created or modified.
 ///
@@ -36,64 +40,33 @@ impl PartialEq for Timestamp {
     }
 }
 
-impl YaSerialize for Timestamp {
-    fn serialize<W: std::io::Write>(
-        &self,
-        writer: &mut yaserde::ser::Serializer<W>,
-    ) -> Result<(), String> {
-        if let Some(start_event_name) = writer.get_start_event_name() {
-            writer
-                .write(xml::writer::XmlEvent::start_element(
-                    start_event_name.as_str(),
-                ))
-                .map_err(|e| e.to_string())?;
-        }
+// A timezone that's undetermined is taken to be UTC, as it is in JSON, so the
+// canonical form always has one.
+impl ToXml for Timestamp {
+    const NAME: &'static str = "Timestamp";
 
-        writer
-            .write(xml::writer::XmlEvent::characters(&self.to_string()))
-            .map_err(|e| e.to_string())?;
-        writer
-            .write(xml::writer::XmlEvent::end_element())
-            .map_err(|e| e.to_string())?;
+    fn write(&self, writer: &mut XmlWriter, name: &str) -> crate::Result<()> {
+        let value = if writer.is_canonical() {
+            Self::from(self.value).to_string()
+        } else {
+            self.to_string()
+        };
+        writer.start(name);
+        writer.text(&value);
+        writer.end(name);
         Ok(())
     }
-
-    fn serialize_attributes(
-        &self,
-        attributes: Vec<xml::attribute::OwnedAttribute>,
-        namespace: xml::namespace::Namespace,
-    ) -> Result<
-        (
-            Vec<xml::attribute::OwnedAttribute>,
-            xml::namespace::Namespace,
-        ),
-        String,
-    > {
-        Ok((attributes, namespace))
-    }
 }
 
-impl YaDeserialize for Timestamp {
-    fn deserialize<R: std::io::Read>(
-        reader: &mut yaserde::de::Deserializer<R>,
-    ) -> Result<Self, String> {
-        if let xml::reader::XmlEvent::StartElement { .. } = reader.next_event()? {
-        } else {
-            return Err("No start event".to_string());
-        }
-
-        let timestamp = if let xml::reader::XmlEvent::Characters(text) = reader.next_event()? {
-            text.parse().map_err(|e: ParseError| e.to_string())?
-        } else {
-            return Err("Characters missing".to_string());
-        };
-
-        // Yaserde seems to depend on us not consuming the end event.
-        if let xml::reader::XmlEvent::EndElement { .. } = reader.peek()? {
-            Ok(timestamp)
-        } else {
-            Err("No end event".to_string())
-        }
+impl FromXml for Timestamp {
+    fn read<R: std::io::Read>(
+        reader: &mut XmlReader<R>,
+        _start: StartElement,
+    ) -> Result<Self, DeserializeError> {
+        let text = reader.read_text()?;
+        text.trim()
+            .parse()
+            .map_err(|e: ParseError| reader.value_error(e.to_string(), &text))
     }
 }
 
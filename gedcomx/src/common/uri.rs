@@ -18,7 +18,7 @@ use crate::{
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
 pub struct Uri(String);
 
-impl_characters_yaserialize_yadeserialize!(Uri, "Uri");
+impl_characters_xml_value!(Uri, "Uri");
 
 impl From<&str> for Uri {
     fn from(s: &str) -> Self {
@@ -0,0 +1,329 @@
+use quickcheck::{Arbitrary, Gen};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::{Attribution, Extensions, Id, Identifier, Lang, Link, Uri};
+
+/// A collection of genealogical resources, such as the records of a census.
+///
+/// Defined by the [GEDCOM X Record Extensions](https://github.com/FamilySearch/gedcomx-record/blob/master/specifications/record-specification.md).
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct Collection {
+    /// An identifier for the collection.
+    pub id: Option<Id>,
+
+    /// The locale identifier for the collection.
+    pub lang: Option<Lang>,
+
+    /// A list of identifiers for the collection.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_identifier_to_map"
+    )]
+    pub identifiers: Vec<Identifier>,
+
+    /// The title of the collection.
+    pub title: Option<String>,
+
+    /// The number of resources in the collection.
+    pub size: Option<u32>,
+
+    /// A summary of the content of the collection, by type of resource.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub content: Vec<CollectionContent>,
+
+    /// The attribution of the collection.
+    pub attribution: Option<Attribution>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Collection: "collection" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+        }
+        elements {
+            identifiers: gx "identifier",
+            title: gx "title",
+            size: gx "size",
+            content: gx "content",
+            attribution: gx "attribution",
+            links: gx "link",
+        }
+        @extensions
+    }
+}
+
+impl Collection {
+    pub fn new(
+        id: Option<Id>,
+        lang: Option<Lang>,
+        identifiers: Vec<Identifier>,
+        title: Option<String>,
+        size: Option<u32>,
+        content: Vec<CollectionContent>,
+        attribution: Option<Attribution>,
+    ) -> Self {
+        Self {
+            id,
+            lang,
+            identifiers,
+            title,
+            size,
+            content,
+            attribution,
+            links: vec![],
+            extensions: Extensions::default(),
+        }
+    }
+
+    pub fn builder() -> CollectionBuilder {
+        CollectionBuilder::new()
+    }
+}
+
+impl Arbitrary for Collection {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::builder()
+            .id(Id::arbitrary(g))
+            .lang(Lang::arbitrary(g))
+            .identifier(Identifier::arbitrary(g))
+            .title(crate::arbitrary_trimmed(g))
+            .size(u32::arbitrary(g))
+            .content(CollectionContent::arbitrary(g))
+            .attribution(Attribution::arbitrary(g))
+            .build()
+    }
+}
+
+pub struct CollectionBuilder(Collection);
+
+impl CollectionBuilder {
+    pub(crate) fn new() -> Self {
+        Self(Collection::default())
+    }
+
+    pub fn id<I: Into<Id>>(&mut self, id: I) -> &mut Self {
+        self.0.id = Some(id.into());
+        self
+    }
+
+    pub fn lang<I: Into<Lang>>(&mut self, lang: I) -> &mut Self {
+        self.0.lang = Some(lang.into());
+        self
+    }
+
+    pub fn identifier(&mut self, identifier: Identifier) -> &mut Self {
+        self.0.identifiers.push(identifier);
+        self
+    }
+
+    pub fn title<I: Into<String>>(&mut self, title: I) -> &mut Self {
+        self.0.title = Some(title.into());
+        self
+    }
+
+    pub fn size(&mut self, size: u32) -> &mut Self {
+        self.0.size = Some(size);
+        self
+    }
+
+    pub fn content(&mut self, content: CollectionContent) -> &mut Self {
+        self.0.content.push(content);
+        self
+    }
+
+    pub fn attribution(&mut self, attribution: Attribution) -> &mut Self {
+        self.0.attribution = Some(attribution);
+        self
+    }
+
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
+    pub fn build(&self) -> Collection {
+        let mut collection = Collection::new(
+            self.0.id.clone(),
+            self.0.lang.clone(),
+            self.0.identifiers.clone(),
+            self.0.title.clone(),
+            self.0.size,
+            self.0.content.clone(),
+            self.0.attribution.clone(),
+        );
+        collection.links.clone_from(&self.0.links);
+        collection
+    }
+}
+
+/// The number of resources of a type in a [`Collection`].
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct CollectionContent {
+    /// The type of resource being counted, such as
+    /// `http://gedcomx.org/Person`.
+    pub resource_type: Option<Uri>,
+
+    /// The number of resources of the type in the collection.
+    pub count: Option<u32>,
+
+    /// The fraction of the collection that has been processed to produce the
+    /// resources, from 0 to 1.
+    pub completeness: Option<f32>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    CollectionContent: "CollectionContent" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            resource_type: "resourceType",
+        }
+        elements {
+            count: gx "count",
+            completeness: gx "completeness",
+        }
+        @extensions
+    }
+}
+
+impl CollectionContent {
+    pub fn new(resource_type: Option<Uri>, count: Option<u32>, completeness: Option<f32>) -> Self {
+        Self {
+            resource_type,
+            count,
+            completeness,
+            extensions: Extensions::default(),
+        }
+    }
+}
+
+impl Arbitrary for CollectionContent {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(
+            Some(Uri::arbitrary(g)),
+            Some(u32::arbitrary(g)),
+            Some(f32::from(u8::arbitrary(g)) / 255.0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{IdentifierType, xmlcodec::Config};
+
+    fn collection() -> Collection {
+        Collection::builder()
+            .id("C-1")
+            .lang("en")
+            .identifier(Identifier::new(
+                "https://familysearch.org/ark:/61903/1:1:XXXX",
+                Some(IdentifierType::Primary),
+            ))
+            .title("1900 United States Census")
+            .size(1000)
+            .content(CollectionContent::new(
+                Some("http://gedcomx.org/Person".into()),
+                Some(750),
+                Some(0.5),
+            ))
+            .build()
+    }
+
+    #[test]
+    fn json_deserialize() {
+        let json = r#"{
+            "id" : "C-1",
+            "lang" : "en",
+            "identifiers" : {
+                "http://gedcomx.org/Primary" : [ "https://familysearch.org/ark:/61903/1:1:XXXX" ]
+            },
+            "title" : "1900 United States Census",
+            "size" : 1000,
+            "content" : [ {
+                "resourceType" : "http://gedcomx.org/Person",
+                "count" : 750,
+                "completeness" : 0.5
+            } ]
+        }"#;
+
+        let collection: Collection = serde_json::from_str(json).unwrap();
+        assert_eq!(collection, self::collection());
+    }
+
+    #[test]
+    fn json_serialize() {
+        let json = serde_json::to_string(&collection()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":"C-1","lang":"en","identifiers":{"http://gedcomx.org/Primary":["https://familysearch.org/ark:/61903/1:1:XXXX"]},"title":"1900 United States Census","size":1000,"content":[{"resourceType":"http://gedcomx.org/Person","count":750,"completeness":0.5}]}"#
+        );
+    }
+
+    #[test]
+    fn xml_deserialize() {
+        let xml = r#"<collection xmlns="http://gedcomx.org/v1/" id="C-1" xml:lang="en">
+            <identifier type="http://gedcomx.org/Primary">https://familysearch.org/ark:/61903/1:1:XXXX</identifier>
+            <title>1900 United States Census</title>
+            <size>1000</size>
+            <content resourceType="http://gedcomx.org/Person">
+                <count>750</count>
+                <completeness>0.5</completeness>
+            </content>
+        </collection>"#;
+
+        let collection: Collection = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(collection, self::collection());
+    }
+
+    #[test]
+    fn xml_serialize() {
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        let xml = crate::xmlcodec::to_string(&collection(), config).unwrap();
+        assert_eq!(
+            xml,
+            r#"<collection xmlns="http://gedcomx.org/v1/" id="C-1" xml:lang="en"><identifier type="http://gedcomx.org/Primary">https://familysearch.org/ark:/61903/1:1:XXXX</identifier><title>1900 United States Census</title><size>1000</size><content resourceType="http://gedcomx.org/Person"><count>750</count><completeness>0.5</completeness></content></collection>"#
+        );
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_json(input: Collection) -> bool {
+        let json = serde_json::to_string(&input).unwrap();
+        let from_json: Collection = serde_json::from_str(&json).unwrap();
+        input == from_json
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_xml(input: Collection) -> bool {
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Collection = crate::xmlcodec::from_str(&xml).unwrap();
+        input == from_xml
+    }
+}
@@ -22,7 +22,8 @@ pub enum ConfidenceLevel {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(ConfidenceLevel, "ConfidenceLevel");
+impl_enumasstring_xml_value!(ConfidenceLevel, "ConfidenceLevel");
+impl_from_uri!(ConfidenceLevel);
 
 impl From<EnumAsString> for ConfidenceLevel {
     fn from(f: EnumAsString) -> Self {
@@ -67,9 +68,8 @@ impl Arbitrary for ConfidenceLevel {
 
 #[cfg(test)]
 mod test {
-    use yaserde::ser::Config;
-
     use super::*;
+    use crate::xmlcodec::Config;
 
     #[test]
     fn roundtrip_to_string() {
@@ -90,14 +90,14 @@ mod test {
     #[test]
     fn deserialize() {
         let xml = "<ConfidenceLevel>http://gedcomx.org/High</ConfidenceLevel>";
-        let cl: ConfidenceLevel = yaserde::de::from_str(xml).unwrap();
+        let cl: ConfidenceLevel = crate::xmlcodec::from_str(xml).unwrap();
         assert_eq!(cl, ConfidenceLevel::High);
     }
 
     #[test]
     fn deserialize_custom() {
         let xml = "<ConfidenceLevel>this is a test</ConfidenceLevel>";
-        let cl: ConfidenceLevel = yaserde::de::from_str(xml).unwrap();
+        let cl: ConfidenceLevel = crate::xmlcodec::from_str(xml).unwrap();
         assert_eq!(cl, ConfidenceLevel::Custom("this is a test".into()));
     }
 
@@ -107,8 +107,8 @@ mod test {
             write_document_declaration: false,
             ..Default::default()
         };
-        let actual = yaserde::ser::to_string_with_config(&ConfidenceLevel::High, &config).unwrap();
-        let expected = "http://gedcomx.org/High";
+        let actual = crate::xmlcodec::to_string(&ConfidenceLevel::High, config).unwrap();
+        let expected = "<ConfidenceLevel>http://gedcomx.org/High</ConfidenceLevel>";
 
         assert_eq!(actual, expected);
     }
@@ -119,12 +119,10 @@ mod test {
             write_document_declaration: false,
             ..Default::default()
         };
-        let actual = yaserde::ser::to_string_with_config(
-            &ConfidenceLevel::Custom("this is a test".into()),
-            &config,
-        )
-        .unwrap();
-        let expected = "this is a test";
+        let actual =
+            crate::xmlcodec::to_string(&ConfidenceLevel::Custom("this is a test".into()), config)
+                .unwrap();
+        let expected = "<ConfidenceLevel>this is a test</ConfidenceLevel>";
 
         assert_eq!(actual, expected);
     }
@@ -1,30 +1,96 @@
 use quickcheck::{Arbitrary, Gen};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, de::Error};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::GedcomxDate;
+use crate::{Extensions, Field, GedcomxDate};
 
 /// A concluded genealogical date.
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(from = "DateJson")]
 #[non_exhaustive]
 pub struct Date {
     /// The original value of the date as supplied by the contributor.
-    #[yaserde(prefix = "gx")]
     pub original: Option<String>,
 
     /// The standardized formal value of the date, formatted according to the
     /// GEDCOM X Date Format specification.
-    #[yaserde(prefix = "gx")]
     pub formal: Option<GedcomxDate>,
+
+    /// A formal value that a lenient read couldn't read, as it was in the
+    /// document.
+    ///
+    /// It's never serialized, so that what's written can be read again
+    /// strictly. To keep it, parse it into `formal` once it's been fixed.
+    #[serde(skip)]
+    pub invalid_formal: Option<String>,
+
+    /// The fields of a record that the data was extracted from.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<Field>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Date: "Date" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        elements {
+            original: gx "original",
+            formal: gx "formal",
+            fields: gx "field",
+        }
+        invalid {
+            invalid_formal: "formal",
+        }
+        @extensions
+    }
+}
+
+// A date as it's read from JSON, where a lenient read can keep a formal value
+// that isn't valid.
+#[derive(Deserialize)]
+struct DateJson {
+    original: Option<String>,
+    formal: Option<Formal>,
+    #[serde(default)]
+    fields: Vec<Field>,
+    #[serde(flatten)]
+    extensions: Extensions,
+}
+
+enum Formal {
+    Valid(GedcomxDate),
+    Invalid(String),
+}
+
+impl<'de> Deserialize<'de> for Formal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        match value.parse() {
+            Ok(date) => Ok(Self::Valid(date)),
+            Err(_) if crate::lenient::keep_invalid_formal() => Ok(Self::Invalid(value)),
+            Err(e) => Err(D::Error::custom(e)),
+        }
+    }
+}
+
+impl From<DateJson> for Date {
+    fn from(date: DateJson) -> Self {
+        let (formal, invalid_formal) = match date.formal {
+            Some(Formal::Valid(formal)) => (Some(formal), None),
+            Some(Formal::Invalid(value)) => (None, Some(value)),
+            None => (None, None),
+        };
+        Self {
+            original: date.original,
+            formal,
+            invalid_formal,
+            fields: date.fields,
+            extensions: date.extensions,
+        }
+    }
 }
 
 impl Date {
@@ -32,6 +98,9 @@ impl Date {
         Self {
             original: original.map(std::convert::Into::into),
             formal,
+            invalid_formal: None,
+            fields: vec![],
+            extensions: Extensions::default(),
         }
     }
 }
@@ -48,9 +117,9 @@ impl Arbitrary for Date {
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
-    use yaserde::ser::Config;
 
     use super::*;
+    use crate::xmlcodec::Config;
 
     #[test]
     fn json_deserialize() {
@@ -65,7 +134,10 @@ mod test {
             date,
             Date {
                 original: Some("the original text".to_string()),
-                formal: Some("+0987-01-25T23:59Z".parse().unwrap())
+                formal: Some("+0987-01-25T23:59Z".parse().unwrap()),
+                invalid_formal: None,
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -80,7 +152,10 @@ mod test {
             date,
             Date {
                 original: None,
-                formal: None
+                formal: None,
+                invalid_formal: None,
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -90,6 +165,9 @@ mod test {
         let date = Date {
             original: Some("the original text".to_string()),
             formal: Some("+0987-01-25T23:59Z".parse().unwrap()),
+            invalid_formal: None,
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&date).unwrap();
@@ -105,6 +183,9 @@ mod test {
         let date = Date {
             original: None,
             formal: None,
+            invalid_formal: None,
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&date).unwrap();
@@ -120,13 +201,16 @@ mod test {
             <formal>+0987-01-25T23:59Z</formal>
         </date>"#;
 
-        let date: Date = yaserde::de::from_str(xml).unwrap();
+        let date: Date = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(
             date,
             Date {
                 original: Some("the original text".to_string()),
-                formal: Some("+0987-01-25T23:59Z".parse().unwrap())
+                formal: Some("+0987-01-25T23:59Z".parse().unwrap()),
+                invalid_formal: None,
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -135,13 +219,16 @@ mod test {
     fn xml_deserialize_optional_fields() {
         let xml = r#"<date />"#;
 
-        let date: Date = yaserde::de::from_str(xml).unwrap();
+        let date: Date = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(
             date,
             Date {
                 original: None,
-                formal: None
+                formal: None,
+                invalid_formal: None,
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -151,13 +238,16 @@ mod test {
         let date = Date {
             original: Some("the original text".to_string()),
             formal: Some("+0987-01-25T23:59Z".parse().unwrap()),
+            invalid_formal: None,
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let config = Config {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&date, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&date, config).unwrap();
 
         assert_eq!(
             xml,
@@ -170,13 +260,16 @@ mod test {
         let date = Date {
             original: None,
             formal: None,
+            invalid_formal: None,
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let config = Config {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&date, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&date, config).unwrap();
 
         assert_eq!(xml, r#"<Date xmlns="http://gedcomx.org/v1/" />"#);
     }
@@ -191,8 +284,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Date) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Date = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Date = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -3,31 +3,23 @@ use std::fmt;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, EnumAsString, Id, Lang, Note, ResourceReference, SourceReference,
-    Uri,
+    Attribution, ConfidenceLevel, EnumAsString, Extensions, Id, Lang, Link, Note,
+    ResourceReference, SourceReference, Uri,
 };
 
 /// The base conceptual model for genealogical data that are managed as textual
 /// documents.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Default, Clone)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
 #[non_exhaustive]
 #[serde(rename_all = "camelCase")]
 pub struct Document {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -35,7 +27,6 @@ pub struct Document {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -43,43 +34,69 @@ pub struct Document {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// Enumerated value identifying the type of the document.
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub document_type: Option<DocumentType>,
 
     /// Whether this document is to be constrained as an extracted conclusion,
     /// meaning it captures information extracted from a single source.
-    #[yaserde(attribute)]
     pub extracted: Option<bool>,
 
     /// The type of text in the `text` property.
     ///
     /// If no value is provided, "plain" is assumed.
-    #[yaserde(rename = "textType", attribute)]
     pub text_type: Option<TextType>,
 
     /// The text of the document.
-    #[yaserde(prefix = "gx")]
     pub text: String,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Document: "Document" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            document_type: "type",
+            extracted: "extracted",
+            text_type: "textType",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            text: gx "text",
+            links: gx "link",
+        }
+        @extensions
+    }
 }
 
 impl Document {
@@ -108,6 +125,8 @@ impl Document {
             extracted,
             text_type,
             text,
+            links: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -163,8 +182,13 @@ impl DocumentBuilder {
         self
     }
 
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
     pub fn build(&self) -> Document {
-        Document::new(
+        let mut document = Document::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.sources.clone(),
@@ -176,7 +200,9 @@ impl DocumentBuilder {
             self.0.extracted,
             self.0.text_type.clone(),
             self.0.text.clone(),
-        )
+        );
+        document.links.clone_from(&self.0.links);
+        document
     }
 }
 
@@ -200,7 +226,8 @@ pub enum DocumentType {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(DocumentType, "DocumentType");
+impl_enumasstring_xml_value!(DocumentType, "DocumentType");
+impl_from_uri!(DocumentType);
 
 impl From<EnumAsString> for DocumentType {
     fn from(f: EnumAsString) -> Self {
@@ -265,7 +292,8 @@ pub enum TextType {
     Xhtml,
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(TextType, "TextType");
+impl_enumasstring_xml_value!(TextType, "TextType");
+impl_from_uri!(TextType);
 
 impl From<EnumAsString> for TextType {
     fn from(f: EnumAsString) -> Self {
@@ -296,9 +324,9 @@ impl Arbitrary for TextType {
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
-    use yaserde::ser::Config;
 
     use super::*;
+    use crate::xmlcodec::Config;
 
     #[test]
     fn json_deserialize() {
@@ -344,7 +372,7 @@ mod test {
         <text>...text of the document...</text>
         </Document>"#;
 
-        let document: Document = yaserde::de::from_str(xml).unwrap();
+        let document: Document = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(
             document,
@@ -368,7 +396,7 @@ mod test {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&document, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&document, config).unwrap();
 
         assert_eq!(
             xml,
@@ -385,8 +413,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Document) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Document = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Document = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -3,11 +3,10 @@ use std::{fmt, vec};
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, Date, EnumAsString, EventRole, EvidenceReference, Id, Identifier,
-    Lang, Note, PlaceReference, ResourceReference, SourceReference, Uri,
+    Attribution, ConfidenceLevel, Date, EnumAsString, EventRole, EvidenceReference, Extensions, Id,
+    Identifier, Lang, Link, Note, PlaceReference, ResourceReference, SourceReference, Uri,
 };
 
 /// A description of a historical event.
@@ -49,20 +48,13 @@ use crate::{
 /// between instances of the two data types, although an indirect association
 /// can be found via the event role.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct Event {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -70,7 +62,6 @@ pub struct Event {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -78,26 +69,21 @@ pub struct Event {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// Whether this subject is to be constrained as an extracted conclusion.
-    #[yaserde(attribute)]
     pub extracted: Option<bool>,
 
     /// References to other subjects that support this subject.
@@ -106,7 +92,6 @@ pub struct Event {
     /// the same type as this instance (e.g., if the subject is an instance of
     /// Person, all of its evidence references must resolve to instances of
     /// Person).
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub evidence: Vec<EvidenceReference>,
 
@@ -121,12 +106,10 @@ pub struct Event {
     /// used for multimedia references and therefore MUST resolve to a
     /// `SourceDescription` of the resource, which in turn provides a reference
     /// to the resource itself.
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub media: Vec<SourceReference>,
 
     /// A list of identifiers for the subject.
-    #[yaserde(rename = "identifier", prefix = "gx")]
     #[serde(
         skip_serializing_if = "Vec::is_empty",
         default,
@@ -135,22 +118,56 @@ pub struct Event {
     pub identifiers: Vec<Identifier>,
 
     /// The type of the event.
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub event_type: Option<EventType>,
 
     /// The date of the event.
-    #[yaserde(prefix = "gx")]
     pub date: Option<Date>,
 
     /// A reference to the place applicable to this event.
-    #[yaserde(prefix = "gx")]
     pub place: Option<PlaceReference>,
 
     /// Information about how persons participated in the event.
-    #[yaserde(rename = "role", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub roles: Vec<EventRole>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Event: "Event" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            extracted: "extracted",
+            event_type: "type",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            evidence: gx "evidence",
+            media: gx "media",
+            identifiers: gx "identifier",
+            date: gx "date",
+            place: gx "place",
+            roles: gx "role",
+            links: gx "link",
+        }
+        @extensions
+    }
 }
 
 impl Event {
@@ -187,6 +204,8 @@ impl Event {
             date,
             place,
             roles,
+            links: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -249,8 +268,13 @@ impl EventBuilder {
         self
     }
 
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
     pub fn build(&self) -> Event {
-        Event::new(
+        let mut event = Event::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.sources.clone(),
@@ -266,7 +290,9 @@ impl EventBuilder {
             self.0.date.clone(),
             self.0.place.clone(),
             self.0.roles.clone(),
-        )
+        );
+        event.links.clone_from(&self.0.links);
+        event
     }
 }
 
@@ -340,7 +366,8 @@ pub enum EventType {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(EventType, "EventType");
+impl_enumasstring_xml_value!(EventType, "EventType");
+impl_from_uri!(EventType);
 
 impl From<EnumAsString> for EventType {
     fn from(f: EnumAsString) -> Self {
@@ -479,9 +506,9 @@ impl Arbitrary for EventType {
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
-    use yaserde::ser::Config;
 
     use super::*;
+    use crate::xmlcodec::Config;
 
     #[test]
     fn json_deserialize() {
@@ -525,7 +552,7 @@ mod test {
         </date>
       </Event>"#;
 
-        let event: Event = yaserde::de::from_str(xml).unwrap();
+        let event: Event = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(
             event,
@@ -549,7 +576,7 @@ mod test {
             write_document_declaration: false,
             ..Default::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&event, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&event, config).unwrap();
 
         assert_eq!(
             xml,
@@ -566,8 +593,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Event) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Event = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Event = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
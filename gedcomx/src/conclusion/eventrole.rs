@@ -3,29 +3,21 @@ use std::{convert::TryInto, fmt};
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, EnumAsString, Id, Lang, Note, Person, ResourceReference, Result,
-    SourceReference, Uri,
+    Attribution, ConfidenceLevel, EnumAsString, Extensions, Id, Lang, Note, Person,
+    ResourceReference, Result, SourceReference, Uri,
 };
 
 /// A role played in an event by a person.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct EventRole {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -33,7 +25,6 @@ pub struct EventRole {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -41,38 +32,55 @@ pub struct EventRole {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// Reference to the event participant.
     ///
     /// MUST resolve to an instance of [`Person`](crate::Person).
-    #[yaserde(prefix = "gx")]
     pub person: ResourceReference,
 
     /// The participant's role.
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub event_role_type: Option<EventRoleType>,
 
     /// Details about the role of participant in the event.
-    #[yaserde(prefix = "gx")]
     pub details: Option<String>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    EventRole: "EventRole" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            event_role_type: "type",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            person: gx "person",
+            details: gx "details",
+        }
+        @extensions
+    }
 }
 
 impl EventRole {
@@ -99,6 +107,7 @@ impl EventRole {
             person,
             event_role_type,
             details,
+            extensions: Extensions::default(),
         }
     }
 
@@ -196,7 +205,8 @@ pub enum EventRoleType {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(EventRoleType, "EventRoleType");
+impl_enumasstring_xml_value!(EventRoleType, "EventRoleType");
+impl_from_uri!(EventRoleType);
 
 impl From<EnumAsString> for EventRoleType {
     fn from(f: EnumAsString) -> Self {
@@ -310,7 +320,8 @@ mod test {
                 attribution: data.conclusion_data.attribution,
                 event_role_type: Some(EventRoleType::Witness),
                 details: Some("details".to_string()),
-                person: ResourceReference::from("http://identifier/for/person/1")
+                person: ResourceReference::from("http://identifier/for/person/1"),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -374,7 +385,8 @@ mod test {
                 attribution: data.conclusion_data.attribution,
                 event_role_type: None,
                 details: None,
-                person: ResourceReference::from("http://identifier/for/person/1")
+                person: ResourceReference::from("http://identifier/for/person/1"),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -394,6 +406,7 @@ mod test {
             event_role_type: Some(EventRoleType::Witness),
             details: Some("details".to_string()),
             person: ResourceReference::from("http://identifier/for/person/1"),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&event_role).unwrap();
@@ -419,6 +432,7 @@ mod test {
             event_role_type: None,
             details: None,
             person: ResourceReference::from("http://identifier/for/person/1"),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&event_role).unwrap();
@@ -436,7 +450,7 @@ mod test {
             <person resource="http://identifier/for/person/1" />
         </EventRole>"#;
 
-        let event_role: EventRole = yaserde::de::from_str(xml).unwrap();
+        let event_role: EventRole = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(
             event_role,
@@ -450,7 +464,8 @@ mod test {
                 attribution: None,
                 event_role_type: None,
                 details: None,
-                person: ResourceReference::from("http://identifier/for/person/1")
+                person: ResourceReference::from("http://identifier/for/person/1"),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -468,13 +483,14 @@ mod test {
             event_role_type: None,
             details: None,
             person: ResourceReference::from("http://identifier/for/person/1"),
+            extensions: Extensions::default(),
         };
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&event_role, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&event_role, config).unwrap();
 
         assert_eq!(
             xml,
@@ -491,8 +507,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: EventRole) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: EventRole = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: EventRole = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
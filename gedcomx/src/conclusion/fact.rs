@@ -3,11 +3,10 @@ use std::{fmt, str::FromStr};
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, Date, EnumAsString, GedcomxError, Id, Lang, Note, PlaceReference,
-    Qualifier, ResourceReference, Result, SourceReference, Uri,
+    Attribution, ConfidenceLevel, Date, EnumAsString, Extensions, Field, GedcomxError, Id, Lang,
+    Note, PlaceReference, Qualifier, ResourceReference, Result, SourceReference, Uri,
 };
 
 /// A data item that is presumed to be true about a specific subject, such as a
@@ -50,20 +49,13 @@ use crate::{
 /// between instances of the two data types, although an indirect association
 /// can be found via the event role.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Default, Clone)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
 #[non_exhaustive]
 pub struct Fact {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -71,7 +63,6 @@ pub struct Fact {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -79,39 +70,31 @@ pub struct Fact {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// The type of the fact.
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub fact_type: FactType,
 
     /// The date of applicability of the fact.
-    #[yaserde(prefix = "gx")]
     pub date: Option<Date>,
 
     /// A reference to the place applicable to this fact.
-    #[yaserde(prefix = "gx")]
     pub place: Option<PlaceReference>,
 
     /// The value of the fact.
-    #[yaserde(prefix = "gx")]
     pub value: Option<String>,
 
     /// Qualifiers to add additional details about the fact.
@@ -119,9 +102,39 @@ pub struct Fact {
     /// If present, use of a
     /// [`FactQualifier`](crate::FactQualifier) is
     /// RECOMMENDED.
-    #[yaserde(rename = "qualifier", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub qualifiers: Vec<Qualifier>,
+
+    /// The fields of a record that the data was extracted from.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fields: Vec<Field>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Fact: "Fact" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            fact_type: "type",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            date: gx "date",
+            place: gx "place",
+            value: gx "value",
+            qualifiers: gx "qualifier",
+            fields: gx "field",
+        }
+        @extensions
+    }
 }
 
 impl Fact {
@@ -152,6 +165,8 @@ impl Fact {
             place,
             value,
             qualifiers,
+            fields: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -213,8 +228,13 @@ impl FactBuilder {
         self
     }
 
+    pub fn field(&mut self, field: Field) -> &mut Self {
+        self.0.fields.push(field);
+        self
+    }
+
     pub fn build(&self) -> Fact {
-        Fact::new(
+        let mut fact = Fact::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.sources.clone(),
@@ -227,7 +247,9 @@ impl FactBuilder {
             self.0.place.clone(),
             self.0.value.clone(),
             self.0.qualifiers.clone(),
-        )
+        );
+        fact.fields.clone_from(&self.0.fields);
+        fact
     }
 }
 
@@ -506,7 +528,8 @@ pub enum FactType {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(FactType, "FactType");
+impl_enumasstring_xml_value!(FactType, "FactType");
+impl_from_uri!(FactType);
 
 impl fmt::Display for FactType {
     #[allow(clippy::too_many_lines)]
@@ -986,14 +1009,19 @@ mod test {
                 fact_type: FactType::Birth,
                 place: Some(PlaceReference {
                     original: Some("This is a place reference".to_string()),
-                    description_ref: Some("D-1".into())
+                    description_ref: Some("D-1".into()),
+                    fields: vec![],
+                    extensions: Extensions::default(),
                 }),
                 value: Some("the original value of the fact".to_string()),
                 qualifiers: vec![Qualifier {
                     name: FactQualifier::Age.into(),
-                    value: Some("val".into())
+                    value: Some("val".into()),
+                    extensions: Extensions::default(),
                 }],
-                date: Some(Date::new(Some("date"), None))
+                date: Some(Date::new(Some("date"), None)),
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -1001,7 +1029,7 @@ mod test {
     #[test]
     fn xml_deserialize() {
         let xml = "<Fact xmlns=\"http://gedcomx.org/v1/\" type=\"http://gedcomx.org/Award\"><value>Fact value</value><qualifier name=\"http://gedcomx.org/Cause\">Just because</qualifier></Fact>";
-        let fact: Fact = yaserde::de::from_str(xml).unwrap();
+        let fact: Fact = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(
             fact,
@@ -1071,7 +1099,9 @@ mod test {
                 place: None,
                 value: None,
                 qualifiers: vec![],
-                date: None
+                date: None,
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -1092,13 +1122,18 @@ mod test {
             place: Some(PlaceReference {
                 original: Some("This is a place reference".to_string()),
                 description_ref: Some("D-1".into()),
+                fields: vec![],
+                extensions: Extensions::default(),
             }),
             value: Some("the original value of the fact".to_string()),
             qualifiers: vec![Qualifier {
                 name: FactQualifier::Age.into(),
                 value: Some("val".into()),
+                extensions: Extensions::default(),
             }],
             date: Some(Date::new(Some("date"), None)),
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&fact).unwrap();
@@ -1115,11 +1150,11 @@ mod test {
             .value("Fact value")
             .qualifier(Qualifier::new(FactQualifier::Cause, Some("Just because")))
             .build();
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&fact, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&fact, config).unwrap();
         assert_eq!(
             xml,
             "<Fact xmlns=\"http://gedcomx.org/v1/\" type=\"http://gedcomx.org/Award\"><value>Fact value</value><qualifier name=\"http://gedcomx.org/Cause\">Just because</qualifier></Fact>"
@@ -1143,6 +1178,8 @@ mod test {
             value: None,
             qualifiers: vec![],
             date: None,
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&fact).unwrap();
@@ -1162,8 +1199,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Fact) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Fact = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Fact = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
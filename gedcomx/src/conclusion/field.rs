@@ -0,0 +1,444 @@
+use std::fmt;
+
+use quickcheck::{Arbitrary, Gen};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::{
+    Attribution, ConfidenceLevel, EnumAsString, Extensions, Id, Lang, Note, ResourceReference,
+    SourceReference, Uri,
+};
+
+/// A piece of data extracted from a record, such as a name or a date, along
+/// with the values that were read from the record and interpreted from it.
+///
+/// Defined by the [GEDCOM X Record Extensions](https://github.com/FamilySearch/gedcomx-record/blob/master/specifications/record-specification.md).
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[non_exhaustive]
+pub struct Field {
+    /// An identifier for the field.
+    pub id: Option<Id>,
+
+    /// The type of the field, such as `http://gedcomx.org/Given`.
+    #[serde(rename = "type")]
+    pub field_type: Option<Uri>,
+
+    /// The values of the field.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub values: Vec<FieldValue>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Field: "field" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            field_type: "type",
+        }
+        elements {
+            values: gx "value",
+        }
+        @extensions
+    }
+}
+
+impl Field {
+    pub fn new(id: Option<Id>, field_type: Option<Uri>, values: Vec<FieldValue>) -> Self {
+        Self {
+            id,
+            field_type,
+            values,
+            extensions: Extensions::default(),
+        }
+    }
+
+    pub fn builder() -> FieldBuilder {
+        FieldBuilder::new()
+    }
+}
+
+impl Arbitrary for Field {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::builder()
+            .id(Id::arbitrary(g))
+            .field_type(Uri::arbitrary(g))
+            .value(FieldValue::arbitrary(g))
+            .build()
+    }
+}
+
+pub struct FieldBuilder(Field);
+
+impl FieldBuilder {
+    pub(crate) fn new() -> Self {
+        Self(Field::default())
+    }
+
+    pub fn id<I: Into<Id>>(&mut self, id: I) -> &mut Self {
+        self.0.id = Some(id.into());
+        self
+    }
+
+    pub fn field_type<I: Into<Uri>>(&mut self, field_type: I) -> &mut Self {
+        self.0.field_type = Some(field_type.into());
+        self
+    }
+
+    pub fn value(&mut self, value: FieldValue) -> &mut Self {
+        self.0.values.push(value);
+        self
+    }
+
+    pub fn build(&self) -> Field {
+        Field::new(
+            self.0.id.clone(),
+            self.0.field_type.clone(),
+            self.0.values.clone(),
+        )
+    }
+}
+
+/// A value of a [`Field`].
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct FieldValue {
+    /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
+    pub id: Option<Id>,
+
+    /// The locale identifier for the conclusion.
+    pub lang: Option<Lang>,
+
+    /// The list of references to the sources of related to this conclusion.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub sources: Vec<SourceReference>,
+
+    /// A reference to the analysis document explaining the analysis that went
+    /// into this conclusion. If provided, MUST resolve to an instance of
+    /// [Document](crate::Document) of type
+    /// [Analysis](crate::DocumentType::Analysis).
+    pub analysis: Option<ResourceReference>,
+
+    /// A list of notes about this conclusion.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub notes: Vec<Note>,
+
+    /// The level of confidence the contributor has about the data.
+    pub confidence: Option<ConfidenceLevel>,
+
+    /// The attribution of this conclusion.
+    /// If not provided, the attribution of the containing data set (e.g. file)
+    /// of the conclusion is assumed.
+    pub attribution: Option<Attribution>,
+
+    /// The type of the value.
+    #[serde(rename = "type")]
+    pub field_value_type: Option<FieldValueType>,
+
+    /// The id of the label that applies to the value, which is described by a
+    /// [`FieldValueDescriptor`](crate::FieldValueDescriptor).
+    pub label_id: Option<String>,
+
+    /// The text of the value.
+    pub text: Option<String>,
+
+    /// The data type of the text, such as
+    /// `http://www.w3.org/2001/XMLSchema#integer`. If not provided, the text
+    /// is a string.
+    pub datatype: Option<Uri>,
+
+    /// A reference to the resource the value identifies, such as a place or a
+    /// date.
+    pub resource: Option<Uri>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    FieldValue: "FieldValue" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            field_value_type: "type",
+            label_id: "labelId",
+            datatype: "datatype",
+            resource: "resource",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            text: gx "text",
+        }
+        @extensions
+    }
+}
+
+impl FieldValue {
+    pub fn new(
+        id: Option<Id>,
+        lang: Option<Lang>,
+        sources: Vec<SourceReference>,
+        analysis: Option<ResourceReference>,
+        notes: Vec<Note>,
+        confidence: Option<ConfidenceLevel>,
+        attribution: Option<Attribution>,
+        field_value_type: Option<FieldValueType>,
+        label_id: Option<String>,
+        text: Option<String>,
+        datatype: Option<Uri>,
+        resource: Option<Uri>,
+    ) -> Self {
+        Self {
+            id,
+            lang,
+            sources,
+            analysis,
+            notes,
+            confidence,
+            attribution,
+            field_value_type,
+            label_id,
+            text,
+            datatype,
+            resource,
+            extensions: Extensions::default(),
+        }
+    }
+
+    pub fn builder<I: Into<String>>(text: I) -> FieldValueBuilder {
+        FieldValueBuilder::new(text)
+    }
+}
+
+impl Arbitrary for FieldValue {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let mut value = Self::builder(crate::arbitrary_trimmed(g))
+            .id(Id::arbitrary(g))
+            .lang(Lang::arbitrary(g))
+            .note(Note::arbitrary(g))
+            .confidence(ConfidenceLevel::arbitrary(g))
+            .attribution(Attribution::arbitrary(g))
+            .field_value_type(FieldValueType::arbitrary(g))
+            .label_id(crate::arbitrary_trimmed(g))
+            .datatype(Uri::arbitrary(g))
+            .resource(Uri::arbitrary(g))
+            .build();
+
+        value.analysis = Some(ResourceReference::arbitrary(g));
+        value.sources = vec![SourceReference::arbitrary(g)];
+
+        value
+    }
+}
+
+pub struct FieldValueBuilder(FieldValue);
+
+impl FieldValueBuilder {
+    conclusion_builder_functions!(FieldValue);
+
+    pub(crate) fn new<I: Into<String>>(text: I) -> Self {
+        Self(FieldValue {
+            text: Some(text.into()),
+            ..FieldValue::default()
+        })
+    }
+
+    pub fn field_value_type(&mut self, field_value_type: FieldValueType) -> &mut Self {
+        self.0.field_value_type = Some(field_value_type);
+        self
+    }
+
+    pub fn label_id<I: Into<String>>(&mut self, label_id: I) -> &mut Self {
+        self.0.label_id = Some(label_id.into());
+        self
+    }
+
+    pub fn datatype<I: Into<Uri>>(&mut self, datatype: I) -> &mut Self {
+        self.0.datatype = Some(datatype.into());
+        self
+    }
+
+    pub fn resource<I: Into<Uri>>(&mut self, resource: I) -> &mut Self {
+        self.0.resource = Some(resource.into());
+        self
+    }
+
+    pub fn build(&self) -> FieldValue {
+        FieldValue::new(
+            self.0.id.clone(),
+            self.0.lang.clone(),
+            self.0.sources.clone(),
+            self.0.analysis.clone(),
+            self.0.notes.clone(),
+            self.0.confidence.clone(),
+            self.0.attribution.clone(),
+            self.0.field_value_type.clone(),
+            self.0.label_id.clone(),
+            self.0.text.clone(),
+            self.0.datatype.clone(),
+            self.0.resource.clone(),
+        )
+    }
+}
+
+/// Standard field value types.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Eq)]
+#[non_exhaustive]
+#[serde(from = "EnumAsString", into = "EnumAsString")]
+pub enum FieldValueType {
+    /// The value as it was read from the record.
+    Original,
+
+    /// The value as it was interpreted, such as a standardized place or date.
+    Interpreted,
+    Custom(Uri),
+}
+
+impl_enumasstring_xml_value!(FieldValueType, "FieldValueType");
+impl_from_uri!(FieldValueType);
+
+impl From<EnumAsString> for FieldValueType {
+    fn from(f: EnumAsString) -> Self {
+        match f.0.as_ref() {
+            "http://gedcomx.org/Original" => Self::Original,
+            "http://gedcomx.org/Interpreted" => Self::Interpreted,
+            _ => Self::Custom(f.0.into()),
+        }
+    }
+}
+
+impl fmt::Display for FieldValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Original => write!(f, "http://gedcomx.org/Original"),
+            Self::Interpreted => write!(f, "http://gedcomx.org/Interpreted"),
+            Self::Custom(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+impl Default for FieldValueType {
+    fn default() -> Self {
+        Self::Custom(Uri::default())
+    }
+}
+
+impl Arbitrary for FieldValueType {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let options = vec![
+            Self::Original,
+            Self::Interpreted,
+            Self::Custom(Uri::arbitrary(g)),
+        ];
+
+        g.choose(&options).unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::xmlcodec::Config;
+
+    fn field() -> Field {
+        Field::builder()
+            .field_type("http://gedcomx.org/Given")
+            .value(
+                FieldValue::builder("Jno.")
+                    .field_value_type(FieldValueType::Original)
+                    .label_id("PR_NAME_GN")
+                    .build(),
+            )
+            .value(
+                FieldValue::builder("John")
+                    .field_value_type(FieldValueType::Interpreted)
+                    .label_id("PR_NAME_GN")
+                    .confidence(ConfidenceLevel::High)
+                    .build(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn json_deserialize() {
+        let json = r#"{
+            "type" : "http://gedcomx.org/Given",
+            "values" : [ {
+                "type" : "http://gedcomx.org/Original",
+                "labelId" : "PR_NAME_GN",
+                "text" : "Jno."
+            }, {
+                "type" : "http://gedcomx.org/Interpreted",
+                "labelId" : "PR_NAME_GN",
+                "confidence" : "http://gedcomx.org/High",
+                "text" : "John"
+            } ]
+        }"#;
+
+        let field: Field = serde_json::from_str(json).unwrap();
+        assert_eq!(field, self::field());
+    }
+
+    #[test]
+    fn json_serialize() {
+        let json = serde_json::to_string(&field()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"http://gedcomx.org/Given","values":[{"type":"http://gedcomx.org/Original","labelId":"PR_NAME_GN","text":"Jno."},{"confidence":"http://gedcomx.org/High","type":"http://gedcomx.org/Interpreted","labelId":"PR_NAME_GN","text":"John"}]}"#
+        );
+    }
+
+    #[test]
+    fn xml_deserialize() {
+        let xml = r#"<field xmlns="http://gedcomx.org/v1/" type="http://gedcomx.org/Given">
+            <value type="http://gedcomx.org/Original" labelId="PR_NAME_GN">
+                <text>Jno.</text>
+            </value>
+            <value confidence="http://gedcomx.org/High" type="http://gedcomx.org/Interpreted" labelId="PR_NAME_GN">
+                <text>John</text>
+            </value>
+        </field>"#;
+
+        let field: Field = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(field, self::field());
+    }
+
+    #[test]
+    fn xml_serialize() {
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        let xml = crate::xmlcodec::to_string(&field(), config).unwrap();
+        assert_eq!(
+            xml,
+            r#"<field xmlns="http://gedcomx.org/v1/" type="http://gedcomx.org/Given"><value type="http://gedcomx.org/Original" labelId="PR_NAME_GN"><text>Jno.</text></value><value confidence="http://gedcomx.org/High" type="http://gedcomx.org/Interpreted" labelId="PR_NAME_GN"><text>John</text></value></field>"#
+        );
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_json(input: Field) -> bool {
+        let json = serde_json::to_string(&input).unwrap();
+        let from_json: Field = serde_json::from_str(&json).unwrap();
+        input == from_json
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_xml(input: Field) -> bool {
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Field = crate::xmlcodec::from_str(&xml).unwrap();
+        input == from_xml
+    }
+}
@@ -3,29 +3,21 @@ use std::fmt;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, EnumAsString, Id, Lang, Note, ResourceReference, SourceReference,
-    Uri,
+    Attribution, ConfidenceLevel, EnumAsString, Extensions, Field, Id, Lang, Note,
+    ResourceReference, SourceReference, Uri,
 };
 
 /// A gender of a person.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct Gender {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -33,7 +25,6 @@ pub struct Gender {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -41,28 +32,50 @@ pub struct Gender {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// The type of the gender.
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub gender_type: GenderType,
+
+    /// The fields of a record that the data was extracted from.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fields: Vec<Field>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Gender: "Gender" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            gender_type: "type",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            fields: gx "field",
+        }
+        @extensions
+    }
 }
 
 impl Gender {
@@ -85,6 +98,8 @@ impl Gender {
             confidence,
             attribution,
             gender_type,
+            fields: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -122,8 +137,13 @@ impl GenderBuilder {
         })
     }
 
+    pub fn field(&mut self, field: Field) -> &mut Self {
+        self.0.fields.push(field);
+        self
+    }
+
     pub fn build(&self) -> Gender {
-        Gender::new(
+        let mut gender = Gender::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.sources.clone(),
@@ -132,7 +152,9 @@ impl GenderBuilder {
             self.0.confidence.clone(),
             self.0.attribution.clone(),
             self.0.gender_type.clone(),
-        )
+        );
+        gender.fields.clone_from(&self.0.fields);
+        gender
     }
 }
 
@@ -171,7 +193,8 @@ impl Default for GenderType {
     }
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(GenderType, "GenderType");
+impl_enumasstring_xml_value!(GenderType, "GenderType");
+impl_from_uri!(GenderType);
 
 impl From<EnumAsString> for GenderType {
     fn from(f: EnumAsString) -> Self {
@@ -273,6 +296,8 @@ mod test {
                 confidence: data.conclusion_data.confidence,
                 attribution: data.conclusion_data.attribution,
                 gender_type: GenderType::Male,
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -281,7 +306,7 @@ mod test {
     fn xml_deserialize() {
         let xml = "<Gender xmlns=\"http://gedcomx.org/v1/\" type=\"http://gedcomx.org/Male\" />";
 
-        let gender: Gender = yaserde::de::from_str(xml).unwrap();
+        let gender: Gender = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(gender, Gender::builder(GenderType::Male).build());
     }
@@ -299,6 +324,8 @@ mod test {
             confidence: data.conclusion_data.confidence,
             attribution: data.conclusion_data.attribution,
             gender_type: GenderType::Male,
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&gender).unwrap();
@@ -313,11 +340,11 @@ mod test {
     fn xml_serialize() {
         let gender = Gender::builder(GenderType::Male).build();
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&gender, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&gender, config).unwrap();
 
         assert_eq!(
             xml,
@@ -334,8 +361,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Gender) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Gender = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Gender = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -3,11 +3,10 @@ use std::vec;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, Date, EvidenceReference, GroupRole, Id, Identifier, Lang, Note,
-    PlaceReference, ResourceReference, SourceReference, TextValue,
+    Attribution, ConfidenceLevel, Date, EvidenceReference, Extensions, GroupRole, Id, Identifier,
+    Lang, Link, Note, PlaceReference, ResourceReference, SourceReference, TextValue,
 };
 
 /// A group of of persons.
@@ -17,20 +16,13 @@ use crate::{
 /// Examples of a group could include plantations, orphanages, or military
 /// units.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct Group {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -38,7 +30,6 @@ pub struct Group {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -46,26 +37,21 @@ pub struct Group {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// Whether this subject is to be constrained as an extracted conclusion.
-    #[yaserde(attribute)]
     pub extracted: Option<bool>,
 
     /// References to other subjects that support this subject.
@@ -74,7 +60,6 @@ pub struct Group {
     /// the same type as this instance (e.g., if the subject is an instance of
     /// Person, all of its evidence references must resolve to instances of
     /// Person).
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub evidence: Vec<EvidenceReference>,
 
@@ -89,12 +74,10 @@ pub struct Group {
     /// used for multimedia references and therefore MUST resolve to a
     /// `SourceDescription` of the resource, which in turn provides a reference
     /// to the resource itself.
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub media: Vec<SourceReference>,
 
     /// A list of identifiers for the subject.
-    #[yaserde(rename = "identifier", prefix = "gx")]
     #[serde(
         skip_serializing_if = "Vec::is_empty",
         default,
@@ -103,21 +86,55 @@ pub struct Group {
     pub identifiers: Vec<Identifier>,
 
     /// A list of names of the group. The list must contain at least one name.
-    #[yaserde(rename = "name", prefix = "gx")]
     pub names: Vec<TextValue>,
 
     /// The date of applicability of the group.
-    #[yaserde(prefix = "gx")]
     pub date: Option<Date>,
 
     /// A reference to the place applicable to this group.
-    #[yaserde(prefix = "gx")]
     pub place: Option<PlaceReference>,
 
     /// Information about how persons were associated with the group.
-    #[yaserde(rename = "role", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub roles: Vec<GroupRole>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Group: "Group" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            extracted: "extracted",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            evidence: gx "evidence",
+            media: gx "media",
+            identifiers: gx "identifier",
+            names: gx "name",
+            date: gx "date",
+            place: gx "place",
+            roles: gx "role",
+            links: gx "link",
+        }
+        @extensions
+    }
 }
 
 impl Group {
@@ -154,6 +171,8 @@ impl Group {
             date,
             place,
             roles,
+            links: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -218,8 +237,13 @@ impl GroupBuilder {
         self
     }
 
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
     pub fn build(&self) -> Group {
-        Group::new(
+        let mut group = Group::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.sources.clone(),
@@ -235,7 +259,9 @@ impl GroupBuilder {
             self.0.date.clone(),
             self.0.place.clone(),
             self.0.roles.clone(),
-        )
+        );
+        group.links.clone_from(&self.0.links);
+        group
     }
 }
 
@@ -277,7 +303,7 @@ mod test {
     fn xml_deserialize() {
         let xml = r#"<Group xmlns="http://gedcomx.org/v1/"><name lang="en">Monticello Plantation</name><name lang="zh">monticello种植园</name><date><original>date</original></date><place><original>place</original></place></Group>"#;
 
-        let group: Group = yaserde::de::from_str(xml).unwrap();
+        let group: Group = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(
             group,
@@ -313,11 +339,11 @@ mod test {
             .place(PlaceReference::new(Some("place"), None))
             .build();
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&group, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&group, config).unwrap();
 
         assert_eq!(
             xml,
@@ -334,8 +360,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Group) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Group = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Group = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -3,29 +3,21 @@ use std::{convert::TryInto, fmt};
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, Date, EnumAsString, Id, Lang, Note, Person, ResourceReference,
-    Result, SourceReference, Uri,
+    Attribution, ConfidenceLevel, Date, EnumAsString, Extensions, Id, Lang, Note, Person,
+    ResourceReference, Result, SourceReference, Uri,
 };
 
 /// A role of a person in a group.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct GroupRole {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -33,7 +25,6 @@ pub struct GroupRole {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -41,41 +32,58 @@ pub struct GroupRole {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// Reference to the group participant.	MUST resolve to an instance of
     /// [`Person`](crate::Person).
-    #[yaserde(prefix = "gx")]
     pub person: ResourceReference,
 
     /// The date of applicability of the role.
-    #[yaserde(prefix = "gx")]
     pub date: Option<Date>,
 
     /// Details about the role of the participant in the group.
-    #[yaserde(prefix = "gx")]
     pub details: Option<String>,
 
     /// The participant's role.
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub group_role_type: Option<GroupRoleType>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    GroupRole: "GroupRole" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            group_role_type: "type",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            person: gx "person",
+            date: gx "date",
+            details: gx "details",
+        }
+        @extensions
+    }
 }
 
 impl GroupRole {
@@ -104,6 +112,7 @@ impl GroupRole {
             date,
             details,
             group_role_type,
+            extensions: Extensions::default(),
         }
     }
 
@@ -190,7 +199,8 @@ pub enum GroupRoleType {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(GroupRoleType, "GroupRoleType");
+impl_enumasstring_xml_value!(GroupRoleType, "GroupRoleType");
+impl_from_uri!(GroupRoleType);
 
 impl From<EnumAsString> for GroupRoleType {
     fn from(f: EnumAsString) -> Self {
@@ -289,11 +299,15 @@ mod test {
                 attribution: data.conclusion_data.attribution,
                 date: Some(Date {
                     original: Some("the original text".to_string()),
-                    formal: None
+                    formal: None,
+                    invalid_formal: None,
+                    fields: vec![],
+                    extensions: Extensions::default(),
                 }),
                 group_role_type: Some(GroupRoleType::Custom("testType".into())),
                 details: Some("details".to_string()),
-                person: ResourceReference::from("http://identifier/for/person/1")
+                person: ResourceReference::from("http://identifier/for/person/1"),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -301,7 +315,7 @@ mod test {
     #[test]
     fn xml_deserialize() {
         let xml = r##"<GroupRole xmlns="http://gedcomx.org/v1/" type="hello"><person resource="#pid" /><date><original>date</original></date><details>details</details></GroupRole>"##;
-        let group_role: GroupRole = yaserde::de::from_str(xml).unwrap();
+        let group_role: GroupRole = crate::xmlcodec::from_str(xml).unwrap();
 
         let person = Person::builder().id("pid").build();
 
@@ -376,7 +390,8 @@ mod test {
                 date: None,
                 group_role_type: None,
                 details: None,
-                person: ResourceReference::from("http://identifier/for/person/1")
+                person: ResourceReference::from("http://identifier/for/person/1"),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -396,10 +411,14 @@ mod test {
             date: Some(Date {
                 original: Some("the original text".to_string()),
                 formal: None,
+                invalid_formal: None,
+                fields: vec![],
+                extensions: Extensions::default(),
             }),
             group_role_type: Some(GroupRoleType::Custom("testType".into())),
             details: Some("details".to_string()),
             person: ResourceReference::from("http://identifier/for/person/1"),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&group_role).unwrap();
@@ -421,11 +440,11 @@ mod test {
             .group_role_type(GroupRoleType::Custom("hello".into()))
             .build();
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&group_role, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&group_role, config).unwrap();
 
         assert_eq!(
             xml,
@@ -449,6 +468,7 @@ mod test {
             group_role_type: None,
             details: None,
             person: ResourceReference::from("http://identifier/for/person/1"),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&group_role).unwrap();
@@ -468,8 +488,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: GroupRole) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: GroupRole = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: GroupRole = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -3,7 +3,10 @@ use std::fmt;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 
-use crate::{EnumAsString, Uri};
+use crate::{
+    DeserializeError, EnumAsString, Uri,
+    xmlcodec::{self, FromXml, StartElement, ToXml, XmlReader, XmlValue, XmlWriter},
+};
 
 /// An identifier of a genealogical resource.
 ///
@@ -49,95 +52,50 @@ impl Identifier {
     }
 }
 
-impl yaserde::YaSerialize for Identifier {
-    fn serialize<W: std::io::Write>(
-        &self,
-        writer: &mut yaserde::ser::Serializer<W>,
-    ) -> Result<(), String> {
-        let provided_start_event_name = writer.get_start_event_name();
-        let start_name = provided_start_event_name.as_deref().unwrap_or("identifier");
-        let mut start_builder = xml::writer::XmlEvent::start_element(start_name);
+// The type is an attribute and the value is the text of the element.
+impl ToXml for Identifier {
+    const NAME: &'static str = "identifier";
 
-        let identifier_type_value;
+    fn write(&self, writer: &mut XmlWriter, name: &str) -> crate::Result<()> {
+        writer.start(name);
         if let Some(t) = &self.identifier_type {
-            identifier_type_value = t.to_string();
-            start_builder = start_builder.attr("type", &identifier_type_value);
+            writer.attribute("type", &t.to_xml_value());
         }
-
-        writer.write(start_builder).map_err(|e| e.to_string())?;
-        writer
-            .write(xml::writer::XmlEvent::characters(&self.value.to_string()))
-            .map_err(|e| e.to_string())?;
-        writer
-            .write(xml::writer::XmlEvent::end_element())
-            .map_err(|e| e.to_string())?;
+        writer.text(&self.value.to_xml_value());
+        writer.end(name);
         Ok(())
     }
 
-    fn serialize_attributes(
-        &self,
-        attributes: Vec<xml::attribute::OwnedAttribute>,
-        namespace: xml::namespace::Namespace,
-    ) -> Result<
-        (
-            Vec<xml::attribute::OwnedAttribute>,
-            xml::namespace::Namespace,
-        ),
-        String,
-    > {
-        Ok((attributes, namespace))
+    // Identifiers are grouped by type in JSON, so their order isn't kept.
+    fn canonical_order(values: &mut [&Self]) {
+        values.sort_by_cached_key(|identifier| {
+            (
+                identifier
+                    .identifier_type
+                    .as_ref()
+                    .map(|t| t.to_xml_value().into_owned()),
+                identifier.value.to_xml_value().into_owned(),
+            )
+        });
     }
 }
 
-impl yaserde::YaDeserialize for Identifier {
-    fn deserialize<R: std::io::Read>(
-        reader: &mut yaserde::de::Deserializer<R>,
-    ) -> Result<Self, String> {
-        let mut identifier_type = None;
-
-        if let xml::reader::XmlEvent::StartElement {
-            name: element_name,
-            attributes,
-            ..
-        } = reader.next_event()?
-        {
-            let expected_name = "identifier".to_owned();
-            if element_name.local_name != expected_name {
-                return Err(format!(
-                    "Wrong StartElement name: {element_name}, expected: {expected_name}"
-                ));
-            }
-
-            if attributes.len() > 1 {
-                return Err(format!(
-                    "Too many attributes: {attributes:?}, expected 0 or 1"
-                ));
-            }
-
-            if let Some(t) = attributes.first() {
-                let expected_name = "type";
-                if t.name.local_name != "type" {
-                    return Err(format!(
-                        "Wrong attribute name: {}, expected: {expected_name}",
-                        t.name
-                    ));
-                }
-
-                identifier_type = Some(IdentifierType::from(EnumAsString(t.value.clone())));
-            }
-        } else {
-            return Err("StartElement missing".to_string());
-        }
-
-        if let xml::reader::XmlEvent::Characters(text) = reader.next_event()? {
-            Ok(Self {
-                value: text.into(),
-                identifier_type,
-                value_in_vec: true,
-            })
-        } else {
-            Err("Uri missing".to_string())
-        }
+impl FromXml for Identifier {
+    fn read<R: std::io::Read>(
+        reader: &mut XmlReader<R>,
+        start: StartElement,
+    ) -> Result<Self, DeserializeError> {
+        let identifier_type = start
+            .attributes
+            .into_iter()
+            .find(|a| xmlcodec::is_attribute(&a.name, "type"))
+            .map(|a| IdentifierType::from(EnumAsString(a.value)));
+
+        Ok(Self {
+            value: reader.read_text()?.into(),
+            identifier_type,
+            value_in_vec: true,
+        })
     }
 }
 
@@ -148,7 +106,7 @@ impl Arbitrary for Identifier {
 }
 
 pub mod serde_vec_identifier_to_map {
-    use std::{collections::HashMap, fmt};
+    use std::{collections::BTreeMap, fmt};
 
     use serde::{
         Deserialize, Serialize,
@@ -171,9 +129,10 @@ pub mod serde_vec_identifier_to_map {
     where
         S: Serializer,
     {
-        let mut hashmap: HashMap<String, VecOrUri> = HashMap::with_capacity(identifiers.len());
+        // Grouped in a BTreeMap so the types always come out in the same order.
+        let mut grouped: BTreeMap<String, VecOrUri> = BTreeMap::new();
         for id in identifiers {
-            let e = hashmap
+            let e = grouped
                 .entry(
                     id.identifier_type
                         .as_ref()
@@ -189,22 +148,16 @@ pub mod serde_vec_identifier_to_map {
 
             match e {
                 VecOrUri::Uri(u) => *u = id.value.clone(),
-                VecOrUri::Vec(v) => {
-                    v.push(id.value.clone());
-                    v.sort_by_key(std::string::ToString::to_string);
-                }
+                VecOrUri::Vec(v) => v.push(id.value.clone()),
             }
         }
 
-        let mut map = serializer.serialize_map(Some(identifiers.len()))?;
-
-        let mut keys = hashmap.keys().collect::<Vec<_>>();
-        keys.sort();
-
-        for k in keys {
-            if let Some(v) = hashmap.get(k) {
-                map.serialize_entry(k, v)?;
+        let mut map = serializer.serialize_map(Some(grouped.len()))?;
+        for (k, mut v) in grouped {
+            if let VecOrUri::Vec(values) = &mut v {
+                values.sort_by_key(std::string::ToString::to_string);
             }
+            map.serialize_entry(&k, &v)?;
         }
 
         map.end()
@@ -314,7 +267,8 @@ impl Default for IdentifierType {
     }
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(IdentifierType, "IdentifierType");
+impl_enumasstring_xml_value!(IdentifierType, "IdentifierType");
+impl_from_uri!(IdentifierType);
 
 impl From<EnumAsString> for IdentifierType {
     fn from(f: EnumAsString) -> Self {
@@ -363,9 +317,9 @@ impl Arbitrary for IdentifierType {
 mod test {
     use pretty_assertions::assert_eq;
     use serde::{Deserialize, Serialize};
-    use yaserde::ser::Config;
 
     use super::*;
+    use crate::xmlcodec::Config;
 
     #[derive(Serialize, Deserialize, PartialEq, Debug)]
     struct TestIdentifierGroup {
@@ -445,7 +399,7 @@ mod test {
 
         assert_eq!(
             expected_identifier,
-            yaserde::de::from_str::<Identifier>(xml).unwrap()
+            crate::xmlcodec::from_str::<Identifier>(xml).unwrap()
         );
     }
 
@@ -455,9 +409,9 @@ mod test {
             "https://familysearch.org/platform/records/collections",
             Some(IdentifierType::Primary),
         );
-        let xml = yaserde::ser::to_string_with_config(
+        let xml = crate::xmlcodec::to_string(
             &identifier,
-            &Config {
+            Config {
                 write_document_declaration: false,
                 ..Config::default()
             },
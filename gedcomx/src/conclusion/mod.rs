@@ -1,3 +1,6 @@
+mod collection;
+pub use collection::{Collection, CollectionBuilder, CollectionContent};
+
 mod confidencelevel;
 pub use confidencelevel::ConfidenceLevel;
 
@@ -16,6 +19,9 @@ pub use event::{Event, EventBuilder, EventType};
 mod fact;
 pub use fact::{Fact, FactBuilder, FactQualifier, FactType};
 
+mod field;
+pub use field::{Field, FieldBuilder, FieldValue, FieldValueBuilder, FieldValueType};
+
 mod gender;
 pub use gender::{Gender, GenderBuilder, GenderType};
 
@@ -43,5 +49,8 @@ pub use placedescription::{PlaceDescription, PlaceDescriptionBuilder};
 mod placereference;
 pub use placereference::{PlaceReference, PlaceReferenceBuilder};
 
+mod recorddescriptor;
+pub use recorddescriptor::{FieldDescriptor, FieldValueDescriptor, RecordDescriptor};
+
 mod relationship;
 pub use relationship::{Relationship, RelationshipBuilder, RelationshipType};
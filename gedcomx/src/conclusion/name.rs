@@ -3,11 +3,10 @@ use std::{fmt, str::FromStr, vec};
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, Date, EnumAsString, GedcomxError, Id, Lang, Note, Qualifier,
-    ResourceReference, Result, SourceReference, Uri,
+    Attribution, ConfidenceLevel, Date, EnumAsString, Extensions, Field, GedcomxError, Id, Lang,
+    Note, Qualifier, ResourceReference, Result, SourceReference, Uri,
 };
 
 /// A name of a person.
@@ -58,21 +57,14 @@ use crate::{
 /// Name2.nameForms[1].fullText=Sasha
 /// ```
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 #[serde(rename_all = "camelCase")]
 pub struct Name {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -80,7 +72,6 @@ pub struct Name {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -88,26 +79,21 @@ pub struct Name {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// The name type.
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub name_type: Option<NameType>,
 
@@ -118,12 +104,39 @@ pub struct Name {
     /// At least one name form MUST be provided. All included name forms SHOULD
     /// be representations of the same name, and NOT variants of
     /// the name (i.e., not nicknames or spelling variations).
-    #[yaserde(rename = "nameForm", prefix = "gx")]
     pub name_forms: Vec<NameForm>,
 
     /// The date of applicability of the name.
-    #[yaserde(prefix = "gx")]
     pub date: Option<Date>,
+
+    /// The fields of a record that the data was extracted from.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fields: Vec<Field>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Name: "Name" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            name_type: "type",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            name_forms: gx "nameForm",
+            date: gx "date",
+            fields: gx "field",
+        }
+        @extensions
+    }
 }
 
 impl Name {
@@ -150,6 +163,8 @@ impl Name {
             name_type,
             name_forms,
             date,
+            fields: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -214,8 +229,13 @@ impl NameBuilder {
         self
     }
 
+    pub fn field(&mut self, field: Field) -> &mut Self {
+        self.0.fields.push(field);
+        self
+    }
+
     pub fn build(&self) -> Name {
-        Name::new(
+        let mut name = Name::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.sources.clone(),
@@ -226,7 +246,9 @@ impl NameBuilder {
             self.0.name_type.clone(),
             self.0.name_forms.clone(),
             self.0.date.clone(),
-        )
+        );
+        name.fields.clone_from(&self.0.fields);
+        name
     }
 }
 
@@ -271,7 +293,8 @@ pub enum NameType {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(NameType, "NameType");
+impl_enumasstring_xml_value!(NameType, "NameType");
+impl_from_uri!(NameType);
 
 impl From<EnumAsString> for NameType {
     fn from(f: EnumAsString) -> Self {
@@ -387,29 +410,36 @@ impl Arbitrary for NameType {
 /// NameForm3.parts[2].value=Tchaikovsky
 /// ```
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
 #[serde(rename_all = "camelCase")]
 pub struct NameForm {
     /// The locale identifier for the name form.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// A full rendering of the name (or as much of the name as is known).
-    #[yaserde(rename = "fullText", prefix = "gx")]
     pub full_text: Option<String>,
 
     /// Any identified name parts from the name.
-    #[yaserde(rename = "part", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub parts: Vec<NamePart>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    NameForm: "NameForm" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            lang: "xml:lang",
+        }
+        elements {
+            full_text: gx "fullText",
+            parts: gx "part",
+        }
+        @extensions
+    }
 }
 
 impl NameForm {
@@ -418,6 +448,7 @@ impl NameForm {
             lang,
             full_text,
             parts,
+            extensions: Extensions::default(),
         }
     }
 
@@ -477,18 +508,10 @@ impl NameFormBuilder {
 /// Some name parts may have qualifiers to provide additional semantic meaning
 /// to the name part (e.g., "given name" or "surname").
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct NamePart {
     /// The type of the name part.
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub part_type: Option<NamePartType>,
 
@@ -499,7 +522,6 @@ pub struct NamePart {
     /// Fitzgerald Kennedy". If multiple terms are detailed in a single
     /// `NamePart`, these terms SHOULD be separated using the name separator
     /// appropriate to the locale applicable to the containing name form.
-    #[yaserde(attribute)]
     pub value: String,
 
     /// Qualifiers to add additional semantic meaning to the name part.
@@ -507,9 +529,30 @@ pub struct NamePart {
     /// If present, use of a
     /// [`NamePartQualifier`](crate::NamePartQualifier) is
     /// RECOMMENDED.
-    #[yaserde(rename = "qualifier", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub qualifiers: Vec<Qualifier>,
+
+    /// The fields of a record that the data was extracted from.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fields: Vec<Field>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    NamePart: "NamePart" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            part_type: "type",
+            value: "value",
+        }
+        elements {
+            qualifiers: gx "qualifier",
+            fields: gx "field",
+        }
+        @extensions
+    }
 }
 
 impl NamePart {
@@ -518,6 +561,8 @@ impl NamePart {
             part_type,
             value,
             qualifiers,
+            fields: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -560,12 +605,19 @@ impl NamePartBuilder {
         self
     }
 
+    pub fn field(&mut self, field: Field) -> &mut Self {
+        self.0.fields.push(field);
+        self
+    }
+
     pub fn build(&self) -> NamePart {
-        NamePart::new(
+        let mut name_part = NamePart::new(
             self.0.part_type.clone(),
             self.0.value.clone(),
             self.0.qualifiers.clone(),
-        )
+        );
+        name_part.fields.clone_from(&self.0.fields);
+        name_part
     }
 }
 
@@ -588,7 +640,8 @@ pub enum NamePartType {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(NamePartType, "NamePartType");
+impl_enumasstring_xml_value!(NamePartType, "NamePartType");
+impl_from_uri!(NamePartType);
 
 impl From<EnumAsString> for NamePartType {
     fn from(f: EnumAsString) -> Self {
@@ -776,7 +829,7 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use super::*;
-    use crate::TestData;
+    use crate::{FieldValue, FieldValueType, TestData};
 
     #[test]
     fn json_deserialize() {
@@ -854,15 +907,22 @@ mod test {
                         qualifiers: vec![
                             Qualifier {
                                 name: NamePartQualifier::Family.into(),
-                                value: None
+                                value: None,
+                                extensions: Extensions::default(),
                             },
                             Qualifier {
                                 name: NamePartQualifier::Patronymic.into(),
-                                value: None
+                                value: None,
+                                extensions: Extensions::default(),
                             }
-                        ]
-                    }]
-                }]
+                        ],
+                        fields: vec![],
+                        extensions: Extensions::default(),
+                    }],
+                    extensions: Extensions::default(),
+                }],
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -898,7 +958,7 @@ mod test {
             .name_type(NameType::BirthName)
             .build();
 
-        let deser: Name = yaserde::de::from_str(xml).unwrap();
+        let deser: Name = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(deser, name);
     }
@@ -964,8 +1024,11 @@ mod test {
                 name_forms: vec![NameForm {
                     lang: None,
                     full_text: None,
-                    parts: vec![]
-                }]
+                    parts: vec![],
+                    extensions: Extensions::default(),
+                }],
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -994,14 +1057,21 @@ mod test {
                         Qualifier {
                             name: NamePartQualifier::Family.into(),
                             value: None,
+                            extensions: Extensions::default(),
                         },
                         Qualifier {
                             name: NamePartQualifier::Patronymic.into(),
                             value: None,
+                            extensions: Extensions::default(),
                         },
                     ],
+                    fields: vec![],
+                    extensions: Extensions::default(),
                 }],
+                extensions: Extensions::default(),
             }],
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&name).unwrap();
@@ -1030,7 +1100,10 @@ mod test {
                 lang: None,
                 full_text: None,
                 parts: vec![],
+                extensions: Extensions::default(),
             }],
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&name).unwrap();
@@ -1070,11 +1143,11 @@ mod test {
             .name_type(NameType::BirthName)
             .build();
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&name, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&name, config).unwrap();
 
         assert_eq!(
             xml,
@@ -1082,6 +1155,58 @@ mod test {
         );
     }
 
+    #[test]
+    fn json_roundtrip_fields() {
+        let json = r#"{"nameForms":[{"fullText":"John Kunz","parts":[{"type":"http://gedcomx.org/Given","value":"John","fields":[{"type":"http://gedcomx.org/Given","values":[{"type":"http://gedcomx.org/Original","text":"Jno."}]}]}]}],"fields":[{"type":"http://gedcomx.org/Name","values":[{"type":"http://gedcomx.org/Original","text":"Jno. Kunz"}]}]}"#;
+
+        let field = |field_type: &str, text: &str| {
+            Field::builder()
+                .field_type(field_type)
+                .value(
+                    FieldValue::builder(text)
+                        .field_value_type(FieldValueType::Original)
+                        .build(),
+                )
+                .build()
+        };
+        let name_form = NameForm::builder()
+            .full_text("John Kunz")
+            .part(
+                NamePart::builder("John")
+                    .part_type(NamePartType::Given)
+                    .field(field("http://gedcomx.org/Given", "Jno."))
+                    .build(),
+            )
+            .build();
+        let expected = Name::builder(name_form)
+            .field(field("http://gedcomx.org/Name", "Jno. Kunz"))
+            .build();
+
+        let name: Name = serde_json::from_str(json).unwrap();
+        assert_eq!(name, expected);
+        assert_eq!(serde_json::to_string(&name).unwrap(), json);
+    }
+
+    #[test]
+    fn xml_roundtrip_fields() {
+        let name = Name::builder(NameForm::builder().full_text("John Kunz").build())
+            .field(
+                Field::builder()
+                    .field_type("http://gedcomx.org/Name")
+                    .value(
+                        FieldValue::builder("Jno. Kunz")
+                            .field_value_type(FieldValueType::Original)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
+
+        let xml = crate::xmlcodec::to_string(&name, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Name = crate::xmlcodec::from_str(&xml).unwrap();
+        assert_eq!(from_xml, name);
+    }
+
     #[test]
     fn name_part_qualifier_to_qualifier() {
         {
@@ -1113,8 +1238,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Name) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Name = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Name = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
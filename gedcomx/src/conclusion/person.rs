@@ -1,29 +1,21 @@
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, EvidenceReference, Fact, Gender, Id, Identifier, Lang, Name,
-    Note, ResourceReference, SourceReference,
+    Attribution, ConfidenceLevel, EvidenceReference, Extensions, Fact, Gender, Id, Identifier,
+    Lang, Link, Name, Note, ResourceReference, SourceReference,
 };
 
 /// A description of a person.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Default, Clone)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
 #[non_exhaustive]
 pub struct Person {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -31,7 +23,6 @@ pub struct Person {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -39,26 +30,21 @@ pub struct Person {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// Whether this subject is to be constrained as an extracted conclusion.
-    #[yaserde(attribute)]
     pub extracted: Option<bool>,
 
     /// References to other subjects that support this subject.
@@ -67,7 +53,6 @@ pub struct Person {
     /// the same type as this instance (e.g., if the subject is an instance of
     /// Person, all of its evidence references must resolve to instances of
     /// Person).
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub evidence: Vec<EvidenceReference>,
 
@@ -82,12 +67,10 @@ pub struct Person {
     /// used for multimedia references and therefore MUST resolve to a
     /// `SourceDescription` of the resource, which in turn provides a reference
     /// to the resource itself.
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub media: Vec<SourceReference>,
 
     /// A list of identifiers for the subject.
-    #[yaserde(rename = "identifier", prefix = "gx")]
     #[serde(
         skip_serializing_if = "Vec::is_empty",
         default,
@@ -97,11 +80,9 @@ pub struct Person {
 
     /// Whether this instance of Person has been designated for limited
     /// distribution or display.
-    #[yaserde(attribute)]
     pub private: Option<bool>,
 
     /// The sex of the person as assigned at birth (see [Sex Assignment](https://en.wikipedia.org/wiki/Sex_assignment)).
-    #[yaserde(prefix = "gx")]
     pub gender: Option<Gender>,
 
     /// The names of the person.
@@ -109,14 +90,50 @@ pub struct Person {
     /// If more than one name is provided, names are assumed to be given in
     /// order of preference, with the most preferred name in the first position
     /// in the list.
-    #[yaserde(rename = "name", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub names: Vec<Name>,
 
     /// The facts of the person.
-    #[yaserde(rename = "fact", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub facts: Vec<Fact>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Person: "Person" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            extracted: "extracted",
+            private: "private",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            evidence: gx "evidence",
+            media: gx "media",
+            identifiers: gx "identifier",
+            gender: gx "gender",
+            names: gx "name",
+            facts: gx "fact",
+            links: gx "link",
+        }
+        @extensions
+    }
 }
 
 impl Person {
@@ -153,6 +170,8 @@ impl Person {
             gender,
             names,
             facts,
+            links: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -220,8 +239,13 @@ impl PersonBuilder {
         self
     }
 
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
     pub fn build(&self) -> Person {
-        Person::new(
+        let mut person = Person::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.sources.clone(),
@@ -237,7 +261,9 @@ impl PersonBuilder {
             self.0.gender.clone(),
             self.0.names.clone(),
             self.0.facts.clone(),
-        )
+        );
+        person.links.clone_from(&self.0.links);
+        person
     }
 }
 
@@ -315,7 +341,7 @@ mod test {
                     .build(),
             )
             .build();
-        let person: Person = yaserde::de::from_str(xml).unwrap();
+        let person: Person = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(person, expected_person);
     }
@@ -334,11 +360,11 @@ mod test {
             )
             .build();
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&person, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&person, config).unwrap();
 
         let expected = r##"<Person xmlns="http://gedcomx.org/v1/" id="P-2" extracted="true"><source description="#S-4" /><name><nameForm><fullText>Lo Yau</fullText></nameForm></name><name type="http://gedcomx.org/AlsoKnownAs"><nameForm><fullText>Young Hong Wong</fullText></nameForm></name></Person>"##;
 
@@ -374,8 +400,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Person) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Person = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Person = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
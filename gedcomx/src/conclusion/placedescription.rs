@@ -3,34 +3,24 @@ use std::convert::TryInto;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, Date, EvidenceReference, Id, Identifier, Lang, Note,
-    ResourceReference, Result, SourceReference, TextValue, Uri,
+    Attribution, ConfidenceLevel, Date, EvidenceReference, Extensions, Id, Identifier, Lang, Link,
+    Note, ResourceReference, Result, SourceReference, TextValue, Uri,
 };
 
 /// Describes the details of a place in terms of its name and possibly its type,
 /// time period, and/or a geospatial description -- functioning as a description
 /// of a place as a snapshot in time.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/",
-    // Needed so nested deserialization with the same name works, see https://github.com/media-io/yaserde/issues/110.
-    rename = "place"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 #[serde(rename_all = "camelCase")]
 pub struct PlaceDescription {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -38,7 +28,6 @@ pub struct PlaceDescription {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -46,26 +35,21 @@ pub struct PlaceDescription {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// Whether this subject is to be constrained as an extracted conclusion.
-    #[yaserde(attribute)]
     pub extracted: Option<bool>,
 
     /// References to other subjects that support this subject.
@@ -74,7 +58,6 @@ pub struct PlaceDescription {
     /// the same type as this instance (e.g., if the subject is an instance of
     /// Person, all of its evidence references must resolve to instances of
     /// Person).
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub evidence: Vec<EvidenceReference>,
 
@@ -89,12 +72,10 @@ pub struct PlaceDescription {
     /// used for multimedia references and therefore MUST resolve to a
     /// `SourceDescription` of the resource, which in turn provides a reference
     /// to the resource itself.
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub media: Vec<SourceReference>,
 
     /// A list of identifiers for the subject.
-    #[yaserde(rename = "identifier", prefix = "gx")]
     #[serde(
         skip_serializing_if = "Vec::is_empty",
         default,
@@ -105,24 +86,20 @@ pub struct PlaceDescription {
     /// A list of standardized (or normalized), fully-qualified (in terms of
     /// what is known of the applicable jurisdictional hierarchy) names for this
     /// place that are applicable to this description of this place.
-    #[yaserde(rename = "name", prefix = "gx")]
     pub names: Vec<TextValue>,
 
     /// An implementation-specific uniform resource identifier (URI) used to
     /// identify the type of a place (e.g., address, city, county, province,
     /// state, country, etc.).
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub place_type: Option<Uri>,
 
     /// An identifier for the place being described.
     ///
     /// Descriptions that provide the same value for place are interpreted as alternate descriptions of the same place. If provided, MUST NOT use a base URI of <http://gedcomx.org//>. If provided, the value MAY resolve to an external resource that is application-specific and outside the scope of this specification.
-    #[yaserde(prefix = "gx")]
     pub place: Option<ResourceReference>,
 
     /// A reference to a description of the jurisdiction of this place.	If provided, MUST resolve to an instance of <http://gedcomx.org/v1/PlaceDescription/>.
-    #[yaserde(prefix = "gx")]
     pub jurisdiction: Option<ResourceReference>,
 
     /// Angular distance, in degrees, north or south of the Equator (0.0
@@ -132,7 +109,6 @@ pub struct PlaceDescription {
     /// degrees (south of the equator) to 90.0 degrees (north of the equator).
     /// It is assumed that descriptions that provide the same value for the
     /// place property share identical latitude values.
-    #[yaserde(prefix = "gx")]
     pub latitude: Option<f64>,
 
     /// Angular distance, in degrees, east or west of the Prime Meridian (0.0
@@ -142,20 +118,59 @@ pub struct PlaceDescription {
     /// degrees (west of the Meridian) to 180.0 degrees (east of the Meridian).
     /// It is assumed that descriptions that provide the same value for the
     /// place property share identical longitude values.
-    #[yaserde(prefix = "gx")]
     pub longitude: Option<f64>,
 
     /// A description of the time period to which this place description is
     /// relevant.
-    #[yaserde(rename = "temporalDescription", prefix = "gx")]
     pub temporal_description: Option<Date>,
 
     /// A reference to a geospatial description of this place.
     ///
     /// It is RECOMMENDED that this geospatial description resolve to a KML
     /// document.
-    #[yaserde(rename = "spatialDescription", prefix = "gx")]
     pub spatial_description: Option<ResourceReference>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    PlaceDescription: "place" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            extracted: "extracted",
+            place_type: "type",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            evidence: gx "evidence",
+            media: gx "media",
+            identifiers: gx "identifier",
+            names: gx "name",
+            place: gx "place",
+            jurisdiction: gx "jurisdiction",
+            latitude: gx "latitude",
+            longitude: gx "longitude",
+            temporal_description: gx "temporalDescription",
+            spatial_description: gx "spatialDescription",
+            links: gx "link",
+        }
+        @extensions
+    }
 }
 
 impl PlaceDescription {
@@ -201,6 +216,8 @@ impl PlaceDescription {
             longitude,
             temporal_description,
             spatial_description,
+            links: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -290,8 +307,13 @@ impl PlaceDescriptionBuilder {
         self
     }
 
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
     pub fn build(&self) -> PlaceDescription {
-        PlaceDescription::new(
+        let mut place_description = PlaceDescription::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.sources.clone(),
@@ -311,7 +333,9 @@ impl PlaceDescriptionBuilder {
             self.0.longitude,
             self.0.temporal_description.clone(),
             self.0.spatial_description.clone(),
-        )
+        );
+        place_description.links.clone_from(&self.0.links);
+        place_description
     }
 }
 
@@ -391,7 +415,7 @@ mod test {
         .spatial_description(ResourceReference::from("http://uri/for/KML/document"))
         .build();
 
-        let place_description: PlaceDescription = yaserde::de::from_str(xml).unwrap();
+        let place_description: PlaceDescription = crate::xmlcodec::from_str(xml).unwrap();
         assert_eq!(place_description, expected_place_description);
     }
 
@@ -437,12 +461,12 @@ mod test {
         .spatial_description(ResourceReference::from("http://uri/for/KML/document"))
         .build();
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
 
-        let xml = yaserde::ser::to_string_with_config(&place_description, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&place_description, config).unwrap();
 
         assert_eq!(
             xml,
@@ -460,8 +484,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: PlaceDescription) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: PlaceDescription = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: PlaceDescription = crate::xmlcodec::from_str(&xml).unwrap();
         assert_eq!(input, from_xml);
         input == from_xml
     }
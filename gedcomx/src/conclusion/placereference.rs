@@ -3,32 +3,43 @@ use std::convert::TryInto;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::{PlaceDescription, Result, Uri};
+use crate::{Extensions, Field, PlaceDescription, Result, Uri};
 
 /// A reference to a description of a place.
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct PlaceReference {
     /// The original place name text as supplied by the contributor.
-    #[yaserde(prefix = "gx")]
     pub original: Option<String>,
 
     /// A reference to a description of this place.
     ///
     /// MUST resolve to a `PlaceDescription`.
-    #[yaserde(attribute, rename = "description")]
     #[serde(rename = "description")]
     pub description_ref: Option<Uri>,
+
+    /// The fields of a record that the data was extracted from.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fields: Vec<Field>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    PlaceReference: "PlaceReference" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            description_ref: "description",
+        }
+        elements {
+            original: gx "original",
+            fields: gx "field",
+        }
+        @extensions
+    }
 }
 
 impl PlaceReference {
@@ -36,6 +47,8 @@ impl PlaceReference {
         Self {
             original: original.map(std::convert::Into::into),
             description_ref,
+            fields: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -78,8 +91,16 @@ impl PlaceReferenceBuilder {
         self
     }
 
+    pub fn field(&mut self, field: Field) -> &mut Self {
+        self.0.fields.push(field);
+        self
+    }
+
     pub fn build(&self) -> PlaceReference {
-        PlaceReference::new(self.0.original.clone(), self.0.description_ref.clone())
+        let mut place_reference =
+            PlaceReference::new(self.0.original.clone(), self.0.description_ref.clone());
+        place_reference.fields.clone_from(&self.0.fields);
+        place_reference
     }
 }
 
@@ -104,7 +125,9 @@ mod test {
                 original: Some("the original text".to_string()),
                 description_ref: Some(
                     "http://identifier/of/place-description/being/referenced".into()
-                )
+                ),
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -115,7 +138,7 @@ mod test {
         <original>the original text</original>
       </PlaceReference>"#;
 
-        let place_ref: PlaceReference = yaserde::de::from_str(xml).unwrap();
+        let place_ref: PlaceReference = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(
             place_ref,
@@ -123,7 +146,9 @@ mod test {
                 original: Some("the original text".to_string()),
                 description_ref: Some(
                     "http://identifier/of/place/description/being/referenced".into()
-                )
+                ),
+                fields: vec![],
+                extensions: Extensions::default(),
             }
         );
     }
@@ -142,6 +167,8 @@ mod test {
         let place_ref = PlaceReference {
             original: Some("the original text".to_string()),
             description_ref: Some("http://identifier/of/place/description/being/referenced".into()),
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&place_ref).unwrap();
@@ -157,14 +184,16 @@ mod test {
         let place_ref = PlaceReference {
             original: Some("the original text".to_string()),
             description_ref: Some("http://identifier/of/place/description/being/referenced".into()),
+            fields: vec![],
+            extensions: Extensions::default(),
         };
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
 
-        let xml = yaserde::ser::to_string_with_config(&place_ref, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&place_ref, config).unwrap();
 
         assert_eq!(
             xml,
@@ -190,8 +219,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: PlaceReference) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: PlaceReference = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: PlaceReference = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
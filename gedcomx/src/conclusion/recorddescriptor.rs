@@ -0,0 +1,286 @@
+use quickcheck::{Arbitrary, Gen};
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::{Extensions, FieldValueType, Id, Lang, TextValue};
+
+/// A description of the fields of a type of record, such as the labels a
+/// census form uses for the names of the people it lists.
+///
+/// Defined by the [GEDCOM X Record Extensions](https://github.com/FamilySearch/gedcomx-record/blob/master/specifications/record-specification.md).
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
+#[non_exhaustive]
+pub struct RecordDescriptor {
+    /// An identifier for the record descriptor.
+    pub id: Option<Id>,
+
+    /// The locale identifier for the record descriptor.
+    pub lang: Option<Lang>,
+
+    /// Descriptions of the fields of the record.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub fields: Vec<FieldDescriptor>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    RecordDescriptor: "recordDescriptor" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+        }
+        elements {
+            fields: gx "field",
+        }
+        @extensions
+    }
+}
+
+impl RecordDescriptor {
+    pub fn new(id: Option<Id>, lang: Option<Lang>, fields: Vec<FieldDescriptor>) -> Self {
+        Self {
+            id,
+            lang,
+            fields,
+            extensions: Extensions::default(),
+        }
+    }
+}
+
+impl Arbitrary for RecordDescriptor {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(
+            Some(Id::arbitrary(g)),
+            Some(Lang::arbitrary(g)),
+            vec![FieldDescriptor::arbitrary(g)],
+        )
+    }
+}
+
+/// A description of a field of a record.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct FieldDescriptor {
+    /// The label of the field as it appears on the record.
+    pub original_label: Option<String>,
+
+    /// Descriptions of the field.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub descriptions: Vec<TextValue>,
+
+    /// Descriptions of the values of the field.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub values: Vec<FieldValueDescriptor>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    FieldDescriptor: "FieldDescriptor" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        elements {
+            original_label: gx "originalLabel",
+            descriptions: gx "description",
+            values: gx "value",
+        }
+        @extensions
+    }
+}
+
+impl FieldDescriptor {
+    pub fn new(
+        original_label: Option<String>,
+        descriptions: Vec<TextValue>,
+        values: Vec<FieldValueDescriptor>,
+    ) -> Self {
+        Self {
+            original_label,
+            descriptions,
+            values,
+            extensions: Extensions::default(),
+        }
+    }
+}
+
+impl Arbitrary for FieldDescriptor {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(
+            Some(crate::arbitrary_trimmed(g)),
+            vec![TextValue::arbitrary(g)],
+            vec![FieldValueDescriptor::arbitrary(g)],
+        )
+    }
+}
+
+/// A description of a value of a field of a record, identified by the label
+/// id that [`FieldValue`](crate::FieldValue)s use to refer to it.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct FieldValueDescriptor {
+    /// The id of the label of the value.
+    pub label_id: Option<String>,
+
+    /// The type of the value.
+    #[serde(rename = "type")]
+    pub field_value_type: Option<FieldValueType>,
+
+    /// Whether the value is optional.
+    pub optional: Option<bool>,
+
+    /// Labels to display for the value.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub labels: Vec<TextValue>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    FieldValueDescriptor: "FieldValueDescriptor" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            label_id: "labelId",
+            field_value_type: "type",
+            optional: "optional",
+        }
+        elements {
+            labels: gx "label",
+        }
+        @extensions
+    }
+}
+
+impl FieldValueDescriptor {
+    pub fn new(
+        label_id: Option<String>,
+        field_value_type: Option<FieldValueType>,
+        optional: Option<bool>,
+        labels: Vec<TextValue>,
+    ) -> Self {
+        Self {
+            label_id,
+            field_value_type,
+            optional,
+            labels,
+            extensions: Extensions::default(),
+        }
+    }
+}
+
+impl Arbitrary for FieldValueDescriptor {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(
+            Some(crate::arbitrary_trimmed(g)),
+            Some(FieldValueType::arbitrary(g)),
+            Some(bool::arbitrary(g)),
+            vec![TextValue::arbitrary(g)],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::xmlcodec::Config;
+
+    fn record_descriptor() -> RecordDescriptor {
+        RecordDescriptor::new(
+            Some("RD-1".into()),
+            Some("en".into()),
+            vec![FieldDescriptor::new(
+                Some("Given name".into()),
+                vec![TextValue::from("The given name of the head of household.")],
+                vec![FieldValueDescriptor::new(
+                    Some("PR_NAME_GN".into()),
+                    Some(FieldValueType::Original),
+                    Some(true),
+                    vec![TextValue::new("Given Name", Some("en"))],
+                )],
+            )],
+        )
+    }
+
+    #[test]
+    fn json_deserialize() {
+        let json = r#"{
+            "id" : "RD-1",
+            "lang" : "en",
+            "fields" : [ {
+                "originalLabel" : "Given name",
+                "descriptions" : [ { "value" : "The given name of the head of household." } ],
+                "values" : [ {
+                    "labelId" : "PR_NAME_GN",
+                    "type" : "http://gedcomx.org/Original",
+                    "optional" : true,
+                    "labels" : [ { "lang" : "en", "value" : "Given Name" } ]
+                } ]
+            } ]
+        }"#;
+
+        let record_descriptor: RecordDescriptor = serde_json::from_str(json).unwrap();
+        assert_eq!(record_descriptor, self::record_descriptor());
+    }
+
+    #[test]
+    fn json_serialize() {
+        let json = serde_json::to_string(&record_descriptor()).unwrap();
+        assert_eq!(
+            json,
+            r#"{"id":"RD-1","lang":"en","fields":[{"originalLabel":"Given name","descriptions":[{"value":"The given name of the head of household."}],"values":[{"labelId":"PR_NAME_GN","type":"http://gedcomx.org/Original","optional":true,"labels":[{"lang":"en","value":"Given Name"}]}]}]}"#
+        );
+    }
+
+    #[test]
+    fn xml_deserialize() {
+        let xml = r#"<recordDescriptor xmlns="http://gedcomx.org/v1/" id="RD-1" xml:lang="en">
+            <field>
+                <originalLabel>Given name</originalLabel>
+                <description>The given name of the head of household.</description>
+                <value labelId="PR_NAME_GN" type="http://gedcomx.org/Original" optional="true">
+                    <label xml:lang="en">Given Name</label>
+                </value>
+            </field>
+        </recordDescriptor>"#;
+
+        let record_descriptor: RecordDescriptor = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(record_descriptor, self::record_descriptor());
+    }
+
+    #[test]
+    fn xml_serialize() {
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        let xml = crate::xmlcodec::to_string(&record_descriptor(), config).unwrap();
+        assert_eq!(
+            xml,
+            r#"<recordDescriptor xmlns="http://gedcomx.org/v1/" id="RD-1" xml:lang="en"><field><originalLabel>Given name</originalLabel><description>The given name of the head of household.</description><value labelId="PR_NAME_GN" type="http://gedcomx.org/Original" optional="true"><label xml:lang="en">Given Name</label></value></field></recordDescriptor>"#
+        );
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_json(input: RecordDescriptor) -> bool {
+        let json = serde_json::to_string(&input).unwrap();
+        let from_json: RecordDescriptor = serde_json::from_str(&json).unwrap();
+        input == from_json
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn roundtrip_xml(input: RecordDescriptor) -> bool {
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: RecordDescriptor = crate::xmlcodec::from_str(&xml).unwrap();
+        input == from_xml
+    }
+}
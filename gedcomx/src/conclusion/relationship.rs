@@ -3,11 +3,10 @@ use std::{convert::TryInto, fmt};
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Attribution, ConfidenceLevel, EnumAsString, EvidenceReference, Fact, Id, Identifier, Lang,
-    Note, Person, ResourceReference, Result, SourceReference, Uri,
+    Attribution, ConfidenceLevel, EnumAsString, EvidenceReference, Extensions, Fact, Id,
+    Identifier, Lang, Link, Note, Person, ResourceReference, Result, SourceReference, Uri,
 };
 
 /// A relationship between two persons.
@@ -18,20 +17,13 @@ use crate::{
 /// person1 property refers to the parent and the person2 property refers to the
 /// child.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Default, Clone)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
 #[non_exhaustive]
 pub struct Relationship {
     /// An identifier for the conclusion data. The id is to be used as a "fragment identifier" as defined by [RFC 3986, Section 3.5](https://tools.ietf.org/html/rfc3986#section-3.5).
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the conclusion.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The list of references to the sources of related to this conclusion.
@@ -39,7 +31,6 @@ pub struct Relationship {
     /// to be sources of the entities that contain them. For example, a source
     /// associated with the `Name` of a `Person` is also source for the
     /// `Person`.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
@@ -47,26 +38,21 @@ pub struct Relationship {
     /// into this conclusion. If provided, MUST resolve to an instance of
     /// [Document](crate::Document) of type
     /// [Analysis](crate::DocumentType::Analysis).
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A list of notes about this conclusion.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
     /// The level of confidence the contributor has about the data.
-    #[yaserde(attribute)]
     pub confidence: Option<ConfidenceLevel>,
 
     /// The attribution of this conclusion.
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the conclusion is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// Whether this subject is to be constrained as an extracted conclusion.
-    #[yaserde(attribute)]
     pub extracted: Option<bool>,
 
     /// References to other subjects that support this subject.
@@ -75,7 +61,6 @@ pub struct Relationship {
     /// the same type as this instance (e.g., if the subject is an instance of
     /// Person, all of its evidence references must resolve to instances of
     /// Person).
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub evidence: Vec<EvidenceReference>,
 
@@ -90,12 +75,10 @@ pub struct Relationship {
     /// used for multimedia references and therefore MUST resolve to a
     /// `SourceDescription` of the resource, which in turn provides a reference
     /// to the resource itself.
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub media: Vec<SourceReference>,
 
     /// A list of identifiers for the subject.
-    #[yaserde(rename = "identifier", prefix = "gx")]
     #[serde(
         skip_serializing_if = "Vec::is_empty",
         default,
@@ -104,26 +87,60 @@ pub struct Relationship {
     pub identifiers: Vec<Identifier>,
 
     /// The type of the relationship.
-    #[yaserde(rename = "type", attribute)]
     #[serde(rename = "type")]
     pub relationship_type: Option<RelationshipType>,
 
     /// Reference to the first person in the relationship.
     ///
     /// MUST resolve to an instance of <http://gedcomx.org/v1/Person/>.
-    #[yaserde(prefix = "gx")]
     pub person1: ResourceReference,
 
     /// Reference to the second person in the relationship.
     ///
     /// MUST resolve to an instance of <http://gedcomx.org/v1/Person/>.
-    #[yaserde(prefix = "gx")]
     pub person2: ResourceReference,
 
     /// The facts about the relationship.
-    #[yaserde(rename = "fact", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub facts: Vec<Fact>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Relationship: "Relationship" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            confidence: "confidence",
+            extracted: "extracted",
+            relationship_type: "type",
+        }
+        elements {
+            sources: gx "source",
+            analysis: gx "analysis",
+            notes: gx "note",
+            attribution: gx "attribution",
+            evidence: gx "evidence",
+            media: gx "media",
+            identifiers: gx "identifier",
+            person1: gx "person1",
+            person2: gx "person2",
+            facts: gx "fact",
+            links: gx "link",
+        }
+        @extensions
+    }
 }
 
 impl Relationship {
@@ -160,6 +177,8 @@ impl Relationship {
             person1,
             person2,
             facts,
+            links: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -245,8 +264,13 @@ impl RelationshipBuilder {
         self
     }
 
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
     pub fn build(&self) -> Relationship {
-        Relationship::new(
+        let mut relationship = Relationship::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.sources.clone(),
@@ -262,7 +286,9 @@ impl RelationshipBuilder {
             self.0.person1.clone(),
             self.0.person2.clone(),
             self.0.facts.clone(),
-        )
+        );
+        relationship.links.clone_from(&self.0.links);
+        relationship
     }
 }
 
@@ -290,7 +316,8 @@ pub enum RelationshipType {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(RelationshipType, "RelationshipType");
+impl_enumasstring_xml_value!(RelationshipType, "RelationshipType");
+impl_from_uri!(RelationshipType);
 
 impl From<EnumAsString> for RelationshipType {
     fn from(f: EnumAsString) -> Self {
@@ -396,7 +423,7 @@ mod test {
         .relationship_type(RelationshipType::Couple)
         .build();
 
-        let relationship: Relationship = yaserde::de::from_str(xml).unwrap();
+        let relationship: Relationship = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(relationship, expected_relationship);
     }
@@ -438,12 +465,12 @@ mod test {
         .relationship_type(RelationshipType::Couple)
         .build();
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
 
-        let xml = yaserde::ser::to_string_with_config(&relationship, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&relationship, config).unwrap();
 
         let expected_xml = r##"<Relationship xmlns="http://gedcomx.org/v1/" id="local_id" extracted="false" type="http://gedcomx.org/Couple"><person1 resource="#http://identifier/for/person/1" /><person2 resource="#http://identifier/for/person/2" /></Relationship>"##;
 
@@ -460,8 +487,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Relationship) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Relationship = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Relationship = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
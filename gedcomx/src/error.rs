@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug};
 
 use thiserror::Error;
 
@@ -32,13 +32,21 @@ pub enum GedcomxError {
     #[error("Error parsing {parsed_string} as qualifier name")]
     QualifierParse { parsed_string: String },
 
-    /// Error returned while attempting to serialize / deserialize as JSON.
-    #[error("Error serializing or deserializing JSON")]
+    /// Error returned while attempting to serialize as JSON.
+    #[error("Error serializing JSON")]
     JSONError(#[from] serde_json::Error),
 
-    /// Error returned while attempting to serialize / deserialize as XML.
-    #[error("Error serializing or deserializing XML")]
-    XMLError(String),
+    /// Error returned while attempting to serialize as XML.
+    #[error("Error serializing XML: {0}")]
+    XMLSerializeError(String),
+
+    /// Error returned while attempting to deserialize JSON or XML.
+    #[error("Error deserializing: {0}")]
+    DeserializeError(Box<DeserializeError>),
+
+    /// Error returned while reading a document from an IO stream.
+    #[error("Error reading: {0}")]
+    IoError(#[from] std::io::Error),
 }
 
 impl GedcomxError {
@@ -46,3 +54,126 @@ impl GedcomxError {
         Self::NoId(format!("{t:#?}"))
     }
 }
+
+impl From<DeserializeError> for GedcomxError {
+    fn from(e: DeserializeError) -> Self {
+        Self::DeserializeError(Box::new(e))
+    }
+}
+
+/// Why and where deserializing a JSON or XML document failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeserializeError {
+    /// What went wrong.
+    pub message: String,
+
+    /// Where in the document the error was detected, if known.
+    pub position: Option<Position>,
+
+    /// The logical path to the value being read when the error was detected,
+    /// such as `persons[812].names[0].nameForms[0]`, which is the same for
+    /// JSON and XML. In XML, an element that isn't read as a field, such as
+    /// an extension element, is given by its name and its position among its
+    /// siblings of the same name. Empty if the error is at the top of the
+    /// document.
+    pub path: String,
+
+    /// The offending value, such as the text of an element or a JSON string
+    /// or number, if the error is about a value.
+    pub value: Option<String>,
+}
+
+impl DeserializeError {
+    #[cfg(feature = "familysearch")]
+    pub(crate) fn new<M: Into<String>>(message: M) -> Self {
+        Self {
+            message: message.into(),
+            position: None,
+            path: String::new(),
+            value: None,
+        }
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        write!(f, "{}", self.message)?;
+        if let Some(value) = &self.value {
+            write!(f, " (value {value})")?;
+        }
+        if let Some(position) = &self.position {
+            write!(f, " at {position}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A position in a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Position {
+    /// The line, starting from 1.
+    pub line: u64,
+
+    /// The column, starting from 1.
+    pub column: u64,
+
+    /// The number of bytes before the position in the document.
+    pub offset: u64,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// A problem in a document that was recovered from when reading it leniently,
+/// such as with
+/// [`Gedcomx::from_json_str_lenient`](crate::Gedcomx::from_json_str_lenient).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeserializeWarning {
+    /// The error reading the document strictly would have returned.
+    pub error: DeserializeError,
+
+    /// How the problem was recovered from.
+    pub recovery: Recovery,
+}
+
+impl fmt::Display for DeserializeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.error, self.recovery)
+    }
+}
+
+/// How a lenient read recovered from a problem in a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Recovery {
+    /// The value was kept as it was, in a field for it that isn't serialized,
+    /// such as [`Date::invalid_formal`](crate::Date::invalid_formal) for an
+    /// invalid formal date.
+    Preserved,
+
+    /// The value was left out, so the field it's for has its default value,
+    /// or one element fewer if it's a list.
+    Defaulted,
+
+    /// The entity it's in was left out of the document.
+    Skipped,
+}
+
+impl fmt::Display for Recovery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Preserved => "kept as it was",
+            Self::Defaulted => "left out",
+            Self::Skipped => "entity skipped",
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::{EnumAsString, ResourceReference, Uri};
+
+/// Information about a change made to a resource in the FamilySearch tree.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ChangeInfo {
+    /// The operation of the change.
+    pub operation: Option<ChangeOperation>,
+
+    /// The type of the object that was changed, such as
+    /// `http://gedcomx.org/BirthName`.
+    pub object_type: Option<Uri>,
+
+    /// The type of the object the changed object belongs to, such as
+    /// `http://gedcomx.org/Person`.
+    pub object_modifier: Option<Uri>,
+
+    /// The reason given for the change.
+    pub reason: Option<String>,
+
+    /// The change this change is a part of.
+    pub parent: Option<ResourceReference>,
+}
+
+impl_xml! {
+    ChangeInfo: "fs:changeInfo" in _ ["fs" = crate::xmlcodec::FAMILYSEARCH_NAMESPACE] {
+        attributes {
+            operation: "operation",
+            object_type: "objectType",
+            object_modifier: "objectModifier",
+        }
+        elements {
+            reason: fs "reason",
+            parent: fs "parent",
+        }
+    }
+}
+
+impl ChangeInfo {
+    pub fn builder(operation: ChangeOperation) -> ChangeInfoBuilder {
+        ChangeInfoBuilder::new(operation)
+    }
+}
+
+pub struct ChangeInfoBuilder(ChangeInfo);
+
+impl ChangeInfoBuilder {
+    pub(crate) fn new(operation: ChangeOperation) -> Self {
+        Self(ChangeInfo {
+            operation: Some(operation),
+            ..ChangeInfo::default()
+        })
+    }
+
+    pub fn object_type<I: Into<Uri>>(&mut self, object_type: I) -> &mut Self {
+        self.0.object_type = Some(object_type.into());
+        self
+    }
+
+    pub fn object_modifier<I: Into<Uri>>(&mut self, object_modifier: I) -> &mut Self {
+        self.0.object_modifier = Some(object_modifier.into());
+        self
+    }
+
+    pub fn reason<I: Into<String>>(&mut self, reason: I) -> &mut Self {
+        self.0.reason = Some(reason.into());
+        self
+    }
+
+    pub fn parent(&mut self, parent: ResourceReference) -> &mut Self {
+        self.0.parent = Some(parent);
+        self
+    }
+
+    pub fn build(&self) -> ChangeInfo {
+        self.0.clone()
+    }
+}
+
+/// The operation of a change.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Eq)]
+#[non_exhaustive]
+#[serde(from = "EnumAsString", into = "EnumAsString")]
+pub enum ChangeOperation {
+    /// The resource was created.
+    Create,
+
+    /// The resource was read.
+    Read,
+
+    /// The resource was updated.
+    Update,
+
+    /// The resource was deleted.
+    Delete,
+
+    /// The resource was merged into another.
+    Merge,
+
+    /// A merge of the resource was undone.
+    Unmerge,
+
+    /// The resource was restored after being deleted.
+    Restore,
+
+    Custom(Uri),
+}
+
+impl Default for ChangeOperation {
+    fn default() -> Self {
+        Self::Custom(Uri::from(String::default()))
+    }
+}
+
+impl_enumasstring_xml_value!(ChangeOperation, "ChangeOperation");
+impl_from_uri!(ChangeOperation);
+
+impl From<EnumAsString> for ChangeOperation {
+    fn from(f: EnumAsString) -> Self {
+        match f.0.as_ref() {
+            "http://familysearch.org/v1/Create" => Self::Create,
+            "http://familysearch.org/v1/Read" => Self::Read,
+            "http://familysearch.org/v1/Update" => Self::Update,
+            "http://familysearch.org/v1/Delete" => Self::Delete,
+            "http://familysearch.org/v1/Merge" => Self::Merge,
+            "http://familysearch.org/v1/Unmerge" => Self::Unmerge,
+            "http://familysearch.org/v1/Restore" => Self::Restore,
+            _ => Self::Custom(f.0.into()),
+        }
+    }
+}
+
+impl fmt::Display for ChangeOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Create => write!(f, "http://familysearch.org/v1/Create"),
+            Self::Read => write!(f, "http://familysearch.org/v1/Read"),
+            Self::Update => write!(f, "http://familysearch.org/v1/Update"),
+            Self::Delete => write!(f, "http://familysearch.org/v1/Delete"),
+            Self::Merge => write!(f, "http://familysearch.org/v1/Merge"),
+            Self::Unmerge => write!(f, "http://familysearch.org/v1/Unmerge"),
+            Self::Restore => write!(f, "http://familysearch.org/v1/Restore"),
+            Self::Custom(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::xmlcodec::Config;
+
+    fn change_info() -> ChangeInfo {
+        ChangeInfo::builder(ChangeOperation::Update)
+            .object_type("http://gedcomx.org/BirthName")
+            .object_modifier("http://gedcomx.org/Person")
+            .reason("Spelling")
+            .parent(ResourceReference::from("#CHANGE-1"))
+            .build()
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let json = r##"{"operation":"http://familysearch.org/v1/Update","objectType":"http://gedcomx.org/BirthName","objectModifier":"http://gedcomx.org/Person","reason":"Spelling","parent":{"resource":"#CHANGE-1"}}"##;
+
+        let change_info: ChangeInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(change_info, self::change_info());
+        assert_eq!(serde_json::to_string(&change_info).unwrap(), json);
+    }
+
+    #[test]
+    fn xml_roundtrip() {
+        let xml = r##"<fs:changeInfo xmlns:fs="http://familysearch.org/v1/" operation="http://familysearch.org/v1/Update" objectType="http://gedcomx.org/BirthName" objectModifier="http://gedcomx.org/Person"><fs:reason>Spelling</fs:reason><fs:parent resource="#CHANGE-1" /></fs:changeInfo>"##;
+
+        let change_info: ChangeInfo = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(change_info, self::change_info());
+
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            crate::xmlcodec::to_string(&change_info, config).unwrap(),
+            xml
+        );
+    }
+
+    #[test]
+    fn operation_custom() {
+        assert_eq!(
+            ChangeOperation::from(EnumAsString("http://example.com/Touch".into())),
+            ChangeOperation::Custom("http://example.com/Touch".into())
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::{Attribution, Uri};
+
+/// A reference to a discussion about a person in the FamilySearch tree.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DiscussionReference {
+    /// The id of the discussion being referenced.
+    pub resource_id: Option<String>,
+
+    /// The URI to the discussion being referenced.
+    pub resource: Uri,
+
+    /// The attribution of this reference.
+    pub attribution: Option<Attribution>,
+}
+
+impl_xml! {
+    DiscussionReference: "fs:discussion-reference" in _ ["fs" = crate::xmlcodec::FAMILYSEARCH_NAMESPACE, "gx" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            resource_id: "resourceId",
+            resource: "resource",
+        }
+        elements {
+            attribution: gx "attribution",
+        }
+    }
+}
+
+impl DiscussionReference {
+    pub fn new(
+        resource: Uri,
+        resource_id: Option<String>,
+        attribution: Option<Attribution>,
+    ) -> Self {
+        Self {
+            resource_id,
+            resource,
+            attribution,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::xmlcodec::Config;
+
+    #[test]
+    fn json_roundtrip() {
+        let json = r#"{"resourceId":"dis-MMMM-MMM","resource":"https://api.familysearch.org/platform/discussions/discussions/dis-MMMM-MMM"}"#;
+
+        let reference: DiscussionReference = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            reference,
+            DiscussionReference::new(
+                "https://api.familysearch.org/platform/discussions/discussions/dis-MMMM-MMM".into(),
+                Some("dis-MMMM-MMM".into()),
+                None
+            )
+        );
+        assert_eq!(serde_json::to_string(&reference).unwrap(), json);
+    }
+
+    #[test]
+    fn xml_roundtrip() {
+        let xml = r#"<fs:discussion-reference xmlns:fs="http://familysearch.org/v1/" xmlns:gx="http://gedcomx.org/v1/" resourceId="dis-MMMM-MMM" resource="https://api.familysearch.org/platform/discussions/discussions/dis-MMMM-MMM"><gx:attribution xmlns="http://gedcomx.org/v1/"><contributor resource="A-1" /></gx:attribution></fs:discussion-reference>"#;
+
+        let reference: DiscussionReference = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(
+            reference,
+            DiscussionReference::new(
+                "https://api.familysearch.org/platform/discussions/discussions/dis-MMMM-MMM".into(),
+                Some("dis-MMMM-MMM".into()),
+                Some(Attribution {
+                    contributor: Some("A-1".into()),
+                    ..Attribution::default()
+                })
+            )
+        );
+
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        assert_eq!(crate::xmlcodec::to_string(&reference, config).unwrap(), xml);
+    }
+}
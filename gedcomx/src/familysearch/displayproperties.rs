@@ -0,0 +1,194 @@
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A summary of a person, computed by FamilySearch for display.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DisplayProperties {
+    /// The displayable name of the person.
+    pub name: Option<String>,
+
+    /// The displayable label for the gender of the person.
+    pub gender: Option<String>,
+
+    /// The displayable label for the lifespan of the person, such as
+    /// "1900-1960" or "1993-Living".
+    pub lifespan: Option<String>,
+
+    /// The displayable label for the birth date of the person.
+    pub birth_date: Option<String>,
+
+    /// The displayable label for the birth place of the person.
+    pub birth_place: Option<String>,
+
+    /// The displayable label for the death date of the person.
+    pub death_date: Option<String>,
+
+    /// The displayable label for the death place of the person.
+    pub death_place: Option<String>,
+
+    /// The displayable label for the marriage date of the person.
+    pub marriage_date: Option<String>,
+
+    /// The displayable label for the marriage place of the person.
+    pub marriage_place: Option<String>,
+
+    /// The context-specific ascendancy number for the person in relation to
+    /// the other persons in the request, such as an Ahnentafel number.
+    pub ascendancy_number: Option<String>,
+
+    /// The context-specific descendancy number for the person in relation to
+    /// the other persons in the request, such as a d'Aboville number.
+    pub descendancy_number: Option<String>,
+
+    /// A description of how the person is related to the user.
+    pub relationship_description: Option<String>,
+}
+
+impl_xml! {
+    DisplayProperties: "fs:display" in _ ["fs" = crate::xmlcodec::FAMILYSEARCH_NAMESPACE] {
+        elements {
+            name: fs "name",
+            gender: fs "gender",
+            lifespan: fs "lifespan",
+            birth_date: fs "birthDate",
+            birth_place: fs "birthPlace",
+            death_date: fs "deathDate",
+            death_place: fs "deathPlace",
+            marriage_date: fs "marriageDate",
+            marriage_place: fs "marriagePlace",
+            ascendancy_number: fs "ascendancyNumber",
+            descendancy_number: fs "descendancyNumber",
+            relationship_description: fs "relationshipDescription",
+        }
+    }
+}
+
+impl DisplayProperties {
+    pub fn builder() -> DisplayPropertiesBuilder {
+        DisplayPropertiesBuilder::new()
+    }
+}
+
+pub struct DisplayPropertiesBuilder(DisplayProperties);
+
+impl DisplayPropertiesBuilder {
+    pub(crate) fn new() -> Self {
+        Self(DisplayProperties::default())
+    }
+
+    pub fn name<I: Into<String>>(&mut self, name: I) -> &mut Self {
+        self.0.name = Some(name.into());
+        self
+    }
+
+    pub fn gender<I: Into<String>>(&mut self, gender: I) -> &mut Self {
+        self.0.gender = Some(gender.into());
+        self
+    }
+
+    pub fn lifespan<I: Into<String>>(&mut self, lifespan: I) -> &mut Self {
+        self.0.lifespan = Some(lifespan.into());
+        self
+    }
+
+    pub fn birth_date<I: Into<String>>(&mut self, birth_date: I) -> &mut Self {
+        self.0.birth_date = Some(birth_date.into());
+        self
+    }
+
+    pub fn birth_place<I: Into<String>>(&mut self, birth_place: I) -> &mut Self {
+        self.0.birth_place = Some(birth_place.into());
+        self
+    }
+
+    pub fn death_date<I: Into<String>>(&mut self, death_date: I) -> &mut Self {
+        self.0.death_date = Some(death_date.into());
+        self
+    }
+
+    pub fn death_place<I: Into<String>>(&mut self, death_place: I) -> &mut Self {
+        self.0.death_place = Some(death_place.into());
+        self
+    }
+
+    pub fn marriage_date<I: Into<String>>(&mut self, marriage_date: I) -> &mut Self {
+        self.0.marriage_date = Some(marriage_date.into());
+        self
+    }
+
+    pub fn marriage_place<I: Into<String>>(&mut self, marriage_place: I) -> &mut Self {
+        self.0.marriage_place = Some(marriage_place.into());
+        self
+    }
+
+    pub fn ascendancy_number<I: Into<String>>(&mut self, ascendancy_number: I) -> &mut Self {
+        self.0.ascendancy_number = Some(ascendancy_number.into());
+        self
+    }
+
+    pub fn descendancy_number<I: Into<String>>(&mut self, descendancy_number: I) -> &mut Self {
+        self.0.descendancy_number = Some(descendancy_number.into());
+        self
+    }
+
+    pub fn relationship_description<I: Into<String>>(
+        &mut self,
+        relationship_description: I,
+    ) -> &mut Self {
+        self.0.relationship_description = Some(relationship_description.into());
+        self
+    }
+
+    pub fn build(&self) -> DisplayProperties {
+        self.0.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::xmlcodec::Config;
+
+    #[test]
+    fn json_roundtrip() {
+        let json =
+            r#"{"name":"Howard William Kunz","lifespan":"1967-2011","ascendancyNumber":"2"}"#;
+
+        let display: DisplayProperties = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            display,
+            DisplayProperties::builder()
+                .name("Howard William Kunz")
+                .lifespan("1967-2011")
+                .ascendancy_number("2")
+                .build()
+        );
+        assert_eq!(serde_json::to_string(&display).unwrap(), json);
+    }
+
+    #[test]
+    fn xml_roundtrip() {
+        let xml = r#"<fs:display xmlns:fs="http://familysearch.org/v1/"><fs:name>Howard William Kunz</fs:name><fs:lifespan>1967-2011</fs:lifespan><fs:ascendancyNumber>2</fs:ascendancyNumber></fs:display>"#;
+
+        let display: DisplayProperties = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(
+            display,
+            DisplayProperties::builder()
+                .name("Howard William Kunz")
+                .lifespan("1967-2011")
+                .ascendancy_number("2")
+                .build()
+        );
+
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        assert_eq!(crate::xmlcodec::to_string(&display, config).unwrap(), xml);
+    }
+}
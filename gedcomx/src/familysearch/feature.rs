@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::Timestamp;
+
+/// A feature of the FamilySearch API that can be enabled or disabled.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct Feature {
+    /// The name of the feature.
+    pub name: Option<String>,
+
+    /// A description of the feature.
+    pub description: Option<String>,
+
+    /// Whether the feature is enabled.
+    pub enabled: Option<bool>,
+
+    /// When the feature was or will be activated.
+    pub activation_date: Option<Timestamp>,
+}
+
+impl_xml! {
+    Feature: "fs:feature" in _ ["fs" = crate::xmlcodec::FAMILYSEARCH_NAMESPACE] {
+        elements {
+            name: fs "name",
+            description: fs "description",
+            enabled: fs "enabled",
+            activation_date: fs "activationDate",
+        }
+    }
+}
+
+impl Feature {
+    pub fn new<I: Into<String>>(
+        name: I,
+        description: Option<String>,
+        enabled: Option<bool>,
+        activation_date: Option<Timestamp>,
+    ) -> Self {
+        Self {
+            name: Some(name.into()),
+            description,
+            enabled,
+            activation_date,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::xmlcodec::Config;
+
+    fn feature() -> Feature {
+        Feature::new(
+            "discussions",
+            Some("Discussions about a person.".into()),
+            Some(true),
+            Some(
+                chrono::DateTime::from_timestamp(1_394_175_600, 0)
+                    .unwrap()
+                    .into(),
+            ),
+        )
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let json = r#"{"name":"discussions","description":"Discussions about a person.","enabled":true,"activationDate":1394175600000}"#;
+
+        let feature: Feature = serde_json::from_str(json).unwrap();
+        assert_eq!(feature, self::feature());
+        assert_eq!(serde_json::to_string(&feature).unwrap(), json);
+    }
+
+    #[test]
+    fn xml_roundtrip() {
+        let xml = r#"<fs:feature xmlns:fs="http://familysearch.org/v1/"><fs:name>discussions</fs:name><fs:description>Discussions about a person.</fs:description><fs:enabled>true</fs:enabled><fs:activationDate>2014-03-07T07:00:00Z</fs:activationDate></fs:feature>"#;
+
+        let feature: Feature = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(feature, self::feature());
+
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        assert_eq!(crate::xmlcodec::to_string(&feature, config).unwrap(), xml);
+    }
+}
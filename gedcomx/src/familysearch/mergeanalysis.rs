@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::ResourceReference;
+
+/// An analysis of what would happen if two persons in the FamilySearch tree
+/// were merged.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MergeAnalysis {
+    /// The resources of the survivor that would be kept.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub survivor_resources: Vec<ResourceReference>,
+
+    /// The resources of the duplicate that could be copied to the survivor.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub duplicate_resources: Vec<ResourceReference>,
+
+    /// The resources of the survivor and duplicate that conflict.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub conflicting_resources: Vec<MergeConflict>,
+
+    /// The person that would survive the merge.
+    pub survivor: Option<ResourceReference>,
+
+    /// The person that would be merged into the survivor.
+    pub duplicate: Option<ResourceReference>,
+}
+
+impl_xml! {
+    MergeAnalysis: "fs:mergeAnalysis" in _ ["fs" = crate::xmlcodec::FAMILYSEARCH_NAMESPACE] {
+        elements {
+            survivor_resources: fs "survivorResource",
+            duplicate_resources: fs "duplicateResource",
+            conflicting_resources: fs "conflictingResource",
+            survivor: fs "survivor",
+            duplicate: fs "duplicate",
+        }
+    }
+}
+
+impl MergeAnalysis {
+    pub fn new(
+        survivor_resources: Vec<ResourceReference>,
+        duplicate_resources: Vec<ResourceReference>,
+        conflicting_resources: Vec<MergeConflict>,
+        survivor: Option<ResourceReference>,
+        duplicate: Option<ResourceReference>,
+    ) -> Self {
+        Self {
+            survivor_resources,
+            duplicate_resources,
+            conflicting_resources,
+            survivor,
+            duplicate,
+        }
+    }
+}
+
+/// A resource of the survivor of a merge that conflicts with a resource of the
+/// duplicate, such as two different birth facts.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct MergeConflict {
+    /// The resource of the survivor.
+    pub survivor_resource: Option<ResourceReference>,
+
+    /// The resource of the duplicate.
+    pub duplicate_resource: Option<ResourceReference>,
+}
+
+impl_xml! {
+    MergeConflict: "fs:MergeConflict" in _ ["fs" = crate::xmlcodec::FAMILYSEARCH_NAMESPACE] {
+        elements {
+            survivor_resource: fs "survivorResource",
+            duplicate_resource: fs "duplicateResource",
+        }
+    }
+}
+
+impl MergeConflict {
+    pub fn new(
+        survivor_resource: Option<ResourceReference>,
+        duplicate_resource: Option<ResourceReference>,
+    ) -> Self {
+        Self {
+            survivor_resource,
+            duplicate_resource,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::xmlcodec::Config;
+
+    fn merge_analysis() -> MergeAnalysis {
+        MergeAnalysis::new(
+            vec![ResourceReference::from("#NAME-1")],
+            vec![ResourceReference::from("#NAME-2")],
+            vec![MergeConflict::new(
+                Some(ResourceReference::from("#BIRTH-1")),
+                Some(ResourceReference::from("#BIRTH-2")),
+            )],
+            Some(ResourceReference::from("#P-1")),
+            Some(ResourceReference::from("#P-2")),
+        )
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let json = r##"{"survivorResources":[{"resource":"#NAME-1"}],"duplicateResources":[{"resource":"#NAME-2"}],"conflictingResources":[{"survivorResource":{"resource":"#BIRTH-1"},"duplicateResource":{"resource":"#BIRTH-2"}}],"survivor":{"resource":"#P-1"},"duplicate":{"resource":"#P-2"}}"##;
+
+        let merge_analysis: MergeAnalysis = serde_json::from_str(json).unwrap();
+        assert_eq!(merge_analysis, self::merge_analysis());
+        assert_eq!(serde_json::to_string(&merge_analysis).unwrap(), json);
+    }
+
+    #[test]
+    fn xml_roundtrip() {
+        let xml = r##"<fs:mergeAnalysis xmlns:fs="http://familysearch.org/v1/"><fs:survivorResource resource="#NAME-1" /><fs:duplicateResource resource="#NAME-2" /><fs:conflictingResource><fs:survivorResource resource="#BIRTH-1" /><fs:duplicateResource resource="#BIRTH-2" /></fs:conflictingResource><fs:survivor resource="#P-1" /><fs:duplicate resource="#P-2" /></fs:mergeAnalysis>"##;
+
+        let merge_analysis: MergeAnalysis = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(merge_analysis, self::merge_analysis());
+
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            crate::xmlcodec::to_string(&merge_analysis, config).unwrap(),
+            xml
+        );
+    }
+}
@@ -0,0 +1,29 @@
+//! Models for the extensions to GEDCOM X used by the FamilySearch API.
+//!
+//! The extensions are defined in the [FamilySearch GEDCOM X Extensions](https://github.com/FamilySearch/gedcomx-familysearch-extensions/blob/master/specifications/gedcomx-familysearch-specification.md).
+//!
+//! The extensions are kept in the [`Extensions`](crate::Extensions) of the
+//! type they're attached to, and are read and written with methods on that
+//! type, such as [`Person::display`](crate::Person::display) and
+//! [`Gedcomx::users`](crate::Gedcomx::users). In XML they're in the
+//! `http://familysearch.org/v1/` namespace.
+
+mod changeinfo;
+pub use changeinfo::{ChangeInfo, ChangeInfoBuilder, ChangeOperation};
+
+mod discussionreference;
+pub use discussionreference::DiscussionReference;
+
+mod displayproperties;
+pub use displayproperties::{DisplayProperties, DisplayPropertiesBuilder};
+
+mod feature;
+pub use feature::Feature;
+
+mod mergeanalysis;
+pub use mergeanalysis::{MergeAnalysis, MergeConflict};
+
+mod properties;
+
+mod user;
+pub use user::{User, UserBuilder};
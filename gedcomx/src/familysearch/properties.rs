@@ -0,0 +1,421 @@
+use serde::{Serialize, de::DeserializeOwned};
+use xml::reader::{EventReader, XmlEvent};
+
+use super::{ChangeInfo, DiscussionReference, DisplayProperties, Feature, MergeAnalysis, User};
+use crate::{
+    DeserializeError, ExtensionAttribute, Extensions, Gedcomx, Person, Relationship, Result,
+    tracking,
+    xmlcodec::{self, Config, FromXml, ToXml},
+};
+
+const NAMESPACE: &str = "http://familysearch.org/v1/";
+const PREFIX: &str = "fs";
+
+// The FamilySearch properties live in the extensions of the type they're
+// attached to, so they're kept when a document is read and written by code that
+// doesn't know about them. A property is read from the JSON members if it's
+// there, and the XML elements otherwise. Setting a property writes both, so it
+// can be serialized in either format.
+
+fn is_element(xml: &str, local_name: &str) -> bool {
+    let reader = EventReader::from_str(xml);
+    for event in reader {
+        match event {
+            Ok(XmlEvent::StartElement { name, .. }) => {
+                return name.namespace.as_deref() == Some(NAMESPACE)
+                    && name.local_name == local_name;
+            }
+            Ok(XmlEvent::EndDocument) | Err(_) => break,
+            _ => {}
+        }
+    }
+    false
+}
+
+fn to_xml<T: ToXml>(value: &T) -> Result<String> {
+    let config = Config {
+        write_document_declaration: false,
+        ..Config::default()
+    };
+    xmlcodec::to_string(value, config)
+}
+
+fn get<T: DeserializeOwned + FromXml>(
+    extensions: &Extensions,
+    json_name: &str,
+    xml_name: &str,
+) -> Result<Option<T>> {
+    if let Some(value) = extensions.json.get(json_name) {
+        return Ok(Some(tracking::from_json_value(value, json_name)?));
+    }
+
+    extensions
+        .xml_elements
+        .iter()
+        .find(|e| is_element(e, xml_name))
+        .map(|e| xmlcodec::from_str(e))
+        .transpose()
+}
+
+fn get_all<T: DeserializeOwned + FromXml>(
+    extensions: &Extensions,
+    json_name: &str,
+    xml_name: &str,
+) -> Result<Vec<T>> {
+    if let Some(value) = extensions.json.get(json_name) {
+        return tracking::from_json_value(value, json_name);
+    }
+
+    extensions
+        .xml_elements
+        .iter()
+        .filter(|e| is_element(e, xml_name))
+        .map(|e| xmlcodec::from_str(e))
+        .collect()
+}
+
+fn set<T: Serialize + ToXml>(
+    extensions: &mut Extensions,
+    json_name: &str,
+    xml_name: &str,
+    value: Option<&T>,
+) -> Result<()> {
+    extensions.json.remove(json_name);
+    extensions.xml_elements.retain(|e| !is_element(e, xml_name));
+
+    if let Some(value) = value {
+        extensions
+            .json
+            .insert(json_name.to_string(), serde_json::to_value(value)?);
+        extensions.xml_elements.push(to_xml(value)?);
+    }
+
+    Ok(())
+}
+
+fn set_all<T: Serialize + ToXml>(
+    extensions: &mut Extensions,
+    json_name: &str,
+    xml_name: &str,
+    values: &[T],
+) -> Result<()> {
+    extensions.json.remove(json_name);
+    extensions.xml_elements.retain(|e| !is_element(e, xml_name));
+
+    if !values.is_empty() {
+        extensions
+            .json
+            .insert(json_name.to_string(), serde_json::to_value(values)?);
+        for value in values {
+            extensions.xml_elements.push(to_xml(value)?);
+        }
+    }
+
+    Ok(())
+}
+
+impl Person {
+    /// Whether FamilySearch considers the person to be living.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::DeserializeError`] if the property isn't a
+    /// boolean.
+    pub fn living(&self) -> Result<Option<bool>> {
+        if let Some(value) = self.extensions.json.get("living") {
+            return Ok(Some(tracking::from_json_value(value, "living")?));
+        }
+
+        self.extensions
+            .xml_attributes
+            .iter()
+            .find(|a| a.namespace == NAMESPACE && a.name == "living")
+            .map(|a| {
+                a.value.parse().map_err(|e: std::str::ParseBoolError| {
+                    let mut error = DeserializeError::new(e.to_string());
+                    error.path = "living".to_string();
+                    error.value = Some(a.value.clone());
+                    error.into()
+                })
+            })
+            .transpose()
+    }
+
+    /// Sets or removes whether FamilySearch considers the person to be living.
+    pub fn set_living(&mut self, living: Option<bool>) {
+        self.extensions.json.remove("living");
+        self.extensions
+            .xml_attributes
+            .retain(|a| !(a.namespace == NAMESPACE && a.name == "living"));
+
+        if let Some(living) = living {
+            self.extensions
+                .json
+                .insert("living".to_string(), living.into());
+            self.extensions.xml_attributes.push(ExtensionAttribute::new(
+                NAMESPACE,
+                PREFIX,
+                "living",
+                living.to_string(),
+            ));
+        }
+    }
+
+    /// The summary of the person that FamilySearch computed for display.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::DeserializeError`] if the property can't be
+    /// deserialized.
+    pub fn display(&self) -> Result<Option<DisplayProperties>> {
+        get(&self.extensions, "display", "display")
+    }
+
+    /// Sets or removes the summary of the person for display.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::JSONError`] or
+    /// [`GedcomxError::XMLSerializeError`] if the property can't be
+    /// serialized.
+    pub fn set_display(&mut self, display: Option<&DisplayProperties>) -> Result<()> {
+        set(&mut self.extensions, "display", "display", display)
+    }
+
+    /// References to the discussions about the person.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::DeserializeError`] if the property can't be
+    /// deserialized.
+    pub fn discussion_references(&self) -> Result<Vec<DiscussionReference>> {
+        get_all(
+            &self.extensions,
+            "discussion-references",
+            "discussion-reference",
+        )
+    }
+
+    /// Sets the references to the discussions about the person.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::JSONError`] or
+    /// [`GedcomxError::XMLSerializeError`] if the property can't be
+    /// serialized.
+    pub fn set_discussion_references(&mut self, references: &[DiscussionReference]) -> Result<()> {
+        set_all(
+            &mut self.extensions,
+            "discussion-references",
+            "discussion-reference",
+            references,
+        )
+    }
+
+    /// Information about the changes made to the person.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::DeserializeError`] if the property can't be
+    /// deserialized.
+    pub fn change_info(&self) -> Result<Vec<ChangeInfo>> {
+        get_all(&self.extensions, "changeInfo", "changeInfo")
+    }
+
+    /// Sets the information about the changes made to the person.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::JSONError`] or
+    /// [`GedcomxError::XMLSerializeError`] if the property can't be
+    /// serialized.
+    pub fn set_change_info(&mut self, change_info: &[ChangeInfo]) -> Result<()> {
+        set_all(
+            &mut self.extensions,
+            "changeInfo",
+            "changeInfo",
+            change_info,
+        )
+    }
+}
+
+impl Relationship {
+    /// Information about the changes made to the relationship.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::DeserializeError`] if the property can't be
+    /// deserialized.
+    pub fn change_info(&self) -> Result<Vec<ChangeInfo>> {
+        get_all(&self.extensions, "changeInfo", "changeInfo")
+    }
+
+    /// Sets the information about the changes made to the relationship.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::JSONError`] or
+    /// [`GedcomxError::XMLSerializeError`] if the property can't be
+    /// serialized.
+    pub fn set_change_info(&mut self, change_info: &[ChangeInfo]) -> Result<()> {
+        set_all(
+            &mut self.extensions,
+            "changeInfo",
+            "changeInfo",
+            change_info,
+        )
+    }
+}
+
+impl Gedcomx {
+    /// The FamilySearch API features in the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::DeserializeError`] if the property can't be
+    /// deserialized.
+    pub fn features(&self) -> Result<Vec<Feature>> {
+        get_all(&self.extensions, "features", "feature")
+    }
+
+    /// Sets the FamilySearch API features in the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::JSONError`] or
+    /// [`GedcomxError::XMLSerializeError`] if the property can't be
+    /// serialized.
+    pub fn set_features(&mut self, features: &[Feature]) -> Result<()> {
+        set_all(&mut self.extensions, "features", "feature", features)
+    }
+
+    /// The FamilySearch users in the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::DeserializeError`] if the property can't be
+    /// deserialized.
+    pub fn users(&self) -> Result<Vec<User>> {
+        get_all(&self.extensions, "users", "user")
+    }
+
+    /// Sets the FamilySearch users in the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::JSONError`] or
+    /// [`GedcomxError::XMLSerializeError`] if the property can't be
+    /// serialized.
+    pub fn set_users(&mut self, users: &[User]) -> Result<()> {
+        set_all(&mut self.extensions, "users", "user", users)
+    }
+
+    /// The merge analyses in the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::DeserializeError`] if the property can't be
+    /// deserialized.
+    pub fn merge_analyses(&self) -> Result<Vec<MergeAnalysis>> {
+        get_all(&self.extensions, "mergeAnalyses", "mergeAnalysis")
+    }
+
+    /// Sets the merge analyses in the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GedcomxError::JSONError`] or
+    /// [`GedcomxError::XMLSerializeError`] if the property can't be
+    /// serialized.
+    pub fn set_merge_analyses(&mut self, merge_analyses: &[MergeAnalysis]) -> Result<()> {
+        set_all(
+            &mut self.extensions,
+            "mergeAnalyses",
+            "mergeAnalysis",
+            merge_analyses,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::GedcomxError;
+
+    #[test]
+    fn living() {
+        let mut person = Person::default();
+        assert_eq!(person.living().unwrap(), None);
+
+        person.set_living(Some(false));
+        assert_eq!(person.living().unwrap(), Some(false));
+        assert_eq!(
+            serde_json::to_string(&person).unwrap(),
+            r#"{"living":false}"#
+        );
+        let xml = crate::xmlcodec::to_string(&person, crate::xmlcodec::Config::default()).unwrap();
+        assert!(xml.contains(r#"xmlns:fs="http://familysearch.org/v1/" fs:living="false""#));
+
+        person.set_living(None);
+        assert!(person.extensions.is_empty());
+    }
+
+    #[test]
+    fn living_not_bool() {
+        let person: Person = serde_json::from_str(r#"{"living":"yes"}"#).unwrap();
+        assert!(matches!(
+            person.living(),
+            Err(GedcomxError::DeserializeError(_))
+        ));
+
+        let person: Person = crate::xmlcodec::from_str(
+            r#"<person xmlns="http://gedcomx.org/v1/" xmlns:fs="http://familysearch.org/v1/" fs:living="yes" />"#,
+        )
+        .unwrap();
+        match person.living() {
+            Err(GedcomxError::DeserializeError(e)) => {
+                assert_eq!(e.path, "living");
+                assert_eq!(e.value.as_deref(), Some("yes"));
+            }
+            _ => panic!("living isn't a bool"),
+        }
+    }
+
+    #[test]
+    fn set_replaces() {
+        let mut gx = Gedcomx::default();
+        gx.set_users(&[User::builder().id("U-1").build()]).unwrap();
+        gx.set_users(&[User::builder().id("U-2").build()]).unwrap();
+
+        assert_eq!(gx.users().unwrap(), vec![User::builder().id("U-2").build()]);
+        assert_eq!(gx.extensions.xml_elements.len(), 1);
+
+        gx.set_users(&[]).unwrap();
+        assert!(gx.extensions.is_empty());
+    }
+
+    #[test]
+    fn xml_roundtrip() {
+        let mut person = Person::builder().id("P-1").build();
+        let display = DisplayProperties::builder()
+            .name("Howard William Kunz")
+            .lifespan("1967-2011")
+            .build();
+        person.set_display(Some(&display)).unwrap();
+        person
+            .set_discussion_references(&[DiscussionReference::new(
+                "https://api.familysearch.org/platform/discussions/discussions/dis-1".into(),
+                None,
+                None,
+            )])
+            .unwrap();
+
+        let xml = crate::xmlcodec::to_string(&person, crate::xmlcodec::Config::default()).unwrap();
+        let person: Person = crate::xmlcodec::from_str(&xml).unwrap();
+        assert!(person.extensions.json.is_empty());
+        assert_eq!(person.display().unwrap(), Some(display));
+        assert_eq!(person.discussion_references().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// A FamilySearch user.
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct User {
+    /// The id of the user.
+    pub id: Option<String>,
+
+    /// The name the user goes by when contacted.
+    pub contact_name: Option<String>,
+
+    /// The PIN the user gives to someone helping them with their account.
+    pub helper_access_pin: Option<String>,
+
+    /// The full name of the user.
+    pub full_name: Option<String>,
+
+    /// The given name of the user.
+    pub given_name: Option<String>,
+
+    /// The family name of the user.
+    pub family_name: Option<String>,
+
+    /// The email address of the user.
+    pub email: Option<String>,
+
+    /// An alternate email address of the user.
+    pub alternate_email: Option<String>,
+
+    /// The country the user lives in.
+    pub country: Option<String>,
+
+    /// The gender of the user.
+    pub gender: Option<String>,
+
+    /// The birth date of the user.
+    pub birth_date: Option<String>,
+
+    /// The phone number of the user.
+    pub phone_number: Option<String>,
+
+    /// The mobile phone number of the user.
+    pub mobile_phone_number: Option<String>,
+
+    /// The mailing address of the user.
+    pub mailing_address: Option<String>,
+
+    /// The language the user prefers, as an IETF BCP 47 tag.
+    pub preferred_language: Option<String>,
+
+    /// The name shown for the user.
+    pub display_name: Option<String>,
+
+    /// The id of the person in the tree that represents the user.
+    pub person_id: Option<String>,
+
+    /// The id of the user in the tree.
+    pub tree_user_id: Option<String>,
+}
+
+impl_xml! {
+    User: "fs:user" in _ ["fs" = crate::xmlcodec::FAMILYSEARCH_NAMESPACE] {
+        attributes {
+            id: "id",
+        }
+        elements {
+            contact_name: fs "contactName",
+            helper_access_pin: fs "helperAccessPin",
+            full_name: fs "fullName",
+            given_name: fs "givenName",
+            family_name: fs "familyName",
+            email: fs "email",
+            alternate_email: fs "alternateEmail",
+            country: fs "country",
+            gender: fs "gender",
+            birth_date: fs "birthDate",
+            phone_number: fs "phoneNumber",
+            mobile_phone_number: fs "mobilePhoneNumber",
+            mailing_address: fs "mailingAddress",
+            preferred_language: fs "preferredLanguage",
+            display_name: fs "displayName",
+            person_id: fs "personId",
+            tree_user_id: fs "treeUserId",
+        }
+    }
+}
+
+impl User {
+    pub fn builder() -> UserBuilder {
+        UserBuilder::new()
+    }
+}
+
+pub struct UserBuilder(User);
+
+impl UserBuilder {
+    pub(crate) fn new() -> Self {
+        Self(User::default())
+    }
+
+    pub fn id<I: Into<String>>(&mut self, id: I) -> &mut Self {
+        self.0.id = Some(id.into());
+        self
+    }
+
+    pub fn contact_name<I: Into<String>>(&mut self, contact_name: I) -> &mut Self {
+        self.0.contact_name = Some(contact_name.into());
+        self
+    }
+
+    pub fn helper_access_pin<I: Into<String>>(&mut self, helper_access_pin: I) -> &mut Self {
+        self.0.helper_access_pin = Some(helper_access_pin.into());
+        self
+    }
+
+    pub fn full_name<I: Into<String>>(&mut self, full_name: I) -> &mut Self {
+        self.0.full_name = Some(full_name.into());
+        self
+    }
+
+    pub fn given_name<I: Into<String>>(&mut self, given_name: I) -> &mut Self {
+        self.0.given_name = Some(given_name.into());
+        self
+    }
+
+    pub fn family_name<I: Into<String>>(&mut self, family_name: I) -> &mut Self {
+        self.0.family_name = Some(family_name.into());
+        self
+    }
+
+    pub fn email<I: Into<String>>(&mut self, email: I) -> &mut Self {
+        self.0.email = Some(email.into());
+        self
+    }
+
+    pub fn alternate_email<I: Into<String>>(&mut self, alternate_email: I) -> &mut Self {
+        self.0.alternate_email = Some(alternate_email.into());
+        self
+    }
+
+    pub fn country<I: Into<String>>(&mut self, country: I) -> &mut Self {
+        self.0.country = Some(country.into());
+        self
+    }
+
+    pub fn gender<I: Into<String>>(&mut self, gender: I) -> &mut Self {
+        self.0.gender = Some(gender.into());
+        self
+    }
+
+    pub fn birth_date<I: Into<String>>(&mut self, birth_date: I) -> &mut Self {
+        self.0.birth_date = Some(birth_date.into());
+        self
+    }
+
+    pub fn phone_number<I: Into<String>>(&mut self, phone_number: I) -> &mut Self {
+        self.0.phone_number = Some(phone_number.into());
+        self
+    }
+
+    pub fn mobile_phone_number<I: Into<String>>(&mut self, mobile_phone_number: I) -> &mut Self {
+        self.0.mobile_phone_number = Some(mobile_phone_number.into());
+        self
+    }
+
+    pub fn mailing_address<I: Into<String>>(&mut self, mailing_address: I) -> &mut Self {
+        self.0.mailing_address = Some(mailing_address.into());
+        self
+    }
+
+    pub fn preferred_language<I: Into<String>>(&mut self, preferred_language: I) -> &mut Self {
+        self.0.preferred_language = Some(preferred_language.into());
+        self
+    }
+
+    pub fn display_name<I: Into<String>>(&mut self, display_name: I) -> &mut Self {
+        self.0.display_name = Some(display_name.into());
+        self
+    }
+
+    pub fn person_id<I: Into<String>>(&mut self, person_id: I) -> &mut Self {
+        self.0.person_id = Some(person_id.into());
+        self
+    }
+
+    pub fn tree_user_id<I: Into<String>>(&mut self, tree_user_id: I) -> &mut Self {
+        self.0.tree_user_id = Some(tree_user_id.into());
+        self
+    }
+
+    pub fn build(&self) -> User {
+        self.0.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::xmlcodec::Config;
+
+    fn user() -> User {
+        User::builder()
+            .id("cis.MMM.RX9")
+            .contact_name("Pete Townsend")
+            .email("peter@acme.org")
+            .preferred_language("en")
+            .person_id("PPPJ-MYY")
+            .tree_user_id("PXRQ-FMXT")
+            .build()
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let json = r#"{"id":"cis.MMM.RX9","contactName":"Pete Townsend","email":"peter@acme.org","preferredLanguage":"en","personId":"PPPJ-MYY","treeUserId":"PXRQ-FMXT"}"#;
+
+        let user: User = serde_json::from_str(json).unwrap();
+        assert_eq!(user, self::user());
+        assert_eq!(serde_json::to_string(&user).unwrap(), json);
+    }
+
+    #[test]
+    fn xml_roundtrip() {
+        let xml = r#"<fs:user xmlns:fs="http://familysearch.org/v1/" id="cis.MMM.RX9"><fs:contactName>Pete Townsend</fs:contactName><fs:email>peter@acme.org</fs:email><fs:preferredLanguage>en</fs:preferredLanguage><fs:personId>PPPJ-MYY</fs:personId><fs:treeUserId>PXRQ-FMXT</fs:treeUserId></fs:user>"#;
+
+        let user: User = crate::xmlcodec::from_str(xml).unwrap();
+        assert_eq!(user, self::user());
+
+        let config = Config {
+            write_document_declaration: false,
+            ..Default::default()
+        };
+        assert_eq!(crate::xmlcodec::to_string(&user, config).unwrap(), xml);
+    }
+}
@@ -3,82 +3,112 @@ use std::convert::TryInto;
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
+#[cfg(feature = "async")]
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 use crate::{
-    Agent, Attribution, Document, Event, GedcomxError, Group, Id, Lang, Person, PlaceDescription,
-    Relationship, Result, SourceDescription, Uri,
+    Agent, Attribution, Collection, DeserializeWarning, Document, Event, Extensions, GedcomxError,
+    Group, Id, Lang, Link, Person, PlaceDescription, RecordDescriptor, Relationship, Result,
+    SourceDescription, Uri, lenient, tracking,
+    xmlcodec::{self, Config},
 };
 
 /// A container for a set of GEDCOM X data. The top level type in the library.
 #[skip_serializing_none]
-#[derive(Deserialize, Serialize, YaSerialize, YaDeserialize, Debug, PartialEq, Clone, Default)]
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
 #[non_exhaustive]
-#[yaserde(
-    rename = "gedcomx",
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
 #[serde(rename_all = "camelCase")]
 pub struct Gedcomx {
     /// An identifier for the data set.
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The locale identifier for the data set.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The attribution of this data set.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// The list of persons contained in the data set.
-    #[yaserde(rename = "person", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub persons: Vec<Person>,
 
     /// The list of relationships contained in the data set.
-    #[yaserde(rename = "relationship", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub relationships: Vec<Relationship>,
 
     /// The list of source descriptions contained in the data set.
-    #[yaserde(rename = "sourceDescription", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub source_descriptions: Vec<SourceDescription>,
 
     /// The list of agents contained in the data set.
-    #[yaserde(rename = "agent", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub agents: Vec<Agent>,
 
     /// The list of events contained in the data set.
-    #[yaserde(rename = "event", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub events: Vec<Event>,
 
     /// The list of documents contained in the data set.
-    #[yaserde(rename = "document", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub documents: Vec<Document>,
 
     /// The list of places contained in the data set.
-    #[yaserde(rename = "place", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub places: Vec<PlaceDescription>,
 
     /// The list of groups contained in the data set.
-    #[yaserde(rename = "group", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub groups: Vec<Group>,
 
+    /// The list of record collections contained in the data set.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub collections: Vec<Collection>,
+
+    /// The list of record descriptors contained in the data set.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub record_descriptors: Vec<RecordDescriptor>,
+
     /// Reference to the description of this data set.
     ///
     /// If provided, MUST resolve to an instance of `SourceDescription`.
-    #[yaserde(attribute)]
     pub description: Option<Uri>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Gedcomx: "gedcomx" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            lang: "xml:lang",
+            description: "description",
+        }
+        elements {
+            attribution: gx "attribution",
+            persons: gx "person",
+            relationships: gx "relationship",
+            source_descriptions: gx "sourceDescription",
+            agents: gx "agent",
+            events: gx "event",
+            documents: gx "document",
+            places: gx "place",
+            groups: gx "group",
+            collections: gx "collection",
+            record_descriptors: gx "recordDescriptor",
+            links: gx "link",
+        }
+        @extensions
+    }
 }
 
 impl Gedcomx {
@@ -109,7 +139,11 @@ impl Gedcomx {
             documents,
             places,
             groups,
+            collections: vec![],
+            record_descriptors: vec![],
             description,
+            links: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -245,6 +279,26 @@ impl GedcomxBuilder {
         self
     }
 
+    pub fn collection(&mut self, collection: Collection) -> &mut Self {
+        self.0.collections.push(collection);
+        self
+    }
+
+    pub fn collections(&mut self, collections: Vec<Collection>) -> &mut Self {
+        self.0.collections = collections;
+        self
+    }
+
+    pub fn record_descriptor(&mut self, record_descriptor: RecordDescriptor) -> &mut Self {
+        self.0.record_descriptors.push(record_descriptor);
+        self
+    }
+
+    pub fn record_descriptors(&mut self, record_descriptors: Vec<RecordDescriptor>) -> &mut Self {
+        self.0.record_descriptors = record_descriptors;
+        self
+    }
+
     /// # Errors
     ///
     /// Will return [`GedcomxError::NoId`](crate::GedcomxError::NoId) if a
@@ -255,8 +309,13 @@ impl GedcomxBuilder {
         Ok(self)
     }
 
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
     pub fn build(&self) -> Gedcomx {
-        Gedcomx::new(
+        let mut gx = Gedcomx::new(
             self.0.id.clone(),
             self.0.lang.clone(),
             self.0.attribution.clone(),
@@ -269,7 +328,11 @@ impl GedcomxBuilder {
             self.0.places.clone(),
             self.0.groups.clone(),
             self.0.description.clone(),
-        )
+        );
+        gx.collections.clone_from(&self.0.collections);
+        gx.record_descriptors.clone_from(&self.0.record_descriptors);
+        gx.links.clone_from(&self.0.links);
+        gx
     }
 }
 
@@ -310,85 +373,244 @@ impl Gedcomx {
     /// Deserialize an instance of the type from a string of JSON text.
     /// # Errors
     ///
-    /// Returns `GedcomxError::JSONError` if deserialization fails.    
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails.
     pub fn from_json_str(s: &str) -> Result<Self> {
-        serde_json::from_str(s).map_err(GedcomxError::JSONError)
+        tracking::from_json_slice(s.as_bytes())
     }
 
     /// Deserialize an instance of the type from an IO stream of JSON.
     /// # Errors
     ///
-    /// Returns `GedcomxError::JSONError` if deserialization fails.
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails, and
+    /// `GedcomxError::IoError` if reading fails.
     pub fn from_json_reader<R: std::io::Read>(rdr: R) -> Result<Self> {
-        serde_json::from_reader(rdr).map_err(GedcomxError::JSONError)
+        tracking::from_json_reader(rdr)
+    }
+
+    /// Deserialize an instance of the type from a string of JSON text,
+    /// recovering from what can't be read. Values that can't be read are left
+    /// out, except for invalid formal dates, which are kept in
+    /// [`Date::invalid_formal`](crate::Date::invalid_formal), and top level
+    /// entities that can't be read are skipped. Each of these is described by
+    /// one of the returned warnings.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if the text isn't JSON or the
+    /// document itself can't be read.
+    pub fn from_json_str_lenient(s: &str) -> Result<(Self, Vec<DeserializeWarning>)> {
+        lenient::from_json_slice(s.as_bytes())
+    }
+
+    /// Deserialize an instance of the type from an IO stream of JSON,
+    /// recovering from what can't be read like
+    /// [`from_json_str_lenient`](Self::from_json_str_lenient).
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if the stream isn't JSON or
+    /// the document itself can't be read, and `GedcomxError::IoError` if
+    /// reading fails.
+    pub fn from_json_reader_lenient<R: std::io::Read>(
+        mut rdr: R,
+    ) -> Result<(Self, Vec<DeserializeWarning>)> {
+        let mut json = vec![];
+        rdr.read_to_end(&mut json)?;
+        lenient::from_json_slice(&json)
     }
 
     /// Serialize the instance as a string of XML.
     /// # Errors
     ///
-    /// Returns `GedcomxError::XMLError` if serialization fails.
+    /// Returns `GedcomxError::XMLSerializeError` if serialization fails.
     pub fn to_xml_string(&self) -> Result<String> {
-        yaserde::ser::to_string(self).map_err(GedcomxError::XMLError)
+        xmlcodec::to_string(self, Config::default())
     }
 
     /// Serialize the instance as a string of pretty-printed XML.
     /// # Errors
     ///
-    /// Returns `GedcomxError::XMLError` if serialization fails.
+    /// Returns `GedcomxError::XMLSerializeError` if serialization fails.
     pub fn to_xml_string_pretty(&self) -> Result<String> {
-        let config = yaserde::ser::Config {
+        let config = Config {
             perform_indent: true,
-            ..yaserde::ser::Config::default()
+            ..Config::default()
         };
-        yaserde::ser::to_string_with_config(self, &config).map_err(GedcomxError::XMLError)
+        xmlcodec::to_string(self, config)
     }
 
     /// Serialize the instance as XML into the IO stream.
     /// # Errors
     ///
-    /// Returns `GedcomxError::XMLError` if serialization fails.
-    pub fn to_writer_as_xml<W: std::io::Write>(&self, writer: W) -> Result<()> {
-        yaserde::ser::serialize_with_writer(self, writer, &yaserde::ser::Config::default())
-            .map(|_| ())
-            .map_err(GedcomxError::XMLError)
+    /// Returns `GedcomxError::XMLSerializeError` if serialization fails.
+    pub fn to_writer_as_xml<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        let xml = self.to_xml_string()?;
+        writer
+            .write_all(xml.as_bytes())
+            .map_err(|e| GedcomxError::XMLSerializeError(e.to_string()))
     }
 
     /// Serialize the instance as pretty-printed XML into the IO stream.
     /// # Errors
     ///
-    /// Returns `GedcomxError::XMLError` if serialization fails.
-    pub fn to_writer_as_xml_pretty<W: std::io::Write>(&self, writer: W) -> Result<()> {
-        let config = yaserde::ser::Config {
-            perform_indent: true,
-            ..yaserde::ser::Config::default()
-        };
-        yaserde::ser::serialize_with_writer(self, writer, &config)
-            .map(|_| ())
-            .map_err(GedcomxError::XMLError)
+    /// Returns `GedcomxError::XMLSerializeError` if serialization fails.
+    pub fn to_writer_as_xml_pretty<W: std::io::Write>(&self, mut writer: W) -> Result<()> {
+        let xml = self.to_xml_string_pretty()?;
+        writer
+            .write_all(xml.as_bytes())
+            .map_err(|e| GedcomxError::XMLSerializeError(e.to_string()))
     }
 
     /// Deserialize an instance of the type from a string of XML text.
     /// # Errors
     ///
-    /// Returns `GedcomxError::XMLError` if deserialization fails.
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails.
     pub fn from_xml_str(s: &str) -> Result<Self> {
-        yaserde::de::from_str(s).map_err(GedcomxError::XMLError)
+        xmlcodec::from_str(s)
     }
 
     /// Deserialize an instance of the type from an IO stream of XML.
     /// # Errors
     ///
-    /// Returns `GedcomxError::XMLError` if deserialization fails.
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails, and
+    /// `GedcomxError::IoError` if reading fails.
     pub fn from_xml_reader<R: std::io::Read>(rdr: R) -> Result<Self> {
-        yaserde::de::from_reader(rdr).map_err(GedcomxError::XMLError)
+        xmlcodec::from_reader(rdr)
+    }
+
+    /// Deserialize an instance of the type from a string of XML text,
+    /// recovering from what can't be read like
+    /// [`from_json_str_lenient`](Self::from_json_str_lenient).
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if the text isn't XML or the
+    /// document itself can't be read.
+    pub fn from_xml_str_lenient(s: &str) -> Result<(Self, Vec<DeserializeWarning>)> {
+        lenient::from_xml_reader(s.as_bytes())
+    }
+
+    /// Deserialize an instance of the type from an IO stream of XML,
+    /// recovering from what can't be read like
+    /// [`from_json_str_lenient`](Self::from_json_str_lenient).
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if the stream isn't XML or
+    /// the document itself can't be read, and `GedcomxError::IoError` if
+    /// reading fails.
+    pub fn from_xml_reader_lenient<R: std::io::Read>(
+        rdr: R,
+    ) -> Result<(Self, Vec<DeserializeWarning>)> {
+        lenient::from_xml_reader(rdr)
+    }
+}
+
+#[cfg(feature = "async")]
+impl Gedcomx {
+    /// Deserialize an instance of the type from an async IO stream of JSON.
+    /// The stream is read to the end, then deserialized on tokio's blocking
+    /// thread pool so that a large document doesn't hold up other tasks. It
+    /// must be called from within a tokio runtime.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails, and
+    /// `GedcomxError::IoError` if reading fails.
+    pub async fn from_json_async_reader<R: AsyncRead + Unpin>(mut rdr: R) -> Result<Self> {
+        let mut json = vec![];
+        rdr.read_to_end(&mut json).await?;
+        parse_blocking(move || tracking::from_json_slice(&json)).await
+    }
+
+    /// Deserialize an instance of the type from an async IO stream of XML.
+    /// The stream is read to the end, then deserialized on tokio's blocking
+    /// thread pool so that a large document doesn't hold up other tasks. It
+    /// must be called from within a tokio runtime.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if deserialization fails, and
+    /// `GedcomxError::IoError` if reading fails.
+    pub async fn from_xml_async_reader<R: AsyncRead + Unpin>(mut rdr: R) -> Result<Self> {
+        let mut xml = vec![];
+        rdr.read_to_end(&mut xml).await?;
+        parse_blocking(move || Self::from_xml_reader(xml.as_slice())).await
+    }
+
+    /// Serialize the instance as JSON into the async IO stream.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if serialization or writing fails.
+    pub async fn to_async_writer_as_json<W: AsyncWrite + Unpin>(&self, writer: W) -> Result<()> {
+        let json = serde_json::to_vec(self)?;
+        write_all(writer, &json)
+            .await
+            .map_err(|e| serde_json::Error::io(e).into())
+    }
+
+    /// Serialize the instance as pretty-printed JSON into the async IO stream.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if serialization or writing fails.
+    pub async fn to_async_writer_as_json_pretty<W: AsyncWrite + Unpin>(
+        &self,
+        writer: W,
+    ) -> Result<()> {
+        let json = serde_json::to_vec_pretty(self)?;
+        write_all(writer, &json)
+            .await
+            .map_err(|e| serde_json::Error::io(e).into())
+    }
+
+    /// Serialize the instance as XML into the async IO stream.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::XMLSerializeError` if serialization or writing
+    /// fails.
+    pub async fn to_async_writer_as_xml<W: AsyncWrite + Unpin>(&self, writer: W) -> Result<()> {
+        let xml = self.to_xml_string()?;
+        write_all(writer, xml.as_bytes())
+            .await
+            .map_err(|e| GedcomxError::XMLSerializeError(e.to_string()))
+    }
+
+    /// Serialize the instance as pretty-printed XML into the async IO stream.
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::XMLSerializeError` if serialization or writing
+    /// fails.
+    pub async fn to_async_writer_as_xml_pretty<W: AsyncWrite + Unpin>(
+        &self,
+        writer: W,
+    ) -> Result<()> {
+        let xml = self.to_xml_string_pretty()?;
+        write_all(writer, xml.as_bytes())
+            .await
+            .map_err(|e| GedcomxError::XMLSerializeError(e.to_string()))
     }
 }
 
+// Runs `parse` on tokio's blocking thread pool. A panic in it is resumed
+// here, and if the runtime shuts down before it's run, that's an IO error.
+#[cfg(feature = "async")]
+async fn parse_blocking<F>(parse: F) -> Result<Gedcomx>
+where
+    F: FnOnce() -> Result<Gedcomx> + Send + 'static,
+{
+    match tokio::task::spawn_blocking(parse).await {
+        Ok(result) => result,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(e) => Err(GedcomxError::IoError(std::io::Error::other(e))),
+    }
+}
+
+#[cfg(feature = "async")]
+async fn write_all<W: AsyncWrite + Unpin>(mut writer: W, bytes: &[u8]) -> std::io::Result<()> {
+    writer.write_all(bytes).await?;
+    writer.flush().await
+}
+
 #[cfg(test)]
 mod test {
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use crate::Recovery;
 
     #[test]
     fn json_deserialize() {
@@ -402,6 +624,264 @@ mod test {
         assert_eq!(gedcomx, Gedcomx::default());
     }
 
+    #[test]
+    fn json_deserialize_error() {
+        let json = "{\n  \"persons\": [\n    {\"gender\": {\"type\": 3}}\n  ]\n}";
+        let Err(GedcomxError::DeserializeError(e)) = Gedcomx::from_json_str(json) else {
+            panic!("the gender type isn't a string");
+        };
+        assert_eq!(e.path, "persons[0].gender.type");
+        assert_eq!(e.value.as_deref(), Some("3"));
+        let position = e.position.unwrap();
+        assert_eq!((position.line, position.column), (3, 25));
+        assert_eq!(&json[usize::try_from(position.offset).unwrap()..][..1], "3");
+    }
+
+    #[test]
+    fn json_error_in_flattened_object() {
+        // A date's fields are read after the whole date, because of its
+        // extensions.
+        let json = "{\"persons\": [{\"facts\": [{\n  \"type\": \"http://gedcomx.org/Birth\",\n  \
+                    \"date\": {\"formal\": \"+1800-99\", \"original\": \"x\"}\n}]}]}";
+        let Err(GedcomxError::DeserializeError(e)) = Gedcomx::from_json_str(json) else {
+            panic!("the formal date isn't valid");
+        };
+        assert_eq!(e.path, "persons[0].facts[0].date.formal");
+        assert_eq!(e.value.as_deref(), Some("+1800-99"));
+        let position = e.position.unwrap();
+        assert_eq!((position.line, position.column), (3, 22));
+        assert!(json[usize::try_from(position.offset).unwrap()..].starts_with("\"+1800-99\""));
+    }
+
+    #[test]
+    fn xml_deserialize_error() {
+        let xml =
+            "<gedcomx>\n<person/>\n<person>\n<fact id=\"F-1\" id=\"F-2\"/>\n</person>\n</gedcomx>";
+        let Err(GedcomxError::DeserializeError(e)) = Gedcomx::from_xml_str(xml) else {
+            panic!("the id is given twice");
+        };
+        assert_eq!(e.path, "persons[1]");
+        let position = e.position.unwrap();
+        assert_eq!((position.line, position.column), (4, 18));
+        assert!(xml[usize::try_from(position.offset).unwrap()..].starts_with("=\"F-2\""));
+
+        // Columns count characters, but offsets count bytes.
+        let xml = "<gedcomx>\n<person>\n<fact type=\"http://gedcomx.org/Birth\">\n<date><formal>ä \
+                   +1800\né -99</formal></date></fact></person></gedcomx>";
+        let Err(GedcomxError::DeserializeError(e)) = Gedcomx::from_xml_str(xml) else {
+            panic!("the formal date isn't valid");
+        };
+        let position = e.position.unwrap();
+        assert_eq!((position.line, position.column), (5, 6));
+        assert!(xml[usize::try_from(position.offset).unwrap()..].starts_with("</formal>"));
+    }
+
+    #[test]
+    fn json_lenient() {
+        let json = r#"{
+  "persons": [
+    { "id": "P-1", "facts": [ { "type": "http://gedcomx.org/Birth", "date": { "original": "x", "formal": "+1800-99" } } ] },
+    { "id": "P-2", "names": [ { "lang": "en" }, { "lang": "fr" } ] }
+  ],
+  "relationships": [ { "id": "R-1" } ],
+  "attribution": { "modified": "yesterday" }
+}"#;
+        let (gedcomx, warnings) = Gedcomx::from_json_str_lenient(json).unwrap();
+        let recoveries: Vec<_> = warnings
+            .iter()
+            .map(|w| (w.error.path.as_str(), w.recovery))
+            .collect();
+        assert_eq!(
+            recoveries,
+            [
+                ("attribution.modified", Recovery::Defaulted),
+                ("persons[0].facts[0].date.formal", Recovery::Preserved),
+                ("persons[1].names[0]", Recovery::Defaulted),
+                ("persons[1].names[1]", Recovery::Defaulted),
+                ("relationships[0]", Recovery::Skipped),
+            ]
+        );
+        assert_eq!(warnings[0].error.value.as_deref(), Some("yesterday"));
+
+        assert_eq!(gedcomx.persons.len(), 2);
+        assert!(gedcomx.persons[1].names.is_empty());
+        assert!(gedcomx.relationships.is_empty());
+        let date = gedcomx.persons[0].facts[0].date.as_ref().unwrap();
+        assert_eq!(date.formal, None);
+        assert_eq!(date.invalid_formal.as_deref(), Some("+1800-99"));
+
+        assert!(Gedcomx::from_json_str_lenient("3").is_err());
+    }
+
+    #[test]
+    fn xml_lenient() {
+        let xml = r#"<gedcomx xmlns="http://gedcomx.org/v1/">
+<person id="P-1"><fact type="http://gedcomx.org/Birth"><date><formal>+1800-99</formal></date></fact></person>
+<relationship id="R-1"/>
+<attribution><modified>yesterday</modified></attribution>
+</gedcomx>"#;
+        let (gedcomx, warnings) = Gedcomx::from_xml_str_lenient(xml).unwrap();
+        let recoveries: Vec<_> = warnings
+            .iter()
+            .map(|w| (w.error.path.as_str(), w.recovery))
+            .collect();
+        assert_eq!(
+            recoveries,
+            [
+                ("persons[0].facts[0].date.formal", Recovery::Preserved),
+                ("relationships[0]", Recovery::Skipped),
+                ("attribution.modified", Recovery::Defaulted),
+            ]
+        );
+        assert_eq!(warnings[1].error.value, None);
+        assert_eq!(warnings[1].error.position.unwrap().line, 3);
+
+        assert_eq!(gedcomx.persons.len(), 1);
+        assert!(gedcomx.relationships.is_empty());
+        let date = gedcomx.persons[0].facts[0].date.as_ref().unwrap();
+        assert_eq!(date.formal, None);
+        assert_eq!(date.invalid_formal.as_deref(), Some("+1800-99"));
+    }
+
+    #[test]
+    fn xml_lenient_leaves_out_the_invalid_attribute() {
+        let xml =
+            r#"<gedcomx xmlns="http://gedcomx.org/v1/"><person id="yes" private="yes"/></gedcomx>"#;
+        let (gedcomx, warnings) = Gedcomx::from_xml_str_lenient(xml).unwrap();
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].error.path, "persons[0].private");
+        assert_eq!(warnings[0].recovery, Recovery::Defaulted);
+        assert_eq!(gedcomx.persons[0].id, Some("yes".into()));
+        assert_eq!(gedcomx.persons[0].private, None);
+    }
+
+    #[test]
+    fn xml_lenient_reads_entities_in_the_gedcomx_namespace() {
+        let xml = r#"<gedcomx xmlns:ex="http://example.com/"><person id="P-1"/><ex:person id="P-2" private="yes"/></gedcomx>"#;
+        let (gedcomx, warnings) = Gedcomx::from_xml_str_lenient(xml).unwrap();
+
+        assert!(warnings.is_empty());
+        assert_eq!(gedcomx, Gedcomx::from_xml_str(xml).unwrap());
+        assert_eq!(gedcomx.persons.len(), 1);
+    }
+
+    // What a lenient read keeps can be written and read again strictly.
+    #[test]
+    fn lenient_read_can_be_written_and_read_strictly() {
+        let json = r#"{ "persons": [ { "facts": [ { "type": "http://gedcomx.org/Birth", "date": { "original": "22 May 1802", "formal": "+1802-5-22" } } ] } ] }"#;
+        let (gedcomx, _) = Gedcomx::from_json_str_lenient(json).unwrap();
+        let reread = Gedcomx::from_json_str(&gedcomx.to_json_string().unwrap()).unwrap();
+        let date = reread.persons[0].facts[0].date.as_ref().unwrap();
+        assert_eq!(date.original.as_deref(), Some("22 May 1802"));
+        assert_eq!((&date.formal, &date.invalid_formal), (&None, &None));
+
+        let xml = r#"<gedcomx xmlns="http://gedcomx.org/v1/"><person><fact type="http://gedcomx.org/Birth"><date><original>22 May 1802</original><formal>+1802-5-22</formal></date></fact></person></gedcomx>"#;
+        let (gedcomx, _) = Gedcomx::from_xml_str_lenient(xml).unwrap();
+        let reread = Gedcomx::from_xml_str(&gedcomx.to_xml_string().unwrap()).unwrap();
+        let date = reread.persons[0].facts[0].date.as_ref().unwrap();
+        assert_eq!(date.original.as_deref(), Some("22 May 1802"));
+        assert_eq!((&date.formal, &date.invalid_formal), (&None, &None));
+    }
+
+    // Each invalid formal value is kept by its own date, even when two are the
+    // same, and not by one that's left out.
+    #[test]
+    fn json_lenient_keeps_each_invalid_formal() {
+        let json = r#"{ "persons": [ { "facts": [
+            { "type": "http://gedcomx.org/Birth", "date": { "formal": "+1800-99" } },
+            { "type": "http://gedcomx.org/Burial", "date": { "formal": "+1850-99" }, "value": [] },
+            { "date": { "formal": "+1900-99" } },
+            { "type": "http://gedcomx.org/Death", "date": { "formal": "+1800-99" } }
+        ] } ] }"#;
+        let (gedcomx, warnings) = Gedcomx::from_json_str_lenient(json).unwrap();
+        let recoveries: Vec<_> = warnings
+            .iter()
+            .map(|w| (w.error.path.as_str(), w.recovery))
+            .collect();
+        assert_eq!(
+            recoveries,
+            [
+                ("persons[0].facts[0].date.formal", Recovery::Preserved),
+                ("persons[0].facts[1].date.formal", Recovery::Preserved),
+                ("persons[0].facts[1].value", Recovery::Defaulted),
+                ("persons[0].facts[2]", Recovery::Defaulted),
+                ("persons[0].facts[3].date.formal", Recovery::Preserved),
+            ]
+        );
+        let invalid: Vec<_> = gedcomx.persons[0]
+            .facts
+            .iter()
+            .map(|f| f.date.as_ref().unwrap().invalid_formal.as_deref())
+            .collect();
+        assert_eq!(
+            invalid,
+            [Some("+1800-99"), Some("+1850-99"), Some("+1800-99")]
+        );
+    }
+
+    #[test]
+    fn xml_lenient_keeps_each_invalid_formal() {
+        let xml = r#"<gedcomx xmlns="http://gedcomx.org/v1/"><person>
+<fact type="http://gedcomx.org/Birth"><date><formal>+1800-99</formal></date></fact>
+<fact><date><formal>+1900-99</formal></date></fact>
+<fact type="http://gedcomx.org/Death"><date><formal>+1950</formal></date></fact>
+</person></gedcomx>"#;
+        let (gedcomx, warnings) = Gedcomx::from_xml_str_lenient(xml).unwrap();
+        let recoveries: Vec<_> = warnings
+            .iter()
+            .map(|w| (w.error.path.as_str(), w.recovery))
+            .collect();
+        assert_eq!(
+            recoveries,
+            [
+                ("persons[0].facts[0].date.formal", Recovery::Preserved),
+                ("persons[0].facts[1].date.formal", Recovery::Preserved),
+                ("persons[0].facts[1]", Recovery::Defaulted),
+            ]
+        );
+        let dates: Vec<_> = gedcomx.persons[0]
+            .facts
+            .iter()
+            .map(|f| f.date.as_ref().unwrap())
+            .map(|d| (d.formal.is_some(), d.invalid_formal.as_deref()))
+            .collect();
+        assert_eq!(dates, [(false, Some("+1800-99")), (true, None)]);
+    }
+
+    // An input that always fails, as if the connection it came from was lost.
+    struct Disconnected;
+
+    impl std::io::Read for Disconnected {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::ConnectionReset.into())
+        }
+    }
+
+    #[test]
+    fn io_errors_are_kept() {
+        fn kind<T: std::fmt::Debug>(result: Result<T>) -> std::io::ErrorKind {
+            match result {
+                Err(GedcomxError::IoError(e)) => e.kind(),
+                other => panic!("not an IO error: {other:?}"),
+            }
+        }
+        let json = || std::io::Read::chain(&br#"{"persons": ["#[..], Disconnected);
+        let xml = || std::io::Read::chain(&b"<gedcomx><person>"[..], Disconnected);
+        let reset = std::io::ErrorKind::ConnectionReset;
+
+        assert_eq!(kind(Gedcomx::from_json_reader(json())), reset);
+        assert_eq!(kind(Gedcomx::from_json_reader_lenient(json())), reset);
+        assert_eq!(kind(Gedcomx::from_xml_reader(xml())), reset);
+        assert_eq!(kind(Gedcomx::from_xml_reader_lenient(xml())), reset);
+        assert_eq!(
+            kind(crate::JsonEntityReader::new(json()).visit(|_| Ok(()))),
+            reset
+        );
+        let mut entities = crate::XmlEntityReader::new(xml()).unwrap();
+        assert_eq!(kind(entities.next().unwrap()), reset);
+    }
+
     #[test]
     fn json_serialize() {
         let gedcomx = Gedcomx::default();
@@ -430,8 +910,47 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Gedcomx) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Gedcomx = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Gedcomx = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn async_roundtrip() {
+        let json = std::fs::read("../data/birth.json").unwrap();
+        let gedcomx = Gedcomx::from_json_async_reader(json.as_slice())
+            .await
+            .unwrap();
+        assert_eq!(gedcomx, Gedcomx::from_json_reader(json.as_slice()).unwrap());
+
+        let mut json = vec![];
+        gedcomx.to_async_writer_as_json(&mut json).await.unwrap();
+        assert_eq!(json, gedcomx.to_json_string().unwrap().as_bytes());
+        let mut json = vec![];
+        gedcomx
+            .to_async_writer_as_json_pretty(&mut json)
+            .await
+            .unwrap();
+        assert_eq!(json, gedcomx.to_json_string_pretty().unwrap().as_bytes());
+
+        let mut xml = vec![];
+        gedcomx.to_async_writer_as_xml(&mut xml).await.unwrap();
+        assert_eq!(xml, gedcomx.to_xml_string().unwrap().as_bytes());
+        let mut pretty_xml = vec![];
+        gedcomx
+            .to_async_writer_as_xml_pretty(&mut pretty_xml)
+            .await
+            .unwrap();
+        assert_eq!(
+            pretty_xml,
+            gedcomx.to_xml_string_pretty().unwrap().as_bytes()
+        );
+
+        let from_xml = Gedcomx::from_xml_async_reader(xml.as_slice())
+            .await
+            .unwrap();
+        assert_eq!(from_xml, Gedcomx::from_xml_reader(xml.as_slice()).unwrap());
+        assert!(Gedcomx::from_json_async_reader(&b"{"[..]).await.is_err());
+    }
 }
@@ -0,0 +1,824 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    Agent, Attribution, Document, Event, EvidenceReference, Gedcomx, Group, Id, Note, Person,
+    PlaceDescription, PlaceReference, Relationship, ResourceReference, SourceDescription,
+    SourceReference, Uri,
+};
+
+/// The kind of top level entity in a [`Gedcomx`](crate::Gedcomx) document.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum EntityKind {
+    Person,
+    Relationship,
+    SourceDescription,
+    Agent,
+    Event,
+    Document,
+    PlaceDescription,
+    Group,
+}
+
+impl fmt::Display for EntityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Person => write!(f, "Person"),
+            Self::Relationship => write!(f, "Relationship"),
+            Self::SourceDescription => write!(f, "SourceDescription"),
+            Self::Agent => write!(f, "Agent"),
+            Self::Event => write!(f, "Event"),
+            Self::Document => write!(f, "Document"),
+            Self::PlaceDescription => write!(f, "PlaceDescription"),
+            Self::Group => write!(f, "Group"),
+        }
+    }
+}
+
+/// A borrowed top level entity of a [`Gedcomx`](crate::Gedcomx) document.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub enum Entity<'a> {
+    Person(&'a Person),
+    Relationship(&'a Relationship),
+    SourceDescription(&'a SourceDescription),
+    Agent(&'a Agent),
+    Event(&'a Event),
+    Document(&'a Document),
+    PlaceDescription(&'a PlaceDescription),
+    Group(&'a Group),
+}
+
+impl<'a> Entity<'a> {
+    pub const fn kind(&self) -> EntityKind {
+        match self {
+            Self::Person(_) => EntityKind::Person,
+            Self::Relationship(_) => EntityKind::Relationship,
+            Self::SourceDescription(_) => EntityKind::SourceDescription,
+            Self::Agent(_) => EntityKind::Agent,
+            Self::Event(_) => EntityKind::Event,
+            Self::Document(_) => EntityKind::Document,
+            Self::PlaceDescription(_) => EntityKind::PlaceDescription,
+            Self::Group(_) => EntityKind::Group,
+        }
+    }
+
+    /// The local identifier of the entity, if it has one.
+    pub const fn id(&self) -> Option<&'a Id> {
+        match self {
+            Self::Person(e) => e.id.as_ref(),
+            Self::Relationship(e) => e.id.as_ref(),
+            Self::SourceDescription(e) => e.id.as_ref(),
+            Self::Agent(e) => e.id.as_ref(),
+            Self::Event(e) => e.id.as_ref(),
+            Self::Document(e) => e.id.as_ref(),
+            Self::PlaceDescription(e) => e.id.as_ref(),
+            Self::Group(e) => e.id.as_ref(),
+        }
+    }
+
+    pub const fn as_person(&self) -> Option<&'a Person> {
+        match self {
+            Self::Person(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub const fn as_relationship(&self) -> Option<&'a Relationship> {
+        match self {
+            Self::Relationship(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub const fn as_source_description(&self) -> Option<&'a SourceDescription> {
+        match self {
+            Self::SourceDescription(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub const fn as_agent(&self) -> Option<&'a Agent> {
+        match self {
+            Self::Agent(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub const fn as_event(&self) -> Option<&'a Event> {
+        match self {
+            Self::Event(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub const fn as_document(&self) -> Option<&'a Document> {
+        match self {
+            Self::Document(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub const fn as_place_description(&self) -> Option<&'a PlaceDescription> {
+        match self {
+            Self::PlaceDescription(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    pub const fn as_group(&self) -> Option<&'a Group> {
+        match self {
+            Self::Group(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// A reference from somewhere in a document to a resource in the same
+/// document (a URI of the form `#id`).
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct Reference<'a> {
+    /// The path to the referencing node, using the JSON property names of the
+    /// document. For example, `relationships[0].person1`.
+    pub path: String,
+
+    /// The referenced URI.
+    pub reference: &'a Uri,
+
+    /// The kind of entity the spec requires the reference to resolve to.
+    pub expected: EntityKind,
+}
+
+/// An index of the top level entities of a [`Gedcomx`](crate::Gedcomx)
+/// document, used to resolve references between them.
+///
+/// References in GEDCOM X are URIs. A reference to a resource in the same
+/// document is a fragment identifier, such as `#P-1`, made up of the local id
+/// of the referenced entity.
+///
+/// # Examples
+///
+/// ```
+/// use gedcomx::{Gedcomx, Person, ReferenceIndex, Relationship};
+///
+/// let p1 = Person::builder().id("P-1").build();
+/// let p2 = Person::builder().id("P-2").build();
+/// let relationship = Relationship::builder(&p1, &p2).unwrap().build();
+/// let gx = Gedcomx::builder()
+///     .person(p1)
+///     .relationship(relationship)
+///     .build();
+///
+/// let index = ReferenceIndex::new(&gx);
+/// let person1 = index.resolve(&gx.relationships[0].person1.resource);
+/// assert_eq!(person1.and_then(|e| e.as_person()), Some(&gx.persons[0]));
+///
+/// let dangling = index.dangling_references();
+/// assert_eq!(dangling.len(), 1);
+/// assert_eq!(dangling[0].path, "relationships[0].person2");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReferenceIndex<'a> {
+    entities: Vec<Entity<'a>>,
+    by_id: HashMap<String, Entity<'a>>,
+    references: Vec<Reference<'a>>,
+}
+
+impl<'a> ReferenceIndex<'a> {
+    /// Index every top level entity of `gx`.
+    ///
+    /// If more than one entity uses the same id, the first one wins.
+    pub fn new(gx: &'a Gedcomx) -> Self {
+        let entities: Vec<_> = gx
+            .persons
+            .iter()
+            .map(Entity::Person)
+            .chain(gx.relationships.iter().map(Entity::Relationship))
+            .chain(gx.source_descriptions.iter().map(Entity::SourceDescription))
+            .chain(gx.agents.iter().map(Entity::Agent))
+            .chain(gx.events.iter().map(Entity::Event))
+            .chain(gx.documents.iter().map(Entity::Document))
+            .chain(gx.places.iter().map(Entity::PlaceDescription))
+            .chain(gx.groups.iter().map(Entity::Group))
+            .collect();
+
+        let mut by_id = HashMap::new();
+        for entity in &entities {
+            if let Some(id) = entity.id() {
+                by_id.entry(id.to_string()).or_insert(*entity);
+            }
+        }
+
+        let mut collector = ReferenceCollector { references: vec![] };
+        collector.gedcomx(gx);
+
+        Self {
+            entities,
+            by_id,
+            references: collector.references,
+        }
+    }
+
+    /// Every top level entity of the document, in document order.
+    pub fn entities(&self) -> impl Iterator<Item = Entity<'a>> + '_ {
+        self.entities.iter().copied()
+    }
+
+    /// Look up an entity by its local id.
+    pub fn get(&self, id: &str) -> Option<Entity<'a>> {
+        self.by_id.get(id).copied()
+    }
+
+    /// Resolve a reference to an entity in this document.
+    ///
+    /// Only fragment URIs (`#id`) are resolved. Any other URI refers to a
+    /// resource outside of this document and resolves to `None`.
+    pub fn resolve(&self, uri: &Uri) -> Option<Entity<'a>> {
+        uri.to_string()
+            .strip_prefix('#')
+            .and_then(|id| self.get(id))
+    }
+
+    /// Every reference in the document to a resource in the same document,
+    /// in document order.
+    pub fn references(&self) -> &[Reference<'a>] {
+        &self.references
+    }
+
+    /// References to resources in this document that don't resolve to any
+    /// entity.
+    pub fn dangling_references(&self) -> Vec<&Reference<'a>> {
+        self.references
+            .iter()
+            .filter(|r| self.resolve(r.reference).is_none())
+            .collect()
+    }
+}
+
+impl<'a> From<&'a Gedcomx> for ReferenceIndex<'a> {
+    fn from(gx: &'a Gedcomx) -> Self {
+        Self::new(gx)
+    }
+}
+
+// The walk over every reference in a document, written once for both shared
+// and mutable references so that the two always find the same ones. Each
+// collector decides what to keep in its own `push`.
+macro_rules! impl_reference_walk {
+    ($collector: ident, $iter: ident, $as_ref: ident $(, $mutability: tt)?) => {
+        impl<'a> $collector<'a> {
+            fn gedcomx(&mut self, gx: &'a $($mutability)? Gedcomx) {
+                if let Some(description) = &$($mutability)? gx.description {
+                    self.push(
+                        "description".to_string(),
+                        description,
+                        EntityKind::SourceDescription,
+                    );
+                }
+                self.attribution("attribution", gx.attribution.$as_ref());
+
+                for (i, person) in gx.persons.$iter().enumerate() {
+                    self.person(&format!("persons[{i}]"), person);
+                }
+                for (i, relationship) in gx.relationships.$iter().enumerate() {
+                    self.relationship(&format!("relationships[{i}]"), relationship);
+                }
+                for (i, source_description) in gx.source_descriptions.$iter().enumerate() {
+                    self.source_description(
+                        &format!("sourceDescriptions[{i}]"),
+                        source_description,
+                    );
+                }
+                for (i, agent) in gx.agents.$iter().enumerate() {
+                    if let Some(person) = &$($mutability)? agent.person {
+                        self.resource(&format!("agents[{i}].person"), person, EntityKind::Person);
+                    }
+                }
+                for (i, event) in gx.events.$iter().enumerate() {
+                    self.event(&format!("events[{i}]"), event);
+                }
+                for (i, document) in gx.documents.$iter().enumerate() {
+                    self.conclusion(
+                        &format!("documents[{i}]"),
+                        &$($mutability)? document.sources,
+                        document.analysis.$as_ref(),
+                        &$($mutability)? document.notes,
+                        document.attribution.$as_ref(),
+                    );
+                }
+                for (i, place) in gx.places.$iter().enumerate() {
+                    self.place_description(&format!("places[{i}]"), place);
+                }
+                for (i, group) in gx.groups.$iter().enumerate() {
+                    self.group(&format!("groups[{i}]"), group);
+                }
+            }
+
+            fn attribution(
+                &mut self,
+                path: &str,
+                attribution: Option<&'a $($mutability)? Attribution>,
+            ) {
+                let Some(attribution) = attribution else {
+                    return;
+                };
+
+                if let Some(contributor) = &$($mutability)? attribution.contributor {
+                    self.resource(
+                        &format!("{path}.contributor"),
+                        contributor,
+                        EntityKind::Agent,
+                    );
+                }
+                if let Some(creator) = &$($mutability)? attribution.creator {
+                    self.resource(&format!("{path}.creator"), creator, EntityKind::Agent);
+                }
+            }
+
+            fn resource(
+                &mut self,
+                path: &str,
+                reference: &'a $($mutability)? ResourceReference,
+                expected: EntityKind,
+            ) {
+                self.push(path.to_string(), &$($mutability)? reference.resource, expected);
+            }
+
+            fn source_references(
+                &mut self,
+                path: &str,
+                sources: &'a $($mutability)? [SourceReference],
+            ) {
+                for (i, source) in sources.$iter().enumerate() {
+                    self.source_reference(&format!("{path}[{i}]"), source);
+                }
+            }
+
+            fn source_reference(
+                &mut self,
+                path: &str,
+                source: &'a $($mutability)? SourceReference,
+            ) {
+                self.push(
+                    format!("{path}.description"),
+                    &$($mutability)? source.description,
+                    EntityKind::SourceDescription,
+                );
+                self.attribution(&format!("{path}.attribution"), source.attribution.$as_ref());
+            }
+
+            fn notes(&mut self, path: &str, notes: &'a $($mutability)? [Note]) {
+                for (i, note) in notes.$iter().enumerate() {
+                    self.attribution(
+                        &format!("{path}.notes[{i}].attribution"),
+                        note.attribution.$as_ref(),
+                    );
+                }
+            }
+
+            fn conclusion(
+                &mut self,
+                path: &str,
+                sources: &'a $($mutability)? [SourceReference],
+                analysis: Option<&'a $($mutability)? ResourceReference>,
+                notes: &'a $($mutability)? [Note],
+                attribution: Option<&'a $($mutability)? Attribution>,
+            ) {
+                self.source_references(&format!("{path}.sources"), sources);
+                if let Some(analysis) = analysis {
+                    self.resource(&format!("{path}.analysis"), analysis, EntityKind::Document);
+                }
+                self.notes(path, notes);
+                self.attribution(&format!("{path}.attribution"), attribution);
+            }
+
+            fn subject(
+                &mut self,
+                path: &str,
+                kind: EntityKind,
+                evidence: &'a $($mutability)? [EvidenceReference],
+                media: &'a $($mutability)? [SourceReference],
+            ) {
+                for (i, e) in evidence.$iter().enumerate() {
+                    let path = format!("{path}.evidence[{i}]");
+                    self.push(format!("{path}.resource"), &$($mutability)? e.resource, kind);
+                    self.attribution(&format!("{path}.attribution"), e.attribution.$as_ref());
+                }
+                self.source_references(&format!("{path}.media"), media);
+            }
+
+            fn place_reference(
+                &mut self,
+                path: &str,
+                place: Option<&'a $($mutability)? PlaceReference>,
+            ) {
+                if let Some(description) = place.and_then(|p| p.description_ref.$as_ref()) {
+                    self.push(
+                        format!("{path}.description"),
+                        description,
+                        EntityKind::PlaceDescription,
+                    );
+                }
+            }
+
+            fn person(&mut self, path: &str, person: &'a $($mutability)? Person) {
+                self.conclusion(
+                    path,
+                    &$($mutability)? person.sources,
+                    person.analysis.$as_ref(),
+                    &$($mutability)? person.notes,
+                    person.attribution.$as_ref(),
+                );
+                self.subject(
+                    path,
+                    EntityKind::Person,
+                    &$($mutability)? person.evidence,
+                    &$($mutability)? person.media,
+                );
+
+                if let Some(gender) = &$($mutability)? person.gender {
+                    self.conclusion(
+                        &format!("{path}.gender"),
+                        &$($mutability)? gender.sources,
+                        gender.analysis.$as_ref(),
+                        &$($mutability)? gender.notes,
+                        gender.attribution.$as_ref(),
+                    );
+                }
+                for (i, name) in person.names.$iter().enumerate() {
+                    self.conclusion(
+                        &format!("{path}.names[{i}]"),
+                        &$($mutability)? name.sources,
+                        name.analysis.$as_ref(),
+                        &$($mutability)? name.notes,
+                        name.attribution.$as_ref(),
+                    );
+                }
+                for (i, fact) in person.facts.$iter().enumerate() {
+                    self.fact(&format!("{path}.facts[{i}]"), fact);
+                }
+            }
+
+            fn fact(&mut self, path: &str, fact: &'a $($mutability)? crate::Fact) {
+                self.conclusion(
+                    path,
+                    &$($mutability)? fact.sources,
+                    fact.analysis.$as_ref(),
+                    &$($mutability)? fact.notes,
+                    fact.attribution.$as_ref(),
+                );
+                self.place_reference(&format!("{path}.place"), fact.place.$as_ref());
+            }
+
+            fn relationship(&mut self, path: &str, relationship: &'a $($mutability)? Relationship) {
+                self.conclusion(
+                    path,
+                    &$($mutability)? relationship.sources,
+                    relationship.analysis.$as_ref(),
+                    &$($mutability)? relationship.notes,
+                    relationship.attribution.$as_ref(),
+                );
+                self.subject(
+                    path,
+                    EntityKind::Relationship,
+                    &$($mutability)? relationship.evidence,
+                    &$($mutability)? relationship.media,
+                );
+                self.resource(
+                    &format!("{path}.person1"),
+                    &$($mutability)? relationship.person1,
+                    EntityKind::Person,
+                );
+                self.resource(
+                    &format!("{path}.person2"),
+                    &$($mutability)? relationship.person2,
+                    EntityKind::Person,
+                );
+                for (i, fact) in relationship.facts.$iter().enumerate() {
+                    self.fact(&format!("{path}.facts[{i}]"), fact);
+                }
+            }
+
+            fn source_description(
+                &mut self,
+                path: &str,
+                sd: &'a $($mutability)? SourceDescription,
+            ) {
+                let agents = [
+                    ("mediator", sd.mediator.$as_ref()),
+                    ("publisher", sd.publisher.$as_ref()),
+                    ("repository", sd.repository.$as_ref()),
+                ];
+                for (name, agent) in agents {
+                    if let Some(agent) = agent {
+                        self.resource(&format!("{path}.{name}"), agent, EntityKind::Agent);
+                    }
+                }
+                for (i, author) in sd.authors.$iter().enumerate() {
+                    self.resource(&format!("{path}.authors[{i}]"), author, EntityKind::Agent);
+                }
+
+                self.source_references(&format!("{path}.sources"), &$($mutability)? sd.sources);
+                if let Some(analysis) = &$($mutability)? sd.analysis {
+                    self.resource(&format!("{path}.analysis"), analysis, EntityKind::Document);
+                }
+                if let Some(component_of) = &$($mutability)? sd.component_of {
+                    self.source_reference(&format!("{path}.componentOf"), component_of);
+                }
+                self.notes(path, &$($mutability)? sd.notes);
+                self.attribution(&format!("{path}.attribution"), sd.attribution.$as_ref());
+                for (i, coverage) in sd.coverage.$iter().enumerate() {
+                    self.place_reference(
+                        &format!("{path}.coverage[{i}].spatial"),
+                        coverage.spatial.$as_ref(),
+                    );
+                }
+            }
+
+            fn event(&mut self, path: &str, event: &'a $($mutability)? Event) {
+                self.conclusion(
+                    path,
+                    &$($mutability)? event.sources,
+                    event.analysis.$as_ref(),
+                    &$($mutability)? event.notes,
+                    event.attribution.$as_ref(),
+                );
+                self.subject(
+                    path,
+                    EntityKind::Event,
+                    &$($mutability)? event.evidence,
+                    &$($mutability)? event.media,
+                );
+                self.place_reference(&format!("{path}.place"), event.place.$as_ref());
+
+                for (i, role) in event.roles.$iter().enumerate() {
+                    let path = format!("{path}.roles[{i}]");
+                    self.conclusion(
+                        &path,
+                        &$($mutability)? role.sources,
+                        role.analysis.$as_ref(),
+                        &$($mutability)? role.notes,
+                        role.attribution.$as_ref(),
+                    );
+                    self.resource(
+                        &format!("{path}.person"),
+                        &$($mutability)? role.person,
+                        EntityKind::Person,
+                    );
+                }
+            }
+
+            fn place_description(
+                &mut self,
+                path: &str,
+                place: &'a $($mutability)? PlaceDescription,
+            ) {
+                self.conclusion(
+                    path,
+                    &$($mutability)? place.sources,
+                    place.analysis.$as_ref(),
+                    &$($mutability)? place.notes,
+                    place.attribution.$as_ref(),
+                );
+                self.subject(
+                    path,
+                    EntityKind::PlaceDescription,
+                    &$($mutability)? place.evidence,
+                    &$($mutability)? place.media,
+                );
+                if let Some(jurisdiction) = &$($mutability)? place.jurisdiction {
+                    self.resource(
+                        &format!("{path}.jurisdiction"),
+                        jurisdiction,
+                        EntityKind::PlaceDescription,
+                    );
+                }
+            }
+
+            fn group(&mut self, path: &str, group: &'a $($mutability)? Group) {
+                self.conclusion(
+                    path,
+                    &$($mutability)? group.sources,
+                    group.analysis.$as_ref(),
+                    &$($mutability)? group.notes,
+                    group.attribution.$as_ref(),
+                );
+                self.subject(
+                    path,
+                    EntityKind::Group,
+                    &$($mutability)? group.evidence,
+                    &$($mutability)? group.media,
+                );
+                self.place_reference(&format!("{path}.place"), group.place.$as_ref());
+
+                for (i, role) in group.roles.$iter().enumerate() {
+                    let path = format!("{path}.roles[{i}]");
+                    self.conclusion(
+                        &path,
+                        &$($mutability)? role.sources,
+                        role.analysis.$as_ref(),
+                        &$($mutability)? role.notes,
+                        role.attribution.$as_ref(),
+                    );
+                    self.resource(
+                        &format!("{path}.person"),
+                        &$($mutability)? role.person,
+                        EntityKind::Person,
+                    );
+                }
+            }
+        }
+    };
+}
+
+struct ReferenceCollector<'a> {
+    references: Vec<Reference<'a>>,
+}
+
+impl<'a> ReferenceCollector<'a> {
+    fn push(&mut self, path: String, reference: &'a Uri, expected: EntityKind) {
+        if reference.to_string().starts_with('#') {
+            self.references.push(Reference {
+                path,
+                reference,
+                expected,
+            });
+        }
+    }
+}
+
+impl_reference_walk!(ReferenceCollector, iter, as_ref);
+
+impl Gedcomx {
+    /// Every reference in the document to another resource, in document
+    /// order, for rewriting. Unlike [`ReferenceIndex::references`], this
+    /// includes references to resources outside of the document, such as
+    /// `other.json#P-1`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use gedcomx::{Gedcomx, Person, Relationship, Uri};
+    ///
+    /// let p1 = Person::builder().id("P-1").build();
+    /// let p2 = Person::builder().id("P-2").build();
+    /// let relationship = Relationship::builder(&p1, &p2).unwrap().build();
+    /// let mut gx = Gedcomx::builder().relationship(relationship).build();
+    ///
+    /// for reference in gx.references_mut() {
+    ///     *reference = Uri::from(format!("people.json{reference}"));
+    /// }
+    /// assert_eq!(
+    ///     gx.relationships[0].person1.resource,
+    ///     Uri::from("people.json#P-1")
+    /// );
+    /// ```
+    pub fn references_mut(&mut self) -> Vec<&mut Uri> {
+        let mut collector = ReferenceCollectorMut { references: vec![] };
+        collector.gedcomx(self);
+        collector.references
+    }
+}
+
+struct ReferenceCollectorMut<'a> {
+    references: Vec<&'a mut Uri>,
+}
+
+impl<'a> ReferenceCollectorMut<'a> {
+    fn push(&mut self, _path: String, reference: &'a mut Uri, _expected: EntityKind) {
+        self.references.push(reference);
+    }
+}
+
+impl_reference_walk!(ReferenceCollectorMut, iter_mut, as_mut, mut);
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{SourceCitation, TextValue};
+
+    fn data() -> Gedcomx {
+        let json = std::fs::read_to_string("../data/birth.json").unwrap();
+        Gedcomx::from_json_str(&json).unwrap()
+    }
+
+    #[test]
+    fn resolves_local_references() {
+        let gx = data();
+        let index = ReferenceIndex::new(&gx);
+
+        for person in &gx.persons {
+            let uri = Uri::from(person.id.as_ref().unwrap());
+            assert_eq!(index.resolve(&uri), Some(Entity::Person(person)));
+        }
+        for sd in &gx.source_descriptions {
+            let uri = Uri::from(sd.id.as_ref().unwrap());
+            assert_eq!(
+                index.resolve(&uri).and_then(|e| e.as_source_description()),
+                Some(sd)
+            );
+        }
+
+        assert!(index.dangling_references().is_empty());
+        assert!(!index.references().is_empty());
+        assert!(
+            index
+                .references()
+                .iter()
+                .all(|r| { index.resolve(r.reference).map(|e| e.kind()) == Some(r.expected) })
+        );
+    }
+
+    #[test]
+    fn external_references_are_not_resolved() {
+        let gx = Gedcomx::builder()
+            .person(Person::builder().id("P-1").build())
+            .build();
+        let index = ReferenceIndex::new(&gx);
+
+        assert_eq!(index.resolve(&"P-1".into()), None);
+        assert_eq!(index.resolve(&"https://example.com/#P-1".into()), None);
+        assert_eq!(
+            index.resolve(&"#P-1".into()).map(|e| e.kind()),
+            Some(EntityKind::Person)
+        );
+        assert_eq!(index.get("P-1"), Some(Entity::Person(&gx.persons[0])));
+    }
+
+    #[test]
+    fn dangling_references() {
+        let p1 = Person::builder().id("P-1").build();
+        let p2 = Person::builder().id("P-2").build();
+        let relationship = Relationship::builder(&p1, &p2)
+            .unwrap()
+            .source_ref(SourceReference::new("#S-1".into(), None, None, vec![]))
+            .build();
+        let gx = Gedcomx::builder()
+            .person(p1)
+            .relationship(relationship)
+            .source_description(
+                SourceDescription::builder(SourceCitation::new("citation", None))
+                    .id("S-1")
+                    .build(),
+            )
+            .group(Group::builder(TextValue::from("group")).id("G-1").build())
+            .build();
+        let index = ReferenceIndex::new(&gx);
+
+        assert_eq!(
+            index.dangling_references(),
+            vec![&Reference {
+                path: "relationships[0].person2".to_string(),
+                reference: &"#P-2".into(),
+                expected: EntityKind::Person,
+            }]
+        );
+        assert_eq!(
+            index.entities().map(|e| e.kind()).collect::<Vec<_>>(),
+            vec![
+                EntityKind::Person,
+                EntityKind::Relationship,
+                EntityKind::SourceDescription,
+                EntityKind::Group
+            ]
+        );
+    }
+
+    #[test]
+    fn first_duplicate_id_wins() {
+        let gx = Gedcomx::builder()
+            .person(Person::builder().id("X").build())
+            .group(Group::builder(TextValue::from("group")).id("X").build())
+            .build();
+        let index = ReferenceIndex::new(&gx);
+
+        assert_eq!(index.get("X").map(|e| e.kind()), Some(EntityKind::Person));
+    }
+
+    #[test]
+    fn references_mut_covers_local_references() {
+        let mut gx = data();
+        let local: Vec<_> = ReferenceIndex::new(&gx)
+            .references()
+            .iter()
+            .map(|r| r.reference.clone())
+            .collect();
+
+        let mut references: Vec<_> = gx.references_mut().into_iter().map(|r| r.clone()).collect();
+        references.retain(|r| r.to_string().starts_with('#'));
+        assert_eq!(references, local);
+
+        for reference in gx.references_mut() {
+            *reference = Uri::from("#rewritten");
+        }
+        assert!(
+            ReferenceIndex::new(&gx)
+                .references()
+                .iter()
+                .all(|r| r.reference == &Uri::from("#rewritten"))
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use std::{cell::Cell, io::Read};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_path_to_error::Segment;
+
+use crate::{
+    DeserializeError, DeserializeWarning, Gedcomx, Recovery, Result, tracking,
+    xmlcodec::{self, XmlReader},
+};
+
+// A lenient JSON read reads the document without its entities first, and
+// then each entity on its own, so that an entity that can't be read can be
+// left out. Reading something that fails is retried after removing whatever
+// the error was about, until it succeeds or there's nothing left to remove.
+// An invalid formal value isn't removed: it's kept by the date it's in on the
+// next try.
+//
+// A lenient XML read recovers from each error as it comes to it, by skipping
+// the rest of the element it's in, so the document is only read once.
+
+/// Deserialize a `Gedcomx` from the JSON document `json`, recovering from
+/// what can't be read.
+pub fn from_json_slice(json: &[u8]) -> Result<(Gedcomx, Vec<DeserializeWarning>)> {
+    let mut document: Value = serde_json::from_slice(json)
+        .map_err(|e| tracking::json_error(&e, &[], None, tracking::json_position(&e, json)))?;
+
+    let mut entities = vec![];
+    if let Value::Object(members) = &mut document {
+        for key in JSON_ENTITY_KEYS {
+            match members.remove(key) {
+                Some(Value::Array(elements)) => entities.push((key, elements)),
+                Some(other) => {
+                    members.insert(key.to_string(), other);
+                }
+                None => (),
+            }
+        }
+    }
+
+    let mut warnings = vec![];
+    let mut gedcomx: Gedcomx = read_json(document, &[], &mut warnings)?;
+    for (key, elements) in entities {
+        for (index, element) in elements.into_iter().enumerate() {
+            let path = [
+                Segment::Map {
+                    key: key.to_string(),
+                },
+                Segment::Seq { index },
+            ];
+            let w = &mut warnings;
+            match key {
+                "persons" => push(&mut gedcomx.persons, read_json(element, &path, w), w),
+                "relationships" => {
+                    push(&mut gedcomx.relationships, read_json(element, &path, w), w);
+                }
+                "sourceDescriptions" => push(
+                    &mut gedcomx.source_descriptions,
+                    read_json(element, &path, w),
+                    w,
+                ),
+                "agents" => push(&mut gedcomx.agents, read_json(element, &path, w), w),
+                "events" => push(&mut gedcomx.events, read_json(element, &path, w), w),
+                "documents" => push(&mut gedcomx.documents, read_json(element, &path, w), w),
+                "places" => push(&mut gedcomx.places, read_json(element, &path, w), w),
+                "groups" => push(&mut gedcomx.groups, read_json(element, &path, w), w),
+                _ => unreachable!("not an entity key"),
+            }
+        }
+    }
+
+    Ok((gedcomx, warnings))
+}
+
+const JSON_ENTITY_KEYS: [&str; 8] = [
+    "persons",
+    "relationships",
+    "sourceDescriptions",
+    "agents",
+    "events",
+    "documents",
+    "places",
+    "groups",
+];
+
+// Adds an entity that was read, or a warning that it was skipped.
+fn push<T>(
+    entities: &mut Vec<T>,
+    entity: std::result::Result<T, DeserializeError>,
+    warnings: &mut Vec<DeserializeWarning>,
+) {
+    match entity {
+        Ok(entity) => entities.push(entity),
+        Err(error) => warnings.push(DeserializeWarning {
+            error,
+            recovery: Recovery::Skipped,
+        }),
+    }
+}
+
+// Reads `value`, found at `path` in the document, removing what it can't read.
+fn read_json<T: DeserializeOwned>(
+    mut value: Value,
+    path: &[Segment],
+    warnings: &mut Vec<DeserializeWarning>,
+) -> std::result::Result<T, DeserializeError> {
+    // Array elements that have been removed, by their path when they were.
+    let mut removed: Vec<Vec<Segment>> = vec![];
+    // The warnings so far, with the path of the formal value each is about if
+    // it's one that's kept.
+    let mut read_warnings: Vec<(DeserializeWarning, Option<Vec<Segment>>)> = vec![];
+
+    let result = loop {
+        let keep = read_warnings
+            .iter()
+            .filter(|(_, kept)| kept.is_some())
+            .count();
+        let (e, rejected_formal) = match deserialize_keeping(&value, keep) {
+            (Ok(t), _) => break Ok(t),
+            (Err(e), rejected_formal) => (e, rejected_formal),
+        };
+
+        let error_path: Vec<Segment> = e.path().iter().cloned().collect();
+        let mut original_path = path.to_vec();
+        original_path.extend(original_json_path(&error_path, &removed));
+        let value_at_error = tracking::value_at(&value, &error_path);
+        let error = tracking::json_error(e.inner(), &original_path, value_at_error, None);
+
+        if rejected_formal
+            && matches!(error_path.last(), Some(Segment::Map { key }) if key == "formal")
+        {
+            let recovery = Recovery::Preserved;
+            read_warnings.push((DeserializeWarning { error, recovery }, Some(error_path)));
+            continue;
+        }
+        match repair_json(&mut value, &error_path) {
+            Some((repaired, element)) => {
+                // The formal values kept in what was removed are gone too.
+                read_warnings.retain(|(_, kept)| {
+                    kept.as_ref()
+                        .is_none_or(|kept| !starts_with(kept, &repaired))
+                });
+                if element {
+                    removed.push(repaired);
+                }
+                let recovery = Recovery::Defaulted;
+                read_warnings.push((DeserializeWarning { error, recovery }, None));
+            }
+            None => break Err(error),
+        }
+    };
+
+    warnings.extend(read_warnings.into_iter().map(|(warning, _)| warning));
+    result
+}
+
+thread_local! {
+    // While a lenient read is deserializing JSON, how many invalid formal
+    // values are kept, how many have been come to, and whether the last one
+    // wasn't kept.
+    static INVALID_FORMALS: Cell<Option<(usize, usize, bool)>> = const { Cell::new(None) };
+}
+
+// Deserializes `value`, keeping the first `keep` invalid formal values it
+// comes to, and whether it failed at one that it didn't keep.
+fn deserialize_keeping<T: DeserializeOwned>(
+    value: &Value,
+    keep: usize,
+) -> (
+    std::result::Result<T, serde_path_to_error::Error<serde_json::Error>>,
+    bool,
+) {
+    INVALID_FORMALS.set(Some((keep, 0, false)));
+    let result = serde_path_to_error::deserialize(value);
+    let rejected = INVALID_FORMALS
+        .take()
+        .is_some_and(|(_, _, rejected)| rejected);
+    (result, rejected)
+}
+
+/// Whether a date should keep the invalid formal value it's come to, which it
+/// does if a lenient read has already warned about it.
+pub fn keep_invalid_formal() -> bool {
+    INVALID_FORMALS.with(|formals| {
+        let Some((keep, seen, _)) = formals.get() else {
+            return false;
+        };
+        let kept = seen < keep;
+        formals.set(Some((keep, seen + 1, !kept)));
+        kept
+    })
+}
+
+fn json_child_mut<'a>(value: &'a mut Value, segment: &Segment) -> Option<&'a mut Value> {
+    match (value, segment) {
+        (Value::Array(elements), Segment::Seq { index }) => elements.get_mut(*index),
+        (Value::Object(members), Segment::Map { key } | Segment::Enum { variant: key }) => {
+            members.get_mut(key)
+        }
+        _ => None,
+    }
+}
+
+// Removes the value at `path`, or the closest thing to it in `value`,
+// returning the path of what was removed and whether it was an array element.
+// Returns `None` if that would mean removing `value` itself.
+fn repair_json(value: &mut Value, path: &[Segment]) -> Option<(Vec<Segment>, bool)> {
+    let mut len = 0;
+    let mut current = &*value;
+    for segment in path {
+        match tracking::json_child(current, segment) {
+            Some(child) => current = child,
+            None => break,
+        }
+        len += 1;
+    }
+    let path = &path[..len];
+
+    let (last, parent_path) = path.split_last()?;
+    let parent = parent_path
+        .iter()
+        .try_fold(value, |value, segment| json_child_mut(value, segment))?;
+    match (parent, last) {
+        (Value::Array(elements), Segment::Seq { index }) => {
+            elements.remove(*index);
+            Some((path.to_vec(), true))
+        }
+        (Value::Object(members), Segment::Map { key } | Segment::Enum { variant: key }) => {
+            members.remove(key)?;
+            Some((path.to_vec(), false))
+        }
+        _ => None,
+    }
+}
+
+// The path in the document as it was of what is at `path` now, after the
+// array elements at `removed` were removed, in order.
+fn original_json_path(path: &[Segment], removed: &[Vec<Segment>]) -> Vec<Segment> {
+    let mut path = path.to_vec();
+    for element in removed.iter().rev() {
+        let Some((
+            Segment::Seq {
+                index: removed_index,
+            },
+            array,
+        )) = element.split_last()
+        else {
+            continue;
+        };
+        if path.len() > array.len()
+            && starts_with(&path, array)
+            && let Segment::Seq { index } = &mut path[array.len()]
+            && *index >= *removed_index
+        {
+            *index += 1;
+        }
+    }
+    path
+}
+
+fn starts_with(path: &[Segment], prefix: &[Segment]) -> bool {
+    path.len() >= prefix.len() && path.iter().zip(prefix).all(|(a, b)| same_segment(a, b))
+}
+
+fn same_segment(a: &Segment, b: &Segment) -> bool {
+    match (a, b) {
+        (Segment::Seq { index: a }, Segment::Seq { index: b }) => a == b,
+        (Segment::Map { key: a }, Segment::Map { key: b })
+        | (Segment::Enum { variant: a }, Segment::Enum { variant: b }) => a == b,
+        _ => false,
+    }
+}
+
+/// Deserialize a `Gedcomx` from an XML document, recovering from what can't
+/// be read.
+pub fn from_xml_reader<R: Read>(reader: R) -> Result<(Gedcomx, Vec<DeserializeWarning>)> {
+    let mut reader = XmlReader::lenient(reader);
+    let gedcomx = xmlcodec::read_document(&mut reader).map_err(|e| reader.fail(e))?;
+    Ok((gedcomx, reader.into_warnings()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tracking::path_string;
+
+    fn seq(index: usize) -> Segment {
+        Segment::Seq { index }
+    }
+
+    fn map(key: &str) -> Segment {
+        Segment::Map {
+            key: key.to_string(),
+        }
+    }
+
+    #[test]
+    fn original_json_paths() {
+        let removed = [
+            vec![map("names"), seq(1)],
+            vec![map("names"), seq(1)],
+            vec![map("facts"), seq(0)],
+        ];
+        let path = |p: &[Segment]| path_string(&original_json_path(p, &removed));
+        assert_eq!(path(&[map("names"), seq(0)]), "names[0]");
+        assert_eq!(path(&[map("names"), seq(1), map("lang")]), "names[3].lang");
+        assert_eq!(path(&[map("facts"), seq(0)]), "facts[1]");
+        assert_eq!(path(&[map("notes"), seq(1)]), "notes[1]");
+    }
+}
@@ -126,6 +126,10 @@ mod macros;
 mod agent;
 pub use agent::*;
 
+pub mod atom;
+
+mod canonical;
+
 mod common;
 pub use common::*;
 
@@ -133,17 +137,38 @@ mod conclusion;
 pub use conclusion::*;
 
 mod error;
-pub use error::GedcomxError;
+pub use error::{DeserializeError, DeserializeWarning, GedcomxError, Position, Recovery};
 
 mod gedcomx;
 pub use crate::gedcomx::*;
 
+#[cfg(feature = "familysearch")]
+pub mod familysearch;
+
+mod index;
+pub use index::{Entity, EntityKind, Reference, ReferenceIndex};
+
+mod lenient;
+
 mod source;
 use std::fmt;
 
 use serde::{Deserialize, Serialize};
 pub use source::*;
 
+mod stream;
+pub use stream::{JsonEntityReader, JsonEntityWriter, OwnedEntity, XmlEntityReader};
+
+mod tracking;
+
+mod traversal;
+pub use traversal::{FamilyGraph, Generations, Related};
+
+mod validation;
+pub use validation::{Severity, ValidationIssue, ValidationIssueKind, ValidationReport};
+
+mod xmlcodec;
+
 pub type Result<T> = std::result::Result<T, GedcomxError>;
 
 // I can't figure out how to get Serde to properly serialize enums with a bunch
@@ -196,6 +221,7 @@ impl TestData {
         let qualifier = Qualifier {
             name: SourceReferenceQualifier::RectangleRegion.into(),
             value: Some("rectangle region value".into()),
+            extensions: Extensions::default(),
         };
         let mut source_reference = SourceReference::new(Uri::from("SD-1"), None, None, vec![]);
         source_reference.description_id = Some("Description id of the target source".into());
@@ -233,10 +259,9 @@ impl TestData {
 }
 
 // XML roundtripping for Option<String> is only guaranteeed when a string is
-// non-empty and has leading and trailing whitespace trimmed. If the string is
-// empty, it will be roundtripped to None. If it has leading or trailing
-// whitespace, that will be stripped due to yaserde setting the trim_whitespace
-// flag on the wrapped xml parser.
+// non-empty and isn't only whitespace. The empty text of an element is read as
+// None, and text that's only whitespace is taken to be formatting when it's
+// next to child elements.
 // Also strip out control characters, since most aren't valid XML and we want
 // our tests to mainly check roundtripping of XML, not failure to parse
 // disallowed chars.
@@ -13,113 +13,243 @@ macro_rules! try_from_evidencereference {
     };
 }
 
-macro_rules! impl_enumasstring_yaserialize_yadeserialize {
+macro_rules! impl_enumasstring_xml_value {
     ($for_type: ty, $name: tt) => {
-        impl yaserde::YaSerialize for $for_type {
-            fn serialize<W: std::io::Write>(
-                &self,
-                writer: &mut yaserde::ser::Serializer<W>,
-            ) -> std::result::Result<(), String> {
-                let _ret = writer.write(xml::writer::XmlEvent::characters(&self.to_string()));
-                Ok(())
+        impl crate::xmlcodec::XmlValue for $for_type {
+            const NAME: &'static str = $name;
+
+            fn to_xml_value(&self) -> std::borrow::Cow<'_, str> {
+                std::borrow::Cow::Owned(self.to_string())
             }
 
-            fn serialize_attributes(
-                &self,
-                attributes: Vec<xml::attribute::OwnedAttribute>,
-                namespace: xml::namespace::Namespace,
-            ) -> std::result::Result<
-                (
-                    Vec<xml::attribute::OwnedAttribute>,
-                    xml::namespace::Namespace,
-                ),
-                String,
-            > {
-                Ok((attributes, namespace))
+            fn from_xml_value(
+                value: String,
+            ) -> std::result::Result<Self, crate::xmlcodec::ValueError> {
+                Ok(Self::from(crate::EnumAsString(value)))
             }
         }
+    };
+}
 
-        impl yaserde::YaDeserialize for $for_type {
-            fn deserialize<R: std::io::Read>(
-                reader: &mut yaserde::de::Deserializer<R>,
-            ) -> std::result::Result<Self, String> {
-                if let xml::reader::XmlEvent::StartElement { name, .. } = reader.peek()?.to_owned()
-                {
-                    let expected_name = $name.to_owned();
-                    if name.local_name != expected_name {
-                        return Err(format!(
-                            "Wrong StartElement name: {}, expected: {}",
-                            name, expected_name
-                        ));
-                    }
-                    let _next = reader.next_event();
-                } else {
-                    return Err("StartElement missing".to_string());
-                }
-
-                if let xml::reader::XmlEvent::Characters(text) = reader.peek()?.to_owned() {
-                    let enum_as_string = crate::EnumAsString(text);
-                    Ok(Self::from(enum_as_string))
-                } else {
-                    Err("Characters missing".to_string())
-                }
+// Standard values are parsed from their URI, anything else is `Custom`.
+macro_rules! impl_from_uri {
+    ($for_type: ty) => {
+        impl From<crate::Uri> for $for_type {
+            fn from(uri: crate::Uri) -> Self {
+                Self::from(crate::EnumAsString(uri.to_string()))
             }
         }
     };
 }
 
-macro_rules! impl_characters_yaserialize_yadeserialize {
+macro_rules! impl_characters_xml_value {
     ($for_type: ty, $name: tt) => {
-        impl yaserde::YaSerialize for $for_type {
-            fn serialize<W: std::io::Write>(
-                &self,
-                writer: &mut yaserde::ser::Serializer<W>,
-            ) -> Result<(), String> {
-                let _ret = writer.write(xml::writer::XmlEvent::characters(&self.0));
-                Ok(())
+        impl crate::xmlcodec::XmlValue for $for_type {
+            const NAME: &'static str = $name;
+
+            fn to_xml_value(&self) -> std::borrow::Cow<'_, str> {
+                std::borrow::Cow::Borrowed(&self.0)
+            }
+
+            fn from_xml_value(
+                value: String,
+            ) -> std::result::Result<Self, crate::xmlcodec::ValueError> {
+                Ok(Self(value))
             }
+        }
+    };
+}
+
+// The name of an element with `$prefix` in a type whose default namespace is
+// `$default`, which is unprefixed in that namespace.
+macro_rules! xml_name {
+    (gx, gx, $name: literal) => {
+        $name
+    };
+    (atom, atom, $name: literal) => {
+        $name
+    };
+    ($default: tt, $prefix: ident, $name: literal) => {
+        concat!(stringify!($prefix), ":", $name)
+    };
+}
 
-            fn serialize_attributes(
+macro_rules! xml_namespace {
+    (gx) => {
+        crate::GEDCOMX_NAMESPACE
+    };
+    (atom) => {
+        crate::xmlcodec::ATOM_NAMESPACE
+    };
+    (fs) => {
+        crate::xmlcodec::FAMILYSEARCH_NAMESPACE
+    };
+}
+
+/// Implements `ToXml` and `FromXml` for a struct from how each of its fields
+/// is written. Attributes come first, then the text or the elements, each in
+/// the order given, then the extensions. An `invalid` field isn't written, but
+/// gets the text of the element named for it when a lenient read can't read
+/// that element.
+///
+/// ```ignore
+/// impl_xml! {
+///     Note: "note" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+///         attributes { lang: "xml:lang" }
+///         elements { subject: gx "subject", text: gx "text" }
+///         @extensions
+///     }
+/// }
+/// ```
+macro_rules! impl_xml {
+    (
+        $for_type: ident: $name: literal in $default: tt [$($prefix: literal = $uri: path),* $(,)?] {
+            $(attributes { $($attribute: ident: $attribute_name: literal),* $(,)? })?
+            $(text $text: ident)?
+            $(elements { $($element: ident: $element_prefix: ident $element_name: literal),* $(,)? })?
+            $(invalid { $($invalid: ident: $invalid_name: literal),* $(,)? })?
+            $(@$extensions: ident)?
+        }
+    ) => {
+        impl crate::xmlcodec::ToXml for $for_type {
+            const NAME: &'static str = $name;
+
+            fn write(
                 &self,
-                attributes: Vec<xml::attribute::OwnedAttribute>,
-                namespace: xml::namespace::Namespace,
-            ) -> Result<
-                (
-                    Vec<xml::attribute::OwnedAttribute>,
-                    xml::namespace::Namespace,
-                ),
-                String,
-            > {
-                Ok((attributes, namespace))
+                writer: &mut crate::xmlcodec::XmlWriter,
+                name: &str,
+            ) -> crate::Result<()> {
+                #[allow(unused_imports)]
+                use crate::xmlcodec::{AttributeField as _, ElementField as _, TextField as _};
+
+                writer.start(name);
+                $(writer.namespace($prefix, $uri);)*
+                $(self.$extensions.write_xml_namespaces(writer);)?
+                $($(self.$attribute.write_attribute(writer, $attribute_name);)*)?
+                $(self.$extensions.write_xml_attributes(writer);)?
+                $(self.$text.write_text(writer);)?
+                $($(
+                    self.$element
+                        .write_elements(writer, xml_name!($default, $element_prefix, $element_name))?;
+                )*)?
+                $(self.$extensions.write_xml_elements(writer)?;)?
+                writer.end(name);
+                Ok(())
             }
         }
 
-        impl yaserde::YaDeserialize for $for_type {
-            fn deserialize<R: std::io::Read>(
-                reader: &mut yaserde::de::Deserializer<R>,
-            ) -> Result<Self, String> {
-                if let xml::reader::XmlEvent::StartElement { name, .. } = reader.peek()?.to_owned()
-                {
-                    let expected_name = $name.to_owned();
-                    if name.local_name != expected_name {
-                        return Err(format!(
-                            "Wrong StartElement name: {}, expected: {}",
-                            name, expected_name
-                        ));
-                    }
-                    let _next = reader.next_event();
-                } else {
-                    return Err("StartElement missing".to_string());
+        impl crate::xmlcodec::FromXml for $for_type {
+            #[allow(dead_code, unused_variables, unused_mut, clippy::needless_continue)]
+            fn read<R: std::io::Read>(
+                reader: &mut crate::xmlcodec::XmlReader<R>,
+                start: crate::xmlcodec::StartElement,
+            ) -> std::result::Result<Self, crate::DeserializeError> {
+                use crate::xmlcodec::{Content, XmlField};
+
+                // The elements whose text is kept if a lenient read can't
+                // read them.
+                const INVALID: &[&str] = &[$($($invalid_name),*)?];
+
+                $($(let mut $attribute = crate::xmlcodec::slot(|s: &Self| &s.$attribute);)*)?
+                $(
+                    let mut $text = crate::xmlcodec::slot(|s: &Self| &s.$text);
+                    let mut text = String::new();
+                    let mut mixed = false;
+                )?
+                $($(let mut $element = crate::xmlcodec::slot(|s: &Self| &s.$element);)*)?
+                $(let mut $extensions = crate::Extensions::default();)?
+
+                for attribute in start.attributes {
+                    $($(
+                        if crate::xmlcodec::is_attribute(&attribute.name, $attribute_name) {
+                            if let Err(e) = crate::xmlcodec::read_attribute(
+                                |s: &Self| &s.$attribute,
+                                &mut $attribute,
+                                attribute.value,
+                            ) {
+                                reader.recover_attribute(&attribute.name, e)?;
+                            }
+                            continue;
+                        }
+                    )*)?
+                    $($extensions.read_xml_attribute(attribute);)?
                 }
 
-                if let xml::reader::XmlEvent::Characters(text) = reader.peek()?.to_owned() {
-                    Ok(Self(text))
-                } else {
-                    Err("Characters missing".to_string())
+                loop {
+                    match reader.next_content()? {
+                        Content::Start(child) => {
+                            $(
+                                let _ = stringify!($text);
+                                mixed = true;
+                            )?
+                            $($(
+                                if crate::xmlcodec::is_element(
+                                    &child.name,
+                                    xml_namespace!($element_prefix),
+                                    $element_name,
+                                ) {
+                                    crate::xmlcodec::read_element(
+                                        stringify!($element),
+                                        INVALID.contains(&$element_name),
+                                        |s: &Self| &s.$element,
+                                        &mut $element,
+                                        reader,
+                                        child,
+                                    )?;
+                                    continue;
+                                }
+                            )*)?
+                            impl_xml!(@unknown reader, child, $($extensions)?);
+                        }
+                        Content::Text(t) => {
+                            impl_xml!(@text text, t, $($text)?);
+                        }
+                        Content::End => break,
+                    }
                 }
+
+                $(crate::xmlcodec::read_text(|s: &Self| &s.$text, &mut $text, text, mixed);)?
+                $($(let $invalid = reader.take_invalid($invalid_name);)*)?
+
+                Ok(Self {
+                    $($(
+                        $attribute: XmlField::finish($attribute, $attribute_name, stringify!($for_type))
+                            .map_err(|e| reader.error(e))?,
+                    )*)?
+                    $(
+                        $text: XmlField::finish($text, "text", stringify!($for_type))
+                            .map_err(|e| reader.error(e))?,
+                    )?
+                    $($(
+                        $element: XmlField::finish($element, $element_name, stringify!($for_type))
+                            .map_err(|e| reader.error(e))?,
+                    )*)?
+                    $($($invalid,)*)?
+                    $($extensions,)?
+                })
             }
         }
     };
+
+    // Elements that aren't fields are kept as extensions, if the type has
+    // them.
+    (@unknown $reader: ident, $child: ident,) => {
+        $reader.skip()?
+    };
+    (@unknown $reader: ident, $child: ident, $extensions: ident) => {
+        $extensions.read_xml_element($reader, &$child)?
+    };
+
+    (@text $buffer: ident, $t: ident,) => {
+        drop($t)
+    };
+    (@text $buffer: ident, $t: ident, $text: ident) => {
+        if $buffer.is_empty() {
+            $buffer = $t;
+        } else {
+            $buffer.push_str(&$t);
+        }
+    };
 }
 
 macro_rules! conclusion_builder_functions {
@@ -1,34 +1,42 @@
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::{Date, PlaceReference};
+use crate::{Date, Extensions, PlaceReference};
 
 /// The coverage of a resource.
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[non_exhaustive]
 pub struct Coverage {
     /// The spatial (i.e., geographic) coverage.
-    #[yaserde(prefix = "gx")]
     pub spatial: Option<PlaceReference>,
 
     /// The temporal coverage.
-    #[yaserde(prefix = "gx")]
     pub temporal: Option<Date>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    Coverage: "Coverage" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        elements {
+            spatial: gx "spatial",
+            temporal: gx "temporal",
+        }
+        @extensions
+    }
 }
 
 impl Coverage {
     pub fn new(spatial: Option<PlaceReference>, temporal: Option<Date>) -> Self {
-        Self { spatial, temporal }
+        Self {
+            spatial,
+            temporal,
+            extensions: Extensions::default(),
+        }
     }
 }
 
@@ -78,7 +86,7 @@ mod test {
     
       </Coverage>"#;
 
-        let coverage: Coverage = yaserde::de::from_str(xml).unwrap();
+        let coverage: Coverage = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(
             coverage,
@@ -122,12 +130,12 @@ mod test {
             Some(Date::new(Some("Original date"), None)),
         );
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
 
-        let xml = yaserde::ser::to_string_with_config(&coverage, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&coverage, config).unwrap();
 
         let expected_xml = r#"<Coverage xmlns="http://gedcomx.org/v1/"><spatial><original>Place reference</original></spatial><temporal><original>Original date</original></temporal></Coverage>"#;
 
@@ -143,8 +151,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: Coverage) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: Coverage = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: Coverage = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
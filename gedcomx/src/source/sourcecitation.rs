@@ -1,24 +1,15 @@
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::Lang;
+use crate::{Extensions, Lang};
 
 /// A container for the metadata necessary for an agent to identify a source(s).
 #[skip_serializing_none]
-#[derive(
-    Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default, Eq,
-)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default, Eq)]
 #[non_exhaustive]
 pub struct SourceCitation {
     /// The locale identifier for the bibliographic metadata.
-    #[yaserde(attribute, prefix = "xml")]
     pub lang: Option<Lang>,
 
     /// The bibliographic metadata rendered as a full citation.
@@ -26,8 +17,23 @@ pub struct SourceCitation {
     ///  This string is plain text, but MAY include an xhtml cite element. If
     /// the value includes a cite element, the text-level semantics defined for
     /// cite MUST apply—i.e., the element MUST represent the title of a work.
-    #[yaserde(prefix = "gx")]
     pub value: String,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    SourceCitation: "SourceCitation" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            lang: "xml:lang",
+        }
+        elements {
+            value: gx "value",
+        }
+        @extensions
+    }
 }
 
 impl SourceCitation {
@@ -35,6 +41,7 @@ impl SourceCitation {
         Self {
             lang,
             value: value.into(),
+            extensions: Extensions::default(),
         }
     }
 }
@@ -50,6 +57,7 @@ mod test {
     use pretty_assertions::assert_eq;
 
     use super::super::SourceCitation;
+    use crate::Extensions;
 
     #[test]
     fn json_deserialize() {
@@ -64,6 +72,7 @@ mod test {
             SourceCitation {
                 lang: Some("en".into()),
                 value: "a rendering of the full citation as a string".to_string(),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -74,12 +83,13 @@ mod test {
         <value>a rendering of the full citation as a string</value>    
       </SourceCitation>"#;
 
-        let source_citation: SourceCitation = yaserde::de::from_str(xml).unwrap();
+        let source_citation: SourceCitation = crate::xmlcodec::from_str(xml).unwrap();
         assert_eq!(
             source_citation,
             SourceCitation {
                 lang: Some("en".into()),
                 value: "a rendering of the full citation as a string".to_string(),
+                extensions: Extensions::default(),
             }
         );
     }
@@ -105,6 +115,7 @@ mod test {
         let source_citation = SourceCitation {
             lang: Some("en".into()),
             value: "a rendering of the full citation as a string".to_string(),
+            extensions: Extensions::default(),
         };
 
         let json = serde_json::to_string(&source_citation).unwrap();
@@ -120,14 +131,15 @@ mod test {
         let source_citation = SourceCitation {
             lang: Some("en".into()),
             value: "a rendering of the full citation as a string".to_string(),
+            extensions: Extensions::default(),
         };
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
 
-        let xml = yaserde::ser::to_string_with_config(&source_citation, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&source_citation, config).unwrap();
 
         assert_eq!(
             xml,
@@ -159,8 +171,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: SourceCitation) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: SourceCitation = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: SourceCitation = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -3,31 +3,23 @@ use std::{convert::TryInto, fmt};
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
 use crate::{
-    Agent, Attribution, Coverage, Document, EnumAsString, Id, Identifier, Note, ResourceReference,
-    Result, SourceCitation, SourceReference, TextValue, Timestamp, Uri,
+    Agent, Attribution, Coverage, Document, EnumAsString, Extensions, Id, Identifier, Link, Note,
+    ResourceReference, Result, SourceCitation, SourceReference, TextValue, Timestamp, Uri,
 };
 
 /// A description of a source of genealogical information.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Default, Clone)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
 #[non_exhaustive]
 #[serde(rename_all = "camelCase")]
 pub struct SourceDescription {
     /// An identifier for the data structure holding the source description
     /// data.
-    #[yaserde(attribute)]
     pub id: Option<Id>,
 
     /// The type of resource being described.
-    #[yaserde(rename = "resourceType", attribute)]
     pub resource_type: Option<ResourceType>,
 
     /// The citation(s) for this source.
@@ -35,54 +27,45 @@ pub struct SourceDescription {
     /// At least one citation MUST be provided. If more than one citation is
     /// provided, citations are assumed to be given in order of preference, with
     /// the most preferred citation in the first position in the list.
-    #[yaserde(rename = "citation", prefix = "gx")]
     pub citations: Vec<SourceCitation>,
 
     /// A hint about the media type of the resource being described.
     ///
     /// If provided, MUST be a valid MIME (media) type as specified by RFC 4288.
-    #[yaserde(rename = "mediaType", attribute)]
     pub media_type: Option<String>,
 
     /// A uniform resource identifier (URI) for the resource being described.
-    #[yaserde(attribute)]
     pub about: Option<Uri>,
 
     /// A reference to the entity that mediates access to the described source.
     ///
     /// If provided, MUST resolve to an instance of <http://gedcomx.org/v1/Agent/>.
-    #[yaserde(prefix = "gx")]
     pub mediator: Option<ResourceReference>,
 
     /// A reference to the entity responsible for making the described source
     /// available.
     ///
     /// If provided, MUST resolve to an instance of <http://gedcomx.org/v1/Agent/>.
-    #[yaserde(prefix = "gx")]
     pub publisher: Option<ResourceReference>,
 
     /// A reference to the entities that authored the described source.
     ///
     /// If provided, MUST resolve to an instance of <http://gedcomx.org/v1/Agent/>.
-    #[yaserde(rename = "author", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub authors: Vec<ResourceReference>,
 
     /// A list of references to any sources from which this source is derived.
-    #[yaserde(rename = "source", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub sources: Vec<SourceReference>,
 
     /// A reference to a document containing analysis about this source.
     ///
     /// If provided, MUST resolve to an instance of <http://gedcomx.org/v1/Document/> of type <http://gedcomx.org/Analysis/>.
-    #[yaserde(prefix = "gx")]
     pub analysis: Option<ResourceReference>,
 
     /// A reference to the source that contains this source, i.e. its parent
     /// context. Used when the description of a source is not complete without
     /// the description of its parent (or containing) source.
-    #[yaserde(rename = "componentOf", prefix = "gx")]
     pub component_of: Option<SourceReference>,
 
     /// The display name(s) for this source.
@@ -90,12 +73,10 @@ pub struct SourceDescription {
     /// If more than one title is provided, titles are assumed to be given in
     /// order of preference, with the most preferred title in the first position
     /// in the list.
-    #[yaserde(rename = "title", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub titles: Vec<TextValue>,
 
     /// A list of notes about a source.
-    #[yaserde(rename = "note", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub notes: Vec<Note>,
 
@@ -103,16 +84,13 @@ pub struct SourceDescription {
     ///
     /// If not provided, the attribution of the containing data set (e.g. file)
     /// of the source description is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// The rights for this resource.
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub rights: Vec<ResourceReference>,
 
     /// The coverage of the resource.
-    #[yaserde(prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub coverage: Vec<Coverage>,
 
@@ -121,12 +99,10 @@ pub struct SourceDescription {
     /// If more than one description is provided, descriptions are assumed to be
     /// given in order of preference, with the most preferred description in the
     /// first position in the list.
-    #[yaserde(rename = "description", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub descriptions: Vec<TextValue>,
 
     /// A list of identifiers for the resource being described.
-    #[yaserde(rename = "identifier", prefix = "gx")]
     #[serde(
         skip_serializing_if = "Vec::is_empty",
         default,
@@ -135,22 +111,63 @@ pub struct SourceDescription {
     pub identifiers: Vec<Identifier>,
 
     /// Timestamp of when the resource being described was created.
-    #[yaserde(prefix = "gx")]
     pub created: Option<Timestamp>,
 
     /// Timestamp of when the resource being described was modified.
-    #[yaserde(prefix = "gx")]
     pub modified: Option<Timestamp>,
 
     /// Timestamp of when the resource being described was published.
-    #[yaserde(prefix = "gx")]
     pub published: Option<Timestamp>,
 
     /// A reference to the repository that contains the described resource.
     ///
     /// If provided, MUST resolve to an instance of <http://gedcomx.org/v1/Agent/>.
-    #[yaserde(prefix = "gx")]
     pub repository: Option<ResourceReference>,
+
+    /// Hypermedia links to related resources.
+    #[serde(
+        skip_serializing_if = "Vec::is_empty",
+        default,
+        with = "crate::serde_vec_link_to_map"
+    )]
+    pub links: Vec<Link>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    SourceDescription: "SourceDescription" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            id: "id",
+            resource_type: "resourceType",
+            media_type: "mediaType",
+            about: "about",
+        }
+        elements {
+            citations: gx "citation",
+            mediator: gx "mediator",
+            publisher: gx "publisher",
+            authors: gx "author",
+            sources: gx "source",
+            analysis: gx "analysis",
+            component_of: gx "componentOf",
+            titles: gx "title",
+            notes: gx "note",
+            attribution: gx "attribution",
+            rights: gx "rights",
+            coverage: gx "coverage",
+            descriptions: gx "description",
+            identifiers: gx "identifier",
+            created: gx "created",
+            modified: gx "modified",
+            published: gx "published",
+            repository: gx "repository",
+            links: gx "link",
+        }
+        @extensions
+    }
 }
 
 #[allow(clippy::similar_names)]
@@ -203,6 +220,8 @@ impl SourceDescription {
             modified,
             published,
             repository,
+            links: vec![],
+            extensions: Extensions::default(),
         }
     }
 
@@ -387,8 +406,13 @@ impl SourceDescriptionBuilder {
         Ok(self)
     }
 
+    pub fn link(&mut self, link: Link) -> &mut Self {
+        self.0.links.push(link);
+        self
+    }
+
     pub fn build(&self) -> SourceDescription {
-        SourceDescription::new(
+        let mut source_description = SourceDescription::new(
             self.0.id.clone(),
             self.0.resource_type.clone(),
             self.0.citations.clone(),
@@ -411,7 +435,9 @@ impl SourceDescriptionBuilder {
             self.0.modified.clone(),
             self.0.published.clone(),
             self.0.repository.clone(),
-        )
+        );
+        source_description.links.clone_from(&self.0.links);
+        source_description
     }
 }
 
@@ -439,7 +465,8 @@ pub enum ResourceType {
     Custom(Uri),
 }
 
-impl_enumasstring_yaserialize_yadeserialize!(ResourceType, "ResourceType");
+impl_enumasstring_xml_value!(ResourceType, "ResourceType");
+impl_from_uri!(ResourceType);
 
 impl From<EnumAsString> for ResourceType {
     fn from(f: EnumAsString) -> Self {
@@ -535,7 +562,7 @@ mod test {
         <publisher resource="#agent" />
       </SourceDescription>"##;
 
-        let source_description: SourceDescription = yaserde::de::from_str(xml).unwrap();
+        let source_description: SourceDescription = crate::xmlcodec::from_str(xml).unwrap();
 
         let agent = Agent::builder().id("agent").build();
         let expected_source_description =
@@ -591,12 +618,12 @@ mod test {
                 .unwrap()
                 .build();
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
 
-        let xml = yaserde::ser::to_string_with_config(&source_description, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&source_description, config).unwrap();
 
         let expected_xml = r##"<SourceDescription xmlns="http://gedcomx.org/v1/" id="local_id" resourceType="http://gedcomx.org/PhysicalArtifact" mediaType="media_type" about="about"><citation xml:lang="en"><value>citation</value></citation><mediator resource="#agent" /><publisher resource="#agent" /></SourceDescription>"##;
 
@@ -613,8 +640,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: SourceDescription) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: SourceDescription = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: SourceDescription = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
@@ -7,36 +7,27 @@ use std::{
 use quickcheck::{Arbitrary, Gen};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
-use yaserde_derive::{YaDeserialize, YaSerialize};
 
-use crate::{Attribution, GedcomxError, Id, Qualifier, Result, SourceDescription, Uri};
+use crate::{Attribution, Extensions, GedcomxError, Id, Qualifier, Result, SourceDescription, Uri};
 
 /// A reference to a source description.
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize, YaSerialize, YaDeserialize, PartialEq, Clone, Default)]
-#[yaserde(
-    prefix = "gx",
-    default_namespace = "gx",
-    namespace = "gx: http://gedcomx.org/v1/"
-)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct SourceReference {
     /// Reference to a description of the target source.
     ///
     /// MUST resolve to an instance of <http://gedcomx.org/v1/SourceDescription/>.
-    #[yaserde(attribute)]
     pub description: Uri,
 
     /// The id of the target source.
-    #[yaserde(rename = "descriptionId", attribute)]
     pub description_id: Option<Id>,
 
     /// The attribution of this source reference.
     ///
     /// If not provided, the attribution of the containing resource of the
     /// source reference is assumed.
-    #[yaserde(prefix = "gx")]
     pub attribution: Option<Attribution>,
 
     /// Qualifiers for the reference, used to identify specific fragments of the
@@ -45,9 +36,26 @@ pub struct SourceReference {
     /// If present, use of a
     /// [`SourceReferenceQualifier`](crate::SourceReferenceQualifier) is
     /// RECOMMENDED.
-    #[yaserde(rename = "qualifier", prefix = "gx")]
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub qualifiers: Vec<Qualifier>,
+
+    /// Data that isn't part of the GEDCOM X model.
+    #[serde(flatten)]
+    pub extensions: Extensions,
+}
+
+impl_xml! {
+    SourceReference: "SourceReference" in gx ["" = crate::GEDCOMX_NAMESPACE] {
+        attributes {
+            description: "description",
+            description_id: "descriptionId",
+        }
+        elements {
+            attribution: gx "attribution",
+            qualifiers: gx "qualifier",
+        }
+        @extensions
+    }
 }
 
 impl SourceReference {
@@ -62,6 +70,7 @@ impl SourceReference {
             description_id,
             attribution,
             qualifiers,
+            extensions: Extensions::default(),
         }
     }
 
@@ -251,7 +260,7 @@ mod test {
       </SourceReference>
       "#;
 
-        let source_reference: SourceReference = yaserde::de::from_str(xml).unwrap();
+        let source_reference: SourceReference = crate::xmlcodec::from_str(xml).unwrap();
 
         assert_eq!(source_reference, data.source_reference);
     }
@@ -286,11 +295,11 @@ mod test {
     fn xml_serialize() {
         let data = TestData::new();
 
-        let config = yaserde::ser::Config {
+        let config = crate::xmlcodec::Config {
             write_document_declaration: false,
-            ..yaserde::ser::Config::default()
+            ..crate::xmlcodec::Config::default()
         };
-        let xml = yaserde::ser::to_string_with_config(&data.source_reference, &config).unwrap();
+        let xml = crate::xmlcodec::to_string(&data.source_reference, config).unwrap();
 
         let expected_xml = r#"<SourceReference xmlns="http://gedcomx.org/v1/" description="SD-1" descriptionId="Description id of the target source"><attribution><contributor resource="A-1" /><modified>2014-03-07T07:00:00Z</modified></attribution><qualifier name="http://gedcomx.org/RectangleRegion">rectangle region value</qualifier></SourceReference>"#;
 
@@ -314,8 +323,8 @@ mod test {
 
     #[quickcheck_macros::quickcheck]
     fn roundtrip_xml(input: SourceReference) -> bool {
-        let xml = yaserde::ser::to_string(&input).unwrap();
-        let from_xml: SourceReference = yaserde::de::from_str(&xml).unwrap();
+        let xml = crate::xmlcodec::to_string(&input, crate::xmlcodec::Config::default()).unwrap();
+        let from_xml: SourceReference = crate::xmlcodec::from_str(&xml).unwrap();
         input == from_xml
     }
 }
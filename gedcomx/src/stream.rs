@@ -0,0 +1,689 @@
+use std::{
+    fmt,
+    io::{self, Read, Write},
+};
+
+use serde::{
+    Deserializer,
+    de::{self, DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    ser,
+};
+use serde_json::Value;
+use serde_path_to_error::Segment;
+
+use crate::{
+    Agent, DeserializeError, Document, Entity, EntityKind, Event, GEDCOMX_NAMESPACE, GedcomxError,
+    Group, Id, Person, PlaceDescription, Relationship, Result, SourceDescription,
+    tracking::{self, json_error, json_position_in_line},
+    xmlcodec::{Content, FromXml, XmlReader},
+};
+
+/// An owned top level entity of a [`Gedcomx`](crate::Gedcomx) document, as
+/// read one at a time from a stream.
+#[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::large_enum_variant)]
+#[non_exhaustive]
+pub enum OwnedEntity {
+    Person(Person),
+    Relationship(Relationship),
+    SourceDescription(SourceDescription),
+    Agent(Agent),
+    Event(Event),
+    Document(Document),
+    PlaceDescription(PlaceDescription),
+    Group(Group),
+}
+
+impl OwnedEntity {
+    pub const fn kind(&self) -> EntityKind {
+        self.as_entity().kind()
+    }
+
+    /// The local identifier of the entity, if it has one.
+    pub const fn id(&self) -> Option<&Id> {
+        self.as_entity().id()
+    }
+
+    /// Borrow the entity.
+    pub const fn as_entity(&self) -> Entity<'_> {
+        match self {
+            Self::Person(e) => Entity::Person(e),
+            Self::Relationship(e) => Entity::Relationship(e),
+            Self::SourceDescription(e) => Entity::SourceDescription(e),
+            Self::Agent(e) => Entity::Agent(e),
+            Self::Event(e) => Entity::Event(e),
+            Self::Document(e) => Entity::Document(e),
+            Self::PlaceDescription(e) => Entity::PlaceDescription(e),
+            Self::Group(e) => Entity::Group(e),
+        }
+    }
+}
+
+/// Reads the top level entities of a GEDCOM X XML document one at a time, so
+/// that only one entity is in memory at once.
+///
+/// Everything else in the document, such as its `attribution` and
+/// `collection`s, is skipped.
+///
+/// # Examples
+///
+/// ```
+/// use gedcomx::{EntityKind, XmlEntityReader};
+///
+/// let file = std::fs::File::open("../data/birth.xml").unwrap();
+/// let mut persons = 0;
+/// for entity in XmlEntityReader::new(std::io::BufReader::new(file)).unwrap() {
+///     if entity.unwrap().kind() == EntityKind::Person {
+///         persons += 1;
+///     }
+/// }
+/// assert_eq!(persons, 4);
+/// ```
+pub struct XmlEntityReader<R: Read> {
+    reader: XmlReader<R>,
+    done: bool,
+}
+
+impl<R: Read> XmlEntityReader<R> {
+    /// Start reading the document from `reader`, reading up to the start of
+    /// its root `gedcomx` element.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if the document isn't XML or
+    /// its root element isn't `gedcomx`, and `GedcomxError::IoError` if
+    /// reading `reader` fails.
+    pub fn new(reader: R) -> Result<Self> {
+        let mut reader = XmlReader::new(reader);
+        let root = reader.root().map_err(|e| reader.fail(e))?;
+        if !root.name.local_name.eq_ignore_ascii_case("gedcomx") {
+            let message = format!("expected a gedcomx element, found {}", root.name.local_name);
+            return Err(reader.error(message).into());
+        }
+
+        Ok(Self {
+            reader,
+            done: false,
+        })
+    }
+
+    fn next_entity(&mut self) -> std::result::Result<Option<OwnedEntity>, DeserializeError> {
+        let reader = &mut self.reader;
+        loop {
+            let start = match reader.next_content()? {
+                Content::Start(start) => start,
+                Content::Text(_) => continue,
+                Content::End => return Ok(None),
+            };
+
+            let in_gedcomx_namespace = start
+                .name
+                .namespace
+                .as_deref()
+                .is_none_or(|ns| ns.is_empty() || ns == GEDCOMX_NAMESPACE);
+            let name = in_gedcomx_namespace.then(|| start.name.local_name.clone());
+            let kind = match name.as_deref() {
+                Some("person") => EntityKind::Person,
+                Some("relationship") => EntityKind::Relationship,
+                Some("sourceDescription") => EntityKind::SourceDescription,
+                Some("agent") => EntityKind::Agent,
+                Some("event") => EntityKind::Event,
+                Some("document") => EntityKind::Document,
+                Some("place") => EntityKind::PlaceDescription,
+                Some("group") => EntityKind::Group,
+                _ => {
+                    reader.skip()?;
+                    continue;
+                }
+            };
+
+            // An entity is read as an element of its list in the document.
+            reader.read_as_field(json_key(kind), true);
+            let entity = match kind {
+                EntityKind::Person => OwnedEntity::Person(FromXml::read(reader, start)?),
+                EntityKind::Relationship => {
+                    OwnedEntity::Relationship(FromXml::read(reader, start)?)
+                }
+                EntityKind::SourceDescription => {
+                    OwnedEntity::SourceDescription(FromXml::read(reader, start)?)
+                }
+                EntityKind::Agent => OwnedEntity::Agent(FromXml::read(reader, start)?),
+                EntityKind::Event => OwnedEntity::Event(FromXml::read(reader, start)?),
+                EntityKind::Document => OwnedEntity::Document(FromXml::read(reader, start)?),
+                EntityKind::PlaceDescription => {
+                    OwnedEntity::PlaceDescription(FromXml::read(reader, start)?)
+                }
+                EntityKind::Group => OwnedEntity::Group(FromXml::read(reader, start)?),
+            };
+            return Ok(Some(entity));
+        }
+    }
+}
+
+impl<R: Read> Iterator for XmlEntityReader<R> {
+    type Item = Result<OwnedEntity>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let next = self.next_entity().map_err(|e| self.reader.fail(e));
+        if !matches!(next, Ok(Some(_))) {
+            self.done = true;
+        }
+        next.transpose()
+    }
+}
+
+/// Reads the top level entities of a GEDCOM X JSON document one at a time, so
+/// that only one entity is in memory at once.
+///
+/// Each element of the `persons`, `relationships`, `sourceDescriptions`,
+/// `agents`, `events`, `documents`, `places` and `groups` arrays is passed to
+/// a function as soon as it's parsed. Everything else in the document is
+/// skipped.
+///
+/// # Examples
+///
+/// ```
+/// use gedcomx::{EntityKind, JsonEntityReader};
+///
+/// let file = std::fs::File::open("../data/birth.json").unwrap();
+/// let mut persons = 0;
+/// JsonEntityReader::new(std::io::BufReader::new(file))
+///     .visit(|entity| {
+///         if entity.kind() == EntityKind::Person {
+///             persons += 1;
+///         }
+///         Ok(())
+///     })
+///     .unwrap();
+/// assert_eq!(persons, 4);
+/// ```
+pub struct JsonEntityReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> JsonEntityReader<R> {
+    pub const fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Read the document, calling `visitor` with each top level entity in
+    /// the order they appear. Reading stops at the first error `visitor`
+    /// returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::DeserializeError` if the document isn't a JSON
+    /// object or an entity can't be deserialized, `GedcomxError::IoError` if
+    /// reading fails, or the error returned by `visitor`.
+    pub fn visit<F: FnMut(OwnedEntity) -> Result<()>>(self, mut visitor: F) -> Result<()> {
+        let mut input = JsonInput {
+            reader: self.reader,
+            offset: 0,
+            lines: [(0, 0), (1, 0)],
+        };
+        let mut deserializer = serde_json::Deserializer::from_reader(&mut input);
+        let mut track = serde_path_to_error::Track::new();
+
+        let mut error = None;
+        let read = DocumentSeed {
+            visitor: &mut visitor,
+            error: &mut error,
+        }
+        .deserialize(serde_path_to_error::Deserializer::new(
+            &mut deserializer,
+            &mut track,
+        ))
+        .and_then(|()| deserializer.end());
+        let position =
+            |e: &serde_json::Error| json_position_in_line(e, input.line_start(e.line() as u64));
+
+        match (read, error) {
+            // serde_json knows where the entity that couldn't be
+            // deserialized ended.
+            (Err(e), Some(GedcomxError::DeserializeError(error))) => Err(DeserializeError {
+                position: position(&e),
+                ..*error
+            }
+            .into()),
+            (_, Some(e)) => Err(e),
+            (Err(e), None) if e.is_io() => Err(GedcomxError::IoError(e.into())),
+            (Err(e), None) => {
+                let path: Vec<_> = track.path().iter().cloned().collect();
+                Err(json_error(&e, &path, None, position(&e)).into())
+            }
+            (Ok(()), None) => Ok(()),
+        }
+    }
+}
+
+// The input of a JSON document, which keeps where the last two lines read
+// start, to find the offset of where serde_json stopped, which can be a byte
+// before the last one read.
+struct JsonInput<R> {
+    reader: R,
+    offset: u64,
+    lines: [(u64, u64); 2],
+}
+
+impl<R> JsonInput<R> {
+    fn line_start(&self, line: u64) -> u64 {
+        self.lines
+            .iter()
+            .find(|(number, _)| *number == line)
+            .map_or(self.offset, |(_, start)| *start)
+    }
+}
+
+impl<R: Read> Read for JsonInput<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        for &b in &buf[..n] {
+            self.offset += 1;
+            if b == b'\n' {
+                self.lines = [self.lines[1], (self.lines[1].0 + 1, self.offset)];
+            }
+        }
+        Ok(n)
+    }
+}
+
+// Deserializes the top level object of a document, passing its entities to
+// `visitor`. An error returned by `visitor`, or deserializing an entity, is
+// stored in `error`.
+struct DocumentSeed<'a, F> {
+    visitor: &'a mut F,
+    error: &'a mut Option<GedcomxError>,
+}
+
+impl<'de, F: FnMut(OwnedEntity) -> Result<()>> DeserializeSeed<'de> for DocumentSeed<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F: FnMut(OwnedEntity) -> Result<()>> Visitor<'de> for DocumentSeed<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a GEDCOM X document")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            let (v, e) = (&mut *self.visitor, &mut *self.error);
+            let k = key.as_str();
+            match k {
+                "persons" => map.next_value_seed(ArraySeed::new(v, e, k, OwnedEntity::Person)),
+                "relationships" => {
+                    map.next_value_seed(ArraySeed::new(v, e, k, OwnedEntity::Relationship))
+                }
+                "sourceDescriptions" => {
+                    map.next_value_seed(ArraySeed::new(v, e, k, OwnedEntity::SourceDescription))
+                }
+                "agents" => map.next_value_seed(ArraySeed::new(v, e, k, OwnedEntity::Agent)),
+                "events" => map.next_value_seed(ArraySeed::new(v, e, k, OwnedEntity::Event)),
+                "documents" => map.next_value_seed(ArraySeed::new(v, e, k, OwnedEntity::Document)),
+                "places" => {
+                    map.next_value_seed(ArraySeed::new(v, e, k, OwnedEntity::PlaceDescription))
+                }
+                "groups" => map.next_value_seed(ArraySeed::new(v, e, k, OwnedEntity::Group)),
+                _ => map.next_value::<IgnoredAny>().map(drop),
+            }?;
+        }
+        Ok(())
+    }
+}
+
+// Deserializes the array `key` of entities of type `T`, passing each to
+// `visitor` as an `OwnedEntity` made by `wrap`.
+struct ArraySeed<'a, F, T> {
+    visitor: &'a mut F,
+    error: &'a mut Option<GedcomxError>,
+    key: &'a str,
+    wrap: fn(T) -> OwnedEntity,
+}
+
+impl<'a, F, T> ArraySeed<'a, F, T> {
+    const fn new(
+        visitor: &'a mut F,
+        error: &'a mut Option<GedcomxError>,
+        key: &'a str,
+        wrap: fn(T) -> OwnedEntity,
+    ) -> Self {
+        Self {
+            visitor,
+            error,
+            key,
+            wrap,
+        }
+    }
+}
+
+impl<'de, F, T> DeserializeSeed<'de> for ArraySeed<'_, F, T>
+where
+    F: FnMut(OwnedEntity) -> Result<()>,
+    T: DeserializeOwned,
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, F, T> Visitor<'de> for ArraySeed<'_, F, T>
+where
+    F: FnMut(OwnedEntity) -> Result<()>,
+    T: DeserializeOwned,
+{
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an array of entities")
+    }
+
+    // Each entity is read before it's deserialized, so that the value of an
+    // error can be found in it.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<(), A::Error> {
+        let mut index = 0;
+        while let Some(value) = seq.next_element::<Value>()? {
+            let result = serde_path_to_error::deserialize(&value).map_err(|e| {
+                let mut path = vec![
+                    Segment::Map {
+                        key: self.key.to_string(),
+                    },
+                    Segment::Seq { index },
+                ];
+                path.extend(e.path().iter().cloned());
+                let value = tracking::value_at(&value, &path[2..]);
+                json_error(e.inner(), &path, value, None).into()
+            });
+            if let Err(e) = result.and_then(|entity| (self.visitor)((self.wrap)(entity))) {
+                *self.error = Some(e);
+                return Err(de::Error::custom("stopped by visitor"));
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a GEDCOM X JSON document one top level entity at a time, so that
+/// only one entity is in memory at once.
+///
+/// Entities of the same kind must be written one after another, since each
+/// kind is written as a single array. The document has no properties other
+/// than its entities.
+///
+/// # Examples
+///
+/// ```
+/// use gedcomx::{Gedcomx, JsonEntityReader, JsonEntityWriter};
+///
+/// let file = std::fs::File::open("../data/birth.json").unwrap();
+/// let mut writer = JsonEntityWriter::new(vec![]).unwrap();
+/// JsonEntityReader::new(std::io::BufReader::new(file))
+///     .visit(|entity| writer.write(entity.as_entity()))
+///     .unwrap();
+/// let json = writer.finish().unwrap();
+///
+/// let gx = Gedcomx::from_json_reader(json.as_slice()).unwrap();
+/// assert_eq!(gx.persons.len(), 4);
+/// ```
+pub struct JsonEntityWriter<W: Write> {
+    writer: W,
+    written: Vec<EntityKind>,
+}
+
+impl<W: Write> JsonEntityWriter<W> {
+    /// Start writing a document to `writer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if writing fails.
+    pub fn new(mut writer: W) -> Result<Self> {
+        writer.write_all(b"{").map_err(serde_json::Error::io)?;
+        Ok(Self {
+            writer,
+            written: vec![],
+        })
+    }
+
+    /// Write the next entity of the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if writing or serialization fails,
+    /// or if an entity of the same kind was written before one of another
+    /// kind.
+    pub fn write(&mut self, entity: Entity<'_>) -> Result<()> {
+        let kind = entity.kind();
+        let separator: &[u8] = match self.written.last().copied() {
+            Some(last) if last == kind => b",",
+            last => {
+                if self.written.contains(&kind) {
+                    return Err(GedcomxError::JSONError(ser::Error::custom(format!(
+                        "{kind} written after entities of another kind"
+                    ))));
+                }
+                self.written.push(kind);
+                let close = if last.is_some() { "]," } else { "" };
+                self.writer
+                    .write_all(format!("{close}\"{}\":[", json_key(kind)).as_bytes())
+                    .map_err(serde_json::Error::io)?;
+                b""
+            }
+        };
+        self.writer
+            .write_all(separator)
+            .map_err(serde_json::Error::io)?;
+
+        match entity {
+            Entity::Person(e) => serde_json::to_writer(&mut self.writer, e),
+            Entity::Relationship(e) => serde_json::to_writer(&mut self.writer, e),
+            Entity::SourceDescription(e) => serde_json::to_writer(&mut self.writer, e),
+            Entity::Agent(e) => serde_json::to_writer(&mut self.writer, e),
+            Entity::Event(e) => serde_json::to_writer(&mut self.writer, e),
+            Entity::Document(e) => serde_json::to_writer(&mut self.writer, e),
+            Entity::PlaceDescription(e) => serde_json::to_writer(&mut self.writer, e),
+            Entity::Group(e) => serde_json::to_writer(&mut self.writer, e),
+        }
+        .map_err(GedcomxError::JSONError)
+    }
+
+    /// Finish the document, returning the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns `GedcomxError::JSONError` if writing fails.
+    pub fn finish(mut self) -> Result<W> {
+        let end: &[u8] = if self.written.is_empty() { b"}" } else { b"]}" };
+        self.writer
+            .write_all(end)
+            .and_then(|()| self.writer.flush())
+            .map_err(serde_json::Error::io)?;
+        Ok(self.writer)
+    }
+}
+
+// The property of a JSON document holding the entities of a kind.
+const fn json_key(kind: EntityKind) -> &'static str {
+    match kind {
+        EntityKind::Person => "persons",
+        EntityKind::Relationship => "relationships",
+        EntityKind::SourceDescription => "sourceDescriptions",
+        EntityKind::Agent => "agents",
+        EntityKind::Event => "events",
+        EntityKind::Document => "documents",
+        EntityKind::PlaceDescription => "places",
+        EntityKind::Group => "groups",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{Gedcomx, ReferenceIndex};
+
+    fn entities(gx: &Gedcomx) -> Vec<OwnedEntity> {
+        ReferenceIndex::new(gx)
+            .entities()
+            .map(|e| match e {
+                Entity::Person(e) => OwnedEntity::Person(e.clone()),
+                Entity::Relationship(e) => OwnedEntity::Relationship(e.clone()),
+                Entity::SourceDescription(e) => OwnedEntity::SourceDescription(e.clone()),
+                Entity::Agent(e) => OwnedEntity::Agent(e.clone()),
+                Entity::Event(e) => OwnedEntity::Event(e.clone()),
+                Entity::Document(e) => OwnedEntity::Document(e.clone()),
+                Entity::PlaceDescription(e) => OwnedEntity::PlaceDescription(e.clone()),
+                Entity::Group(e) => OwnedEntity::Group(e.clone()),
+            })
+            .collect()
+    }
+
+    fn sorted(mut entities: Vec<OwnedEntity>) -> Vec<OwnedEntity> {
+        entities.sort_by_key(|e| e.kind() as u8);
+        entities
+    }
+
+    #[test]
+    fn reads_the_entities_of_documents() {
+        for name in ["birth", "census", "marriage", "military", "names1"] {
+            let xml = std::fs::read_to_string(format!("../data/{name}.xml")).unwrap();
+            let gx = Gedcomx::from_xml_str(&xml).unwrap();
+
+            let streamed: Vec<_> = XmlEntityReader::new(xml.as_bytes())
+                .unwrap()
+                .collect::<Result<_>>()
+                .unwrap();
+            assert_eq!(sorted(streamed), entities(&gx), "{name}");
+        }
+    }
+
+    #[test]
+    fn skips_everything_else() {
+        let xml = r#"<gedcomx xmlns="http://gedcomx.org/v1/" xmlns:x="http://example.org/">
+            <attribution><changeMessage>hi</changeMessage></attribution>
+            <x:person><x:name>Not a GEDCOM X person</x:name></x:person>
+            <person id="P-1"/>
+            <collection id="C-1"/>
+            <person id="P-2"/>
+        </gedcomx>"#;
+        let ids: Vec<_> = XmlEntityReader::new(xml.as_bytes())
+            .unwrap()
+            .map(|e| e.unwrap().id().unwrap().to_string())
+            .collect();
+        assert_eq!(ids, ["P-1", "P-2"]);
+    }
+
+    #[test]
+    fn errors() {
+        assert!(XmlEntityReader::new(&b"<person/>"[..]).is_err());
+        assert!(XmlEntityReader::new(&b""[..]).is_err());
+
+        let mut reader = XmlEntityReader::new(
+            r#"<gedcomx xmlns="http://gedcomx.org/v1/"><person id="P-1"/><person"#.as_bytes(),
+        )
+        .unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+
+        let mut reader =
+            XmlEntityReader::new(&b"<gedcomx><person/><person><gender/></person></gedcomx>"[..])
+                .unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        match reader.next().unwrap() {
+            Err(GedcomxError::DeserializeError(e)) => assert_eq!(e.path, "persons[1].gender"),
+            _ => panic!("the gender has no type"),
+        }
+    }
+
+    fn read_json(json: &[u8]) -> Result<Vec<OwnedEntity>> {
+        let mut entities = vec![];
+        JsonEntityReader::new(json).visit(|e| {
+            entities.push(e);
+            Ok(())
+        })?;
+        Ok(entities)
+    }
+
+    #[test]
+    fn json_reads_the_entities_of_documents() {
+        for name in ["birth", "census", "marriage", "military", "names1", "spec"] {
+            let json = std::fs::read(format!("../data/{name}.json")).unwrap();
+            let gx = Gedcomx::from_json_reader(json.as_slice()).unwrap();
+            assert_eq!(sorted(read_json(&json).unwrap()), entities(&gx), "{name}");
+        }
+    }
+
+    #[test]
+    fn json_reader_errors() {
+        match read_json(b"{\"persons\": [{},\n  {\"id\": 1}]}") {
+            Err(GedcomxError::DeserializeError(e)) => {
+                assert_eq!(e.path, "persons[1].id");
+                assert_eq!(e.value.as_deref(), Some("1"));
+                let position = e.position.unwrap();
+                assert_eq!((position.line, position.column), (2, 12));
+                assert_eq!(position.offset, 28);
+            }
+            _ => panic!("the id isn't a string"),
+        }
+        assert!(read_json(b"[]").is_err());
+        assert!(read_json(br#"{"persons": []} {}"#).is_err());
+
+        let mut visited = 0;
+        let result = JsonEntityReader::new(&br#"{"persons": [{"id": "P-1"}, {"id": "P-2"}]}"#[..])
+            .visit(|_| {
+                visited += 1;
+                Err(GedcomxError::NoId("stop".to_string()))
+            });
+        assert!(matches!(result, Err(GedcomxError::NoId(_))));
+        assert_eq!(visited, 1);
+    }
+
+    #[test]
+    fn json_writes_documents() {
+        let json = std::fs::read("../data/spec.json").unwrap();
+        let gx = Gedcomx::from_json_reader(json.as_slice()).unwrap();
+
+        let mut writer = JsonEntityWriter::new(vec![]).unwrap();
+        for entity in ReferenceIndex::new(&gx).entities() {
+            writer.write(entity).unwrap();
+        }
+        let written = writer.finish().unwrap();
+        let written = Gedcomx::from_json_reader(written.as_slice()).unwrap();
+        assert_eq!(entities(&written), entities(&gx));
+
+        let empty = JsonEntityWriter::new(vec![]).unwrap().finish().unwrap();
+        assert_eq!(empty, b"{}");
+    }
+
+    #[test]
+    fn json_writer_rejects_interleaved_kinds() {
+        let person = Person::default();
+        let agent = Agent::default();
+        let mut writer = JsonEntityWriter::new(vec![]).unwrap();
+        writer.write(Entity::Person(&person)).unwrap();
+        writer.write(Entity::Person(&person)).unwrap();
+        writer.write(Entity::Agent(&agent)).unwrap();
+        assert!(writer.write(Entity::Person(&person)).is_err());
+
+        let written = writer.finish().unwrap();
+        assert_eq!(written, br#"{"persons":[{},{}],"agents":[{}]}"#);
+    }
+}
@@ -0,0 +1,373 @@
+use std::{io::Read, ops::Range};
+
+use serde::de::DeserializeOwned;
+use serde_json::{Value, error::Category};
+use serde_path_to_error::Segment;
+
+use crate::{DeserializeError, Position, Result};
+
+// The longest value kept for an error, in characters.
+const MAX_VALUE_LEN: usize = 100;
+
+// JSON errors are given the path serde_path_to_error tracked to them and the
+// position serde_json stopped at. Objects with flattened fields are read in
+// full before their fields are deserialized, so for an error in one that's
+// the end of the object rather than the value. When the document is in
+// memory, the value is found by its path instead, and an error in the data
+// is given the position the value starts at.
+
+/// `value` as it's kept for an error, shortened if it's long.
+pub fn value_string(value: &str) -> String {
+    match value.char_indices().nth(MAX_VALUE_LEN) {
+        Some((end, _)) => format!("{}…", &value[..end]),
+        None => value.to_string(),
+    }
+}
+
+/// The member or element of `value` that `segment` leads to.
+pub fn json_child<'a>(value: &'a Value, segment: &Segment) -> Option<&'a Value> {
+    match (value, segment) {
+        (Value::Array(elements), Segment::Seq { index }) => elements.get(*index),
+        (Value::Object(members), Segment::Map { key } | Segment::Enum { variant: key }) => {
+            members.get(key)
+        }
+        _ => None,
+    }
+}
+
+/// The value at `path` in `document` as it's kept for an error, unless it's
+/// an object or array. Strings are kept without their quotes.
+pub fn value_at(document: &Value, path: &[Segment]) -> Option<String> {
+    match path
+        .iter()
+        .try_fold(document, |value, segment| json_child(value, segment))?
+    {
+        Value::String(s) => Some(value_string(s)),
+        Value::Object(_) | Value::Array(_) => None,
+        value => Some(value_string(&value.to_string())),
+    }
+}
+
+/// Deserialize JSON from `reader`, with the position, path and value of any
+/// error.
+pub fn from_json_reader<T: DeserializeOwned, R: Read>(mut reader: R) -> Result<T> {
+    // Reading the whole document first lets the value of an error be found
+    // in it by path.
+    let mut json = vec![];
+    reader.read_to_end(&mut json)?;
+    from_json_slice(&json)
+}
+
+/// Deserialize JSON from `json`, with the position, path and value of any
+/// error.
+pub fn from_json_slice<T: DeserializeOwned>(json: &[u8]) -> Result<T> {
+    let mut deserializer = serde_json::Deserializer::from_slice(json);
+    let value = serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+        let path: Vec<Segment> = e.path().iter().cloned().collect();
+        let found = find(json, &path);
+        let value = found.clone().and_then(|range| value_text(&json[range]));
+        let position = match found {
+            Some(range) if e.inner().classify() == Category::Data => {
+                Some(position_at(json, range.start))
+            }
+            _ => json_position(e.inner(), json),
+        };
+        json_error(e.inner(), &path, value, position)
+    })?;
+    deserializer
+        .end()
+        .map_err(|e| json_error(&e, &[], None, json_position(&e, json)))?;
+    Ok(value)
+}
+
+/// Deserialize JSON from `value`, the member named `name` of an object.
+#[cfg(feature = "familysearch")]
+pub fn from_json_value<T: DeserializeOwned>(value: &Value, name: &str) -> Result<T> {
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        let path: Vec<Segment> = e.path().iter().cloned().collect();
+        let mut error = json_error(e.inner(), &path, value_at(value, &path), None);
+        error.path = if error.path.is_empty() || error.path.starts_with('[') {
+            format!("{name}{}", error.path)
+        } else {
+            format!("{name}.{}", error.path)
+        };
+        error.into()
+    })
+}
+
+/// An error deserializing JSON, about `value` at `path`.
+pub fn json_error(
+    e: &serde_json::Error,
+    path: &[Segment],
+    value: Option<String>,
+    position: Option<Position>,
+) -> DeserializeError {
+    DeserializeError {
+        message: json_message(e),
+        position,
+        path: path_string(path),
+        value,
+    }
+}
+
+/// Writes `path` the way `serde_path_to_error` does.
+pub fn path_string(path: &[Segment]) -> String {
+    let mut string = String::new();
+    for segment in path {
+        if !string.is_empty() && !matches!(segment, Segment::Seq { .. }) {
+            string.push('.');
+        }
+        string.push_str(&segment.to_string());
+    }
+    string
+}
+
+/// The position of the byte at `offset` in `json`.
+fn position_at(json: &[u8], offset: usize) -> Position {
+    let line_start = json[..offset]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |newline| newline + 1);
+    Position {
+        line: json[..offset].split(|&b| b == b'\n').count() as u64,
+        column: (offset - line_start) as u64 + 1,
+        offset: offset as u64,
+    }
+}
+
+/// The bytes of the value at `path` in `json`, found without deserializing
+/// the rest of the document. `None` if there isn't one, or if the document
+/// isn't valid JSON up to it.
+fn find(json: &[u8], path: &[Segment]) -> Option<Range<usize>> {
+    let mut scanner = Scanner { json, at: 0 };
+    for segment in path {
+        match segment {
+            Segment::Seq { index } => {
+                scanner.expect(b'[')?;
+                for _ in 0..*index {
+                    scanner.skip_value()?;
+                    scanner.expect(b',')?;
+                }
+                scanner.skip_whitespace();
+                if scanner.peek()? == b']' {
+                    return None;
+                }
+            }
+            Segment::Map { key } | Segment::Enum { variant: key } => {
+                scanner.expect(b'{')?;
+                loop {
+                    let name = scanner.string()?;
+                    scanner.expect(b':')?;
+                    if name == *key {
+                        break;
+                    }
+                    scanner.skip_value()?;
+                    scanner.expect(b',')?;
+                }
+            }
+            Segment::Unknown => return None,
+        }
+    }
+    scanner.skip_whitespace();
+    let start = scanner.at;
+    scanner.skip_value()?;
+    Some(start..scanner.at)
+}
+
+/// The JSON value in `bytes` as it's kept for an error, unless it's an
+/// object or array. Strings are kept without their quotes.
+fn value_text(bytes: &[u8]) -> Option<String> {
+    match bytes.first()? {
+        b'{' | b'[' => None,
+        b'"' => serde_json::from_slice::<String>(bytes)
+            .ok()
+            .map(|s| value_string(&s)),
+        _ => std::str::from_utf8(bytes).ok().map(value_string),
+    }
+}
+
+// Just enough of a JSON reader to step over values to the one at a path.
+struct Scanner<'a> {
+    json: &'a [u8],
+    at: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.json.get(self.at).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.at += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.skip_whitespace();
+        (self.peek()? == byte).then(|| self.at += 1)
+    }
+
+    // Reads a string, unescaping it only if it needs to be.
+    fn string(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let start = self.at;
+        self.skip_string()?;
+        let bytes = &self.json[start..self.at];
+        if bytes.contains(&b'\\') {
+            serde_json::from_slice(bytes).ok()
+        } else {
+            std::str::from_utf8(&bytes[1..bytes.len() - 1])
+                .ok()
+                .map(str::to_string)
+        }
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        if self.peek()? != b'"' {
+            return None;
+        }
+        self.at += 1;
+        loop {
+            match self.peek()? {
+                b'"' => break,
+                b'\\' => self.at += 2,
+                _ => self.at += 1,
+            }
+        }
+        self.at += 1;
+        Some(())
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        self.skip_whitespace();
+        match self.peek()? {
+            b'"' => self.skip_string(),
+            b'{' | b'[' => {
+                let mut depth = 0_usize;
+                loop {
+                    match self.peek()? {
+                        b'"' => {
+                            self.skip_string()?;
+                            continue;
+                        }
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => depth -= 1,
+                        _ => {}
+                    }
+                    self.at += 1;
+                    if depth == 0 {
+                        return Some(());
+                    }
+                }
+            }
+            // A number or literal runs up to whatever follows it.
+            _ => {
+                let start = self.at;
+                while !matches!(
+                    self.peek(),
+                    None | Some(b',' | b'}' | b']' | b' ' | b'\t' | b'\n' | b'\r')
+                ) {
+                    self.at += 1;
+                }
+                (self.at > start).then_some(())
+            }
+        }
+    }
+}
+
+/// Where `serde_json` stopped reading `json`.
+pub fn json_position(e: &serde_json::Error, json: &[u8]) -> Option<Position> {
+    let line_start: usize = json
+        .split_inclusive(|&b| b == b'\n')
+        .take(e.line().saturating_sub(1))
+        .map(<[u8]>::len)
+        .sum();
+    json_position_in_line(e, line_start as u64)
+}
+
+/// Where `serde_json` stopped, on a line that starts at `line_start`, unless
+/// it wasn't reading a document, such as when deserializing from a `Value`.
+pub fn json_position_in_line(e: &serde_json::Error, line_start: u64) -> Option<Position> {
+    // serde_json gives the column of the last byte it read.
+    let column = e.column().max(1) as u64;
+    (e.line() > 0).then(|| Position {
+        line: e.line() as u64,
+        column,
+        offset: line_start + column - 1,
+    })
+}
+
+// The message of `e`, without the position serde_json adds to it.
+fn json_message(e: &serde_json::Error) -> String {
+    let mut message = e.to_string();
+    let suffix = format!(" at line {} column {}", e.line(), e.column());
+    if message.ends_with(&suffix) {
+        message.truncate(message.len() - suffix.len());
+    }
+    message
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    fn map(key: &str) -> Segment {
+        Segment::Map {
+            key: key.to_string(),
+        }
+    }
+
+    #[test]
+    fn values() {
+        let document = json!({"a": [1, {"b": "c", "d": [true, null]}]});
+        let seq = |index| Segment::Seq { index };
+        assert_eq!(value_at(&document, &[]), None);
+        assert_eq!(value_at(&document, &[map("a"), seq(0)]), Some("1".into()));
+        assert_eq!(
+            value_at(&document, &[map("a"), seq(1), map("b")]),
+            Some("c".into())
+        );
+        assert_eq!(
+            value_at(&document, &[map("a"), seq(1), map("d"), seq(1)]),
+            Some("null".into())
+        );
+        assert_eq!(value_at(&document, &[map("a"), seq(2)]), None);
+        assert_eq!(value_at(&document, &[map("e")]), None);
+    }
+
+    #[test]
+    fn values_found_in_documents() {
+        let json = br#"{"x\"": {"a": 1}, "a" : [ 1.5e3 , {"b": "c\"d", "e": [[]]}, true ] }"#;
+        let seq = |index| Segment::Seq { index };
+        let found = |path: &[Segment]| find(json, path).map(|range| &json[range]);
+        assert_eq!(found(&[]), Some(&json[..]));
+        assert_eq!(found(&[map("a"), seq(0)]), Some(&b"1.5e3"[..]));
+        assert_eq!(
+            found(&[map("a"), seq(1), map("b")]),
+            Some(&br#""c\"d""#[..])
+        );
+        assert_eq!(found(&[map("a"), seq(1), map("e")]), Some(&b"[[]]"[..]));
+        assert_eq!(found(&[map("a"), seq(2)]), Some(&b"true"[..]));
+        assert_eq!(found(&[map("a"), seq(3)]), None);
+        assert_eq!(found(&[map("x\"")]), Some(&br#"{"a": 1}"#[..]));
+        assert_eq!(found(&[map("b")]), None);
+
+        assert_eq!(value_text(br#""c\"d""#), Some("c\"d".into()));
+        assert_eq!(value_text(b"1.5e3"), Some("1.5e3".into()));
+        assert_eq!(value_text(b"[[]]"), None);
+    }
+
+    #[test]
+    fn long_values_are_shortened() {
+        let value = "é".repeat(MAX_VALUE_LEN + 1);
+        assert_eq!(
+            value_string(&value),
+            format!("{}…", "é".repeat(MAX_VALUE_LEN))
+        );
+        assert_eq!(value_string("short"), "short");
+    }
+}
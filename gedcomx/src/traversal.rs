@@ -0,0 +1,417 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{Gedcomx, Person, ReferenceIndex, Relationship, RelationshipType};
+
+/// A person reached by walking the relationships of a document.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[non_exhaustive]
+pub struct Related<'a> {
+    /// The person that was reached.
+    pub person: &'a Person,
+
+    /// The relationship that was followed to reach `person`. Facts on this
+    /// relationship (such as
+    /// [`AdoptiveParent`](crate::FactType::AdoptiveParent) or
+    /// [`StepParent`](crate::FactType::StepParent)) qualify the link.
+    pub relationship: &'a Relationship,
+
+    /// How many relationships were followed to reach `person`. Parents,
+    /// children and spouses are 1, grandparents and grandchildren are 2, and
+    /// so on.
+    pub generation: usize,
+}
+
+/// The family structure of a [`Gedcomx`](crate::Gedcomx) document, derived
+/// from its [`ParentChild`](crate::RelationshipType::ParentChild) and
+/// [`Couple`](crate::RelationshipType::Couple) relationships.
+///
+/// Only relationships between persons in the document (referenced with a
+/// fragment URI such as `#P-1`) are followed. A person can have any number of
+/// parents, so multiple parent sets (biological, adoptive, etc.) are all
+/// visited. Cycles in the data are detected, so each person is yielded at most
+/// once by [`ancestors`](Self::ancestors) and
+/// [`descendants`](Self::descendants).
+///
+/// # Examples
+///
+/// ```
+/// use gedcomx::{FamilyGraph, Gedcomx, Person, Relationship, RelationshipType};
+///
+/// let grandparent = Person::builder().id("P-1").build();
+/// let parent = Person::builder().id("P-2").build();
+/// let child = Person::builder().id("P-3").build();
+/// let gx = Gedcomx::builder()
+///     .relationship(
+///         Relationship::builder(&grandparent, &parent)
+///             .unwrap()
+///             .relationship_type(RelationshipType::ParentChild)
+///             .build(),
+///     )
+///     .relationship(
+///         Relationship::builder(&parent, &child)
+///             .unwrap()
+///             .relationship_type(RelationshipType::ParentChild)
+///             .build(),
+///     )
+///     .persons(vec![grandparent, parent, child])
+///     .build();
+///
+/// let graph = FamilyGraph::new(&gx);
+/// let ancestors: Vec<_> = graph
+///     .ancestors(&gx.persons[2], 2)
+///     .map(|r| (r.person, r.generation))
+///     .collect();
+/// assert_eq!(ancestors, vec![(&gx.persons[1], 1), (&gx.persons[0], 2)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FamilyGraph<'a> {
+    parents: HashMap<String, Vec<(&'a Person, &'a Relationship)>>,
+    children: HashMap<String, Vec<(&'a Person, &'a Relationship)>>,
+    spouses: HashMap<String, Vec<(&'a Person, &'a Relationship)>>,
+}
+
+impl<'a> FamilyGraph<'a> {
+    pub fn new(gx: &'a Gedcomx) -> Self {
+        let index = ReferenceIndex::new(gx);
+        let mut graph = Self {
+            parents: HashMap::new(),
+            children: HashMap::new(),
+            spouses: HashMap::new(),
+        };
+
+        for relationship in &gx.relationships {
+            let person1 = index
+                .resolve(&relationship.person1.resource)
+                .and_then(|e| e.as_person());
+            let person2 = index
+                .resolve(&relationship.person2.resource)
+                .and_then(|e| e.as_person());
+            let (Some(person1), Some(person2)) = (person1, person2) else {
+                continue;
+            };
+            let (Some(id1), Some(id2)) = (key(person1), key(person2)) else {
+                continue;
+            };
+            if id1 == id2 {
+                continue;
+            }
+
+            match relationship.relationship_type {
+                Some(RelationshipType::ParentChild) => {
+                    graph
+                        .parents
+                        .entry(id2)
+                        .or_default()
+                        .push((person1, relationship));
+                    graph
+                        .children
+                        .entry(id1)
+                        .or_default()
+                        .push((person2, relationship));
+                }
+                Some(RelationshipType::Couple) => {
+                    graph
+                        .spouses
+                        .entry(id1)
+                        .or_default()
+                        .push((person2, relationship));
+                    graph
+                        .spouses
+                        .entry(id2)
+                        .or_default()
+                        .push((person1, relationship));
+                }
+                _ => {}
+            }
+        }
+
+        graph
+    }
+
+    /// The parents of `person`, from every parent set.
+    pub fn parents(&self, person: &Person) -> impl Iterator<Item = Related<'a>> + '_ {
+        Self::neighbors(&self.parents, person)
+    }
+
+    /// The children of `person`, with any partner.
+    pub fn children(&self, person: &Person) -> impl Iterator<Item = Related<'a>> + '_ {
+        Self::neighbors(&self.children, person)
+    }
+
+    /// The spouses of `person`.
+    pub fn spouses(&self, person: &Person) -> impl Iterator<Item = Related<'a>> + '_ {
+        Self::neighbors(&self.spouses, person)
+    }
+
+    /// Persons that share at least one parent with `person`, including half
+    /// siblings. Each sibling is yielded once, along with the relationship
+    /// between the sibling and the first shared parent found.
+    pub fn siblings(&self, person: &Person) -> impl Iterator<Item = Related<'a>> + '_ {
+        let mut seen: HashSet<String> = key(person).into_iter().collect();
+
+        self.parents(person)
+            .flat_map(|parent| self.children(parent.person))
+            .filter(move |sibling| key(sibling.person).is_some_and(|k| seen.insert(k)))
+    }
+
+    /// The ancestors of `person`, up to and including `generations`
+    /// generations back, in breadth first order.
+    pub fn ancestors(&self, person: &Person, generations: usize) -> Generations<'a, '_> {
+        Generations::new(&self.parents, person, generations)
+    }
+
+    /// The descendants of `person`, up to and including `generations`
+    /// generations forward, in breadth first order.
+    pub fn descendants(&self, person: &Person, generations: usize) -> Generations<'a, '_> {
+        Generations::new(&self.children, person, generations)
+    }
+
+    fn neighbors<'g>(
+        edges: &'g HashMap<String, Vec<(&'a Person, &'a Relationship)>>,
+        person: &Person,
+    ) -> impl Iterator<Item = Related<'a>> + 'g {
+        key(person)
+            .and_then(|k| edges.get(&k))
+            .into_iter()
+            .flatten()
+            .map(|&(person, relationship)| Related {
+                person,
+                relationship,
+                generation: 1,
+            })
+    }
+}
+
+impl<'a> From<&'a Gedcomx> for FamilyGraph<'a> {
+    fn from(gx: &'a Gedcomx) -> Self {
+        Self::new(gx)
+    }
+}
+
+/// An iterator over the ancestors or descendants of a person. Returned by
+/// [`FamilyGraph::ancestors`] and [`FamilyGraph::descendants`].
+#[derive(Debug, Clone)]
+pub struct Generations<'a, 'g> {
+    edges: &'g HashMap<String, Vec<(&'a Person, &'a Relationship)>>,
+    max_generation: usize,
+    queue: VecDeque<Related<'a>>,
+    visited: HashSet<String>,
+}
+
+impl<'a, 'g> Generations<'a, 'g> {
+    fn new(
+        edges: &'g HashMap<String, Vec<(&'a Person, &'a Relationship)>>,
+        person: &Person,
+        max_generation: usize,
+    ) -> Self {
+        let mut generations = Self {
+            edges,
+            max_generation,
+            queue: VecDeque::new(),
+            visited: key(person).into_iter().collect(),
+        };
+        if max_generation > 0 {
+            generations.visit(person, 1);
+        }
+        generations
+    }
+
+    fn visit(&mut self, person: &Person, generation: usize) {
+        let Some(next) = key(person).and_then(|k| self.edges.get(&k)) else {
+            return;
+        };
+
+        for &(person, relationship) in next {
+            if key(person).is_some_and(|k| self.visited.insert(k)) {
+                self.queue.push_back(Related {
+                    person,
+                    relationship,
+                    generation,
+                });
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Generations<'a, '_> {
+    type Item = Related<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let related = self.queue.pop_front()?;
+        if related.generation < self.max_generation {
+            self.visit(related.person, related.generation + 1);
+        }
+        Some(related)
+    }
+}
+
+fn key(person: &Person) -> Option<String> {
+    person.id.as_ref().map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{Fact, FactType};
+
+    fn parent_child(parent: &Person, child: &Person) -> Relationship {
+        Relationship::builder(parent, child)
+            .unwrap()
+            .relationship_type(RelationshipType::ParentChild)
+            .build()
+    }
+
+    fn couple(person1: &Person, person2: &Person) -> Relationship {
+        Relationship::builder(person1, person2)
+            .unwrap()
+            .relationship_type(RelationshipType::Couple)
+            .build()
+    }
+
+    fn person(id: &str) -> Person {
+        Person::builder().id(id).build()
+    }
+
+    fn ids<'a>(related: impl Iterator<Item = Related<'a>>) -> Vec<(String, usize)> {
+        related
+            .map(|r| (r.person.id.as_ref().unwrap().to_string(), r.generation))
+            .collect()
+    }
+
+    // Grandparents G1 and G2 have child F. F and M are a couple with children
+    // C1 and C2. M also has child H with someone else. C1 was adopted by A.
+    fn family() -> Gedcomx {
+        let [g1, g2, f, m, c1, c2, h, a] = ["G1", "G2", "F", "M", "C1", "C2", "H", "A"].map(person);
+
+        let mut adoption = parent_child(&a, &c1);
+        adoption.facts = vec![Fact::builder(FactType::AdoptiveParent).build()];
+
+        Gedcomx::builder()
+            .relationship(couple(&g1, &g2))
+            .relationship(parent_child(&g1, &f))
+            .relationship(parent_child(&g2, &f))
+            .relationship(couple(&f, &m))
+            .relationship(parent_child(&f, &c1))
+            .relationship(parent_child(&m, &c1))
+            .relationship(parent_child(&f, &c2))
+            .relationship(parent_child(&m, &c2))
+            .relationship(parent_child(&m, &h))
+            .relationship(adoption)
+            .persons(vec![g1, g2, f, m, c1, c2, h, a])
+            .build()
+    }
+
+    fn find<'a>(gx: &'a Gedcomx, id: &str) -> &'a Person {
+        gx.persons
+            .iter()
+            .find(|p| p.id.as_ref().unwrap().to_string() == id)
+            .unwrap()
+    }
+
+    #[test]
+    fn parents_children_spouses() {
+        let gx = family();
+        let graph = FamilyGraph::new(&gx);
+
+        assert_eq!(
+            ids(graph.parents(find(&gx, "C1"))),
+            vec![("F".into(), 1), ("M".into(), 1), ("A".into(), 1)]
+        );
+        assert_eq!(
+            ids(graph.children(find(&gx, "M"))),
+            vec![("C1".into(), 1), ("C2".into(), 1), ("H".into(), 1)]
+        );
+        assert_eq!(ids(graph.spouses(find(&gx, "M"))), vec![("F".into(), 1)]);
+        assert_eq!(ids(graph.spouses(find(&gx, "F"))), vec![("M".into(), 1)]);
+        assert_eq!(ids(graph.parents(find(&gx, "G1"))), vec![]);
+    }
+
+    #[test]
+    fn connecting_relationship() {
+        let gx = family();
+        let graph = FamilyGraph::new(&gx);
+
+        let adoptive = graph
+            .parents(find(&gx, "C1"))
+            .find(|r| r.person == find(&gx, "A"))
+            .unwrap();
+        assert_eq!(
+            adoptive.relationship.facts[0].fact_type,
+            FactType::AdoptiveParent
+        );
+    }
+
+    #[test]
+    fn siblings() {
+        let gx = family();
+        let graph = FamilyGraph::new(&gx);
+
+        assert_eq!(
+            ids(graph.siblings(find(&gx, "C1"))),
+            vec![("C2".into(), 1), ("H".into(), 1)]
+        );
+        assert_eq!(ids(graph.siblings(find(&gx, "F"))), vec![]);
+    }
+
+    #[test]
+    fn ancestors_and_descendants() {
+        let gx = family();
+        let graph = FamilyGraph::new(&gx);
+
+        assert_eq!(
+            ids(graph.ancestors(find(&gx, "C2"), 10)),
+            vec![
+                ("F".into(), 1),
+                ("M".into(), 1),
+                ("G1".into(), 2),
+                ("G2".into(), 2)
+            ]
+        );
+        assert_eq!(
+            ids(graph.ancestors(find(&gx, "C2"), 1)),
+            vec![("F".into(), 1), ("M".into(), 1)]
+        );
+        assert_eq!(ids(graph.ancestors(find(&gx, "C2"), 0)), vec![]);
+        assert_eq!(
+            ids(graph.descendants(find(&gx, "G1"), 2)),
+            vec![("F".into(), 1), ("C1".into(), 2), ("C2".into(), 2)]
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let [a, b, c] = ["A", "B", "C"].map(person);
+        let gx = Gedcomx::builder()
+            .relationship(parent_child(&a, &b))
+            .relationship(parent_child(&b, &c))
+            .relationship(parent_child(&c, &a))
+            .relationship(parent_child(&a, &a))
+            .persons(vec![a, b, c])
+            .build();
+        let graph = FamilyGraph::new(&gx);
+
+        assert_eq!(
+            ids(graph.ancestors(&gx.persons[0], usize::MAX)),
+            vec![("C".into(), 1), ("B".into(), 2)]
+        );
+        assert_eq!(
+            ids(graph.descendants(&gx.persons[0], usize::MAX)),
+            vec![("B".into(), 1), ("C".into(), 2)]
+        );
+        assert_eq!(ids(graph.parents(&gx.persons[0])), vec![("C".into(), 1)]);
+    }
+
+    #[test]
+    fn unresolved_persons_are_ignored() {
+        let [a, b] = ["A", "B"].map(person);
+        let gx = Gedcomx::builder()
+            .relationship(parent_child(&a, &b))
+            .person(a)
+            .build();
+        let graph = FamilyGraph::new(&gx);
+
+        assert_eq!(ids(graph.children(&gx.persons[0])), vec![]);
+    }
+}
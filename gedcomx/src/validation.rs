@@ -0,0 +1,651 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::{
+    Agent, Document, DocumentType, Entity, EntityKind, Gedcomx, Name, PlaceDescription, Reference,
+    ReferenceIndex, SourceDescription, Uri,
+};
+
+/// How serious a [`ValidationIssue`] is.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Severity {
+    /// The document violates a SHOULD (or SHOULD NOT) rule of the spec. The
+    /// document is still valid GEDCOM X, but may not be interpreted as
+    /// intended by other applications.
+    Warning,
+
+    /// The document violates a MUST (or MUST NOT) rule of the spec.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A rule of the GEDCOM X spec that a document violates.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum ValidationIssueKind {
+    /// A `Name` has no name forms. At least one MUST be provided.
+    MissingNameForm,
+
+    /// A `SourceDescription` has no citations. At least one MUST be provided.
+    MissingCitation,
+
+    /// A `PlaceDescription` has no names. At least one MUST be provided.
+    MissingPlaceName,
+
+    /// A `Group` has no names. At least one MUST be provided.
+    MissingGroupName,
+
+    /// A `PlaceDescription` provides a latitude but no longitude.
+    LatitudeWithoutLongitude,
+
+    /// A `PlaceDescription` provides a longitude but no latitude.
+    LongitudeWithoutLatitude,
+
+    /// A latitude outside of the range -90.0 to 90.0 degrees.
+    LatitudeOutOfRange(f64),
+
+    /// A longitude outside of the range -180.0 to 180.0 degrees.
+    LongitudeOutOfRange(f64),
+
+    /// The `place` of a `PlaceDescription` uses the reserved base URI
+    /// `http://gedcomx.org/`.
+    ReservedPlaceUri(Uri),
+
+    /// A media type that isn't a valid MIME type as specified by RFC 4288.
+    InvalidMediaType(String),
+
+    /// An agent email that isn't a `mailto:` URI.
+    InvalidEmail(Uri),
+
+    /// An agent phone that isn't a `tel:` URI.
+    InvalidPhone(Uri),
+
+    /// More than one top level entity in the document uses the same id.
+    DuplicateId(String),
+
+    /// A reference to a resource in this document (a URI of the form `#id`)
+    /// that doesn't resolve to anything.
+    UnresolvedReference {
+        reference: Uri,
+        expected: EntityKind,
+    },
+
+    /// A reference to a resource in this document that resolves to an entity
+    /// of the wrong kind.
+    WrongReferenceTarget {
+        reference: Uri,
+        expected: EntityKind,
+        actual: EntityKind,
+    },
+
+    /// An `analysis` reference that resolves to a `Document` which isn't of
+    /// type [`Analysis`](crate::DocumentType::Analysis).
+    NotAnAnalysisDocument {
+        reference: Uri,
+        actual: DocumentType,
+    },
+}
+
+impl ValidationIssueKind {
+    /// The severity of violating this rule.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::DuplicateId(_) | Self::ReservedPlaceUri(_) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::MissingNameForm => write!(f, "name has no name forms"),
+            Self::MissingCitation => write!(f, "source description has no citations"),
+            Self::MissingPlaceName => write!(f, "place description has no names"),
+            Self::MissingGroupName => write!(f, "group has no names"),
+            Self::LatitudeWithoutLongitude => write!(f, "latitude provided without longitude"),
+            Self::LongitudeWithoutLatitude => write!(f, "longitude provided without latitude"),
+            Self::LatitudeOutOfRange(l) => write!(f, "latitude {l} is out of range"),
+            Self::LongitudeOutOfRange(l) => write!(f, "longitude {l} is out of range"),
+            Self::ReservedPlaceUri(u) => write!(f, "place {u} uses the gedcomx.org base URI"),
+            Self::InvalidMediaType(m) => write!(f, "{m} is not a valid media type"),
+            Self::InvalidEmail(u) => write!(f, "{u} is not a mailto: URI"),
+            Self::InvalidPhone(u) => write!(f, "{u} is not a tel: URI"),
+            Self::DuplicateId(id) => write!(f, "id {id} is used more than once"),
+            Self::UnresolvedReference {
+                reference,
+                expected,
+            } => write!(f, "{reference} does not resolve to a {expected}"),
+            Self::WrongReferenceTarget {
+                reference,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{reference} resolves to a {actual}, expected a {expected}"
+            ),
+            Self::NotAnAnalysisDocument { reference, actual } => write!(
+                f,
+                "{reference} resolves to a document of type {actual}, expected http://gedcomx.org/Analysis"
+            ),
+        }
+    }
+}
+
+/// A single violation of the spec found while validating a document.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct ValidationIssue {
+    /// How serious the violation is.
+    pub severity: Severity,
+
+    /// The path to the offending node, using the JSON property names of the
+    /// document. For example, `persons[0].names[1].nameForms`.
+    pub path: String,
+
+    /// The rule that was violated.
+    pub kind: ValidationIssueKind,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{} at {}: {}", self.severity, self.path, self.kind)
+    }
+}
+
+/// The result of validating a [`Gedcomx`](crate::Gedcomx) document.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ValidationReport {
+    /// Every issue found. Issues with the entities themselves come first,
+    /// followed by issues with references between them.
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Whether the document violates no MUST rules. Warnings are allowed.
+    pub fn is_valid(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Whether no issues of any severity were found.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Issues with a severity of [`Severity::Error`].
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(|i| i.severity == Severity::Error)
+    }
+
+    /// Issues with a severity of [`Severity::Warning`].
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|i| i.severity == Severity::Warning)
+    }
+}
+
+impl IntoIterator for ValidationReport {
+    type IntoIter = std::vec::IntoIter<ValidationIssue>;
+    type Item = ValidationIssue;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.issues.into_iter()
+    }
+}
+
+struct Validator<'a> {
+    index: ReferenceIndex<'a>,
+    issues: Vec<ValidationIssue>,
+}
+
+impl<'a> Validator<'a> {
+    fn new(gx: &'a Gedcomx) -> Self {
+        Self {
+            index: ReferenceIndex::new(gx),
+            issues: vec![],
+        }
+    }
+
+    fn push(&mut self, path: String, kind: ValidationIssueKind) {
+        self.issues.push(ValidationIssue {
+            severity: kind.severity(),
+            path,
+            kind,
+        });
+    }
+
+    fn validate(&mut self) {
+        let mut seen = HashSet::new();
+        let entities: Vec<_> = self.index.entities().collect();
+        let mut counts: HashMap<EntityKind, usize> = HashMap::new();
+        for entity in entities {
+            let count = counts.entry(entity.kind()).or_default();
+            let path = format!("{}[{count}]", collection_name(entity.kind()));
+            *count += 1;
+
+            if let Some(id) = entity.id()
+                && !seen.insert(id.to_string())
+            {
+                self.push(
+                    format!("{path}.id"),
+                    ValidationIssueKind::DuplicateId(id.to_string()),
+                );
+            }
+
+            match entity {
+                Entity::Person(person) => {
+                    for (i, name) in person.names.iter().enumerate() {
+                        self.name(&format!("{path}.names[{i}]"), name);
+                    }
+                }
+                Entity::SourceDescription(sd) => self.source_description(&path, sd),
+                Entity::Agent(agent) => self.agent(&path, agent),
+                Entity::PlaceDescription(place) => self.place_description(&path, place),
+                Entity::Group(group) if group.names.is_empty() => self.push(
+                    format!("{path}.names"),
+                    ValidationIssueKind::MissingGroupName,
+                ),
+                _ => {}
+            }
+        }
+
+        self.references();
+    }
+
+    fn references(&mut self) {
+        let references = self.index.references().to_vec();
+        for reference in references {
+            let Reference {
+                path,
+                reference,
+                expected,
+            } = reference;
+
+            match self.index.resolve(reference) {
+                None => self.push(
+                    path,
+                    ValidationIssueKind::UnresolvedReference {
+                        reference: reference.clone(),
+                        expected,
+                    },
+                ),
+                Some(entity) if entity.kind() != expected => self.push(
+                    path,
+                    ValidationIssueKind::WrongReferenceTarget {
+                        reference: reference.clone(),
+                        expected,
+                        actual: entity.kind(),
+                    },
+                ),
+                // The only references to documents are to analysis documents.
+                Some(Entity::Document(Document {
+                    document_type: Some(document_type),
+                    ..
+                })) if document_type != &DocumentType::Analysis => self.push(
+                    path,
+                    ValidationIssueKind::NotAnAnalysisDocument {
+                        reference: reference.clone(),
+                        actual: document_type.clone(),
+                    },
+                ),
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn name(&mut self, path: &str, name: &Name) {
+        if name.name_forms.is_empty() {
+            self.push(
+                format!("{path}.nameForms"),
+                ValidationIssueKind::MissingNameForm,
+            );
+        }
+    }
+
+    fn source_description(&mut self, path: &str, sd: &SourceDescription) {
+        if sd.citations.is_empty() {
+            self.push(
+                format!("{path}.citations"),
+                ValidationIssueKind::MissingCitation,
+            );
+        }
+        if let Some(media_type) = &sd.media_type
+            && !is_media_type(media_type)
+        {
+            self.push(
+                format!("{path}.mediaType"),
+                ValidationIssueKind::InvalidMediaType(media_type.clone()),
+            );
+        }
+    }
+
+    fn agent(&mut self, path: &str, agent: &Agent) {
+        for (i, email) in agent.emails.iter().enumerate() {
+            if !email.resource.to_string().starts_with("mailto:") {
+                self.push(
+                    format!("{path}.emails[{i}]"),
+                    ValidationIssueKind::InvalidEmail(email.resource.clone()),
+                );
+            }
+        }
+        for (i, phone) in agent.phones.iter().enumerate() {
+            if !phone.resource.to_string().starts_with("tel:") {
+                self.push(
+                    format!("{path}.phones[{i}]"),
+                    ValidationIssueKind::InvalidPhone(phone.resource.clone()),
+                );
+            }
+        }
+    }
+
+    fn place_description(&mut self, path: &str, place: &PlaceDescription) {
+        if place.names.is_empty() {
+            self.push(
+                format!("{path}.names"),
+                ValidationIssueKind::MissingPlaceName,
+            );
+        }
+
+        match (place.latitude, place.longitude) {
+            (Some(_), None) => self.push(
+                format!("{path}.latitude"),
+                ValidationIssueKind::LatitudeWithoutLongitude,
+            ),
+            (None, Some(_)) => self.push(
+                format!("{path}.longitude"),
+                ValidationIssueKind::LongitudeWithoutLatitude,
+            ),
+            _ => {}
+        }
+        if let Some(latitude) = place.latitude
+            && !(-90.0..=90.0).contains(&latitude)
+        {
+            self.push(
+                format!("{path}.latitude"),
+                ValidationIssueKind::LatitudeOutOfRange(latitude),
+            );
+        }
+        if let Some(longitude) = place.longitude
+            && !(-180.0..=180.0).contains(&longitude)
+        {
+            self.push(
+                format!("{path}.longitude"),
+                ValidationIssueKind::LongitudeOutOfRange(longitude),
+            );
+        }
+
+        if let Some(p) = &place.place
+            && p.resource.to_string().starts_with("http://gedcomx.org/")
+        {
+            self.push(
+                format!("{path}.place"),
+                ValidationIssueKind::ReservedPlaceUri(p.resource.clone()),
+            );
+        }
+    }
+}
+
+const fn collection_name(kind: EntityKind) -> &'static str {
+    match kind {
+        EntityKind::Person => "persons",
+        EntityKind::Relationship => "relationships",
+        EntityKind::SourceDescription => "sourceDescriptions",
+        EntityKind::Agent => "agents",
+        EntityKind::Event => "events",
+        EntityKind::Document => "documents",
+        EntityKind::PlaceDescription => "places",
+        EntityKind::Group => "groups",
+    }
+}
+
+// A media type is a type and subtype separated by a slash, each made up of
+// RFC 4288 restricted name characters. Parameters after a ';' aren't checked.
+fn is_media_type(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    let is_restricted_name = |s: &str| {
+        !s.is_empty()
+            && s.len() <= 127
+            && s.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || "!#$&.+-^_".contains(c))
+    };
+
+    essence
+        .split_once('/')
+        .is_some_and(|(t, s)| is_restricted_name(t) && is_restricted_name(s))
+}
+
+impl Gedcomx {
+    /// Check the document against the MUST and SHOULD rules of the GEDCOM X
+    /// spec.
+    ///
+    /// Builders enforce some of these rules when a document is created, but
+    /// documents that were deserialized or modified through public fields are
+    /// never checked. Every entity in the document is visited and all
+    /// violations are returned, rather than stopping at the first one.
+    ///
+    /// References are only checked when they point at a resource in this
+    /// document (a URI of the form `#id`).
+    pub fn validate(&self) -> ValidationReport {
+        let mut validator = Validator::new(self);
+        validator.validate();
+
+        ValidationReport {
+            issues: validator.issues,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        Group, NameForm, Person, Relationship, ResourceReference, SourceCitation, SourceReference,
+        TextValue,
+    };
+
+    #[test]
+    fn valid_document() {
+        let json = std::fs::read_to_string("../data/birth.json").unwrap();
+        let gx = Gedcomx::from_json_str(&json).unwrap();
+        let report = gx.validate();
+        assert!(report.is_empty(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn all_data_files_are_valid() {
+        // relationships.json is left out since it refers to persons that
+        // aren't in the document.
+        for file in [
+            "birth",
+            "census",
+            "death",
+            "education",
+            "marriage",
+            "military",
+            "names1",
+            "religious",
+            "spec",
+        ] {
+            let json = std::fs::read_to_string(format!("../data/{file}.json")).unwrap();
+            let gx = Gedcomx::from_json_str(&json).unwrap();
+            assert!(gx.validate().is_valid(), "{file}: {:?}", gx.validate());
+        }
+    }
+
+    #[test]
+    fn missing_name_form() {
+        let mut name = Name::builder(NameForm::default()).build();
+        name.name_forms.clear();
+        let gx = Gedcomx::builder()
+            .person(Person::builder().name(name).build())
+            .build();
+
+        let report = gx.validate();
+        assert_eq!(
+            report.issues,
+            vec![ValidationIssue {
+                severity: Severity::Error,
+                path: "persons[0].names[0].nameForms".to_string(),
+                kind: ValidationIssueKind::MissingNameForm
+            }]
+        );
+        assert!(!report.is_valid());
+    }
+
+    #[test]
+    fn missing_citation() {
+        let mut sd = SourceDescription::builder(SourceCitation::new("citation", None)).build();
+        sd.citations.clear();
+        let gx = Gedcomx::builder().source_description(sd).build();
+
+        let report = gx.validate();
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].path, "sourceDescriptions[0].citations");
+        assert_eq!(report.issues[0].kind, ValidationIssueKind::MissingCitation);
+    }
+
+    #[test]
+    fn latitude_without_longitude() {
+        let mut place = PlaceDescription::builder("Provo").build();
+        place.latitude = Some(40.0);
+        let mut place2 = PlaceDescription::builder("Nowhere").build();
+        place2.latitude = Some(100.0);
+        place2.longitude = Some(-111.0);
+        let gx = Gedcomx::builder().place(place).place(place2).build();
+
+        let kinds: Vec<_> = gx
+            .validate()
+            .into_iter()
+            .map(|i| (i.path, i.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    "places[0].latitude".to_string(),
+                    ValidationIssueKind::LatitudeWithoutLongitude
+                ),
+                (
+                    "places[1].latitude".to_string(),
+                    ValidationIssueKind::LatitudeOutOfRange(100.0)
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn analysis_not_analysis_document() {
+        let document = Document::builder("text")
+            .id("D-1")
+            .document_type(DocumentType::Transcription)
+            .build();
+        let mut person = Person::builder().id("P-1").build();
+        person.analysis = Some(ResourceReference::from("#D-1"));
+        let gx = Gedcomx::builder().document(document).person(person).build();
+
+        let report = gx.validate();
+        assert_eq!(
+            report.issues,
+            vec![ValidationIssue {
+                severity: Severity::Error,
+                path: "persons[0].analysis".to_string(),
+                kind: ValidationIssueKind::NotAnAnalysisDocument {
+                    reference: "#D-1".into(),
+                    actual: DocumentType::Transcription
+                }
+            }]
+        );
+    }
+
+    #[test]
+    fn unresolved_and_wrong_references() {
+        let p1 = Person::builder().id("P-1").build();
+        let p2 = Person::builder().id("P-2").build();
+        let mut relationship = Relationship::builder(&p1, &p2).unwrap().build();
+        relationship.person2 = ResourceReference::from("#S-1");
+        let sd = SourceDescription::builder(SourceCitation::new("citation", None))
+            .id("S-1")
+            .build();
+        let gx = Gedcomx::builder()
+            .person(p1)
+            .relationship(relationship)
+            .source_description(sd)
+            .build();
+
+        let report = gx.validate();
+        assert_eq!(
+            report.issues,
+            vec![ValidationIssue {
+                severity: Severity::Error,
+                path: "relationships[0].person2".to_string(),
+                kind: ValidationIssueKind::WrongReferenceTarget {
+                    reference: "#S-1".into(),
+                    expected: EntityKind::Person,
+                    actual: EntityKind::SourceDescription
+                }
+            }]
+        );
+
+        let mut gx = gx;
+        gx.persons.clear();
+        let report = gx.validate();
+        assert_eq!(
+            report.issues[0].kind,
+            ValidationIssueKind::UnresolvedReference {
+                reference: "#P-1".into(),
+                expected: EntityKind::Person
+            }
+        );
+    }
+
+    #[test]
+    fn external_references_are_not_checked() {
+        let mut person = Person::builder().id("P-1").build();
+        person.sources = vec![SourceReference::new(
+            "https://example.com/sources/1".into(),
+            None,
+            None,
+            vec![],
+        )];
+        let gx = Gedcomx::builder().person(person).build();
+        assert!(gx.validate().is_empty());
+    }
+
+    #[test]
+    fn warnings_do_not_invalidate() {
+        let gx = Gedcomx::builder()
+            .person(Person::builder().id("P-1").build())
+            .group(Group::builder(TextValue::from("group")).id("P-1").build())
+            .build();
+
+        let report = gx.validate();
+        assert!(report.is_valid());
+        assert_eq!(report.warnings().count(), 1);
+        assert_eq!(report.issues[0].path, "groups[0].id");
+    }
+
+    #[test]
+    fn media_type() {
+        assert!(is_media_type("image/png"));
+        assert!(is_media_type("application/x-gedcomx-v1+json"));
+        assert!(is_media_type("text/plain; charset=utf-8"));
+        assert!(!is_media_type("png"));
+        assert!(!is_media_type("image/"));
+        assert!(!is_media_type("image/p ng"));
+    }
+}
@@ -0,0 +1,550 @@
+//! Reading and writing the XML form of GEDCOM X.
+//!
+//! Types that are XML elements implement [`ToXml`] and [`FromXml`], mostly
+//! with the `impl_xml!` macro. Types that are text, in an attribute or an
+//! element of their own, implement [`XmlValue`].
+
+use std::{borrow::Cow, io::Read};
+
+use xml::{name::OwnedName, namespace::NS_XML_URI};
+
+use crate::{DeserializeError, Result};
+
+mod reader;
+pub use reader::{Content, StartElement, XmlReader, parser_config};
+
+mod writer;
+pub use writer::{Config, XmlWriter};
+
+pub const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+
+#[cfg(feature = "familysearch")]
+pub const FAMILYSEARCH_NAMESPACE: &str = "http://familysearch.org/v1/";
+
+/// A type written as an XML element.
+pub trait ToXml {
+    /// The name of the element when the value is written on its own.
+    const NAME: &'static str;
+
+    /// Write the value as an element named `name`.
+    fn write(&self, writer: &mut XmlWriter, name: &str) -> Result<()>;
+
+    /// Put a list of values in the order canonical XML writes them in. The
+    /// order is kept unless it doesn't mean anything.
+    fn canonical_order(_values: &mut [&Self]) {}
+}
+
+/// A type read from an XML element.
+pub trait FromXml: Sized {
+    /// Read the value from the element that `start` was read for, through
+    /// its end.
+    fn read<R: Read>(
+        reader: &mut XmlReader<R>,
+        start: StartElement,
+    ) -> std::result::Result<Self, DeserializeError>;
+}
+
+/// A type written as text, either as an attribute or as an element with
+/// nothing but text in it.
+pub trait XmlValue: Sized {
+    /// The name of the element when the value is written on its own.
+    const NAME: &'static str;
+
+    fn to_xml_value(&self) -> Cow<'_, str>;
+
+    /// # Errors
+    ///
+    /// Returns a message if `value` isn't valid.
+    fn from_xml_value(value: String) -> std::result::Result<Self, ValueError>;
+}
+
+/// A value that couldn't be read.
+#[derive(Debug)]
+pub struct ValueError {
+    pub message: String,
+    pub value: String,
+}
+
+impl ValueError {
+    pub fn new<M: ToString + ?Sized>(message: &M, value: String) -> Self {
+        Self {
+            message: message.to_string(),
+            value,
+        }
+    }
+}
+
+impl<T: XmlValue> ToXml for T {
+    const NAME: &'static str = T::NAME;
+
+    fn write(&self, writer: &mut XmlWriter, name: &str) -> Result<()> {
+        writer.start(name);
+        writer.text(&self.to_xml_value());
+        writer.end(name);
+        Ok(())
+    }
+}
+
+impl<T: XmlValue> FromXml for T {
+    fn read<R: Read>(
+        reader: &mut XmlReader<R>,
+        _start: StartElement,
+    ) -> std::result::Result<Self, DeserializeError> {
+        let text = reader.read_text()?;
+        Self::from_xml_value(text).map_err(|e| reader.value_error(e.message, &e.value))
+    }
+}
+
+impl XmlValue for String {
+    const NAME: &'static str = "string";
+
+    fn to_xml_value(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self)
+    }
+
+    fn from_xml_value(value: String) -> std::result::Result<Self, ValueError> {
+        Ok(value)
+    }
+}
+
+// Whitespace around values that aren't strings isn't part of them.
+macro_rules! impl_xml_value_from_str {
+    ($for_type: ty, $name: literal) => {
+        impl XmlValue for $for_type {
+            const NAME: &'static str = $name;
+
+            fn to_xml_value(&self) -> Cow<'_, str> {
+                Cow::Owned(self.to_string())
+            }
+
+            fn from_xml_value(value: String) -> std::result::Result<Self, ValueError> {
+                value.trim().parse().map_err(|e| ValueError::new(&e, value))
+            }
+        }
+    };
+}
+
+impl_xml_value_from_str!(u32, "unsignedInt");
+impl_xml_value_from_str!(f32, "float");
+impl_xml_value_from_str!(f64, "double");
+
+impl XmlValue for bool {
+    const NAME: &'static str = "boolean";
+
+    fn to_xml_value(&self) -> Cow<'_, str> {
+        Cow::Borrowed(if *self { "true" } else { "false" })
+    }
+
+    fn from_xml_value(value: String) -> std::result::Result<Self, ValueError> {
+        match value.trim() {
+            "true" | "1" => Ok(true),
+            "false" | "0" => Ok(false),
+            _ => Err(ValueError::new("expected a boolean", value)),
+        }
+    }
+}
+
+/// A field of a type that's read from and written to XML, which is either
+/// optional, a list, or required.
+pub trait XmlField: Sized {
+    /// Where the field is kept while it's read.
+    type Slot: Default;
+
+    /// The field, once everything has been read.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the field is required and wasn't read.
+    fn finish(slot: Self::Slot, name: &str, parent: &str) -> std::result::Result<Self, String>;
+}
+
+/// A field written as one or more elements.
+pub trait ElementField: XmlField {
+    /// Whether the field is a list, of one value for each element.
+    const LIST: bool = false;
+
+    fn write_elements(&self, writer: &mut XmlWriter, name: &str) -> Result<()>;
+
+    /// # Errors
+    ///
+    /// Returns an error if the element can't be read.
+    fn read_element<R: Read>(
+        slot: &mut Self::Slot,
+        reader: &mut XmlReader<R>,
+        start: StartElement,
+    ) -> std::result::Result<(), DeserializeError>;
+}
+
+/// A field written as an attribute.
+pub trait AttributeField: XmlField {
+    fn write_attribute(&self, writer: &mut XmlWriter, name: &str);
+
+    /// # Errors
+    ///
+    /// Returns an error if the value isn't valid.
+    fn read_attribute(slot: &mut Self::Slot, value: String) -> std::result::Result<(), ValueError>;
+}
+
+/// A field written as the text of its element.
+pub trait TextField: XmlField {
+    fn write_text(&self, writer: &mut XmlWriter);
+
+    /// Read the text of an element, which also had child elements if
+    /// `mixed`. Then whitespace on its own is only there for formatting.
+    fn read_text(slot: &mut Self::Slot, text: String, mixed: bool);
+}
+
+/// The slot of a field, the type of which is found from the field in `S`.
+pub fn slot<S, T: XmlField>(_field: impl Fn(&S) -> &T) -> T::Slot {
+    T::Slot::default()
+}
+
+impl<T> XmlField for Option<T> {
+    type Slot = Self;
+
+    fn finish(slot: Self, _name: &str, _parent: &str) -> std::result::Result<Self, String> {
+        Ok(slot)
+    }
+}
+
+impl<T> XmlField for Vec<T> {
+    type Slot = Self;
+
+    fn finish(slot: Self, _name: &str, _parent: &str) -> std::result::Result<Self, String> {
+        Ok(slot)
+    }
+}
+
+// The last element wins if there's more than one.
+impl<T: ToXml + FromXml> ElementField for Option<T> {
+    fn write_elements(&self, writer: &mut XmlWriter, name: &str) -> Result<()> {
+        self.as_ref()
+            .map_or(Ok(()), |value| value.write(writer, name))
+    }
+
+    fn read_element<R: Read>(
+        slot: &mut Self,
+        reader: &mut XmlReader<R>,
+        start: StartElement,
+    ) -> std::result::Result<(), DeserializeError> {
+        *slot = Some(T::read(reader, start)?);
+        Ok(())
+    }
+}
+
+impl<T: ToXml + FromXml> ElementField for Vec<T> {
+    const LIST: bool = true;
+
+    fn write_elements(&self, writer: &mut XmlWriter, name: &str) -> Result<()> {
+        let mut values: Vec<&T> = self.iter().collect();
+        if writer.is_canonical() {
+            T::canonical_order(&mut values);
+        }
+        values
+            .into_iter()
+            .try_for_each(|value| value.write(writer, name))
+    }
+
+    fn read_element<R: Read>(
+        slot: &mut Self,
+        reader: &mut XmlReader<R>,
+        start: StartElement,
+    ) -> std::result::Result<(), DeserializeError> {
+        slot.push(T::read(reader, start)?);
+        Ok(())
+    }
+}
+
+impl<T: XmlValue> AttributeField for Option<T> {
+    fn write_attribute(&self, writer: &mut XmlWriter, name: &str) {
+        if let Some(value) = self {
+            writer.attribute(name, &value.to_xml_value());
+        }
+    }
+
+    fn read_attribute(slot: &mut Self, value: String) -> std::result::Result<(), ValueError> {
+        *slot = Some(T::from_xml_value(value)?);
+        Ok(())
+    }
+}
+
+// No text is written as empty text, so the element has an end tag.
+impl TextField for Option<String> {
+    fn write_text(&self, writer: &mut XmlWriter) {
+        writer.text(self.as_deref().unwrap_or_default());
+    }
+
+    fn read_text(slot: &mut Self, text: String, mixed: bool) {
+        if !(text.is_empty() || (mixed && text.trim().is_empty())) {
+            *slot = Some(text);
+        }
+    }
+}
+
+/// Implements the field traits for types that are required, which have to be
+/// read once.
+macro_rules! impl_required_xml_field {
+    ($($for_type: ty),*) => {
+        $(
+            impl XmlField for $for_type {
+                type Slot = Option<Self>;
+
+                fn finish(
+                    slot: Option<Self>,
+                    name: &str,
+                    parent: &str,
+                ) -> std::result::Result<Self, String> {
+                    slot.ok_or_else(|| format!("{name} is a required field of {parent}"))
+                }
+            }
+
+            impl ElementField for $for_type {
+
+                fn write_elements(&self, writer: &mut XmlWriter, name: &str) -> Result<()> {
+                    self.write(writer, name)
+                }
+
+                fn read_element<R: Read>(
+                    slot: &mut Option<Self>,
+                    reader: &mut XmlReader<R>,
+                    start: StartElement,
+                ) -> std::result::Result<(), DeserializeError> {
+                    *slot = Some(Self::read(reader, start)?);
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_required_xml_attribute {
+    ($($for_type: ty),*) => {
+        $(
+            impl AttributeField for $for_type {
+                fn write_attribute(&self, writer: &mut XmlWriter, name: &str) {
+                    writer.attribute(name, &self.to_xml_value());
+                }
+
+                fn read_attribute(
+                    slot: &mut Option<Self>,
+                    value: String,
+                ) -> std::result::Result<(), ValueError> {
+                    *slot = Some(Self::from_xml_value(value)?);
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_required_xml_field!(
+    String,
+    crate::ResourceReference,
+    crate::Uri,
+    crate::FactType,
+    crate::GenderType
+);
+impl_required_xml_attribute!(String, crate::Uri, crate::FactType, crate::GenderType);
+
+// Missing text is empty text.
+impl TextField for String {
+    fn write_text(&self, writer: &mut XmlWriter) {
+        writer.text(self);
+    }
+
+    fn read_text(slot: &mut Option<Self>, text: String, mixed: bool) {
+        *slot = Some(if mixed && text.trim().is_empty() {
+            Self::new()
+        } else {
+            text
+        });
+    }
+}
+
+// The field functions the `impl_xml!` macro uses, which find the type of a
+// field from the field itself.
+
+pub fn read_attribute<S, T: AttributeField>(
+    _field: impl Fn(&S) -> &T,
+    slot: &mut T::Slot,
+    value: String,
+) -> std::result::Result<(), ValueError> {
+    T::read_attribute(slot, value)
+}
+
+/// Read an element of the field named `name`, recovering from an error in it
+/// if the read is lenient. The text of an element that can't be read is kept
+/// if `keep_invalid`.
+pub fn read_element<S, T: ElementField, R: Read>(
+    name: &'static str,
+    keep_invalid: bool,
+    _field: impl Fn(&S) -> &T,
+    slot: &mut T::Slot,
+    reader: &mut XmlReader<R>,
+    start: StartElement,
+) -> std::result::Result<(), DeserializeError> {
+    reader.read_as_field(name, T::LIST);
+    let depth = reader.depth();
+    T::read_element(slot, reader, start).or_else(|e| reader.recover(e, depth, keep_invalid))
+}
+
+pub fn read_text<S, T: TextField>(
+    _field: impl Fn(&S) -> &T,
+    slot: &mut T::Slot,
+    text: String,
+    mixed: bool,
+) {
+    T::read_text(slot, text, mixed);
+}
+
+/// Whether an attribute is the one named `name`, which may have the `xml`
+/// prefix. Documents that leave the prefix off, such as `lang` for
+/// `xml:lang`, can be read too.
+pub fn is_attribute(attribute: &OwnedName, name: &str) -> bool {
+    let (namespace, local_name) = name
+        .strip_prefix("xml:")
+        .map_or((None, name), |local_name| (Some(NS_XML_URI), local_name));
+    attribute.local_name == local_name
+        && (attribute.namespace.is_none() || attribute.namespace.as_deref() == namespace)
+}
+
+/// Whether an element is the one named `local_name` in `namespace`. An
+/// element in no namespace at all is taken to be in the namespace it would
+/// be in, so documents that don't declare it can be read.
+pub fn is_element(element: &OwnedName, namespace: &str, local_name: &str) -> bool {
+    element.local_name == local_name
+        && element
+            .namespace
+            .as_deref()
+            .is_none_or(|ns| ns.is_empty() || ns == namespace)
+}
+
+/// Write `value` as a document of its own.
+///
+/// # Errors
+///
+/// Returns `GedcomxError::XMLSerializeError` if the extensions of an element
+/// aren't XML.
+pub fn to_string<T: ToXml>(value: &T, config: Config) -> Result<String> {
+    let mut writer = XmlWriter::new(config);
+    value.write(&mut writer, T::NAME)?;
+    Ok(writer.into_string())
+}
+
+/// Read a document from `reader`, with the position, path and value of any
+/// error.
+///
+/// # Errors
+///
+/// Returns `GedcomxError::DeserializeError` if the document isn't XML or
+/// can't be read as a `T`, and `GedcomxError::IoError` if reading `reader`
+/// fails.
+pub fn from_reader<T: FromXml, R: Read>(reader: R) -> Result<T> {
+    let mut reader = XmlReader::new(reader);
+    read_document(&mut reader).map_err(|e| reader.fail(e))
+}
+
+/// Read the document of `reader` as a `T`.
+///
+/// # Errors
+///
+/// Returns `DeserializeError` if the document isn't XML or can't be read as a
+/// `T`.
+pub fn read_document<T: FromXml, R: Read>(
+    reader: &mut XmlReader<R>,
+) -> std::result::Result<T, DeserializeError> {
+    let start = reader.root()?;
+    let value = T::read(reader, start)?;
+    reader.end_document()?;
+    Ok(value)
+}
+
+/// Read a document from the string `xml`.
+///
+/// # Errors
+///
+/// Returns `GedcomxError::DeserializeError` if the document isn't XML or
+/// can't be read as a `T`.
+pub fn from_str<T: FromXml>(xml: &str) -> Result<T> {
+    from_reader(xml.as_bytes())
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{GedcomxError, Note};
+
+    const CONFIG: Config = Config {
+        write_document_declaration: false,
+        perform_indent: false,
+        canonical: false,
+    };
+
+    #[test]
+    fn whitespace_is_kept() {
+        let xml = "<note xmlns=\"http://gedcomx.org/v1/\"><text>  Two\n  lines </text></note>";
+
+        let note: Note = from_str(xml).unwrap();
+        assert_eq!(note.text, "  Two\n  lines ");
+        assert_eq!(to_string(&note, CONFIG).unwrap(), xml);
+    }
+
+    #[test]
+    fn prefixed_namespace() {
+        let xml =
+            r#"<gx:note xmlns:gx="http://gedcomx.org/v1/"><gx:text>A note</gx:text></gx:note>"#;
+
+        let note: Note = from_str(xml).unwrap();
+        assert_eq!(note.text, "A note");
+    }
+
+    #[test]
+    fn other_namespace_is_not_a_field() {
+        let xml = r#"<note xmlns="http://gedcomx.org/v1/" xmlns:ex="http://example.com/"><text>A note</text><ex:text>Not a note</ex:text></note>"#;
+
+        let note: Note = from_str(xml).unwrap();
+        assert_eq!(note.text, "A note");
+        assert_eq!(
+            note.extensions.xml_elements,
+            vec![
+                r#"<ex:text xmlns="http://gedcomx.org/v1/" xmlns:ex="http://example.com/">Not a note</ex:text>"#
+            ]
+        );
+        // The extension declares its own namespace.
+        assert_eq!(
+            to_string(&note, CONFIG).unwrap(),
+            r#"<note xmlns="http://gedcomx.org/v1/"><text>A note</text><ex:text xmlns:ex="http://example.com/">Not a note</ex:text></note>"#
+        );
+    }
+
+    #[test]
+    fn missing_required_field() {
+        let xml = r#"<gedcomx xmlns="http://gedcomx.org/v1/"><person><name><nameForm /></name></person><agent><account><accountName>name</accountName></account></agent></gedcomx>"#;
+
+        let Err(GedcomxError::DeserializeError(e)) = from_str::<crate::Gedcomx>(xml) else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            e.message,
+            "serviceHomepage is a required field of OnlineAccount"
+        );
+        assert_eq!(e.path, "agents[0].accounts[0]");
+    }
+
+    #[test]
+    fn indent() {
+        let config = Config {
+            perform_indent: true,
+            ..CONFIG
+        };
+        let note = Note::builder("A note").subject("Subject").build();
+
+        assert_eq!(
+            to_string(&note, config).unwrap(),
+            "<note xmlns=\"http://gedcomx.org/v1/\">\n  <subject>Subject</subject>\n  <text>A \
+             note</text>\n</note>"
+        );
+    }
+}
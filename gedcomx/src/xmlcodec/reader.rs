@@ -0,0 +1,485 @@
+use std::io::{self, Read};
+
+use xml::{
+    attribute::OwnedAttribute,
+    common::{Position as _, TextPosition},
+    name::OwnedName,
+    namespace::Namespace,
+    reader::{EventReader, ParserConfig, XmlEvent},
+};
+
+use super::ValueError;
+use crate::{
+    DeserializeError, DeserializeWarning, GedcomxError, Position, Recovery, tracking::value_string,
+};
+
+/// How documents are parsed: text is kept as it is, since whitespace can be
+/// significant, such as in the text of a note.
+pub fn parser_config() -> ParserConfig {
+    ParserConfig::new()
+        .whitespace_to_characters(true)
+        .cdata_to_characters(true)
+        .ignore_comments(true)
+        .coalesce_characters(true)
+}
+
+/// The start of an element.
+#[derive(Debug)]
+pub struct StartElement {
+    pub name: OwnedName,
+    pub attributes: Vec<OwnedAttribute>,
+
+    /// The namespaces in scope.
+    pub namespace: Namespace,
+}
+
+/// What comes next in an element.
+#[derive(Debug)]
+pub enum Content {
+    Start(StartElement),
+    Text(String),
+    End,
+}
+
+// An open element, to give the path to where an error is.
+#[derive(Debug)]
+struct Frame {
+    name: String,
+
+    // The element's position among its siblings of the same name.
+    index: usize,
+
+    // The field the element is read as, and whether the field is a list.
+    field: Option<(&'static str, bool)>,
+
+    // How many children of each name have been opened.
+    children: Vec<(String, usize)>,
+}
+
+// The input of a document. It keeps the error reading it failed with, since
+// xml-rs only keeps a description of it, and the lines from the one the last
+// event started on, to find the offset of a position in them.
+struct Input<R> {
+    reader: R,
+    error: Option<io::Error>,
+
+    // The lines kept, the first of which is `row`, starting at `offset`.
+    lines: Vec<u8>,
+    row: u64,
+    offset: u64,
+}
+
+impl<R> Input<R> {
+    const fn new(reader: R) -> Self {
+        Self {
+            reader,
+            error: None,
+            lines: vec![],
+            row: 0,
+            offset: 0,
+        }
+    }
+
+    // Where the line `row` starts in the lines kept, or where they end if
+    // it hasn't been read.
+    fn line_start(&self, row: u64) -> (u64, usize) {
+        let mut line = (self.row, 0);
+        while line.0 < row {
+            match self.lines[line.1..].iter().position(|&b| b == b'\n') {
+                Some(end) => line = (line.0 + 1, line.1 + end + 1),
+                None => break,
+            }
+        }
+        line
+    }
+
+    // Stop keeping the lines before `row`.
+    fn forget_before(&mut self, row: u64) {
+        let (row, start) = self.line_start(row);
+        self.lines.drain(..start);
+        self.row = row;
+        self.offset += start as u64;
+    }
+
+    // The offset of `position`, whose column counts characters, which are
+    // read as UTF-8.
+    fn offset_of(&self, position: TextPosition) -> u64 {
+        let (_, start) = self.line_start(position.row);
+        let column = usize::try_from(position.column).unwrap_or(usize::MAX);
+        let in_line = self.lines[start..]
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| (*b & 0xC0) != 0x80)
+            .nth(column)
+            .map_or(self.lines.len() - start, |(i, _)| i);
+        self.offset + (start + in_line) as u64
+    }
+}
+
+impl<R: Read> Read for Input<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.reader.read(buf) {
+            Ok(n) => {
+                self.lines.extend_from_slice(&buf[..n]);
+                Ok(n)
+            }
+            Err(e) => {
+                let description = io::Error::new(e.kind(), e.to_string());
+                // An interrupted read is retried.
+                if e.kind() != io::ErrorKind::Interrupted {
+                    self.error = Some(e);
+                }
+                Err(description)
+            }
+        }
+    }
+}
+
+/// Reads an XML document one element at a time, keeping track of where it is
+/// for errors.
+pub struct XmlReader<R: Read> {
+    events: EventReader<Input<R>>,
+    frames: Vec<Frame>,
+
+    // The current element has ended, but errors about it can still be made.
+    ended: bool,
+
+    // What a lenient read has recovered from, or `None` if the read is
+    // strict.
+    warnings: Option<Vec<DeserializeWarning>>,
+
+    // The whole of the last invalid value a lenient read found, which it may
+    // keep.
+    invalid_value: Option<String>,
+
+    // The invalid values a lenient read has kept, by the name of the element
+    // they were in, with the depth of the element they're kept for.
+    kept: Vec<(usize, String, String)>,
+
+    // The document isn't well-formed XML, so nothing can be recovered from.
+    malformed: bool,
+}
+
+// The top level entities of a document, which a lenient read skips if they
+// can't be read.
+const ENTITY_NAMES: [&str; 8] = [
+    "person",
+    "relationship",
+    "sourceDescription",
+    "agent",
+    "event",
+    "document",
+    "place",
+    "group",
+];
+
+impl<R: Read> XmlReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            events: EventReader::new_with_config(Input::new(reader), parser_config()),
+            frames: vec![],
+            ended: false,
+            warnings: None,
+            invalid_value: None,
+            kept: vec![],
+            malformed: false,
+        }
+    }
+
+    /// Read a document leniently: errors in the fields of elements are
+    /// recovered from, with a warning, instead of being returned.
+    pub fn lenient(reader: R) -> Self {
+        Self {
+            warnings: Some(vec![]),
+            ..Self::new(reader)
+        }
+    }
+
+    /// The warnings of a lenient read, in the order of the document.
+    pub fn into_warnings(self) -> Vec<DeserializeWarning> {
+        self.warnings.unwrap_or_default()
+    }
+
+    /// `error`, returned by the read, as the IO error that caused it if it was
+    /// caused by reading the input failing.
+    pub fn fail(&mut self, error: DeserializeError) -> GedcomxError {
+        self.events
+            .source_mut()
+            .error
+            .take()
+            .map_or_else(|| error.into(), GedcomxError::IoError)
+    }
+
+    /// How many elements are open, including the current one.
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Read up to the start of the root element.
+    pub fn root(&mut self) -> Result<StartElement, DeserializeError> {
+        match self.next_content()? {
+            Content::Start(start) => Ok(start),
+            Content::Text(_) | Content::End => Err(self.error("no root element")),
+        }
+    }
+
+    /// Read the rest of the document after the root element.
+    pub fn end_document(&mut self) -> Result<(), DeserializeError> {
+        loop {
+            match self.next_event()? {
+                XmlEvent::EndDocument => return Ok(()),
+                XmlEvent::StartElement { .. } => {
+                    return Err(self.error("more than one root element"));
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Read what comes next in the current element. After a `Start`, the
+    /// current element is the one that started, until its `End`.
+    pub fn next_content(&mut self) -> Result<Content, DeserializeError> {
+        if std::mem::take(&mut self.ended) {
+            self.frames.pop();
+        }
+        loop {
+            match self.next_event()? {
+                XmlEvent::StartElement {
+                    name,
+                    attributes,
+                    namespace,
+                } => {
+                    self.open(&name.local_name);
+                    return Ok(Content::Start(StartElement {
+                        name,
+                        attributes,
+                        namespace,
+                    }));
+                }
+                XmlEvent::EndElement { .. } => {
+                    self.ended = true;
+                    return Ok(Content::End);
+                }
+                XmlEvent::Characters(text) => return Ok(Content::Text(text)),
+                XmlEvent::EndDocument => return Ok(Content::End),
+                _ => (),
+            }
+        }
+    }
+
+    /// Read the text of the current element, through its end.
+    pub fn read_text(&mut self) -> Result<String, DeserializeError> {
+        let mut text = String::new();
+        loop {
+            match self.next_content()? {
+                Content::Text(t) if text.is_empty() => text = t,
+                Content::Text(t) => text.push_str(&t),
+                Content::Start(start) => {
+                    let message = format!("expected text, found element {}", start.name);
+                    return Err(self.error(message));
+                }
+                Content::End => return Ok(text),
+            }
+        }
+    }
+
+    /// Skip the rest of the current element, through its end.
+    pub fn skip(&mut self) -> Result<(), DeserializeError> {
+        let mut depth = 0_usize;
+        loop {
+            match self.next_content()? {
+                Content::Start(_) => depth += 1,
+                Content::End if depth == 0 => return Ok(()),
+                Content::End => depth -= 1,
+                Content::Text(_) => (),
+            }
+        }
+    }
+
+    /// Read the current element as the field `field` of the type of its
+    /// parent, which is a list if `list`, so errors in it have the path they
+    /// would have in JSON.
+    pub fn read_as_field(&mut self, field: &'static str, list: bool) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.field = Some((field, list));
+        }
+    }
+
+    /// An error about the current element. Its path is the path in JSON,
+    /// except for elements that aren't read as fields, which are given by
+    /// name and position among their siblings of the same name.
+    pub fn error<M: Into<String>>(&self, message: M) -> DeserializeError {
+        let path = self
+            .frames
+            .iter()
+            .skip(1)
+            .map(|frame| match frame.field {
+                Some((field, true)) => format!("{}[{}]", json_name(field), frame.index),
+                Some((field, false)) => json_name(field),
+                None => format!("{}[{}]", frame.name, frame.index),
+            })
+            .collect::<Vec<_>>()
+            .join(".");
+        DeserializeError {
+            message: message.into(),
+            position: Some(self.position(self.events.position())),
+            path,
+            value: None,
+        }
+    }
+
+    /// An error about `value`, read from the current element.
+    pub fn value_error<M: Into<String>>(&mut self, message: M, value: &str) -> DeserializeError {
+        if self.warnings.is_some() {
+            self.invalid_value = Some(value.to_string());
+        }
+        DeserializeError {
+            value: Some(value_string(value)),
+            ..self.error(message)
+        }
+    }
+
+    /// Recover from `error` in the element at `depth`, which was being read
+    /// as a field, by skipping the rest of it. Its invalid value is kept, to
+    /// be read with [`take_invalid`](Self::take_invalid), if `keep_invalid`,
+    /// and an invalid entity is left out of the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `error` if the read is strict, and any error skipping the
+    /// element.
+    pub fn recover(
+        &mut self,
+        error: DeserializeError,
+        depth: usize,
+        keep_invalid: bool,
+    ) -> Result<(), DeserializeError> {
+        if self.warnings.is_none() || self.malformed {
+            return Err(error);
+        }
+        let invalid_value = self.invalid_value.take();
+        while !(self.ended && self.frames.len() == depth) {
+            self.next_content()?;
+        }
+        // What was kept for the element, or for those in it, won't be taken.
+        self.kept.retain(|(d, ..)| *d < depth);
+
+        let name = &self.frames[depth - 1].name;
+        let recovery = if depth == 2 && ENTITY_NAMES.contains(&name.as_str()) {
+            Recovery::Skipped
+        } else if let Some(value) = invalid_value.filter(|_| keep_invalid) {
+            self.kept.push((depth - 1, name.clone(), value));
+            Recovery::Preserved
+        } else {
+            Recovery::Defaulted
+        };
+        self.warn(error, recovery);
+        Ok(())
+    }
+
+    /// Recover from the value of the attribute `name` of the current element
+    /// being invalid by leaving it out.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error if the read is strict.
+    pub fn recover_attribute(
+        &mut self,
+        name: &OwnedName,
+        e: ValueError,
+    ) -> Result<(), DeserializeError> {
+        let mut error = self.value_error(e.message, &e.value);
+        self.invalid_value = None;
+        error.path = if error.path.is_empty() {
+            name.local_name.clone()
+        } else {
+            format!("{}.{}", error.path, name.local_name)
+        };
+        if self.warnings.is_none() {
+            return Err(error);
+        }
+        self.warn(error, Recovery::Defaulted);
+        Ok(())
+    }
+
+    /// The invalid value a lenient read kept from the element named `name` in
+    /// the current element, the last one if there was more than one.
+    pub fn take_invalid(&mut self, name: &str) -> Option<String> {
+        let depth = self.frames.len();
+        let mut value = None;
+        self.kept.retain_mut(|(d, n, v)| {
+            let take = *d == depth && n == name;
+            if take {
+                value = Some(std::mem::take(v));
+            }
+            !take
+        });
+        value
+    }
+
+    fn warn(&mut self, error: DeserializeError, recovery: Recovery) {
+        if let Some(warnings) = &mut self.warnings {
+            warnings.push(DeserializeWarning { error, recovery });
+        }
+    }
+
+    fn next_event(&mut self) -> Result<XmlEvent, DeserializeError> {
+        match self.events.next() {
+            Ok(event) => {
+                let row = self.events.position().row;
+                self.events.source_mut().forget_before(row);
+                Ok(event)
+            }
+            Err(e) => {
+                self.malformed = true;
+                Err(DeserializeError {
+                    position: Some(self.position(e.position())),
+                    ..self.error(e.msg())
+                })
+            }
+        }
+    }
+
+    // xml-rs counts lines and columns from 0.
+    fn position(&self, position: TextPosition) -> Position {
+        Position {
+            line: position.row + 1,
+            column: position.column + 1,
+            offset: self.events.source().offset_of(position),
+        }
+    }
+
+    fn open(&mut self, name: &str) {
+        let index = self.frames.last_mut().map_or(0, |parent| {
+            if let Some((_, count)) = parent.children.iter_mut().find(|(n, _)| n == name) {
+                *count += 1;
+                *count - 1
+            } else {
+                parent.children.push((name.to_string(), 1));
+                0
+            }
+        });
+        self.frames.push(Frame {
+            name: name.to_string(),
+            index,
+            field: None,
+            children: vec![],
+        });
+    }
+}
+
+// The name of a field in JSON, from its name in Rust.
+fn json_name(field: &str) -> String {
+    let mut name = String::with_capacity(field.len());
+    let mut upper = false;
+    for c in field.chars() {
+        if c == '_' {
+            upper = true;
+        } else if std::mem::take(&mut upper) {
+            name.push(c.to_ascii_uppercase());
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
@@ -0,0 +1,271 @@
+use std::borrow::Cow;
+
+use xml::namespace::{NS_XML_PREFIX, NS_XMLNS_PREFIX};
+
+/// How a document is written.
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Whether the document starts with an XML declaration.
+    pub write_document_declaration: bool,
+
+    /// Whether elements are put on their own lines and indented.
+    pub perform_indent: bool,
+
+    /// Whether values that can be written more than one way are written in
+    /// their canonical form, such as timestamps in UTC.
+    pub canonical: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            write_document_declaration: true,
+            perform_indent: false,
+            canonical: false,
+        }
+    }
+}
+
+// What was last written in an element, which decides whether the next markup
+// in it goes on a new line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Wrote {
+    Nothing,
+    Markup,
+    Text,
+}
+
+/// Writes an XML document into a string.
+///
+/// Each element declares the namespaces it's given that aren't already in
+/// scope with the same prefix, so the same document always comes out the same
+/// way, and pretty-printing only adds whitespace where an element has no text.
+#[derive(Debug)]
+pub struct XmlWriter {
+    out: String,
+    indent: bool,
+    canonical: bool,
+
+    // The namespaces in scope, and where those of each open element start.
+    namespaces: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    scopes: Vec<usize>,
+
+    // The attributes of the start tag being written, which come after its
+    // namespace declarations.
+    attributes: String,
+    in_start_tag: bool,
+
+    wrote: Vec<Wrote>,
+}
+
+const INDENT: &str = "  ";
+
+impl XmlWriter {
+    pub fn new(config: Config) -> Self {
+        let mut writer = Self {
+            out: String::new(),
+            indent: config.perform_indent,
+            canonical: config.canonical,
+            namespaces: vec![],
+            scopes: vec![],
+            attributes: String::new(),
+            in_start_tag: false,
+            wrote: vec![Wrote::Nothing],
+        };
+        if config.write_document_declaration {
+            writer.before_markup();
+            writer
+                .out
+                .push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+            writer.set_wrote(Wrote::Markup);
+        }
+        writer
+    }
+
+    pub fn into_string(self) -> String {
+        self.out
+    }
+
+    /// Whether values are to be written in their canonical form.
+    pub const fn is_canonical(&self) -> bool {
+        self.canonical
+    }
+
+    /// Start an element named `name`, which may have a prefix.
+    pub fn start(&mut self, name: &str) {
+        self.close_start_tag();
+        self.before_markup();
+        self.wrote.push(Wrote::Nothing);
+        self.out.push('<');
+        self.out.push_str(name);
+        self.scopes.push(self.namespaces.len());
+        self.in_start_tag = true;
+        self.set_wrote(Wrote::Markup);
+    }
+
+    /// Declare a namespace on the element that was just started, unless it's
+    /// already in scope. An empty prefix declares the default namespace.
+    pub fn namespace<P, U>(&mut self, prefix: P, uri: U)
+    where
+        P: Into<Cow<'static, str>>,
+        U: Into<Cow<'static, str>>,
+    {
+        let (prefix, uri) = (prefix.into(), uri.into());
+        if prefix == NS_XML_PREFIX || prefix == NS_XMLNS_PREFIX {
+            return;
+        }
+        // No namespace is the default until another is declared.
+        let bound = self.namespace_uri(&prefix).unwrap_or_default();
+        let scope = self.scopes.last().copied().unwrap_or_default();
+        let declared = self.namespaces[scope..].iter().any(|(p, _)| *p == prefix);
+        if bound == uri || declared {
+            return;
+        }
+        self.namespaces.push((prefix, uri));
+    }
+
+    /// The namespace `prefix` is bound to in the current scope.
+    fn namespace_uri(&self, prefix: &str) -> Option<&str> {
+        self.namespaces
+            .iter()
+            .rev()
+            .find(|(p, _)| p == prefix)
+            .map(|(_, u)| u.as_ref())
+    }
+
+    /// Add an attribute to the element that was just started.
+    pub fn attribute(&mut self, name: &str, value: &str) {
+        self.attributes.push(' ');
+        self.attributes.push_str(name);
+        self.attributes.push_str("=\"");
+        escape_attribute(&mut self.attributes, value);
+        self.attributes.push('"');
+    }
+
+    /// Write text in the current element.
+    pub fn text(&mut self, text: &str) {
+        self.close_start_tag();
+        escape_text(&mut self.out, text);
+        self.set_wrote(Wrote::Text);
+    }
+
+    /// End the current element, which was started as `name`.
+    pub fn end(&mut self, name: &str) {
+        if self.in_start_tag {
+            self.write_start_tag();
+            self.out.push_str(" />");
+        } else {
+            if self.indent && self.wrote() == Wrote::Markup {
+                self.newline(self.scopes.len().saturating_sub(1));
+            }
+            self.out.push_str("</");
+            self.out.push_str(name);
+            self.out.push('>');
+        }
+        if let Some(scope) = self.scopes.pop() {
+            self.namespaces.truncate(scope);
+            self.wrote.pop();
+        }
+        self.set_wrote(Wrote::Markup);
+    }
+
+    fn close_start_tag(&mut self) {
+        if self.in_start_tag {
+            self.write_start_tag();
+            self.out.push('>');
+        }
+    }
+
+    fn write_start_tag(&mut self) {
+        let scope = self.scopes.last().copied().unwrap_or_default();
+        let mut declared: Vec<_> = self.namespaces[scope..].iter().collect();
+        declared.sort_by(|a, b| a.0.cmp(&b.0));
+        for (prefix, uri) in declared {
+            self.out.push_str(" xmlns");
+            if !prefix.is_empty() {
+                self.out.push(':');
+                self.out.push_str(prefix);
+            }
+            self.out.push_str("=\"");
+            escape_attribute(&mut self.out, uri);
+            self.out.push('"');
+        }
+        self.out.push_str(&self.attributes);
+        self.attributes.clear();
+        self.in_start_tag = false;
+    }
+
+    fn wrote(&self) -> Wrote {
+        self.wrote.last().copied().unwrap_or(Wrote::Nothing)
+    }
+
+    fn set_wrote(&mut self, wrote: Wrote) {
+        if let Some(last) = self.wrote.last_mut() {
+            *last = wrote;
+        }
+    }
+
+    // Markup goes on its own line, unless it's the first thing in the document
+    // or follows text.
+    fn before_markup(&mut self) {
+        let level = self.scopes.len();
+        if self.indent
+            && self.wrote() != Wrote::Text
+            && (level > 0 || self.wrote() == Wrote::Markup)
+        {
+            self.newline(level);
+            if level > 0 {
+                self.set_wrote(Wrote::Markup);
+            }
+        }
+    }
+
+    fn newline(&mut self, level: usize) {
+        self.out.push('\n');
+        for _ in 0..level {
+            self.out.push_str(INDENT);
+        }
+    }
+}
+
+fn escape_attribute(out: &mut String, value: &str) {
+    escape(out, value, |b| match b {
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
+        b'"' => Some("&quot;"),
+        b'\'' => Some("&apos;"),
+        b'&' => Some("&amp;"),
+        // Whitespace other than a space would be read back as a space.
+        b'\n' => Some("&#xA;"),
+        b'\r' => Some("&#xD;"),
+        b'\t' => Some("&#x9;"),
+        _ => None,
+    });
+}
+
+fn escape_text(out: &mut String, text: &str) {
+    escape(out, text, |b| match b {
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
+        b'&' => Some("&amp;"),
+        // A carriage return would be read back as a line feed.
+        b'\r' => Some("&#xD;"),
+        _ => None,
+    });
+}
+
+// Only ASCII characters are escaped, and their bytes never appear within
+// other characters, so the string can be searched byte by byte.
+fn escape(out: &mut String, s: &str, escaped: impl Fn(u8) -> Option<&'static str>) {
+    let mut rest = s;
+    while let Some((i, escape)) = rest
+        .bytes()
+        .enumerate()
+        .find_map(|(i, b)| escaped(b).map(|e| (i, e)))
+    {
+        out.push_str(&rest[..i]);
+        out.push_str(escape);
+        rest = &rest[i + 1..];
+    }
+    out.push_str(rest);
+}
@@ -0,0 +1,63 @@
+use gedcomx::atom::Feed;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn search_results_json_and_xml_match() {
+    let json = std::fs::read_to_string("../data/search_results.json").unwrap();
+    let from_json = Feed::from_json_str(&json).unwrap();
+
+    let xml = std::fs::read_to_string("../data/search_results.xml").unwrap();
+    let from_xml = Feed::from_xml_str(&xml).unwrap();
+
+    assert_eq!(from_json, from_xml);
+}
+
+#[test]
+fn search_results_paging() {
+    let json = std::fs::read_to_string("../data/search_results.json").unwrap();
+    let feed = Feed::from_json_str(&json).unwrap();
+
+    assert_eq!(feed.results, Some(42));
+    assert_eq!(feed.index, Some(0));
+    assert_eq!(feed.entries.len(), 2);
+
+    let next = feed.link("next").unwrap();
+    assert_eq!(
+        next.href.as_deref(),
+        Some("https://api.example.org/platform/tree/search?q=surname%3AKunz&offset=2&count=2")
+    );
+    assert_eq!(
+        next.link_type.as_deref(),
+        Some("application/x-gedcomx-atom+json")
+    );
+    assert!(feed.link("previous").is_none());
+}
+
+#[test]
+fn search_results_entries() {
+    let xml = std::fs::read_to_string("../data/search_results.xml").unwrap();
+    let feed = Feed::from_xml_str(&xml).unwrap();
+
+    let entry = &feed.entries[0];
+    assert_eq!(entry.score, Some(4.25));
+    assert_eq!(entry.confidence, Some(5));
+    assert_eq!(entry.links[0].rel.as_deref(), Some("person"));
+
+    let person = &entry.gedcomx().unwrap().persons[0];
+    assert_eq!(person.id, Some("LF7S-JD8".into()));
+    let rels: Vec<_> = person.links.iter().map(|l| l.rel.as_deref()).collect();
+    assert_eq!(rels, vec![Some("self"), Some("ancestry")]);
+    assert_eq!(person.links[1].title.as_deref(), Some("Ancestry"));
+}
+
+#[test]
+fn search_results_roundtrip() {
+    let json = std::fs::read_to_string("../data/search_results.json").unwrap();
+    let feed = Feed::from_json_str(&json).unwrap();
+
+    let from_json = Feed::from_json_str(&feed.to_json_string().unwrap()).unwrap();
+    assert_eq!(from_json, feed);
+
+    let from_xml = Feed::from_xml_str(&feed.to_xml_string().unwrap()).unwrap();
+    assert_eq!(from_xml, feed);
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "familysearch")]
+
+use gedcomx::{
+    Gedcomx,
+    familysearch::{ChangeInfo, ChangeOperation, DisplayProperties, Feature},
+};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn current_person_json() {
+    let json = std::fs::read_to_string("../data/current_person_fs_extensions.json").unwrap();
+    let gx = Gedcomx::from_json_str(&json).unwrap();
+
+    let person = &gx.persons[0];
+    assert_eq!(person.living().unwrap(), Some(true));
+    assert_eq!(
+        person.display().unwrap(),
+        Some(
+            DisplayProperties::builder()
+                .name("Ephraim Howard Kunz")
+                .gender("Male")
+                .lifespan("1993-Living")
+                .birth_date("1993")
+                .birth_place("Boise, Ada, Idaho, United States")
+                .build()
+        )
+    );
+    assert!(person.discussion_references().unwrap().is_empty());
+}
+
+#[test]
+fn parents_xml() {
+    let xml = std::fs::read_to_string("../data/parents_fs_extensions.xml").unwrap();
+    let gx = Gedcomx::from_xml_str(&xml).unwrap();
+
+    let father = &gx.persons[0];
+    assert_eq!(father.living().unwrap(), Some(false));
+    assert_eq!(
+        father.display().unwrap(),
+        Some(
+            DisplayProperties::builder()
+                .name("Howard William Kunz")
+                .lifespan("1967-2011")
+                .build()
+        )
+    );
+
+    let mother = &gx.persons[1];
+    assert_eq!(mother.living().unwrap(), None);
+    assert_eq!(mother.display().unwrap(), None);
+}
+
+#[test]
+fn modify_and_roundtrip() {
+    let xml = std::fs::read_to_string("../data/parents_fs_extensions.xml").unwrap();
+    let mut gx = Gedcomx::from_xml_str(&xml).unwrap();
+
+    let display = DisplayProperties::builder()
+        .name("Tricia Joy Stockett")
+        .ascendancy_number("3")
+        .build();
+    gx.persons[1].set_display(Some(&display)).unwrap();
+    gx.persons[1].set_living(Some(true));
+
+    let change = ChangeInfo::builder(ChangeOperation::Create)
+        .reason("Married")
+        .build();
+    gx.relationships[0]
+        .set_change_info(std::slice::from_ref(&change))
+        .unwrap();
+
+    let feature = Feature::new("discussions", None, Some(true), None);
+    gx.set_features(std::slice::from_ref(&feature)).unwrap();
+
+    for gx in [
+        Gedcomx::from_xml_str(&gx.to_xml_string().unwrap()).unwrap(),
+        Gedcomx::from_json_str(&gx.to_json_string().unwrap()).unwrap(),
+    ] {
+        assert_eq!(gx.persons[1].display().unwrap(), Some(display.clone()));
+        assert_eq!(gx.persons[1].living().unwrap(), Some(true));
+        assert_eq!(
+            gx.relationships[0].change_info().unwrap(),
+            vec![change.clone()]
+        );
+        assert_eq!(gx.features().unwrap(), vec![feature.clone()]);
+    }
+}
@@ -9,3 +9,13 @@ fn test_current_person() {
 fn test_parents() {
     common::assert_roundtrip_xml("parents_fs");
 }
+
+#[test]
+fn test_current_person_extensions() {
+    common::assert_roundtrip_json("current_person_fs_extensions");
+}
+
+#[test]
+fn test_parents_extensions() {
+    common::assert_roundtrip_xml("parents_fs_extensions");
+}
@@ -0,0 +1,193 @@
+use std::io::{self, Cursor};
+
+use gedcomx::Gedcomx;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::{GedcomxFile, GedcomxFileError, GedcomxFileWriter, GedcomxFormat};
+
+impl GedcomxFile<Cursor<Vec<u8>>> {
+    /// Read a GedcomxFile from an async IO stream. Since the directory of a
+    /// zip archive is at its end, the whole stream is read into memory first.
+    /// The directory is then read on tokio's blocking thread pool, so this
+    /// must be called from within a tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::IoError`] if the stream can't be read,
+    /// or any of the errors of [`from_reader`](Self::from_reader).
+    pub async fn from_async_reader<A: AsyncRead + Unpin>(
+        mut reader: A,
+    ) -> Result<Self, GedcomxFileError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        match tokio::task::spawn_blocking(|| Self::from_reader(Cursor::new(bytes))).await {
+            Ok(result) => result,
+            Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+            Err(e) => Err(GedcomxFileError::IoError(io::Error::other(e))),
+        }
+    }
+}
+
+/// Writes a GEDCOM X file to an async IO stream.
+///
+/// The file is built in memory, since writing a zip archive needs to seek,
+/// and written to the stream by [`finish`](Self::finish).
+///
+/// # Examples
+/// ```
+/// use gedcomx::Gedcomx;
+/// use gedcomx_file::{AsyncGedcomxFileWriter, GedcomxFile, GedcomxFormat};
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut writer = AsyncGedcomxFileWriter::new(vec![]);
+/// writer
+///     .add_gedcomx("tree.xml", &Gedcomx::default(), GedcomxFormat::Xml, &[])
+///     .unwrap();
+/// writer
+///     .add_resource("person1.png", "image/png", &[], &[0x89, b'P', b'N', b'G'][..])
+///     .await
+///     .unwrap();
+/// let bytes = writer.finish().await.unwrap();
+///
+/// let file = GedcomxFile::from_async_reader(bytes.as_slice()).await.unwrap();
+/// assert_eq!(file.len(), 3);
+/// # });
+/// ```
+pub struct AsyncGedcomxFileWriter<W: AsyncWrite + Unpin> {
+    writer: W,
+    inner: GedcomxFileWriter<Cursor<Vec<u8>>>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncGedcomxFileWriter<W> {
+    /// Create a writer that writes a GEDCOM X file to `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            inner: GedcomxFileWriter::new(Cursor::new(vec![])),
+        }
+    }
+
+    /// Set the `Created-By` attribute of the manifest. See
+    /// [`GedcomxFileWriter::created_by`].
+    pub fn created_by<I: Into<String>>(&mut self, created_by: I) -> &mut Self {
+        self.inner.created_by(created_by);
+        self
+    }
+
+    /// Add a GEDCOM X document to the file. See
+    /// [`GedcomxFileWriter::add_gedcomx`].
+    ///
+    /// # Errors
+    ///
+    /// The same as [`GedcomxFileWriter::add_gedcomx`].
+    pub fn add_gedcomx(
+        &mut self,
+        name: &str,
+        gx: &Gedcomx,
+        format: GedcomxFormat,
+        attributes: &[(&str, &str)],
+    ) -> Result<(), GedcomxFileError> {
+        self.inner.add_gedcomx(name, gx, format, attributes)
+    }
+
+    /// Add a resource read from an async IO stream to the file. See
+    /// [`GedcomxFileWriter::add_resource`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::IoError`] if `reader` can't be read,
+    /// or any of the errors of [`GedcomxFileWriter::add_resource`].
+    pub async fn add_resource<A: AsyncRead + Unpin>(
+        &mut self,
+        name: &str,
+        content_type: &str,
+        attributes: &[(&str, &str)],
+        mut reader: A,
+    ) -> Result<(), GedcomxFileError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).await?;
+        self.inner
+            .add_resource(name, content_type, attributes, bytes.as_slice())
+    }
+
+    /// Write the manifest and finish the file, writing it to the underlying
+    /// writer and returning it.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::ZipError`] or
+    /// [`GedcomxFileError::IoError`] if writing fails.
+    pub async fn finish(mut self) -> Result<W, GedcomxFileError> {
+        let bytes = self.inner.finish()?.into_inner();
+        self.writer.write_all(&bytes).await?;
+        self.writer.flush().await?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gedcomx::Person;
+
+    use super::*;
+    use crate::GedcomxFileEntry;
+
+    #[tokio::test]
+    async fn read_sample() {
+        let bytes = std::fs::read("data/sample.gedx").unwrap();
+        let mut file = GedcomxFile::from_async_reader(bytes.as_slice())
+            .await
+            .unwrap();
+        assert_eq!(file.len(), 4);
+        assert!(matches!(
+            file.by_name("tree.xml").unwrap(),
+            GedcomxFileEntry::Gedcomx(_)
+        ));
+
+        assert!(matches!(
+            GedcomxFile::from_async_reader(&b"not a zip"[..]).await,
+            Err(GedcomxFileError::ZipError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn write_and_read() {
+        let gx = Gedcomx::builder()
+            .person(Person::builder().id("P-1").build())
+            .build();
+
+        let mut writer = AsyncGedcomxFileWriter::new(vec![]);
+        writer.created_by("test");
+        writer
+            .add_gedcomx("tree.json", &gx, GedcomxFormat::Json, &[])
+            .unwrap();
+        writer
+            .add_resource("notes.txt", "text/plain", &[], &b"notes"[..])
+            .await
+            .unwrap();
+        assert!(matches!(
+            writer
+                .add_resource("notes.txt", "text/plain", &[], &b"notes"[..])
+                .await,
+            Err(GedcomxFileError::DuplicateResource(_))
+        ));
+        let bytes = writer.finish().await.unwrap();
+
+        let mut file = GedcomxFile::from_async_reader(bytes.as_slice())
+            .await
+            .unwrap();
+        assert!(file.check_integrity().unwrap().is_empty());
+        assert_eq!(
+            file.manifest().unwrap().main_attributes().get("Created-By"),
+            Some("test")
+        );
+        match file.by_name("tree.json").unwrap() {
+            GedcomxFileEntry::Gedcomx(read) => assert_eq!(*read, gx),
+            _ => panic!("tree.json isn't a GEDCOM X document"),
+        }
+        assert!(matches!(
+            file.by_name("notes.txt").unwrap(),
+            GedcomxFileEntry::Text(text) if text == "notes"
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+
+/// A way in which a [`GedcomxFile`](crate::GedcomxFile) doesn't conform to
+/// the
+/// [GEDCOM X File Format](https://github.com/FamilySearch/gedcomx/blob/master/specifications/file-format-specification.md),
+/// found by [`GedcomxFile::check`](crate::GedcomxFile::check).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConformanceIssue {
+    /// The file has no manifest.
+    MissingManifest,
+
+    /// The manifest can't be read.
+    InvalidManifest,
+
+    /// The main section of the manifest has no `Manifest-Version`.
+    MissingManifestVersion,
+
+    /// The manifest has more than one section for the named resource.
+    DuplicateSection(String),
+
+    /// The manifest has a section for the named resource, but the file
+    /// doesn't contain it.
+    MissingEntry(String),
+
+    /// The file contains the named resource, but the manifest has no section
+    /// for it.
+    MissingSection(String),
+
+    /// The content of the named resource doesn't have the format of the
+    /// `Content-Type` the manifest gives it.
+    ContentTypeMismatch {
+        /// The name of the resource.
+        name: String,
+
+        /// The `Content-Type` of the resource in the manifest.
+        content_type: String,
+    },
+
+    /// The name of a resource isn't a relative path within the file, such as
+    /// `../evil.sh` or `/etc/passwd`, so extracting it could write outside of
+    /// the destination directory.
+    UnsafePath(String),
+
+    /// The file contains no GEDCOM X document.
+    MissingGedcomx,
+
+    /// The named main GEDCOM X document of the file can't be parsed.
+    InvalidGedcomx(String),
+}
+
+impl fmt::Display for ConformanceIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingManifest => write!(f, "no manifest in gedcomx file"),
+            Self::InvalidManifest => write!(f, "invalid manifest"),
+            Self::MissingManifestVersion => write!(f, "manifest has no Manifest-Version"),
+            Self::DuplicateSection(name) => {
+                write!(f, "manifest has more than one section for {name}")
+            }
+            Self::MissingEntry(name) => write!(f, "{name} is in the manifest but not the file"),
+            Self::MissingSection(name) => write!(f, "{name} is in the file but not the manifest"),
+            Self::ContentTypeMismatch { name, content_type } => {
+                write!(f, "{name} doesn't have content type {content_type}")
+            }
+            Self::UnsafePath(name) => write!(f, "unsafe resource name: {name}"),
+            Self::MissingGedcomx => write!(f, "no gedcomx document in gedcomx file"),
+            Self::InvalidGedcomx(name) => write!(f, "invalid gedcomx document: {name}"),
+        }
+    }
+}
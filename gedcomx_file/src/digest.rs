@@ -0,0 +1,190 @@
+use std::io::{self, Read, Write};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use sha2::{Digest, Sha256};
+
+use crate::{GedcomxManifest, manifest::raw_sections};
+
+/// The manifest attribute holding the base64-encoded SHA-256 digest of a
+/// resource.
+pub const DIGEST_ATTRIBUTE: &str = "SHA-256-Digest";
+
+// Attributes of a signature file holding digests of the manifest.
+const MANIFEST_DIGEST_ATTRIBUTE: &str = "SHA-256-Digest-Manifest";
+const MAIN_ATTRIBUTES_DIGEST_ATTRIBUTE: &str = "SHA-256-Digest-Manifest-Main-Attributes";
+
+/// A problem found by [`GedcomxFile::check_integrity`](crate::GedcomxFile::check_integrity).
+///
+/// These show that a file was damaged, or changed without its manifest being
+/// updated. None of them say who made a file: anyone who changes it can
+/// compute its digests again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityIssue {
+    /// The contents of the named resource don't match its digest in the
+    /// manifest, so it was changed after the digest was computed.
+    DigestMismatch(String),
+
+    /// The manifest has a digest for the named resource, but the file doesn't
+    /// contain it.
+    MissingEntry(String),
+
+    /// The file contains the named resource, but the manifest has no
+    /// `SHA-256-Digest` for it.
+    MissingDigest(String),
+
+    /// The digests in the named signature file don't match the manifest, so
+    /// the manifest was changed after the signature file was written. The
+    /// signature file itself isn't authenticated.
+    StaleSignatureFile(String),
+}
+
+/// The base64-encoded SHA-256 digest of everything read from `reader`.
+pub(crate) fn digest<R: Read>(mut reader: R) -> io::Result<String> {
+    let mut writer = DigestWriter::new(io::sink());
+    io::copy(&mut reader, &mut writer)?;
+    Ok(writer.finish())
+}
+
+/// Whether the digests in a signature file match the manifest. The manifest
+/// matches if its digest does, or failing that, if the digests of its main
+/// attributes and of every section listed in the signature file do. Only the
+/// digests are compared; the signature block signing the signature file
+/// isn't checked.
+pub(crate) fn signature_file_matches(signature: &GedcomxManifest, manifest: &str) -> bool {
+    let main = signature.main_attributes();
+    if main.get(MANIFEST_DIGEST_ATTRIBUTE) == Some(digest_bytes(manifest.as_bytes()).as_str()) {
+        return true;
+    }
+
+    let sections = raw_sections(manifest);
+    if let Some(expected) = main.get(MAIN_ATTRIBUTES_DIGEST_ATTRIBUTE) {
+        match sections.first() {
+            Some((_, raw)) if digest_bytes(raw.as_bytes()) == expected => (),
+            _ => return false,
+        }
+    }
+
+    let mut checked = 0;
+    for (name, section) in signature.sections() {
+        let Some(expected) = section.get(DIGEST_ATTRIBUTE) else {
+            continue;
+        };
+        match sections.iter().find(|(n, _)| n.as_deref() == Some(name)) {
+            Some((_, raw)) if digest_bytes(raw.as_bytes()) == expected => checked += 1,
+            _ => return false,
+        }
+    }
+    checked > 0
+}
+
+fn digest_bytes(bytes: &[u8]) -> String {
+    STANDARD.encode(Sha256::digest(bytes))
+}
+
+/// Passes writes through to the inner writer, computing the digest of
+/// everything written.
+pub(crate) struct DigestWriter<W> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W: Write> DigestWriter<W> {
+    pub(crate) fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// The base64-encoded digest of everything written.
+    pub(crate) fn finish(self) -> String {
+        STANDARD.encode(self.hasher.finalize())
+    }
+}
+
+impl<W: Write> Write for DigestWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = "Manifest-Version: 1.0\r\n\r\n\
+                            Name: tree.json\r\nSHA-256-Digest: a\r\n\r\n\
+                            Name: person1.png\r\nSHA-256-Digest: b\r\n\r\n";
+
+    #[test]
+    fn digest_of_empty_input() {
+        assert_eq!(
+            digest(&b""[..]).unwrap(),
+            "47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="
+        );
+    }
+
+    #[test]
+    fn signature_with_manifest_digest() {
+        let mut signature = GedcomxManifest::new();
+        signature
+            .main_attributes_mut()
+            .set(MANIFEST_DIGEST_ATTRIBUTE, digest_bytes(MANIFEST.as_bytes()))
+            .unwrap();
+        assert!(signature_file_matches(&signature, MANIFEST));
+        assert!(!signature_file_matches(
+            &signature,
+            &MANIFEST.replace('b', "c")
+        ));
+    }
+
+    #[test]
+    fn signature_with_section_digests() {
+        let mut signature = GedcomxManifest::new();
+        signature
+            .main_attributes_mut()
+            .set(MANIFEST_DIGEST_ATTRIBUTE, "stale")
+            .unwrap();
+        signature
+            .main_attributes_mut()
+            .set(
+                MAIN_ATTRIBUTES_DIGEST_ATTRIBUTE,
+                digest_bytes(b"Manifest-Version: 1.0\r\n\r\n"),
+            )
+            .unwrap();
+        signature
+            .add_section("tree.json")
+            .unwrap()
+            .set(
+                DIGEST_ATTRIBUTE,
+                digest_bytes(b"Name: tree.json\r\nSHA-256-Digest: a\r\n\r\n"),
+            )
+            .unwrap();
+
+        // Sections that aren't signed can change.
+        assert!(signature_file_matches(&signature, MANIFEST));
+        assert!(signature_file_matches(
+            &signature,
+            &MANIFEST.replace("Digest: b", "Digest: c")
+        ));
+        assert!(!signature_file_matches(
+            &signature,
+            &MANIFEST.replace("Digest: a", "Digest: c")
+        ));
+        assert!(!signature_file_matches(
+            &signature,
+            &MANIFEST.replace("Version: 1.0", "Version: 1.1")
+        ));
+    }
+
+    #[test]
+    fn signature_without_digests() {
+        assert!(!signature_file_matches(&GedcomxManifest::new(), MANIFEST));
+    }
+}
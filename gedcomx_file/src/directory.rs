@@ -0,0 +1,359 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, Read, Seek, Write},
+    path::{Path, PathBuf},
+};
+
+use zip::{ZipWriter, result::ZipError, write::SimpleFileOptions};
+
+use crate::{
+    GedcomxFile, GedcomxFileEntry, GedcomxFileError, GedcomxManifest, MANIFEST_STR,
+    entry_from_reader, writer::is_valid_resource_name,
+};
+
+/// A GEDCOM X file that has been extracted into a directory, such as for
+/// keeping it under version control.
+///
+/// Each resource of the file is a file under the directory, named by its path
+/// relative to the directory, so the manifest is `META-INF/MANIFEST.MF`.
+/// Hidden files and directories, whose names start with `.`, such as `.git`,
+/// aren't resources. It
+/// has the same methods for reading resources as a [`GedcomxFile`], and can be
+/// converted to and from one with [`pack`](Self::pack) and
+/// [`GedcomxFile::unpack`].
+///
+/// # Examples
+/// ```no_run
+/// use std::fs::File;
+///
+/// use gedcomx_file::{GedcomxDirectory, GedcomxFile};
+///
+/// let mut file = GedcomxFile::from_reader(File::open("tree.gedx").unwrap()).unwrap();
+/// let directory = file.unpack("tree").unwrap();
+///
+/// // Edit the files under `tree`...
+///
+/// let directory = GedcomxDirectory::open("tree").unwrap();
+/// directory.pack(File::create("tree.gedx").unwrap()).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct GedcomxDirectory {
+    root: PathBuf,
+    names: Vec<String>,
+
+    // The `Content-Type`s of the resources in the manifest.
+    content_types: HashMap<String, String>,
+}
+
+impl GedcomxDirectory {
+    /// Open the GEDCOM X file extracted into the directory `root`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::IoError`] if the directory can't be
+    /// read, or [`GedcomxFileError::InvalidResourceName`] if the name of a
+    /// file under it isn't valid UTF-8.
+    pub fn open<P: AsRef<Path>>(root: P) -> Result<Self, GedcomxFileError> {
+        let root = root.as_ref().to_path_buf();
+        let mut names = vec![];
+        collect_names(&root, "", &mut names)?;
+        names.sort();
+
+        let mut directory = Self {
+            root,
+            names,
+            content_types: HashMap::new(),
+        };
+
+        // A missing or invalid manifest is reported by `manifest`, not here.
+        if let Ok(manifest) = directory.manifest() {
+            directory.content_types = manifest
+                .sections()
+                .filter_map(|(name, section)| {
+                    Some((name.to_string(), section.get("Content-Type")?.to_string()))
+                })
+                .collect();
+        }
+        Ok(directory)
+    }
+
+    /// The directory the file was extracted into.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Number of files contained in this GedcomxDirectory.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether this GedcomxDirectory contains no files.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Get the names of the files in this GedcomxDirectory, in order. These
+    /// can be used as arguments for `by_name`.
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Get the resource entries by index in this GEDCOM X directory.
+    pub fn by_index(
+        &self,
+        file_number: usize,
+    ) -> Result<GedcomxFileEntry<impl Read + use<>>, GedcomxFileError> {
+        let name = self
+            .names
+            .get(file_number)
+            .ok_or(GedcomxFileError::ZipError(ZipError::FileNotFound))?;
+        self.by_name(name)
+    }
+
+    /// Get the resource entries by name in this GEDCOM X directory.
+    pub fn by_name(
+        &self,
+        name: &str,
+    ) -> Result<GedcomxFileEntry<impl Read + use<>>, GedcomxFileError> {
+        if !self.names.iter().any(|n| n == name) {
+            return Err(GedcomxFileError::ZipError(ZipError::FileNotFound));
+        }
+        let file = File::open(self.root.join(name))?;
+        entry_from_reader(name, file, self.content_types.get(name).cloned())
+    }
+
+    /// Get the manifest, or return an error if it's missing or unreadable.
+    pub fn manifest(&self) -> Result<GedcomxManifest, GedcomxFileError> {
+        match self.by_name(MANIFEST_STR)? {
+            GedcomxFileEntry::Manifest(m) => Ok(m),
+            _ => Err(GedcomxFileError::MissingManifest),
+        }
+    }
+
+    /// Get the attributes for a resource specified by name. Use
+    /// `GEDCOMX_RESOURCE_NAME` to get the attributes for the GEDCOM X file
+    /// itself.
+    pub fn attributes_by_name(
+        &self,
+        name: &str,
+    ) -> Result<HashMap<String, String>, GedcomxFileError> {
+        let manifest = self.manifest()?;
+        manifest
+            .attributes_by_name(name)
+            .ok_or(GedcomxFileError::ZipError(ZipError::FileNotFound))
+    }
+
+    /// Get the attributes for a resource specified by index.
+    pub fn attributes_by_index(
+        &self,
+        file_number: usize,
+    ) -> Result<HashMap<String, String>, GedcomxFileError> {
+        let name = self
+            .names
+            .get(file_number)
+            .ok_or(GedcomxFileError::ZipError(ZipError::FileNotFound))?;
+        self.attributes_by_name(name)
+    }
+
+    /// Pack the directory into a GEDCOM X file written to `writer`, returning
+    /// the writer. The files are copied as they are, manifest first, so
+    /// digests in the manifest still match.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::ZipError`] or
+    /// [`GedcomxFileError::IoError`] if reading or writing fails.
+    pub fn pack<W: Write + Seek>(&self, writer: W) -> Result<W, GedcomxFileError> {
+        let mut zip = ZipWriter::new(writer);
+        let manifest = self.names.iter().filter(|n| *n == MANIFEST_STR);
+        let others = self.names.iter().filter(|n| *n != MANIFEST_STR);
+        for name in manifest.chain(others) {
+            zip.start_file(name, SimpleFileOptions::default())?;
+            io::copy(&mut File::open(self.root.join(name))?, &mut zip)?;
+        }
+        Ok(zip.finish()?)
+    }
+}
+
+impl<R: Read + Seek> GedcomxFile<R> {
+    /// Extract the file into the directory `root`, creating it if needed, and
+    /// open it as a [`GedcomxDirectory`]. Files already in the directory with
+    /// the same name as a resource are overwritten.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::InvalidResourceName`] if the name of a
+    /// resource isn't a relative path within the file, so that extracting it
+    /// could write outside of `root`, or [`GedcomxFileError::ZipError`] or
+    /// [`GedcomxFileError::IoError`] if reading or writing fails.
+    pub fn unpack<P: AsRef<Path>>(
+        &mut self,
+        root: P,
+    ) -> Result<GedcomxDirectory, GedcomxFileError> {
+        let root = root.as_ref();
+        let unsafe_name = self
+            .inner
+            .file_names()
+            .find(|n| !n.ends_with('/') && *n != MANIFEST_STR && !is_valid_resource_name(n));
+        if let Some(name) = unsafe_name {
+            return Err(GedcomxFileError::InvalidResourceName(name.to_string()));
+        }
+        fs::create_dir_all(root)?;
+
+        for index in 0..self.inner.len() {
+            let mut entry = self.inner.by_index(index)?;
+            if entry.is_dir() {
+                continue;
+            }
+
+            let path = root.join(entry.name());
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            io::copy(&mut entry, &mut File::create(path)?)?;
+        }
+
+        GedcomxDirectory::open(root)
+    }
+}
+
+// Collect the names of the files under `directory`, relative to the root of
+// the GedcomxDirectory, which `prefix` is the name of `directory` in. Hidden
+// files and directories are skipped.
+fn collect_names(
+    directory: &Path,
+    prefix: &str,
+    names: &mut Vec<String>,
+) -> Result<(), GedcomxFileError> {
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        if file_name.as_encoded_bytes().starts_with(b".") {
+            continue;
+        }
+        let file_name = file_name.to_str().ok_or_else(|| {
+            GedcomxFileError::InvalidResourceName(file_name.to_string_lossy().into_owned())
+        })?;
+        let name = format!("{prefix}{file_name}");
+
+        if entry.file_type()?.is_dir() {
+            collect_names(&entry.path(), &format!("{name}/"), names)?;
+        } else {
+            names.push(name);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::File, io::Cursor};
+
+    use super::*;
+
+    // An empty directory for a test, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("gedcomx_file-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn read_all<A: Read>(mut reader: A) -> Vec<u8> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn unpack_and_pack() {
+        let dir = TempDir::new("unpack_and_pack");
+        let mut file = GedcomxFile::from_reader(File::open("data/sample.gedx").unwrap()).unwrap();
+        let directory = file.unpack(&dir.0).unwrap();
+
+        assert_eq!(
+            directory.file_names().collect::<Vec<_>>(),
+            [MANIFEST_STR, "person1.png", "person2.png", "tree.xml"]
+        );
+        assert!(dir.0.join("META-INF/MANIFEST.MF").is_file());
+        assert_eq!(directory.manifest().unwrap(), file.manifest().unwrap());
+        assert_eq!(
+            directory.attributes_by_name("person1.png").unwrap(),
+            file.attributes_by_name("person1.png").unwrap()
+        );
+        match (
+            directory.by_name("tree.xml").unwrap(),
+            file.by_name("tree.xml").unwrap(),
+        ) {
+            (GedcomxFileEntry::Gedcomx(a), GedcomxFileEntry::Gedcomx(b)) => assert_eq!(a, b),
+            _ => panic!("tree.xml isn't a GEDCOM X document"),
+        }
+        assert!(matches!(
+            directory.by_name("missing.png"),
+            Err(GedcomxFileError::ZipError(ZipError::FileNotFound))
+        ));
+
+        let mut packed =
+            GedcomxFile::from_reader(directory.pack(Cursor::new(vec![])).unwrap()).unwrap();
+        assert_eq!(packed.len(), 4);
+        assert_eq!(packed.file_names().next(), Some(MANIFEST_STR));
+        for name in ["person1.png", "person2.png", "tree.xml", MANIFEST_STR] {
+            assert_eq!(
+                read_all(packed.inner.by_name(name).unwrap()),
+                read_all(file.inner.by_name(name).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn unpack_rejects_unsafe_names() {
+        let dir = TempDir::new("unpack_rejects_unsafe_names");
+        let mut writer = ZipWriter::new(Cursor::new(vec![]));
+        writer
+            .start_file("../evil.sh", SimpleFileOptions::default())
+            .unwrap();
+        let mut file = GedcomxFile::from_reader(writer.finish().unwrap()).unwrap();
+
+        assert!(matches!(
+            file.unpack(&dir.0),
+            Err(GedcomxFileError::InvalidResourceName(_))
+        ));
+        assert!(!dir.0.exists());
+    }
+
+    #[test]
+    fn pack_skips_hidden_files() {
+        let dir = TempDir::new("pack_skips_hidden_files");
+        let mut file = GedcomxFile::from_reader(File::open("data/sample.gedx").unwrap()).unwrap();
+        file.unpack(&dir.0).unwrap();
+        fs::create_dir_all(dir.0.join(".git/objects")).unwrap();
+        fs::write(dir.0.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+        fs::write(dir.0.join(".git/objects/ab"), "").unwrap();
+        fs::write(dir.0.join(".DS_Store"), "").unwrap();
+        fs::write(dir.0.join("META-INF/.tree.xml.swp"), "").unwrap();
+
+        let directory = GedcomxDirectory::open(&dir.0).unwrap();
+        assert_eq!(
+            directory.file_names().collect::<Vec<_>>(),
+            [MANIFEST_STR, "person1.png", "person2.png", "tree.xml"]
+        );
+
+        let packed =
+            GedcomxFile::from_reader(directory.pack(Cursor::new(vec![])).unwrap()).unwrap();
+        assert_eq!(
+            packed.file_names().collect::<Vec<_>>(),
+            [MANIFEST_STR, "person1.png", "person2.png", "tree.xml"]
+        );
+    }
+}
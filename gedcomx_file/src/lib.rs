@@ -1,14 +1,47 @@
+use gedcomx::{Gedcomx, SourceReference, Uri};
 use std::{
     collections::HashMap,
     fmt::Debug,
-    io::{self, BufRead, BufReader, Read},
-    path::Path,
+    io::{self, Read},
 };
 use thiserror::Error;
-use zip::{read::ZipFile, result::ZipError};
+use zip::result::ZipError;
+
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "async")]
+pub use asynchronous::AsyncGedcomxFileWriter;
+
+mod check;
+pub use check::ConformanceIssue;
+
+mod directory;
+pub use directory::GedcomxDirectory;
+
+mod digest;
+pub use digest::{DIGEST_ATTRIBUTE, IntegrityIssue};
+
+mod manifest;
+pub use manifest::{GedcomxManifest, ManifestSection};
+
+mod merge;
+pub use merge::{EntityOrigin, MergedGedcomx};
+
+mod resolve;
+pub use resolve::ResolvedResource;
+
+mod sniff;
+
+mod writer;
+pub use writer::{GedcomxFileWriter, GedcomxFormat};
 
 /// Types of entries in a GedcomxFile.
+///
+/// The type of an entry comes from its `Content-Type` in the manifest, or if
+/// it has none, from the extension of its name, or failing that, from its
+/// content.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum GedcomxFileEntry<R: Read> {
     /// A JSON or XML document in GEDCOM X format that has been deserialized.
     Gedcomx(Box<gedcomx::Gedcomx>),
@@ -16,24 +49,70 @@ pub enum GedcomxFileEntry<R: Read> {
     /// A GedcomxFile manifest.
     Manifest(GedcomxManifest),
 
+    /// A PNG, JPEG or GIF image whose content matches its type.
+    Image(ImageEntry<R>),
+
+    /// A plain text file that has been read.
+    Text(String),
+
+    /// A PDF document, returned as a type implementing Read.
+    Pdf(R),
+
     /// Any other filetypes are returned as a type implementing Read.
     Reader(R),
 }
 
+/// An image in a GedcomxFile.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ImageEntry<R: Read> {
+    /// The media type of the image, such as `image/png`.
+    pub media_type: String,
+
+    /// The width of the image in pixels.
+    pub width: u32,
+
+    /// The height of the image in pixels.
+    pub height: u32,
+
+    /// The contents of the image.
+    pub reader: R,
+}
+
 const MANIFEST_STR: &str = "META-INF/MANIFEST.MF";
+
+// How much of an image to read looking for its dimensions.
+const MAX_IMAGE_HEADER_LEN: usize = 1 << 20;
 pub const GEDCOMX_RESOURCE_NAME: &str = "main";
 
 /// A file containing a bundle of genealogical resources.
 #[derive(Debug)]
 pub struct GedcomxFile<R> {
     inner: zip::ZipArchive<R>,
+
+    // The `Content-Type`s of the resources in the manifest.
+    content_types: HashMap<String, String>,
 }
 
 impl<R: io::Read + io::Seek> GedcomxFile<R> {
     /// Create from a reader. This is usually a std::fs::File.
     pub fn from_reader(reader: R) -> Result<Self, GedcomxFileError> {
         let zip = zip::ZipArchive::new(reader)?;
-        Ok(Self { inner: zip })
+        let mut file = Self {
+            inner: zip,
+            content_types: HashMap::new(),
+        };
+
+        // A missing or invalid manifest is reported by `manifest`, not here.
+        if let Ok(manifest) = file.manifest() {
+            file.content_types = manifest
+                .sections()
+                .filter_map(|(name, section)| {
+                    Some((name.to_string(), section.get("Content-Type")?.to_string()))
+                })
+                .collect();
+        }
+        Ok(file)
     }
 
     /// Number of files contained in this GedcomxFile.
@@ -51,8 +130,14 @@ impl<R: io::Read + io::Seek> GedcomxFile<R> {
         &mut self,
         file_number: usize,
     ) -> Result<GedcomxFileEntry<impl Read + '_>, GedcomxFileError> {
+        let content_type = self
+            .inner
+            .name_for_index(file_number)
+            .and_then(|name| self.content_types.get(name))
+            .cloned();
         let entry = self.inner.by_index(file_number)?;
-        Self::file_entry_from_entry(entry)
+        let name = entry.name().to_string();
+        entry_from_reader(&name, entry, content_type)
     }
 
     /// Get the names of the files in this GedcomxFile. These can be used as arguments for `by_name`.
@@ -64,36 +149,10 @@ impl<R: io::Read + io::Seek> GedcomxFile<R> {
     pub fn by_name(
         &mut self,
         name: &str,
-    ) -> Result<GedcomxFileEntry<impl Read + '_>, GedcomxFileError> {
+    ) -> Result<GedcomxFileEntry<impl Read + use<'_, R>>, GedcomxFileError> {
+        let content_type = self.content_types.get(name).cloned();
         let entry = self.inner.by_name(name)?;
-        Self::file_entry_from_entry(entry)
-    }
-
-    fn file_entry_from_entry<A: Read>(
-        mut entry: ZipFile<'_, A>,
-    ) -> Result<GedcomxFileEntry<impl Read + '_>, GedcomxFileError> {
-        if entry.enclosed_name() == Some(Path::new(MANIFEST_STR).to_path_buf()) {
-            return Ok(GedcomxFileEntry::Manifest(GedcomxManifest::from_reader(
-                entry,
-            )?));
-        }
-
-        match entry
-            .enclosed_name()
-            .as_ref()
-            .and_then(|n| n.extension())
-            .and_then(|e| e.to_str())
-        {
-            Some("json") => match gedcomx::Gedcomx::from_json_reader(&mut entry) {
-                Ok(gx) => Ok(GedcomxFileEntry::Gedcomx(Box::new(gx))),
-                Err(e) => Err(GedcomxFileError::GedcomxError(e)),
-            },
-            Some("xml") => match gedcomx::Gedcomx::from_xml_reader(&mut entry) {
-                Ok(gx) => Ok(GedcomxFileEntry::Gedcomx(Box::new(gx))),
-                Err(e) => Err(GedcomxFileError::GedcomxError(e)),
-            },
-            _ => Ok(GedcomxFileEntry::Reader(entry)),
-        }
+        entry_from_reader(name, entry, content_type)
     }
 
     /// Get the manifest, or return an error if it's missing or unreadable.
@@ -115,6 +174,337 @@ impl<R: io::Read + io::Seek> GedcomxFile<R> {
             .ok_or(GedcomxFileError::ZipError(ZipError::FileNotFound))
     }
 
+    /// The name of the main GEDCOM X document of the file: the first resource
+    /// of the manifest that's a GEDCOM X document, or if there isn't one, the
+    /// first GEDCOM X document of the file. A resource is a GEDCOM X document
+    /// if the manifest gives it a GEDCOM X `Content-Type`, or if it has no
+    /// `Content-Type` and its name ends in `.json` or `.xml`. Returns `None`
+    /// if the file has no GEDCOM X documents.
+    pub fn main_gedcomx_name(&mut self) -> Result<Option<String>, GedcomxFileError> {
+        let manifest = match self.manifest() {
+            Ok(manifest) => Some(manifest),
+            Err(GedcomxFileError::ZipError(ZipError::FileNotFound)) => None,
+            Err(e) => return Err(e),
+        };
+        Ok(self.main_gedcomx_name_in(manifest.as_ref()))
+    }
+
+    fn main_gedcomx_name_in(&self, manifest: Option<&GedcomxManifest>) -> Option<String> {
+        let names: Vec<&str> = self.inner.file_names().collect();
+
+        let from_manifest = manifest
+            .iter()
+            .flat_map(|m| m.sections())
+            .find(|(name, section)| {
+                names.contains(name) && Self::gedcomx_format(name, Some(section)).is_some()
+            });
+        if let Some((name, _)) = from_manifest {
+            return Some(name.to_string());
+        }
+
+        names
+            .into_iter()
+            .filter(|n| !n.starts_with("META-INF/"))
+            .find(|n| Self::gedcomx_format(n, manifest.and_then(|m| m.section(n))).is_some())
+            .map(str::to_string)
+    }
+
+    // The format of the named resource, from the `Content-Type` of its
+    // section of the manifest, or if it doesn't have one, its extension.
+    fn gedcomx_format(name: &str, section: Option<&ManifestSection>) -> Option<GedcomxFormat> {
+        if let Some(content_type) = section.and_then(|s| s.get("Content-Type")) {
+            return GedcomxFormat::from_content_type(content_type);
+        }
+        GedcomxFormat::from_content_type(sniff::media_type_for_name(name)?)
+    }
+
+    /// Load every GEDCOM X document in the file and merge them into one,
+    /// starting with the [main document](Self::main_gedcomx_name).
+    ///
+    /// References from one document to an entity of another, like
+    /// `people.json#P-1`, are rewritten to refer to the entity in the merged
+    /// document, and relative references to other resources of the file are
+    /// rewritten to resolve the same way from the main document. Entities
+    /// whose id was already used by an earlier document are given a new id.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::MissingGedcomx`] if the file has no
+    /// GEDCOM X documents, or [`GedcomxFileError::GedcomxError`] if one of
+    /// them can't be parsed.
+    pub fn load_all(&mut self) -> Result<MergedGedcomx, GedcomxFileError> {
+        let manifest = match self.manifest() {
+            Ok(manifest) => Some(manifest),
+            Err(GedcomxFileError::ZipError(ZipError::FileNotFound)) => None,
+            Err(e) => return Err(e),
+        };
+        let main = self
+            .main_gedcomx_name_in(manifest.as_ref())
+            .ok_or(GedcomxFileError::MissingGedcomx)?;
+        let names: Vec<String> = std::iter::once(main.as_str())
+            .chain(
+                self.inner
+                    .file_names()
+                    .filter(|n| *n != main && !n.starts_with("META-INF/")),
+            )
+            .map(str::to_string)
+            .collect();
+
+        let mut documents = vec![];
+        for name in names {
+            let section = manifest.as_ref().and_then(|m| m.section(&name));
+            if let Some(format) = Self::gedcomx_format(&name, section) {
+                let gx = format.read(self.inner.by_name(&name)?)?;
+                documents.push((name, gx));
+            }
+        }
+
+        Ok(merge::merge(documents))
+    }
+
+    /// Resolve a URI found in the resource named `base`, such as the `about`
+    /// of a source description in the main document, to the resource of the
+    /// file it refers to. Relative URIs are resolved against `base`, or
+    /// against the root of the file if `base` is `None`, and a URI that's only
+    /// a fragment, like `#P-1`, refers to `base` itself.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::UnresolvableUri`] if the URI can't
+    /// refer to a resource of the file, such as an `https` URI, or
+    /// [`GedcomxFileError::ZipError`] if the file has no such resource.
+    pub fn resolve(
+        &mut self,
+        uri: &Uri,
+        base: Option<&str>,
+    ) -> Result<ResolvedResource<impl Read + use<'_, R>>, GedcomxFileError> {
+        let (name, fragment) = resolve::resolve_reference(&uri.to_string(), base)
+            .ok_or_else(|| GedcomxFileError::UnresolvableUri(uri.to_string()))?;
+
+        let attributes = match self.manifest() {
+            Ok(manifest) => manifest.attributes_by_name(&name).unwrap_or_default(),
+            Err(GedcomxFileError::ZipError(ZipError::FileNotFound)) => HashMap::new(),
+            Err(e) => return Err(e),
+        };
+        let content_type = attributes.get("Content-Type").cloned();
+
+        Ok(ResolvedResource {
+            entry: self.by_name(&name)?,
+            name,
+            fragment,
+            content_type,
+            attributes,
+        })
+    }
+
+    /// Resolve a reference to media, such as one of the `media` of a person in
+    /// the GEDCOM X document `gx` named `base`, to the resource that the
+    /// referenced source description is `about`. If the manifest doesn't give
+    /// the resource a `Content-Type`, the source description's `media_type`
+    /// is used.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::UnresolvableUri`] if the source
+    /// description can't be found in the file or isn't about a resource of
+    /// the file, or any of the errors of [`resolve`](Self::resolve).
+    pub fn resolve_media(
+        &mut self,
+        gx: &Gedcomx,
+        base: &str,
+        media: &SourceReference,
+    ) -> Result<ResolvedResource<impl Read + use<'_, R>>, GedcomxFileError> {
+        let unresolvable = || GedcomxFileError::UnresolvableUri(media.description.to_string());
+
+        let (name, id) = resolve::resolve_reference(&media.description.to_string(), Some(base))
+            .ok_or_else(unresolvable)?;
+        let id = id.ok_or_else(unresolvable)?;
+
+        let find = |gx: &Gedcomx| {
+            gx.source_descriptions
+                .iter()
+                .find(|sd| sd.id.as_ref().is_some_and(|i| i.to_string() == id))
+                .map(|sd| (sd.about.clone(), sd.media_type.clone()))
+        };
+        let description = if name == base {
+            find(gx)
+        } else {
+            match self.by_name(&name)? {
+                GedcomxFileEntry::Gedcomx(other) => find(&other),
+                _ => None,
+            }
+        };
+        let (Some(about), media_type) = description.ok_or_else(unresolvable)? else {
+            return Err(unresolvable());
+        };
+
+        let mut resource = self.resolve(&about, Some(&name))?;
+        if resource.content_type.is_none() {
+            resource.content_type = media_type;
+        }
+        Ok(resource)
+    }
+
+    /// Check the resources of the file against the `SHA-256-Digest`
+    /// attributes of the manifest, and the manifest against the digests in
+    /// any signature files (`META-INF/*.SF`). Returns the problems found,
+    /// which is empty if everything matches.
+    ///
+    /// This is an integrity check, which finds files that were damaged or
+    /// changed without updating their manifest. It doesn't say whether a file
+    /// can be trusted: the signature blocks (`META-INF/*.RSA`, `*.DSA` and
+    /// `*.EC`) aren't checked, so anyone who changes a resource can compute
+    /// the digests of the manifest and signature files again, and no issues
+    /// will be found. Digests made with algorithms other than SHA-256 aren't
+    /// checked either.
+    pub fn check_integrity(&mut self) -> Result<Vec<IntegrityIssue>, GedcomxFileError> {
+        let manifest_text = {
+            let mut entry = self.inner.by_name(MANIFEST_STR).map_err(|e| match e {
+                ZipError::FileNotFound => GedcomxFileError::MissingManifest,
+                e => GedcomxFileError::ZipError(e),
+            })?;
+            let mut text = String::new();
+            entry
+                .read_to_string(&mut text)
+                .map_err(|_| GedcomxFileError::InvalidManifest)?;
+            text
+        };
+        let manifest = GedcomxManifest::from_reader(manifest_text.as_bytes())?;
+
+        let names: Vec<String> = self.inner.file_names().map(str::to_string).collect();
+        let mut issues = vec![];
+
+        for name in names
+            .iter()
+            .filter(|n| !n.ends_with('/') && !n.starts_with("META-INF/"))
+        {
+            match manifest.section(name).and_then(|s| s.get(DIGEST_ATTRIBUTE)) {
+                Some(expected) => {
+                    if digest::digest(self.inner.by_name(name)?)? != expected {
+                        issues.push(IntegrityIssue::DigestMismatch(name.clone()));
+                    }
+                }
+                None => issues.push(IntegrityIssue::MissingDigest(name.clone())),
+            }
+        }
+
+        for (name, section) in manifest.sections() {
+            if section.get(DIGEST_ATTRIBUTE).is_some() && !names.iter().any(|n| n == name) {
+                issues.push(IntegrityIssue::MissingEntry(name.to_string()));
+            }
+        }
+
+        for name in names
+            .iter()
+            .filter(|n| n.starts_with("META-INF/") && n.to_ascii_uppercase().ends_with(".SF"))
+        {
+            let signature = GedcomxManifest::from_reader(self.inner.by_name(name)?)?;
+            if !digest::signature_file_matches(&signature, &manifest_text) {
+                issues.push(IntegrityIssue::StaleSignatureFile(name.clone()));
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// Check that the file conforms to the
+    /// [GEDCOM X File Format](https://github.com/FamilySearch/gedcomx/blob/master/specifications/file-format-specification.md):
+    /// that it has a manifest with a `Manifest-Version`, that the manifest
+    /// describes exactly the resources of the file and gives them the right
+    /// `Content-Type`, that every resource name is a safe relative path, and
+    /// that its main GEDCOM X document can be parsed. Returns the problems
+    /// found, which is empty if the file conforms.
+    ///
+    /// Only `Content-Type`s that can be recognized from the content of a
+    /// resource, such as GEDCOM X documents and common image formats, are
+    /// checked.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::ZipError`] or
+    /// [`GedcomxFileError::IoError`] if a resource can't be read.
+    pub fn check(&mut self) -> Result<Vec<ConformanceIssue>, GedcomxFileError> {
+        let mut issues = vec![];
+
+        let mut names = vec![];
+        for index in 0..self.inner.len() {
+            let entry = self.inner.by_index_raw(index)?;
+            let name = entry.name().to_string();
+            if entry.enclosed_name().is_none() || name.starts_with('/') || name.contains('\\') {
+                issues.push(ConformanceIssue::UnsafePath(name.clone()));
+            }
+            if !entry.is_dir() {
+                names.push(name);
+            }
+        }
+
+        let manifest = match self.manifest() {
+            Ok(manifest) => Some(manifest),
+            Err(GedcomxFileError::ZipError(ZipError::FileNotFound)) => {
+                issues.push(ConformanceIssue::MissingManifest);
+                None
+            }
+            Err(GedcomxFileError::InvalidManifest) => {
+                issues.push(ConformanceIssue::InvalidManifest);
+                None
+            }
+            Err(e) => return Err(e),
+        };
+
+        if let Some(manifest) = &manifest {
+            if manifest.main_attributes().get("Manifest-Version").is_none() {
+                issues.push(ConformanceIssue::MissingManifestVersion);
+            }
+
+            let mut seen = vec![];
+            for (name, section) in manifest.sections() {
+                if seen.contains(&name) {
+                    issues.push(ConformanceIssue::DuplicateSection(name.to_string()));
+                    continue;
+                }
+                seen.push(name);
+
+                if !names.iter().any(|n| n == name) {
+                    issues.push(ConformanceIssue::MissingEntry(name.to_string()));
+                    continue;
+                }
+                if let Some(content_type) = section.get("Content-Type") {
+                    let mut start = vec![];
+                    self.inner
+                        .by_name(name)?
+                        .take(sniff::SNIFF_LEN as u64)
+                        .read_to_end(&mut start)?;
+                    if sniff::matches(content_type, &start) == Some(false) {
+                        issues.push(ConformanceIssue::ContentTypeMismatch {
+                            name: name.to_string(),
+                            content_type: content_type.to_string(),
+                        });
+                    }
+                }
+            }
+
+            for name in names.iter().filter(|n| !n.starts_with("META-INF/")) {
+                if manifest.section(name).is_none() {
+                    issues.push(ConformanceIssue::MissingSection(name.clone()));
+                }
+            }
+        }
+
+        match self.main_gedcomx_name_in(manifest.as_ref()) {
+            Some(name) => {
+                let section = manifest.as_ref().and_then(|m| m.section(&name));
+                let format = Self::gedcomx_format(&name, section)
+                    .expect("the main document is in a GEDCOM X format");
+                if format.read(self.inner.by_name(&name)?).is_err() {
+                    issues.push(ConformanceIssue::InvalidGedcomx(name));
+                }
+            }
+            None => issues.push(ConformanceIssue::MissingGedcomx),
+        }
+
+        Ok(issues)
+    }
+
     /// Get the attributes for a resource specified by index.
     pub fn attributes_by_index(
         &mut self,
@@ -128,50 +518,87 @@ impl<R: io::Read + io::Seek> GedcomxFile<R> {
     }
 }
 
-/// Required entry in a GEDCOM X file that provides metadata about the file and each of the resources in the file.
-#[derive(Debug)]
-pub struct GedcomxManifest {
-    inner: HashMap<String, HashMap<String, String>>,
-}
+// Read the named resource of a GedcomxFile as the type of entry given by its
+// `Content-Type`, its extension or its content.
+fn entry_from_reader<A: Read>(
+    name: &str,
+    mut entry: A,
+    content_type: Option<String>,
+) -> Result<GedcomxFileEntry<impl Read + use<A>>, GedcomxFileError> {
+    if name == MANIFEST_STR {
+        return Ok(GedcomxFileEntry::Manifest(GedcomxManifest::from_reader(
+            entry,
+        )?));
+    }
 
-impl GedcomxManifest {
-    fn from_reader<R>(reader: R) -> Result<Self, GedcomxFileError>
-    where
-        R: Read,
-    {
-        let mut sections = HashMap::new();
-
-        let mut current_section = {
-            let mut m = HashMap::new();
-            m.insert("Name".to_string(), GEDCOMX_RESOURCE_NAME.to_string());
-            m
-        };
+    let mut start = vec![];
+    read_more(&mut entry, &mut start, sniff::SNIFF_LEN)?;
+    let declared = content_type
+        .as_deref()
+        .or_else(|| sniff::media_type_for_name(name));
+    let media_type = declared
+        .or_else(|| sniff::sniff(&start))
+        .map(sniff::essence)
+        .unwrap_or_default();
+
+    if let Some(format) = GedcomxFormat::from_content_type(&media_type) {
+        if declared.is_some() {
+            let gx = format.read(io::Cursor::new(start).chain(entry))?;
+            return Ok(GedcomxFileEntry::Gedcomx(Box::new(gx)));
+        }
 
-        let buf_reader = BufReader::new(reader);
-        for line in buf_reader.lines() {
-            let line = line.map_err(|_| GedcomxFileError::InvalidManifest)?;
-
-            if line.is_empty() {
-                // New section, save the old one.
-                if !current_section.is_empty() {
-                    let name = current_section
-                        .get("Name")
-                        .ok_or(GedcomxFileError::InvalidManifest)?
-                        .to_string();
-                    sections.insert(name, current_section.clone());
-                    current_section.clear();
+        // Any JSON or XML looks like GEDCOM X, so a resource that was only
+        // guessed to be one, and isn't, is returned as it is.
+        entry.read_to_end(&mut start)?;
+        return Ok(match format.read(start.as_slice()) {
+            Ok(gx) => GedcomxFileEntry::Gedcomx(Box::new(gx)),
+            Err(_) => GedcomxFileEntry::Reader(io::Cursor::new(start).chain(entry)),
+        });
+    }
+
+    match media_type.as_str() {
+        "image/png" | "image/jpeg" | "image/gif"
+            if sniff::sniff(&start) == Some(media_type.as_str()) =>
+        {
+            // A JPEG's dimensions can be after a lot of metadata.
+            let mut dimensions = sniff::image_dimensions(&start);
+            while dimensions.is_none() && start.len() < MAX_IMAGE_HEADER_LEN {
+                let len = start.len();
+                if read_more(&mut entry, &mut start, len)? == 0 {
+                    break;
                 }
-            } else if let Some((key, value)) = line.split_once(':') {
-                current_section.insert(key.trim().to_string(), value.trim().to_string());
+                dimensions = sniff::image_dimensions(&start);
             }
+
+            let reader = io::Cursor::new(start).chain(entry);
+            Ok(match dimensions {
+                Some((width, height)) => GedcomxFileEntry::Image(ImageEntry {
+                    media_type,
+                    width,
+                    height,
+                    reader,
+                }),
+                _ => GedcomxFileEntry::Reader(reader),
+            })
+        }
+        "text/plain" => {
+            entry.read_to_end(&mut start)?;
+            Ok(match String::from_utf8(start) {
+                Ok(text) => GedcomxFileEntry::Text(text),
+                Err(e) => GedcomxFileEntry::Reader(io::Cursor::new(e.into_bytes()).chain(entry)),
+            })
         }
-        Ok(Self { inner: sections })
+        "application/pdf" => Ok(GedcomxFileEntry::Pdf(io::Cursor::new(start).chain(entry))),
+        _ => Ok(GedcomxFileEntry::Reader(
+            io::Cursor::new(start).chain(entry),
+        )),
     }
+}
 
-    /// Attributes in the form of key -> value mappings for a given resource name. Use `GEDCOMX_RESOURCE_NAME` as the name to get the attributes of the GEDCOM X file itself.
-    pub fn attributes_by_name(&self, name: &str) -> Option<HashMap<String, String>> {
-        self.inner.get(name).cloned()
-    }
+// Read up to `len` more bytes from `reader` into `buf`, returning how many
+// were read.
+fn read_more<A: Read>(reader: &mut A, buf: &mut Vec<u8>, len: usize) -> io::Result<usize> {
+    reader.take(len as u64).read_to_end(buf)
 }
 
 /// Errors produced by the crate.
@@ -192,6 +619,30 @@ pub enum GedcomxFileError {
     /// The manifest did not have the correct format.
     #[error("invalid manifest")]
     InvalidManifest,
+
+    /// Error while reading or writing the contents of a GedcomxFile.
+    #[error("io error")]
+    IoError(#[from] io::Error),
+
+    /// A resource name isn't a relative path within the GedcomxFile.
+    #[error("invalid resource name: {0}")]
+    InvalidResourceName(String),
+
+    /// A resource with the same name was already added to the GedcomxFile.
+    #[error("duplicate resource: {0}")]
+    DuplicateResource(String),
+
+    /// An attribute name or value can't be written to the manifest.
+    #[error("invalid manifest attribute: {0}")]
+    InvalidAttribute(String),
+
+    /// The GedcomxFile doesn't contain a GEDCOM X document.
+    #[error("no gedcomx document in gedcomx file")]
+    MissingGedcomx,
+
+    /// A URI doesn't refer to a resource in the GedcomxFile.
+    #[error("uri doesn't refer to a resource in the gedcomx file: {0}")]
+    UnresolvableUri(String),
 }
 
 #[cfg(test)]
@@ -210,6 +661,9 @@ mod tests {
             match gx {
                 GedcomxFileEntry::Gedcomx(g) => println!("{:?}", g),
                 GedcomxFileEntry::Manifest(m) => println!("Manifest: {:?}", m),
+                GedcomxFileEntry::Image(i) => println!("Image: {}x{}", i.width, i.height),
+                GedcomxFileEntry::Text(t) => println!("Text: {}", t),
+                GedcomxFileEntry::Pdf(_) => println!("Pdf"),
                 GedcomxFileEntry::Reader(_) => println!("Reader"),
             }
         }
@@ -225,6 +679,9 @@ mod tests {
             match gx {
                 GedcomxFileEntry::Gedcomx(g) => println!("Gedcomx {:?}", g),
                 GedcomxFileEntry::Manifest(m) => println!("Manifest: {:?}", m),
+                GedcomxFileEntry::Image(i) => println!("Image: {}x{}", i.width, i.height),
+                GedcomxFileEntry::Text(t) => println!("Text: {}", t),
+                GedcomxFileEntry::Pdf(_) => println!("Pdf"),
                 GedcomxFileEntry::Reader(_) => println!("Reader"),
             }
         }
@@ -291,7 +748,11 @@ mod tests {
             ])
         };
 
-        let actual = gxf.manifest().unwrap().inner;
+        let manifest = gxf.manifest().unwrap();
+        let actual: HashMap<_, _> = std::iter::once(GEDCOMX_RESOURCE_NAME)
+            .chain(manifest.sections().map(|(name, _)| name))
+            .map(|name| (name.to_string(), manifest.attributes_by_name(name).unwrap()))
+            .collect();
 
         // Outer keys
         assert!(actual.keys().all(|k| expected.contains_key(k)));
@@ -310,4 +771,458 @@ mod tests {
             }
         }
     }
+
+    fn zip(entries: &[(&str, &str)]) -> GedcomxFile<io::Cursor<Vec<u8>>> {
+        let mut writer = zip::ZipWriter::new(io::Cursor::new(vec![]));
+        for (name, contents) in entries {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            io::Write::write_all(&mut writer, contents.as_bytes()).unwrap();
+        }
+        GedcomxFile::from_reader(writer.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn integrity_sample() {
+        let f = File::open("data/sample.gedx").unwrap();
+        let mut gxf = GedcomxFile::from_reader(f).unwrap();
+        let mut issues = gxf.check_integrity().unwrap();
+        issues.sort_by_key(|i| format!("{:?}", i));
+        assert_eq!(
+            issues,
+            ["person1.png", "person2.png", "tree.xml"]
+                .map(|n| IntegrityIssue::MissingDigest(n.to_string()))
+        );
+    }
+
+    #[test]
+    fn integrity_digests() {
+        let a = digest::digest("a".as_bytes()).unwrap();
+        let manifest = format!(
+            "Manifest-Version: 1.0\r\n\r\n\
+             Name: a.txt\r\nSHA-256-Digest: {a}\r\n\r\n\
+             Name: b.txt\r\nSHA-256-Digest: {a}\r\n\r\n\
+             Name: c.txt\r\nSHA-256-Digest: {a}\r\n\r\n"
+        );
+        let mut gxf = zip(&[
+            ("a.txt", "a"),
+            ("b.txt", "tampered"),
+            ("d.txt", "d"),
+            (MANIFEST_STR, &manifest),
+        ]);
+        assert_eq!(
+            gxf.check_integrity().unwrap(),
+            [
+                IntegrityIssue::DigestMismatch("b.txt".to_string()),
+                IntegrityIssue::MissingDigest("d.txt".to_string()),
+                IntegrityIssue::MissingEntry("c.txt".to_string()),
+            ]
+        );
+
+        assert!(matches!(
+            zip(&[("a.txt", "a")]).check_integrity(),
+            Err(GedcomxFileError::MissingManifest)
+        ));
+    }
+
+    #[test]
+    fn integrity_signature_files() {
+        let a = digest::digest("a".as_bytes()).unwrap();
+        let manifest =
+            format!("Manifest-Version: 1.0\r\n\r\nName: a.txt\r\nSHA-256-Digest: {a}\r\n\r\n");
+        let signed = format!(
+            "Signature-Version: 1.0\r\nSHA-256-Digest-Manifest: {}\r\n\r\n",
+            digest::digest(manifest.as_bytes()).unwrap()
+        );
+        let mut gxf = zip(&[
+            ("a.txt", "a"),
+            (MANIFEST_STR, &manifest),
+            ("META-INF/SIGNER.SF", &signed),
+            (
+                "META-INF/OTHER.SF",
+                "Signature-Version: 1.0\r\nSHA-256-Digest-Manifest: stale\r\n\r\n",
+            ),
+        ]);
+        assert_eq!(
+            gxf.check_integrity().unwrap(),
+            [IntegrityIssue::StaleSignatureFile(
+                "META-INF/OTHER.SF".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn resolve_sample() {
+        let f = File::open("data/sample.gedx").unwrap();
+        let mut gxf = GedcomxFile::from_reader(f).unwrap();
+
+        let resource = gxf
+            .resolve(&"person1.png#face".into(), Some("tree.xml"))
+            .unwrap();
+        assert_eq!(resource.name, "person1.png");
+        assert_eq!(resource.fragment.as_deref(), Some("face"));
+        assert_eq!(resource.content_type.as_deref(), Some("image/png"));
+        assert_eq!(
+            resource.attributes.get("X-DC-modified").unwrap(),
+            "2014-10-07T21:15:57.161Z"
+        );
+        assert!(matches!(resource.entry, GedcomxFileEntry::Image(_)));
+        drop(resource);
+
+        let resource = gxf.resolve(&"tree.xml#98765".into(), None).unwrap();
+        assert_eq!(resource.fragment.as_deref(), Some("98765"));
+        assert!(matches!(resource.entry, GedcomxFileEntry::Gedcomx(_)));
+        drop(resource);
+
+        assert!(matches!(
+            gxf.resolve(&"https://example.org/person1.png".into(), None),
+            Err(GedcomxFileError::UnresolvableUri(_))
+        ));
+        assert!(matches!(
+            gxf.resolve(&"missing.png".into(), None),
+            Err(GedcomxFileError::ZipError(ZipError::FileNotFound))
+        ));
+    }
+
+    #[test]
+    fn resolve_media() {
+        let main = r##"{
+            "persons": [{"id": "P-1", "media": [{"description": "#SD-1"}, {"description": "sources.json#SD-2"}]}],
+            "sourceDescriptions": [{"id": "SD-1", "citations": [], "about": "images/photo.jpg", "mediaType": "image/jpeg"}]
+        }"##;
+        let sources = r#"{
+            "sourceDescriptions": [{"id": "SD-2", "citations": [], "about": "images/scan.tif"}]
+        }"#;
+        let manifest = "Manifest-Version: 1.0\r\n\r\n\
+                        Name: trees/images/scan.tif\r\nContent-Type: image/tiff\r\n\r\n";
+        let mut gxf = zip(&[
+            ("trees/main.json", main),
+            ("trees/sources.json", sources),
+            ("trees/images/photo.jpg", "jpeg"),
+            ("trees/images/scan.tif", "tiff"),
+            (MANIFEST_STR, manifest),
+        ]);
+
+        let GedcomxFileEntry::Gedcomx(gx) = gxf.by_name("trees/main.json").unwrap() else {
+            panic!("expected a GEDCOM X document");
+        };
+        let media = &gx.persons[0].media;
+
+        {
+            let resource = gxf
+                .resolve_media(&gx, "trees/main.json", &media[0])
+                .unwrap();
+            assert_eq!(resource.name, "trees/images/photo.jpg");
+            assert_eq!(resource.content_type.as_deref(), Some("image/jpeg"));
+            assert!(resource.attributes.is_empty());
+            let GedcomxFileEntry::Reader(mut r) = resource.entry else {
+                panic!("expected a reader");
+            };
+            let mut contents = String::new();
+            r.read_to_string(&mut contents).unwrap();
+            assert_eq!(contents, "jpeg");
+        }
+
+        let resource = gxf
+            .resolve_media(&gx, "trees/main.json", &media[1])
+            .unwrap();
+        assert_eq!(resource.name, "trees/images/scan.tif");
+        assert_eq!(resource.content_type.as_deref(), Some("image/tiff"));
+        drop(resource);
+
+        let missing = SourceReference::new("#SD-3".into(), None, None, vec![]);
+        assert!(matches!(
+            gxf.resolve_media(&gx, "trees/main.json", &missing),
+            Err(GedcomxFileError::UnresolvableUri(_))
+        ));
+    }
+
+    #[test]
+    fn main_gedcomx_name() {
+        let f = File::open("data/sample.gedx").unwrap();
+        let mut gxf = GedcomxFile::from_reader(f).unwrap();
+        assert_eq!(
+            gxf.main_gedcomx_name().unwrap().as_deref(),
+            Some("tree.xml")
+        );
+
+        let manifest = "Manifest-Version: 1.0\r\n\r\n\
+                        Name: b.json\r\nContent-Type: application/x-gedcomx-v1+json\r\n\r\n";
+        let mut gxf = zip(&[("a.json", "{}"), ("b.json", "{}"), (MANIFEST_STR, manifest)]);
+        assert_eq!(gxf.main_gedcomx_name().unwrap().as_deref(), Some("b.json"));
+
+        let mut gxf = zip(&[("a.txt", "a"), ("b.json", "{}")]);
+        assert_eq!(gxf.main_gedcomx_name().unwrap().as_deref(), Some("b.json"));
+
+        let mut gxf = zip(&[("a.txt", "a")]);
+        assert_eq!(gxf.main_gedcomx_name().unwrap(), None);
+        assert!(matches!(
+            gxf.load_all(),
+            Err(GedcomxFileError::MissingGedcomx)
+        ));
+    }
+
+    #[test]
+    fn load_all() {
+        let main = r##"{
+            "persons": [{"id": "P-1"}],
+            "relationships": [{
+                "type": "http://gedcomx.org/ParentChild",
+                "person1": {"resource": "#P-1"},
+                "person2": {"resource": "people/more.json#P-1"}
+            }]
+        }"##;
+        let more = r##"{
+            "persons": [{"id": "P-1", "media": [{"description": "#SD-1"}]}, {"id": "P-2"}],
+            "relationships": [{
+                "type": "http://gedcomx.org/Couple",
+                "person1": {"resource": "#P-1"},
+                "person2": {"resource": "#P-2"}
+            }],
+            "sourceDescriptions": [{"id": "SD-1", "citations": [], "about": "photo.jpg"}]
+        }"##;
+        let manifest = "Manifest-Version: 1.0\r\n\r\n\
+                        Name: main.json\r\nContent-Type: application/x-gedcomx-v1+json\r\n\r\n";
+        let mut gxf = zip(&[
+            ("people/more.json", more),
+            ("main.json", main),
+            ("people/photo.jpg", "jpeg"),
+            (MANIFEST_STR, manifest),
+        ]);
+
+        let merged = gxf.load_all().unwrap();
+        assert_eq!(merged.main, "main.json");
+        assert_eq!(merged.documents, ["main.json", "people/more.json"]);
+
+        let gx = &merged.gedcomx;
+        let ids: Vec<_> = gx.persons.iter().map(|p| p.id.clone().unwrap()).collect();
+        assert_eq!(ids, ["P-1".into(), "P-1-2".into(), "P-2".into()]);
+        assert_eq!(gx.relationships[0].person2.resource, Uri::from("#P-1-2"));
+        assert_eq!(gx.relationships[1].person1.resource, Uri::from("#P-1-2"));
+        assert_eq!(gx.relationships[1].person2.resource, Uri::from("#P-2"));
+        assert_eq!(
+            gx.source_descriptions[0].about,
+            Some(Uri::from("/people/photo.jpg"))
+        );
+        assert!(
+            gedcomx::ReferenceIndex::new(gx)
+                .dangling_references()
+                .is_empty()
+        );
+
+        let origin = merged.origin(gedcomx::EntityKind::Person, 1).unwrap();
+        assert_eq!(origin.document, "people/more.json");
+        assert_eq!(origin.id, Some("P-1".into()));
+        assert_eq!(
+            merged
+                .origin(gedcomx::EntityKind::Relationship, 0)
+                .unwrap()
+                .document,
+            "main.json"
+        );
+
+        let resource = gxf
+            .resolve_media(gx, &merged.main, &gx.persons[1].media[0])
+            .unwrap();
+        assert_eq!(resource.name, "people/photo.jpg");
+    }
+
+    #[test]
+    fn check_sample() {
+        let f = File::open("data/sample.gedx").unwrap();
+        let mut gxf = GedcomxFile::from_reader(f).unwrap();
+        assert_eq!(gxf.check().unwrap(), []);
+    }
+
+    #[test]
+    fn check() {
+        let manifest = "Manifest-Version: 1.0\r\n\r\n\
+                        Name: tree.json\r\nContent-Type: application/x-gedcomx-v1+json\r\n\r\n\
+                        Name: photo.png\r\nContent-Type: image/png\r\n\r\n\
+                        Name: photo.png\r\nContent-Type: image/png\r\n\r\n\
+                        Name: notes.txt\r\nContent-Type: text/plain\r\n\r\n\
+                        Name: missing.jpg\r\n\r\n";
+        let mut gxf = zip(&[
+            ("tree.json", "<gedcomx/>"),
+            ("photo.png", "not a png"),
+            ("notes.txt", "notes"),
+            ("extra.txt", "extra"),
+            ("../evil.sh", "evil"),
+            (MANIFEST_STR, manifest),
+        ]);
+        assert_eq!(
+            gxf.check().unwrap(),
+            [
+                ConformanceIssue::UnsafePath("../evil.sh".to_string()),
+                ConformanceIssue::ContentTypeMismatch {
+                    name: "tree.json".to_string(),
+                    content_type: "application/x-gedcomx-v1+json".to_string(),
+                },
+                ConformanceIssue::ContentTypeMismatch {
+                    name: "photo.png".to_string(),
+                    content_type: "image/png".to_string(),
+                },
+                ConformanceIssue::DuplicateSection("photo.png".to_string()),
+                ConformanceIssue::MissingEntry("missing.jpg".to_string()),
+                ConformanceIssue::MissingSection("extra.txt".to_string()),
+                ConformanceIssue::MissingSection("../evil.sh".to_string()),
+                ConformanceIssue::InvalidGedcomx("tree.json".to_string()),
+            ]
+        );
+
+        let mut gxf = zip(&[("tree.xml", "<gedcomx xmlns=\"http://gedcomx.org/v1/\"/>")]);
+        assert_eq!(gxf.check().unwrap(), [ConformanceIssue::MissingManifest]);
+
+        let mut gxf = zip(&[("a.txt", "a"), (MANIFEST_STR, "Created-By: me\r\n\r\nbad")]);
+        assert_eq!(
+            gxf.check().unwrap(),
+            [
+                ConformanceIssue::InvalidManifest,
+                ConformanceIssue::MissingGedcomx
+            ]
+        );
+
+        let mut gxf = zip(&[
+            ("a.txt", "a"),
+            (MANIFEST_STR, "Created-By: me\r\n\r\nName: a.txt\r\n\r\n"),
+        ]);
+        assert_eq!(
+            gxf.check().unwrap(),
+            [
+                ConformanceIssue::MissingManifestVersion,
+                ConformanceIssue::MissingGedcomx
+            ]
+        );
+    }
+
+    #[test]
+    fn entry_types() {
+        let f = File::open("data/sample.gedx").unwrap();
+        let mut gxf = GedcomxFile::from_reader(f).unwrap();
+        let GedcomxFileEntry::Image(image) = gxf.by_name("person1.png").unwrap() else {
+            panic!("person1.png isn't an image");
+        };
+        assert_eq!(image.media_type, "image/png");
+        assert!(image.width > 0 && image.height > 0);
+        let mut bytes = vec![];
+        { image.reader }.read_to_end(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 1996);
+        assert!(bytes.starts_with(b"\x89PNG"));
+
+        let gif = b"GIF89a\x20\x01\x10\x00 the rest of the gif";
+        let jpeg = b"\xff\xd8\xff\xe0\x00\x04ab\xff\xc0\x00\x11\x08\x00\x30\x00\x40 the rest";
+        let manifest = "Manifest-Version: 1.0\r\n\r\n\
+                        Name: tree\r\nContent-Type: application/x-gedcomx-v1+xml\r\n\r\n\
+                        Name: notes\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n\
+                        Name: fake.png\r\nContent-Type: image/png\r\n\r\n";
+        let mut writer = zip::ZipWriter::new(io::Cursor::new(vec![]));
+        for (name, contents) in [
+            ("tree", &b"<gedcomx xmlns=\"http://gedcomx.org/v1/\"/>"[..]),
+            ("notes", b"some notes"),
+            ("fake.png", b"not a png"),
+            ("image", gif),
+            ("photo.jpg", jpeg),
+            ("doc.pdf", b"%PDF-1.7"),
+            ("sniffed", b" {}"),
+            ("other.bin", b"\x00\x01"),
+            (MANIFEST_STR, manifest.as_bytes()),
+        ] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            io::Write::write_all(&mut writer, contents).unwrap();
+        }
+        let mut gxf = GedcomxFile::from_reader(writer.finish().unwrap()).unwrap();
+
+        assert!(matches!(
+            gxf.by_name("tree").unwrap(),
+            GedcomxFileEntry::Gedcomx(_)
+        ));
+        assert!(matches!(
+            gxf.by_name("sniffed").unwrap(),
+            GedcomxFileEntry::Gedcomx(_)
+        ));
+        assert!(matches!(
+            gxf.by_name("notes").unwrap(),
+            GedcomxFileEntry::Text(t) if t == "some notes"
+        ));
+        assert!(matches!(
+            gxf.by_name("fake.png").unwrap(),
+            GedcomxFileEntry::Reader(_)
+        ));
+        assert!(matches!(
+            gxf.by_name("image").unwrap(),
+            GedcomxFileEntry::Image(ImageEntry {
+                width: 288,
+                height: 16,
+                ..
+            })
+        ));
+        assert!(matches!(
+            gxf.by_name("photo.jpg").unwrap(),
+            GedcomxFileEntry::Image(ImageEntry {
+                width: 64,
+                height: 48,
+                ..
+            })
+        ));
+        assert!(matches!(
+            gxf.by_name("doc.pdf").unwrap(),
+            GedcomxFileEntry::Pdf(_)
+        ));
+        assert!(matches!(
+            gxf.by_name("other.bin").unwrap(),
+            GedcomxFileEntry::Reader(_)
+        ));
+    }
+
+    #[test]
+    fn sniffed_resources_that_are_not_gedcomx() {
+        let html = "<!DOCTYPE html><html><body>Family reunion</body></html>";
+        let unclosed = r#"<gedcomx xmlns="http://gedcomx.org/v1/"><person>"#;
+        let manifest = "Manifest-Version: 1.0\r\n\r\n\
+                        Name: declared\r\nContent-Type: application/x-gedcomx-v1+json\r\n\r\n";
+        let mut writer = zip::ZipWriter::new(io::Cursor::new(vec![]));
+        for (name, contents) in [
+            ("page", html),
+            ("settings", r#"{"persons": "not a list"}"#),
+            ("declared", r#"{"persons": "not a list"}"#),
+            ("draft", unclosed),
+            ("bad.xml", unclosed),
+            (MANIFEST_STR, manifest),
+        ] {
+            writer
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            io::Write::write_all(&mut writer, contents.as_bytes()).unwrap();
+        }
+        let mut gxf = GedcomxFile::from_reader(writer.finish().unwrap()).unwrap();
+
+        let GedcomxFileEntry::Reader(mut reader) = gxf.by_name("page").unwrap() else {
+            panic!("the page isn't GEDCOM X");
+        };
+        let mut contents = String::new();
+        reader.read_to_string(&mut contents).unwrap();
+        drop(reader);
+        assert_eq!(contents, html);
+        assert!(matches!(
+            gxf.by_name("settings").unwrap(),
+            GedcomxFileEntry::Reader(_)
+        ));
+        assert!(matches!(
+            gxf.by_name("draft").unwrap(),
+            GedcomxFileEntry::Reader(_)
+        ));
+
+        // A declared type, or one given by the extension, is trusted.
+        assert!(matches!(
+            gxf.by_name("declared"),
+            Err(GedcomxFileError::GedcomxError(_))
+        ));
+        assert!(matches!(
+            gxf.by_name("bad.xml"),
+            Err(GedcomxFileError::GedcomxError(_))
+        ));
+    }
 }
@@ -0,0 +1,581 @@
+use std::{
+    collections::HashMap,
+    io::{self, Read, Write},
+};
+
+use crate::{GEDCOMX_RESOURCE_NAME, GedcomxFileError};
+
+// Lines of a manifest can't be longer than 72 bytes, not counting the line
+// break. Longer lines continue on the next line, which starts with a space.
+const MAX_LINE_LEN: usize = 72;
+
+const MANIFEST_VERSION: &str = "Manifest-Version";
+
+/// Required entry in a GEDCOM X file that provides metadata about the file and
+/// each of the resources in the file.
+///
+/// The manifest has the format of a
+/// [JAR manifest](https://docs.oracle.com/en/java/javase/17/docs/specs/jar/jar.html#jar-manifest):
+/// a main section of attributes describing the file itself, followed by a
+/// section of attributes for each resource. Sections and attributes keep the
+/// order they were read or added in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GedcomxManifest {
+    main: ManifestSection,
+    sections: Vec<(String, ManifestSection)>,
+}
+
+impl Default for GedcomxManifest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GedcomxManifest {
+    /// Create a manifest with a `Manifest-Version` of 1.0 and no resource
+    /// sections.
+    pub fn new() -> Self {
+        let mut main = ManifestSection::new();
+        main.attributes
+            .push((MANIFEST_VERSION.to_string(), "1.0".to_string()));
+        Self {
+            main,
+            sections: vec![],
+        }
+    }
+
+    /// Read a manifest, joining continuation lines.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::InvalidManifest`] if the manifest isn't
+    /// valid UTF-8, a line isn't an attribute, or a resource section has no
+    /// `Name`.
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self, GedcomxFileError> {
+        let mut contents = String::new();
+        reader
+            .read_to_string(&mut contents)
+            .map_err(|_| GedcomxFileError::InvalidManifest)?;
+
+        let mut manifest = Self {
+            main: ManifestSection::new(),
+            sections: vec![],
+        };
+        let mut in_main = true;
+        let mut current = ManifestSection::new();
+        let mut current_name: Option<String> = None;
+
+        for line in logical_lines(&contents) {
+            if line.is_empty() {
+                // End of a section, save it.
+                if in_main {
+                    manifest.main = std::mem::take(&mut current);
+                    in_main = false;
+                } else if let Some(name) = current_name.take() {
+                    manifest.sections.push((name, std::mem::take(&mut current)));
+                } else if !current.is_empty() {
+                    return Err(GedcomxFileError::InvalidManifest);
+                }
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once(':')
+                .ok_or(GedcomxFileError::InvalidManifest)?;
+            let (key, value) = (key.trim(), value.trim_start());
+            if !in_main && current_name.is_none() && current.is_empty() {
+                // The first attribute of a resource section names it.
+                if !key.eq_ignore_ascii_case("Name") {
+                    return Err(GedcomxFileError::InvalidManifest);
+                }
+                current_name = Some(value.to_string());
+            } else {
+                current
+                    .attributes
+                    .push((key.to_string(), value.to_string()));
+            }
+        }
+
+        // The last section might not end with a blank line.
+        if in_main {
+            manifest.main = current;
+        } else if let Some(name) = current_name {
+            manifest.sections.push((name, current));
+        } else if !current.is_empty() {
+            return Err(GedcomxFileError::InvalidManifest);
+        }
+
+        Ok(manifest)
+    }
+
+    /// Write the manifest, wrapping lines longer than 72 bytes. The
+    /// `Manifest-Version` attribute is always written first.
+    ///
+    /// # Errors
+    ///
+    /// Will return an [`io::Error`] if writing fails.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        if let Some(version) = self.main.get(MANIFEST_VERSION) {
+            write_attribute(&mut writer, MANIFEST_VERSION, version)?;
+        }
+        for (key, value) in self
+            .main
+            .iter()
+            .filter(|(k, _)| !k.eq_ignore_ascii_case(MANIFEST_VERSION))
+        {
+            write_attribute(&mut writer, key, value)?;
+        }
+        writer.write_all(b"\r\n")?;
+
+        for (name, section) in &self.sections {
+            write_attribute(&mut writer, "Name", name)?;
+            for (key, value) in section.iter() {
+                write_attribute(&mut writer, key, value)?;
+            }
+            writer.write_all(b"\r\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// The attributes of the GEDCOM X file itself.
+    pub fn main_attributes(&self) -> &ManifestSection {
+        &self.main
+    }
+
+    /// The attributes of the GEDCOM X file itself, for editing.
+    pub fn main_attributes_mut(&mut self) -> &mut ManifestSection {
+        &mut self.main
+    }
+
+    /// The attributes of the resource named `name`.
+    pub fn section(&self, name: &str) -> Option<&ManifestSection> {
+        self.sections
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, section)| section)
+    }
+
+    /// The attributes of the resource named `name`, for editing.
+    pub fn section_mut(&mut self, name: &str) -> Option<&mut ManifestSection> {
+        self.sections
+            .iter_mut()
+            .find(|(n, _)| n == name)
+            .map(|(_, section)| section)
+    }
+
+    /// The resource sections of the manifest, in order.
+    pub fn sections(&self) -> impl Iterator<Item = (&str, &ManifestSection)> {
+        self.sections
+            .iter()
+            .map(|(name, section)| (name.as_str(), section))
+    }
+
+    /// Add an empty section for the resource named `name` at the end of the
+    /// manifest, returning it for adding attributes.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::DuplicateResource`] if the manifest
+    /// already has a section for `name`, or
+    /// [`GedcomxFileError::InvalidAttribute`] if `name` can't be written to
+    /// the manifest.
+    pub fn add_section(&mut self, name: &str) -> Result<&mut ManifestSection, GedcomxFileError> {
+        if name.is_empty() || !is_valid_attribute_value(name) {
+            return Err(GedcomxFileError::InvalidAttribute("Name".to_string()));
+        }
+        if self.section(name).is_some() {
+            return Err(GedcomxFileError::DuplicateResource(name.to_string()));
+        }
+        self.sections
+            .push((name.to_string(), ManifestSection::new()));
+        Ok(&mut self.sections.last_mut().expect("section was just added").1)
+    }
+
+    /// Remove the section for the resource named `name`, returning it.
+    pub fn remove_section(&mut self, name: &str) -> Option<ManifestSection> {
+        let index = self.sections.iter().position(|(n, _)| n == name)?;
+        Some(self.sections.remove(index).1)
+    }
+
+    /// Attributes in the form of key -> value mappings for a given resource
+    /// name, including its `Name`. Use `GEDCOMX_RESOURCE_NAME` as the name to
+    /// get the attributes of the GEDCOM X file itself.
+    pub fn attributes_by_name(&self, name: &str) -> Option<HashMap<String, String>> {
+        let section = if name == GEDCOMX_RESOURCE_NAME {
+            &self.main
+        } else {
+            self.section(name)?
+        };
+
+        let mut attributes: HashMap<_, _> = section
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        attributes.insert("Name".to_string(), name.to_string());
+        Some(attributes)
+    }
+}
+
+/// The attributes of a section of a [`GedcomxManifest`], in order. Attribute
+/// names are case-insensitive.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ManifestSection {
+    attributes: Vec<(String, String)>,
+}
+
+impl ManifestSection {
+    /// Create a section with no attributes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value of the attribute named `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Set the attribute named `name` to `value`, returning its old value.
+    /// An existing attribute keeps its place in the section, and a new one is
+    /// added at the end.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::InvalidAttribute`] if `name` isn't made
+    /// of letters, digits, `-` and `_`, is `Name`, which is set by the
+    /// manifest, or if `value` contains a line break.
+    pub fn set<I: Into<String>>(
+        &mut self,
+        name: &str,
+        value: I,
+    ) -> Result<Option<String>, GedcomxFileError> {
+        let value = value.into();
+        if !is_valid_attribute_name(name)
+            || name.eq_ignore_ascii_case("Name")
+            || !is_valid_attribute_value(&value)
+        {
+            return Err(GedcomxFileError::InvalidAttribute(name.to_string()));
+        }
+
+        match self
+            .attributes
+            .iter_mut()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        {
+            Some((_, v)) => Ok(Some(std::mem::replace(v, value))),
+            None => {
+                self.attributes.push((name.to_string(), value));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Remove the attribute named `name`, returning its value.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let index = self
+            .attributes
+            .iter()
+            .position(|(k, _)| k.eq_ignore_ascii_case(name))?;
+        Some(self.attributes.remove(index).1)
+    }
+
+    /// The attributes of the section as (name, value) pairs, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.attributes
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Number of attributes in the section.
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Whether the section has no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+/// Split a manifest into the exact text of each of its sections, including
+/// the blank line that ends it, along with the section's `Name`. The main
+/// section comes first and has no name. Signature files digest these.
+pub(crate) fn raw_sections(contents: &str) -> Vec<(Option<String>, &str)> {
+    let mut chunks = vec![];
+    let (mut start, mut pos) = (0, 0);
+    while pos < contents.len() {
+        let rest = &contents[pos..];
+        let (line_len, break_len) = match rest.find(['\r', '\n']) {
+            Some(i) if rest[i..].starts_with("\r\n") => (i, 2),
+            Some(i) => (i, 1),
+            None => (rest.len(), 0),
+        };
+        pos += line_len + break_len;
+        if line_len == 0 {
+            chunks.push(&contents[start..pos]);
+            start = pos;
+        }
+    }
+    if start < contents.len() {
+        chunks.push(&contents[start..]);
+    }
+
+    chunks
+        .into_iter()
+        .enumerate()
+        .filter(|(i, chunk)| *i == 0 || !chunk.trim().is_empty())
+        .map(|(i, chunk)| {
+            let name = (i > 0)
+                .then(|| {
+                    let line = logical_lines(chunk).into_iter().next()?;
+                    let (key, value) = line.split_once(':')?;
+                    key.trim()
+                        .eq_ignore_ascii_case("Name")
+                        .then(|| value.trim_start().to_string())
+                })
+                .flatten();
+            (name, chunk)
+        })
+        .collect()
+}
+
+// Split the manifest into lines, accepting any of the line breaks a manifest
+// may use, and join continuation lines onto the line they continue.
+fn logical_lines(contents: &str) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut continuable = false;
+    for line in contents.split("\r\n").flat_map(|l| l.split(['\r', '\n'])) {
+        match line.strip_prefix(' ') {
+            Some(rest) if continuable => lines
+                .last_mut()
+                .expect("continuable implies a previous line")
+                .push_str(rest),
+            _ => {
+                continuable = !line.is_empty();
+                lines.push(line.to_string());
+            }
+        }
+    }
+
+    // A manifest ends with a line break, which leaves an empty line that isn't
+    // the end of a section.
+    if contents.ends_with(['\r', '\n']) {
+        lines.pop();
+    }
+    lines
+}
+
+fn write_attribute<W: Write>(writer: &mut W, name: &str, value: &str) -> io::Result<()> {
+    let line = format!("{name}: {value}");
+    let mut rest = line.as_str();
+    let mut max = MAX_LINE_LEN;
+    while rest.len() > max {
+        // Don't split multi-byte characters across lines.
+        let mut split = max;
+        while !rest.is_char_boundary(split) {
+            split -= 1;
+        }
+        writer.write_all(&rest.as_bytes()[..split])?;
+        writer.write_all(b"\r\n ")?;
+        rest = &rest[split..];
+        max = MAX_LINE_LEN - 1;
+    }
+    writer.write_all(rest.as_bytes())?;
+    writer.write_all(b"\r\n")
+}
+
+// Attribute names in a manifest are made of letters, digits, `-` and `_`.
+fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// Values can't span lines, since a line break ends the attribute.
+fn is_valid_attribute_value(value: &str) -> bool {
+    !value.contains(['\r', '\n', '\0'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> GedcomxManifest {
+        let mut manifest = GedcomxManifest::new();
+        manifest
+            .main_attributes_mut()
+            .set("Created-By", "Test Suite 1.0")
+            .unwrap();
+        let tree = manifest.add_section("tree.xml").unwrap();
+        tree.set("Content-Type", "application/x-gedcomx-v1+xml")
+            .unwrap();
+        tree.set("X-DC-modified", "2014-10-07T21:15:57.148Z")
+            .unwrap();
+        manifest
+            .add_section("person1.png")
+            .unwrap()
+            .set("Content-Type", "image/png")
+            .unwrap();
+        manifest
+    }
+
+    fn to_string(manifest: &GedcomxManifest) -> String {
+        let mut bytes = vec![];
+        manifest.write_to(&mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn write() {
+        assert_eq!(
+            to_string(&manifest()),
+            "Manifest-Version: 1.0\r\n\
+             Created-By: Test Suite 1.0\r\n\
+             \r\n\
+             Name: tree.xml\r\n\
+             Content-Type: application/x-gedcomx-v1+xml\r\n\
+             X-DC-modified: 2014-10-07T21:15:57.148Z\r\n\
+             \r\n\
+             Name: person1.png\r\n\
+             Content-Type: image/png\r\n\
+             \r\n"
+        );
+    }
+
+    #[test]
+    fn read() {
+        let manifest = GedcomxManifest::from_reader(
+            "Manifest-Version: 1.0\nCreated-By: Test Suite 1.0\n\n\
+             Name: tree.xml\nContent-Type: application/x-gedcomx-v1+xml\n\
+             X-DC-modified: 2014-10-07T21:15:57.148Z\n\n\
+             Name: person1.png\nContent-Type: image/png"
+                .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(manifest, self::manifest());
+
+        let names: Vec<_> = manifest.sections().map(|(name, _)| name).collect();
+        assert_eq!(names, ["tree.xml", "person1.png"]);
+    }
+
+    #[test]
+    fn read_continuation_lines() {
+        let manifest = GedcomxManifest::from_reader(
+            "Manifest-Version: 1.0\r\n\r\n\
+             Name: images/a-very-long-directory-name/another-very-long-directory-na\r\n \
+             me/person1.png\r\n\
+             X-DC-title: A portrait of \r\n  the person\r\n\r\n"
+                .as_bytes(),
+        )
+        .unwrap();
+
+        let name = "images/a-very-long-directory-name/another-very-long-directory-name/person1.png";
+        let section = manifest.section(name).unwrap();
+        assert_eq!(section.get("X-DC-title"), Some("A portrait of  the person"));
+    }
+
+    #[test]
+    fn write_wraps_long_lines() {
+        let name = format!("images/{}/person1.png", "é".repeat(60));
+        let title = "t".repeat(200);
+
+        let mut manifest = GedcomxManifest::new();
+        manifest
+            .add_section(&name)
+            .unwrap()
+            .set("X-DC-title", title.as_str())
+            .unwrap();
+
+        let written = to_string(&manifest);
+        for line in written.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_LEN, "{line}");
+        }
+
+        let read = GedcomxManifest::from_reader(written.as_bytes()).unwrap();
+        assert_eq!(read, manifest);
+        assert_eq!(
+            read.section(&name).unwrap().get("X-DC-title"),
+            Some(title.as_str())
+        );
+    }
+
+    #[test]
+    fn edit() {
+        let mut manifest = manifest();
+
+        let tree = manifest.section_mut("tree.xml").unwrap();
+        assert_eq!(
+            tree.set("x-dc-MODIFIED", "2020-01-01T00:00:00Z").unwrap(),
+            Some("2014-10-07T21:15:57.148Z".to_string())
+        );
+        assert_eq!(
+            tree.remove("Content-Type"),
+            Some("application/x-gedcomx-v1+xml".to_string())
+        );
+        assert_eq!(
+            tree.iter().collect::<Vec<_>>(),
+            [("X-DC-modified", "2020-01-01T00:00:00Z")]
+        );
+
+        assert!(manifest.remove_section("person1.png").is_some());
+        assert!(manifest.remove_section("person1.png").is_none());
+        assert!(matches!(
+            manifest.add_section("tree.xml"),
+            Err(GedcomxFileError::DuplicateResource(_))
+        ));
+
+        manifest
+            .main_attributes_mut()
+            .set("Manifest-Version", "1.1")
+            .unwrap();
+        assert_eq!(
+            to_string(&manifest),
+            "Manifest-Version: 1.1\r\n\
+             Created-By: Test Suite 1.0\r\n\
+             \r\n\
+             Name: tree.xml\r\n\
+             X-DC-modified: 2020-01-01T00:00:00Z\r\n\
+             \r\n"
+        );
+    }
+
+    #[test]
+    fn invalid_attributes() {
+        let mut section = ManifestSection::new();
+        for name in ["", "X DC", "X-DC:title", "Name", "name"] {
+            assert!(
+                matches!(
+                    section.set(name, "a"),
+                    Err(GedcomxFileError::InvalidAttribute(_))
+                ),
+                "{name}"
+            );
+        }
+        assert!(matches!(
+            section.set("X-DC-title", "a\r\nName: b"),
+            Err(GedcomxFileError::InvalidAttribute(_))
+        ));
+        assert!(section.is_empty());
+    }
+
+    #[test]
+    fn invalid_manifests() {
+        for manifest in [
+            "Manifest-Version: 1.0\r\n\r\nContent-Type: image/png\r\n\r\n",
+            "Manifest-Version: 1.0\r\nnot an attribute\r\n",
+            " continuation with nothing to continue\r\n",
+        ] {
+            assert!(
+                matches!(
+                    GedcomxManifest::from_reader(manifest.as_bytes()),
+                    Err(GedcomxFileError::InvalidManifest)
+                ),
+                "{manifest}"
+            );
+        }
+    }
+}
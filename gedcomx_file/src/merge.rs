@@ -0,0 +1,215 @@
+use std::collections::{HashMap, HashSet};
+
+use gedcomx::{EntityKind, Gedcomx, Id, ReferenceIndex, Uri};
+
+use crate::resolve::resolve_reference;
+
+/// The GEDCOM X documents of a [`GedcomxFile`](crate::GedcomxFile) merged
+/// into one, as returned by
+/// [`GedcomxFile::load_all`](crate::GedcomxFile::load_all).
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub struct MergedGedcomx {
+    /// The merged document. Its top level entities are those of the main
+    /// document followed by those of the other documents, in the order of
+    /// the file, and its other properties, such as `attribution`, are those
+    /// of the main document.
+    pub gedcomx: Gedcomx,
+
+    /// The name of the main document within the file.
+    pub main: String,
+
+    /// The names of the documents that were merged, main document first.
+    pub documents: Vec<String>,
+
+    /// Where each top level entity of [`gedcomx`](Self::gedcomx) came from.
+    pub origins: Vec<EntityOrigin>,
+}
+
+impl MergedGedcomx {
+    /// Where the `index`th entity of the given kind in
+    /// [`gedcomx`](Self::gedcomx) came from, such as `(EntityKind::Person, 0)`
+    /// for its first person.
+    pub fn origin(&self, kind: EntityKind, index: usize) -> Option<&EntityOrigin> {
+        self.origins
+            .iter()
+            .find(|o| o.kind == kind && o.index == index)
+    }
+}
+
+/// The document a top level entity of a [`MergedGedcomx`] came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EntityOrigin {
+    /// The kind of the entity.
+    pub kind: EntityKind,
+
+    /// The position of the entity among those of its kind in the merged
+    /// document.
+    pub index: usize,
+
+    /// The name of the document the entity came from.
+    pub document: String,
+
+    /// The id of the entity in that document. The entity is given a new id
+    /// when another document already used this one.
+    pub id: Option<Id>,
+}
+
+/// Merge `documents`, main document first, rewriting references between
+/// them to references within the merged document.
+pub(crate) fn merge(documents: Vec<(String, Gedcomx)>) -> MergedGedcomx {
+    let names: Vec<String> = documents.iter().map(|(name, _)| name.clone()).collect();
+    let main = names[0].clone();
+
+    // Entities get new ids when an earlier document already used theirs.
+    let mut used = HashSet::new();
+    let mut renamed = HashMap::new();
+    for (name, gx) in &documents {
+        let ids: Vec<String> = ReferenceIndex::new(gx)
+            .entities()
+            .filter_map(|e| e.id())
+            .chain(gx.collections.iter().filter_map(|c| c.id.as_ref()))
+            .chain(gx.record_descriptors.iter().filter_map(|r| r.id.as_ref()))
+            .map(ToString::to_string)
+            .collect();
+        let own: HashSet<String> = ids.iter().cloned().collect();
+        let mut seen = HashSet::new();
+        for id in ids {
+            if !seen.insert(id.clone()) || used.insert(id.clone()) {
+                continue;
+            }
+            let new_id = (2..)
+                .map(|n| format!("{id}-{n}"))
+                .find(|new_id| !used.contains(new_id) && !own.contains(new_id))
+                .expect("there are always unused ids");
+            used.insert(new_id.clone());
+            renamed.insert((name.clone(), id), new_id);
+        }
+    }
+
+    let mut merged = MergedGedcomx {
+        gedcomx: Gedcomx::default(),
+        main: main.clone(),
+        documents: names.clone(),
+        origins: vec![],
+    };
+    let mut counts: HashMap<EntityKind, usize> = HashMap::new();
+
+    for (name, mut gx) in documents {
+        for entity in ReferenceIndex::new(&gx).entities() {
+            let index = counts.entry(entity.kind()).or_default();
+            merged.origins.push(EntityOrigin {
+                kind: entity.kind(),
+                index: *index,
+                document: name.clone(),
+                id: entity.id().cloned(),
+            });
+            *index += 1;
+        }
+
+        let rewrite = |uri: &mut Uri| {
+            if let Some(rewritten) = rewrite(uri, &name, &main, &names, &renamed) {
+                *uri = rewritten;
+            }
+        };
+        for reference in gx.references_mut() {
+            rewrite(reference);
+        }
+        for about in gx
+            .source_descriptions
+            .iter_mut()
+            .filter_map(|sd| sd.about.as_mut())
+        {
+            rewrite(about);
+        }
+        for id in ids_mut(&mut gx) {
+            if let Some(new_id) = renamed.get(&(name.clone(), id.to_string())) {
+                *id = new_id.as_str().into();
+            }
+        }
+
+        let target = &mut merged.gedcomx;
+        if name == main {
+            target.id = gx.id;
+            target.lang = gx.lang;
+            target.attribution = gx.attribution;
+            target.description = gx.description;
+            target.links = gx.links;
+            target.extensions = gx.extensions;
+        }
+        target.persons.extend(gx.persons);
+        target.relationships.extend(gx.relationships);
+        target.source_descriptions.extend(gx.source_descriptions);
+        target.agents.extend(gx.agents);
+        target.events.extend(gx.events);
+        target.documents.extend(gx.documents);
+        target.places.extend(gx.places);
+        target.groups.extend(gx.groups);
+        target.collections.extend(gx.collections);
+        target.record_descriptors.extend(gx.record_descriptors);
+    }
+
+    merged
+}
+
+// The ids of the top level entities of a document.
+fn ids_mut(gx: &mut Gedcomx) -> impl Iterator<Item = &mut Id> {
+    gx.persons
+        .iter_mut()
+        .map(|e| &mut e.id)
+        .chain(gx.relationships.iter_mut().map(|e| &mut e.id))
+        .chain(gx.source_descriptions.iter_mut().map(|e| &mut e.id))
+        .chain(gx.agents.iter_mut().map(|e| &mut e.id))
+        .chain(gx.events.iter_mut().map(|e| &mut e.id))
+        .chain(gx.documents.iter_mut().map(|e| &mut e.id))
+        .chain(gx.places.iter_mut().map(|e| &mut e.id))
+        .chain(gx.groups.iter_mut().map(|e| &mut e.id))
+        .chain(gx.collections.iter_mut().map(|e| &mut e.id))
+        .chain(gx.record_descriptors.iter_mut().map(|e| &mut e.id))
+        .filter_map(Option::as_mut)
+}
+
+// Rewrite a URI found in the document named `name` so that it means the same
+// thing in the merged document, which is named `main`. References to
+// entities of the merged documents become fragments, and relative references
+// to other resources of the file become absolute if they'd resolve
+// differently from `main`. Returns `None` if the URI doesn't need rewriting.
+fn rewrite(
+    uri: &Uri,
+    name: &str,
+    main: &str,
+    documents: &[String],
+    renamed: &HashMap<(String, String), String>,
+) -> Option<Uri> {
+    let uri = uri.to_string();
+    let (target, fragment) = resolve_reference(&uri, Some(name))?;
+
+    if documents.contains(&target) {
+        let id = fragment?;
+        let id = renamed.get(&(target, id.clone())).unwrap_or(&id);
+        let rewritten = format!("#{id}");
+        return (rewritten != uri).then(|| rewritten.into());
+    }
+
+    if name == main || resolve_reference(&uri, Some(main)).is_some_and(|(t, _)| t == target) {
+        return None;
+    }
+    let mut rewritten = format!("/{}", percent_encode(&target));
+    if let Some((_, fragment)) = uri.split_once('#') {
+        rewritten = format!("{rewritten}#{fragment}");
+    }
+    Some(rewritten.into())
+}
+
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"/-._~!$&'()*+,;=:@".contains(&b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    encoded
+}
@@ -0,0 +1,169 @@
+use std::{collections::HashMap, io::Read};
+
+use crate::GedcomxFileEntry;
+
+/// A resource of a [`GedcomxFile`](crate::GedcomxFile) that a URI resolved
+/// to.
+#[derive(Debug)]
+pub struct ResolvedResource<R: Read> {
+    /// The name of the resource within the file.
+    pub name: String,
+
+    /// The fragment of the URI, if any. When the resource is a GEDCOM X
+    /// document, this is usually the id of an element of the document.
+    pub fragment: Option<String>,
+
+    /// The media type of the resource, from the `Content-Type` attribute of
+    /// its section of the manifest.
+    pub content_type: Option<String>,
+
+    /// The attributes of the resource's section of the manifest. Empty if the
+    /// manifest doesn't describe the resource.
+    pub attributes: HashMap<String, String>,
+
+    /// The resource itself.
+    pub entry: GedcomxFileEntry<R>,
+}
+
+/// Resolve a URI reference against the name of the resource it appears in,
+/// per [RFC 3986](https://tools.ietf.org/html/rfc3986#section-5.2), returning
+/// the name of the resource it refers to and its fragment. A URI that has a
+/// scheme, an authority or a query, or that climbs out of the root of the
+/// file, doesn't refer to a resource in the file.
+pub(crate) fn resolve_reference(uri: &str, base: Option<&str>) -> Option<(String, Option<String>)> {
+    let (reference, fragment) = match uri.split_once('#') {
+        Some((reference, fragment)) => (reference, Some(percent_decode(fragment)?)),
+        None => (uri, None),
+    };
+    let fragment = fragment.filter(|f| !f.is_empty());
+
+    if has_scheme(reference) || reference.starts_with("//") || reference.contains('?') {
+        return None;
+    }
+
+    // An empty reference is the resource the URI appears in.
+    if reference.is_empty() {
+        return Some((base?.to_string(), fragment));
+    }
+
+    let path = match reference.strip_prefix('/') {
+        Some(absolute) => absolute.to_string(),
+        None => match base.and_then(|b| b.rsplit_once('/')) {
+            Some((directory, _)) => format!("{directory}/{reference}"),
+            None => reference.to_string(),
+        },
+    };
+
+    let mut segments: Vec<String> = vec![];
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => (),
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(percent_decode(segment)?),
+        }
+    }
+
+    if segments.is_empty() {
+        return None;
+    }
+    Some((segments.join("/"), fragment))
+}
+
+// Whether a URI reference starts with a scheme, like `https:`.
+fn has_scheme(reference: &str) -> bool {
+    let scheme = reference.split_once(':').map_or("", |(scheme, _)| scheme);
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(uri: &str, base: Option<&str>) -> Option<(String, Option<String>)> {
+        resolve_reference(uri, base)
+    }
+
+    fn resolved(name: &str, fragment: Option<&str>) -> Option<(String, Option<String>)> {
+        Some((name.to_string(), fragment.map(str::to_string)))
+    }
+
+    #[test]
+    fn relative_paths() {
+        assert_eq!(resolve("person1.png", None), resolved("person1.png", None));
+        assert_eq!(
+            resolve("person1.png", Some("tree.xml")),
+            resolved("person1.png", None)
+        );
+        assert_eq!(
+            resolve("images/person1.png", Some("trees/tree.xml")),
+            resolved("trees/images/person1.png", None)
+        );
+        assert_eq!(
+            resolve("../images/./person1.png", Some("trees/tree.xml")),
+            resolved("images/person1.png", None)
+        );
+        assert_eq!(
+            resolve("/images/person1.png", Some("trees/tree.xml")),
+            resolved("images/person1.png", None)
+        );
+        assert_eq!(
+            resolve("my%20photo.png", None),
+            resolved("my photo.png", None)
+        );
+    }
+
+    #[test]
+    fn fragments() {
+        assert_eq!(
+            resolve("#P-1", Some("trees/tree.xml")),
+            resolved("trees/tree.xml", Some("P-1"))
+        );
+        assert_eq!(
+            resolve("other.json#P-1", Some("trees/tree.xml")),
+            resolved("trees/other.json", Some("P-1"))
+        );
+        assert_eq!(resolve("other.json#", None), resolved("other.json", None));
+        assert_eq!(resolve("", Some("tree.xml")), resolved("tree.xml", None));
+        assert_eq!(resolve("#P-1", None), None);
+    }
+
+    #[test]
+    fn outside_the_file() {
+        for uri in [
+            "https://example.org/person1.png",
+            "file:person1.png",
+            "urn:uuid:1234",
+            "//example.org/person1.png",
+            "person1.png?size=large",
+            "../person1.png",
+            "a/../../person1.png",
+            "/",
+            "bad%2",
+            "bad%zz",
+        ] {
+            assert_eq!(resolve(uri, Some("tree.xml")), None, "{uri}");
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use crate::GedcomxFormat;
+
+// Whether the first bytes of a resource have the signature of a media type.
+type Signature = fn(&[u8]) -> bool;
+
+// Media types that can be recognized from the first bytes of a resource.
+const SIGNATURES: &[(&str, Signature)] = &[
+    ("image/png", |b| b.starts_with(b"\x89PNG\r\n\x1a\n")),
+    ("image/jpeg", |b| b.starts_with(b"\xff\xd8\xff")),
+    ("image/gif", |b| {
+        b.starts_with(b"GIF87a") || b.starts_with(b"GIF89a")
+    }),
+    ("image/tiff", |b| {
+        b.starts_with(b"II*\0") || b.starts_with(b"MM\0*")
+    }),
+    ("application/pdf", |b| b.starts_with(b"%PDF-")),
+    ("application/x-gedcomx-v1+json", |b| {
+        first_non_whitespace(b) == Some(b'{')
+    }),
+    ("application/x-gedcomx-v1+xml", |b| {
+        first_non_whitespace(b) == Some(b'<')
+    }),
+];
+
+/// How many bytes of a resource are needed to recognize its media type.
+pub(crate) const SNIFF_LEN: usize = 512;
+
+/// Whether a resource whose first bytes are `bytes` has the media type
+/// `content_type`, or `None` if its media type can't be recognized.
+pub(crate) fn matches(content_type: &str, bytes: &[u8]) -> Option<bool> {
+    let media_type = essence(content_type);
+    SIGNATURES
+        .iter()
+        .find(|(t, _)| *t == media_type)
+        .map(|(_, matches)| matches(bytes))
+}
+
+// The first byte that isn't whitespace or part of a UTF-8 byte order mark.
+fn first_non_whitespace(bytes: &[u8]) -> Option<u8> {
+    bytes
+        .strip_prefix(b"\xef\xbb\xbf")
+        .unwrap_or(bytes)
+        .iter()
+        .copied()
+        .find(|b| !b.is_ascii_whitespace())
+}
+
+// Any XML document can be read as GEDCOM X, so XML is only guessed to be
+// GEDCOM X if its first bytes declare the namespace.
+const GEDCOMX_NAMESPACE: &[u8] = b"http://gedcomx.org/v1/";
+
+/// Guess the media type of a resource from its first bytes. JSON, and XML
+/// that declares the GEDCOM X namespace, are assumed to be GEDCOM X
+/// documents.
+pub(crate) fn sniff(bytes: &[u8]) -> Option<&'static str> {
+    SIGNATURES
+        .iter()
+        .find(|(media_type, matches)| {
+            matches(bytes)
+                && (*media_type != GedcomxFormat::Xml.content_type()
+                    || bytes
+                        .windows(GEDCOMX_NAMESPACE.len())
+                        .any(|w| w == GEDCOMX_NAMESPACE))
+        })
+        .map(|(media_type, _)| *media_type)
+}
+
+/// Guess the media type of a resource from the extension of its name.
+pub(crate) fn media_type_for_name(name: &str) -> Option<&'static str> {
+    let extension = Path::new(name).extension()?.to_str()?.to_ascii_lowercase();
+    let media_type = match extension.as_str() {
+        "json" => GedcomxFormat::Json.content_type(),
+        "xml" => GedcomxFormat::Xml.content_type(),
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "tif" | "tiff" => "image/tiff",
+        "pdf" => "application/pdf",
+        "txt" => "text/plain",
+        _ => return None,
+    };
+    Some(media_type)
+}
+
+/// The media type `content_type` without its parameters, in lowercase.
+pub(crate) fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// The width and height of a PNG, JPEG or GIF image whose first bytes are
+/// `bytes`, or `None` if they don't contain them. A JPEG's dimensions can be
+/// far from its start, after its metadata.
+pub(crate) fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    match sniff(bytes)? {
+        "image/png" => {
+            // The IHDR chunk is always first.
+            let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+            let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+            Some((width, height))
+        }
+        "image/gif" => {
+            let width = u16::from_le_bytes(bytes.get(6..8)?.try_into().ok()?);
+            let height = u16::from_le_bytes(bytes.get(8..10)?.try_into().ok()?);
+            Some((width.into(), height.into()))
+        }
+        "image/jpeg" => jpeg_dimensions(bytes),
+        _ => None,
+    }
+}
+
+// Find the dimensions in the start of frame segment of a JPEG, skipping the
+// segments before it.
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |i: usize| -> Option<u16> {
+        Some(u16::from_be_bytes(bytes.get(i..i + 2)?.try_into().ok()?))
+    };
+
+    let mut i = 2;
+    loop {
+        if *bytes.get(i)? != 0xff {
+            return None;
+        }
+        let marker = *bytes.get(i + 1)?;
+        match marker {
+            // Fill bytes before a marker.
+            0xff => i += 1,
+            // Markers without a length.
+            0x01 | 0xd0..=0xd8 => i += 2,
+            // Start of frame, except DHT, JPG and DAC, which share the range.
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                let height = be16(i + 5)?;
+                let width = be16(i + 7)?;
+                return Some((width.into(), height.into()));
+            }
+            _ => i += 2 + usize::from(be16(i + 2)?),
+        }
+    }
+}
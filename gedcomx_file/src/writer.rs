@@ -0,0 +1,363 @@
+use std::{
+    io::{self, Read, Seek, Write},
+    path::{Component, Path},
+};
+
+use gedcomx::Gedcomx;
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    DIGEST_ATTRIBUTE, GedcomxFileError, GedcomxManifest, MANIFEST_STR, ManifestSection,
+    digest::DigestWriter,
+};
+
+/// Formats a GEDCOM X document can be written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GedcomxFormat {
+    /// The GEDCOM X JSON format, with content type
+    /// `application/x-gedcomx-v1+json`.
+    Json,
+
+    /// The GEDCOM X XML format, with content type
+    /// `application/x-gedcomx-v1+xml`.
+    Xml,
+}
+
+impl GedcomxFormat {
+    /// The media type of documents in this format.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/x-gedcomx-v1+json",
+            Self::Xml => "application/x-gedcomx-v1+xml",
+        }
+    }
+
+    /// Parse a document in this format.
+    pub(crate) fn read<R: Read>(self, reader: R) -> Result<Gedcomx, gedcomx::GedcomxError> {
+        match self {
+            Self::Json => Gedcomx::from_json_reader(reader),
+            Self::Xml => Gedcomx::from_xml_reader(reader),
+        }
+    }
+
+    /// The format of documents with the media type `content_type`, ignoring
+    /// any parameters such as `charset`.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let media_type = content_type.split(';').next().unwrap_or_default().trim();
+        [Self::Json, Self::Xml]
+            .into_iter()
+            .find(|f| f.content_type().eq_ignore_ascii_case(media_type))
+    }
+}
+
+/// Writes a GEDCOM X file.
+///
+/// Resources are written to the file as they're added, and the manifest
+/// describing them is written by [`finish`](Self::finish), so it's the last
+/// entry of the file. The manifest records the `SHA-256-Digest` of each
+/// resource, which
+/// [`GedcomxFile::check_integrity`](crate::GedcomxFile::check_integrity)
+/// checks.
+///
+/// # Examples
+/// ```
+/// use std::io::Cursor;
+///
+/// use gedcomx::Gedcomx;
+/// use gedcomx_file::{GedcomxFile, GedcomxFileWriter, GedcomxFormat};
+///
+/// let mut writer = GedcomxFileWriter::new(Cursor::new(vec![]));
+/// writer
+///     .add_gedcomx("tree.xml", &Gedcomx::default(), GedcomxFormat::Xml, &[])
+///     .unwrap();
+/// writer
+///     .add_resource(
+///         "person1.png",
+///         "image/png",
+///         &[("X-DC-modified", "2014-10-07T21:15:57.161Z")],
+///         &[0x89, b'P', b'N', b'G'][..],
+///     )
+///     .unwrap();
+/// let cursor = writer.finish().unwrap();
+///
+/// let mut file = GedcomxFile::from_reader(cursor).unwrap();
+/// let attributes = file.attributes_by_name("person1.png").unwrap();
+/// assert_eq!(attributes["Content-Type"], "image/png");
+/// ```
+pub struct GedcomxFileWriter<W: Write + Seek> {
+    inner: ZipWriter<W>,
+    manifest: GedcomxManifest,
+}
+
+impl<W: Write + Seek> GedcomxFileWriter<W> {
+    /// Create a writer that writes a GEDCOM X file to `writer`.
+    pub fn new(writer: W) -> Self {
+        let mut writer = Self {
+            inner: ZipWriter::new(writer),
+            manifest: GedcomxManifest::new(),
+        };
+        writer.created_by(format!(
+            "{} {}",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        ));
+        writer
+    }
+
+    /// Set the `Created-By` attribute of the manifest, which names the
+    /// application that created the file. Defaults to this crate.
+    /// Line breaks in `created_by` are replaced with spaces, since they can't
+    /// be written to the manifest.
+    pub fn created_by<I: Into<String>>(&mut self, created_by: I) -> &mut Self {
+        let created_by = created_by.into().replace(['\r', '\n', '\0'], " ");
+        self.manifest
+            .main_attributes_mut()
+            .set("Created-By", created_by)
+            .expect("Created-By is a valid attribute");
+        self
+    }
+
+    /// Add a GEDCOM X document to the file, along with any other attributes
+    /// for its section of the manifest, such as `X-DC-modified`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::GedcomxError`] if the document can't
+    /// be serialized, or any of the errors of
+    /// [`add_resource`](Self::add_resource).
+    pub fn add_gedcomx(
+        &mut self,
+        name: &str,
+        gx: &Gedcomx,
+        format: GedcomxFormat,
+        attributes: &[(&str, &str)],
+    ) -> Result<(), GedcomxFileError> {
+        let document = match format {
+            GedcomxFormat::Json => gx.to_json_string()?,
+            GedcomxFormat::Xml => gx.to_xml_string()?,
+        };
+        self.add_resource(name, format.content_type(), attributes, document.as_bytes())
+    }
+
+    /// Add a resource, such as an image, to the file. Its section of the
+    /// manifest gets its `Content-Type`, its `SHA-256-Digest`, and any other
+    /// `attributes`, such as
+    /// the `X-DC-*` attributes of the
+    /// [Standard Header Set](https://github.com/FamilySearch/gedcomx/blob/master/specifications/standard-header-set-specification.md).
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::InvalidResourceName`] if `name` isn't
+    /// a relative path within the file or is the name of the manifest,
+    /// [`GedcomxFileError::DuplicateResource`] if the file already has a
+    /// resource named `name`, [`GedcomxFileError::InvalidAttribute`] if an
+    /// attribute name or value isn't allowed in a manifest, or
+    /// [`GedcomxFileError::ZipError`] or [`GedcomxFileError::IoError`] if
+    /// writing fails.
+    pub fn add_resource<R: Read>(
+        &mut self,
+        name: &str,
+        content_type: &str,
+        attributes: &[(&str, &str)],
+        mut reader: R,
+    ) -> Result<(), GedcomxFileError> {
+        if !is_valid_resource_name(name) {
+            return Err(GedcomxFileError::InvalidResourceName(name.to_string()));
+        }
+        if self.manifest.section(name).is_some() {
+            return Err(GedcomxFileError::DuplicateResource(name.to_string()));
+        }
+
+        let mut section = ManifestSection::new();
+        section.set("Content-Type", content_type)?;
+        for (attribute, value) in attributes {
+            if attribute.eq_ignore_ascii_case("Content-Type")
+                || attribute.eq_ignore_ascii_case(DIGEST_ATTRIBUTE)
+            {
+                return Err(GedcomxFileError::InvalidAttribute((*attribute).to_string()));
+            }
+            section.set(attribute, *value)?;
+        }
+
+        self.inner.start_file(name, SimpleFileOptions::default())?;
+        let mut writer = DigestWriter::new(&mut self.inner);
+        io::copy(&mut reader, &mut writer)?;
+        section.set(DIGEST_ATTRIBUTE, writer.finish())?;
+        *self.manifest.add_section(name)? = section;
+
+        Ok(())
+    }
+
+    /// Write the manifest and finish the file, returning the underlying
+    /// writer.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`GedcomxFileError::ZipError`] or
+    /// [`GedcomxFileError::IoError`] if writing fails.
+    pub fn finish(mut self) -> Result<W, GedcomxFileError> {
+        self.inner
+            .start_file(MANIFEST_STR, SimpleFileOptions::default())?;
+        self.manifest.write_to(&mut self.inner)?;
+        Ok(self.inner.finish()?)
+    }
+}
+
+// Resource names are relative paths that stay within the file, so they can't
+// overwrite anything when the file is extracted.
+pub(crate) fn is_valid_resource_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != MANIFEST_STR
+        && !name.ends_with('/')
+        && Path::new(name)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use gedcomx::Person;
+
+    use super::*;
+    use crate::{GEDCOMX_RESOURCE_NAME, GedcomxFile, GedcomxFileEntry};
+
+    fn gedcomx() -> Gedcomx {
+        Gedcomx::builder()
+            .person(Person::builder().id("P-1").build())
+            .build()
+    }
+
+    #[test]
+    fn write_and_read() {
+        let mut writer = GedcomxFileWriter::new(Cursor::new(vec![]));
+        writer.created_by("Test Suite 1.0");
+        writer
+            .add_gedcomx(
+                "tree.json",
+                &gedcomx(),
+                GedcomxFormat::Json,
+                &[("X-DC-modified", "2014-10-07T21:15:57.148Z")],
+            )
+            .unwrap();
+        writer
+            .add_gedcomx("tree.xml", &gedcomx(), GedcomxFormat::Xml, &[])
+            .unwrap();
+        writer
+            .add_resource(
+                "images/person1.png",
+                "image/png",
+                &[("X-DC-title", "Portrait")],
+                &b"not really a png"[..],
+            )
+            .unwrap();
+        let cursor = writer.finish().unwrap();
+
+        let mut file = GedcomxFile::from_reader(cursor).unwrap();
+        assert_eq!(file.len(), 4);
+
+        for name in ["tree.json", "tree.xml"] {
+            match file.by_name(name).unwrap() {
+                GedcomxFileEntry::Gedcomx(gx) => assert_eq!(*gx, gedcomx()),
+                _ => panic!("{name} isn't a GEDCOM X document"),
+            }
+        }
+        match file.by_name("images/person1.png").unwrap() {
+            GedcomxFileEntry::Reader(mut r) => {
+                let mut bytes = vec![];
+                r.read_to_end(&mut bytes).unwrap();
+                assert_eq!(bytes, b"not really a png");
+            }
+            _ => panic!("images/person1.png isn't a reader"),
+        }
+
+        let main = file.attributes_by_name(GEDCOMX_RESOURCE_NAME).unwrap();
+        assert_eq!(main["Manifest-Version"], "1.0");
+        assert_eq!(main["Created-By"], "Test Suite 1.0");
+
+        let json = file.attributes_by_name("tree.json").unwrap();
+        assert_eq!(json["Content-Type"], "application/x-gedcomx-v1+json");
+        assert_eq!(json["X-DC-modified"], "2014-10-07T21:15:57.148Z");
+
+        let xml = file.attributes_by_name("tree.xml").unwrap();
+        assert_eq!(xml["Content-Type"], "application/x-gedcomx-v1+xml");
+
+        let image = file.attributes_by_name("images/person1.png").unwrap();
+        assert_eq!(image["Content-Type"], "image/png");
+        assert_eq!(image["X-DC-title"], "Portrait");
+        assert_eq!(
+            image[DIGEST_ATTRIBUTE],
+            "6QE3053jBO77vniLxTXH6C8nq7+AaVBfu9ip3NxPICQ="
+        );
+
+        assert!(file.check_integrity().unwrap().is_empty());
+    }
+
+    #[test]
+    fn duplicate_resource() {
+        let mut writer = GedcomxFileWriter::new(Cursor::new(vec![]));
+        writer
+            .add_resource("a.txt", "text/plain", &[], &b"a"[..])
+            .unwrap();
+        assert!(matches!(
+            writer.add_resource("a.txt", "text/plain", &[], &b"a"[..]),
+            Err(GedcomxFileError::DuplicateResource(name)) if name == "a.txt"
+        ));
+    }
+
+    #[test]
+    fn invalid_resource_names() {
+        let mut writer = GedcomxFileWriter::new(Cursor::new(vec![]));
+        for name in [
+            "",
+            "/etc/passwd",
+            "../a.txt",
+            "a/../../b.txt",
+            "dir/",
+            MANIFEST_STR,
+        ] {
+            assert!(
+                matches!(
+                    writer.add_resource(name, "text/plain", &[], &b"a"[..]),
+                    Err(GedcomxFileError::InvalidResourceName(_))
+                ),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_attributes() {
+        let mut writer = GedcomxFileWriter::new(Cursor::new(vec![]));
+        for attribute in [
+            "",
+            "X DC",
+            "X-DC:title",
+            "Name",
+            "Content-Type",
+            DIGEST_ATTRIBUTE,
+        ] {
+            assert!(
+                matches!(
+                    writer.add_resource("a.txt", "text/plain", &[(attribute, "a")], &b"a"[..]),
+                    Err(GedcomxFileError::InvalidAttribute(_))
+                ),
+                "{attribute}"
+            );
+        }
+        assert!(matches!(
+            writer.add_resource(
+                "a.txt",
+                "text/plain",
+                &[("X-DC-title", "a\r\nName: b")],
+                &b"a"[..]
+            ),
+            Err(GedcomxFileError::InvalidAttribute(_))
+        ));
+        assert!(matches!(
+            writer.add_resource("a.txt", "text/plain\n", &[], &b"a"[..]),
+            Err(GedcomxFileError::InvalidAttribute(_))
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+use std::fmt::Write;
+
+use gedcomx::{
+    Date, GedcomxDate, Timestamp,
+    gedcomx_date::{self, DateTimeOrDuration},
+};
+
+use crate::{Version, WarningKind};
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// Convert a GEDCOM date value into a GEDCOM X `Date`.
+///
+/// The GEDCOM value is always kept as the original. A formal date is added
+/// when the value is a Gregorian date or date range. Date phrases (text in
+/// parentheses) only have an original. If the value can't be parsed, a warning
+/// kind is returned along with the date.
+pub fn from_gedcom(value: &str) -> (Date, Option<WarningKind>) {
+    let original = value.trim();
+    let (formal, warning) = match formal(original) {
+        Ok(formal) => (formal, None),
+        Err(warning) => (None, Some(warning)),
+    };
+
+    (Date::new(Some(original), formal), warning)
+}
+
+fn formal(value: &str) -> Result<Option<GedcomxDate>, WarningKind> {
+    let upper = value.to_uppercase();
+    let mut tokens: Vec<&str> = upper.split_whitespace().collect();
+
+    if tokens.first().is_some_and(|t| t.starts_with('(')) {
+        return Ok(None);
+    }
+    // GEDCOM 5.5.1 calendars are escapes like `@#DJULIAN@`, GEDCOM 7.0
+    // calendars are plain keywords.
+    if let Some(calendar) = tokens.first().filter(|t| {
+        t.starts_with("@#")
+            || t.starts_with('_')
+            || matches!(**t, "GREGORIAN" | "JULIAN" | "FRENCH_R" | "HEBREW")
+    }) {
+        if !matches!(*calendar, "@#DGREGORIAN@" | "GREGORIAN") {
+            return Err(WarningKind::UnsupportedCalendar(value.to_string()));
+        }
+        tokens.remove(0);
+    }
+    // An interpreted date keeps the phrase it was interpreted from, which
+    // is already preserved in the original.
+    if let Some(phrase) = tokens.iter().position(|t| t.starts_with('(')) {
+        tokens.truncate(phrase);
+    }
+
+    let unparsed = || WarningKind::UnparsedDate(value.to_string());
+    let simple = |tokens: &[&str]| simple(tokens).ok_or_else(unparsed);
+
+    let formal = match tokens.as_slice() {
+        ["ABT" | "CAL" | "EST", rest @ ..] => format!("A{}", simple(rest)?),
+        ["BEF" | "TO", rest @ ..] => format!("/{}", simple(rest)?),
+        ["AFT", rest @ ..] => format!("{}/", simple(rest)?),
+        ["BET" | "FROM", rest @ ..] => {
+            let separator = if tokens[0] == "BET" { "AND" } else { "TO" };
+            match rest.iter().position(|t| *t == separator) {
+                Some(i) => format!("{}/{}", simple(&rest[..i])?, simple(&rest[i + 1..])?),
+                None if tokens[0] == "FROM" => format!("{}/", simple(rest)?),
+                None => return Err(unparsed()),
+            }
+        }
+        // INT dates are interpreted from a phrase, which was removed above.
+        ["INT", rest @ ..] | rest => simple(rest)?,
+    };
+
+    formal.parse().map(Some).map_err(|_| unparsed())
+}
+
+/// Convert the date and time of a `CHAN` structure into a timestamp. The
+/// date must be an exact date, and the time is `hh:mm[:ss[.fs]]`.
+pub fn timestamp(date: &str, time: Option<&str>) -> Option<Timestamp> {
+    let upper = date.to_uppercase();
+    let tokens: Vec<&str> = upper.split_whitespace().collect();
+    let [_, _, _] = tokens.as_slice() else {
+        return None;
+    };
+    let date = simple(&tokens)?;
+
+    let time = time.unwrap_or("00:00:00").trim();
+    let time = match time.split(':').count() {
+        2 => format!("{time}:00"),
+        _ => time.to_string(),
+    };
+
+    format!("{}T{time}", date.trim_start_matches('+'))
+        .parse()
+        .ok()
+}
+
+/// Convert a formal date into a GEDCOM date value.
+///
+/// Closed ranges are written as `BET .. AND ..`, or as `FROM .. TO ..` when
+/// `period` is set, since GEDCOM distinguishes a date somewhere in a range
+/// from a state that lasted for the whole range. Returns `None` for dates
+/// GEDCOM can't represent, such as recurring dates and durations.
+pub fn to_gedcom(formal: &GedcomxDate, period: bool, version: Version) -> Option<String> {
+    match &formal.0 {
+        gedcomx_date::GedcomxDate::Simple(simple) => {
+            let date = simple_to_gedcom(&simple.date, version)?;
+            Some(if simple.approximate {
+                format!("ABT {date}")
+            } else {
+                date
+            })
+        }
+        gedcomx_date::GedcomxDate::Range(range) => {
+            let start = match range.start {
+                Some(start) => Some(simple_to_gedcom(&start.date, version)?),
+                None => None,
+            };
+            let end = match range.end {
+                Some(DateTimeOrDuration::DateTime(end)) => {
+                    Some(simple_to_gedcom(&end.date, version)?)
+                }
+                Some(DateTimeOrDuration::Duration(_)) => return None,
+                None => None,
+            };
+
+            match (start, end) {
+                (Some(start), Some(end)) if period => Some(format!("FROM {start} TO {end}")),
+                (Some(start), Some(end)) => Some(format!("BET {start} AND {end}")),
+                (Some(start), None) if period => Some(format!("FROM {start}")),
+                (Some(start), None) => Some(format!("AFT {start}")),
+                (None, Some(end)) if period => Some(format!("TO {end}")),
+                (None, Some(end)) => Some(format!("BEF {end}")),
+                (None, None) => None,
+            }
+        }
+        gedcomx_date::GedcomxDate::Recurring(_) => None,
+    }
+}
+
+fn simple_to_gedcom(date: &gedcomx_date::Date, version: Version) -> Option<String> {
+    let mut gedcom = String::new();
+    if let Some(day) = date.day {
+        let _ = write!(gedcom, "{day} ");
+    }
+    if let Some(month) = date.month {
+        let month = usize::try_from(month).ok()?.checked_sub(1)?;
+        let _ = write!(gedcom, "{} ", MONTHS.get(month)?);
+    }
+    let epoch = match version {
+        Version::Gedcom551 => "B.C.",
+        Version::Gedcom70 => "BCE",
+    };
+    if date.year > 0 {
+        let _ = write!(gedcom, "{}", date.year);
+    } else {
+        let _ = write!(gedcom, "{} {epoch}", 1 - date.year);
+    }
+
+    Some(gedcom)
+}
+
+/// The GEDCOM date and time (`DATE` and `TIME` of a `CHAN` structure) for a
+/// timestamp.
+pub fn from_timestamp(timestamp: &Timestamp) -> Option<(String, String)> {
+    // Timestamps display as xsd:dateTime, `YYYY-MM-DDThh:mm:ss...`.
+    let s = timestamp.to_string();
+    let (date, time) = s.split_once('T')?;
+    let mut pieces = date.splitn(3, '-');
+    let year: i32 = pieces.next()?.parse().ok()?;
+    let month: usize = pieces.next()?.parse().ok()?;
+    let day: u32 = pieces.next()?.parse().ok()?;
+
+    Some((
+        format!("{day} {} {year}", MONTHS.get(month.checked_sub(1)?)?),
+        time.get(..8)?.to_string(),
+    ))
+}
+
+// A single date: `[[day] month] year [B.C.]`, formatted as a formal date
+// without any approximate marker.
+fn simple(tokens: &[&str]) -> Option<String> {
+    let (tokens, bce) = match tokens {
+        [rest @ .., "B.C." | "BC" | "BCE" | "(B.C.)"] => (rest, true),
+        _ => (tokens, false),
+    };
+
+    let (day, month, year) = match tokens {
+        [year] => (None, None, *year),
+        [month, year] => (None, Some(*month), *year),
+        [day, month, year] => (Some(*day), Some(*month), *year),
+        _ => return None,
+    };
+
+    // Dual dates like 1750/51 use the first year.
+    let year: i32 = year.split('/').next()?.parse().ok()?;
+    let year = if bce { 1 - year } else { year };
+    let month = match month {
+        Some(m) => Some(MONTHS.iter().position(|x| *x == m)? + 1),
+        None => None,
+    };
+    let day: Option<u32> = day.map(str::parse).transpose().ok()?;
+
+    let sign = if year < 0 { '-' } else { '+' };
+    let mut formal = format!("{sign}{:04}", year.unsigned_abs());
+    if let Some(month) = month {
+        let _ = write!(formal, "-{month:02}");
+    }
+    if let Some(day) = day {
+        let _ = write!(formal, "-{day:02}");
+    }
+
+    Some(formal)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn formal_string(value: &str) -> Option<String> {
+        let (date, warning) = from_gedcom(value);
+        assert_eq!(warning, None, "{value}");
+        assert_eq!(date.original.as_deref(), Some(value));
+        date.formal.map(|f| f.to_string())
+    }
+
+    #[test]
+    fn simple_dates() {
+        assert_eq!(formal_string("1850").as_deref(), Some("+1850"));
+        assert_eq!(formal_string("JAN 1850").as_deref(), Some("+1850-01"));
+        assert_eq!(formal_string("3 mar 1850").as_deref(), Some("+1850-03-03"));
+        assert_eq!(formal_string("1750/51").as_deref(), Some("+1750"));
+        assert_eq!(formal_string("44 B.C.").as_deref(), Some("-0043"));
+        assert_eq!(
+            formal_string("@#DGREGORIAN@ 1 JAN 1900").as_deref(),
+            Some("+1900-01-01")
+        );
+    }
+
+    #[test]
+    fn approximate_and_ranges() {
+        assert_eq!(formal_string("ABT 1850").as_deref(), Some("A+1850"));
+        assert_eq!(formal_string("EST 1850").as_deref(), Some("A+1850"));
+        assert_eq!(formal_string("BEF 1850").as_deref(), Some("/+1850"));
+        assert_eq!(formal_string("AFT 1850").as_deref(), Some("+1850/"));
+        assert_eq!(
+            formal_string("BET 1850 AND FEB 1860").as_deref(),
+            Some("+1850/+1860-02")
+        );
+        assert_eq!(
+            formal_string("FROM 1850 TO 1860").as_deref(),
+            Some("+1850/+1860")
+        );
+        assert_eq!(formal_string("FROM 1850").as_deref(), Some("+1850/"));
+        assert_eq!(formal_string("TO 1860").as_deref(), Some("/+1860"));
+    }
+
+    #[test]
+    fn phrases() {
+        assert_eq!(formal_string("(sometime in spring)"), None);
+        assert_eq!(
+            formal_string("INT 1850 (about the time of the war)").as_deref(),
+            Some("+1850")
+        );
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(
+            timestamp("12 JAN 2020", Some("10:20:30")),
+            Some("2020-01-12T10:20:30".parse().unwrap())
+        );
+        assert_eq!(
+            timestamp("12 Jan 2020", Some("10:20")),
+            Some("2020-01-12T10:20:00".parse().unwrap())
+        );
+        assert_eq!(
+            timestamp("12 JAN 2020", None),
+            Some("2020-01-12T00:00:00".parse().unwrap())
+        );
+        assert_eq!(timestamp("JAN 2020", None), None);
+        assert_eq!(timestamp("12 JAN 2020", Some("noon")), None);
+    }
+
+    fn gedcom_string(formal: &str, period: bool) -> Option<String> {
+        to_gedcom(&formal.parse().unwrap(), period, Version::Gedcom551)
+    }
+
+    #[test]
+    fn to_gedcom_dates() {
+        assert_eq!(gedcom_string("+1850", false).as_deref(), Some("1850"));
+        assert_eq!(
+            gedcom_string("+1850-03-04T10:00:00", false).as_deref(),
+            Some("4 MAR 1850")
+        );
+        assert_eq!(
+            gedcom_string("A+1850-03", false).as_deref(),
+            Some("ABT MAR 1850")
+        );
+        assert_eq!(gedcom_string("-0043", false).as_deref(), Some("44 B.C."));
+        assert_eq!(
+            gedcom_string("+1850/+1860", false).as_deref(),
+            Some("BET 1850 AND 1860")
+        );
+        assert_eq!(
+            gedcom_string("+1850/+1860", true).as_deref(),
+            Some("FROM 1850 TO 1860")
+        );
+        assert_eq!(gedcom_string("/+1860", false).as_deref(), Some("BEF 1860"));
+        assert_eq!(gedcom_string("+1850/", false).as_deref(), Some("AFT 1850"));
+        assert_eq!(gedcom_string("+1850/", true).as_deref(), Some("FROM 1850"));
+        assert_eq!(gedcom_string("+1850/P10Y", false), None);
+        assert_eq!(gedcom_string("R3/+1850/P1Y", false), None);
+    }
+
+    #[test]
+    fn roundtrip() {
+        for gedcom in [
+            "3 MAR 1850",
+            "ABT 1850",
+            "BET 1850 AND FEB 1860",
+            "FROM 1850 TO 1860",
+            "BEF 1850",
+            "AFT 1850",
+            "44 B.C.",
+        ] {
+            let (date, _) = from_gedcom(gedcom);
+            let period = gedcom.starts_with("FROM");
+            assert_eq!(
+                to_gedcom(date.formal.as_ref().unwrap(), period, Version::Gedcom551).as_deref(),
+                Some(gedcom)
+            );
+        }
+    }
+
+    #[test]
+    fn gedcom7_dates() {
+        assert_eq!(
+            formal_string("GREGORIAN 1 JAN 1900").as_deref(),
+            Some("+1900-01-01")
+        );
+        assert_eq!(formal_string("44 BCE").as_deref(), Some("-0043"));
+        assert_eq!(
+            to_gedcom(&"-0043".parse().unwrap(), false, Version::Gedcom70).as_deref(),
+            Some("44 BCE")
+        );
+
+        let (_, warning) = from_gedcom("JULIAN 1 JAN 1700");
+        assert!(matches!(warning, Some(WarningKind::UnsupportedCalendar(_))));
+    }
+
+    #[test]
+    fn timestamps_to_gedcom() {
+        let timestamp = timestamp("12 JAN 2020", Some("10:20:30")).unwrap();
+        assert_eq!(
+            from_timestamp(&timestamp),
+            Some(("12 JAN 2020".to_string(), "10:20:30".to_string()))
+        );
+    }
+
+    #[test]
+    fn unparsed() {
+        let (date, warning) = from_gedcom("32 JAN 1850");
+        assert_eq!(date.formal, None);
+        assert_eq!(
+            warning,
+            Some(WarningKind::UnparsedDate("32 JAN 1850".to_string()))
+        );
+
+        let (_, warning) = from_gedcom("@#DJULIAN@ 1 JAN 1700");
+        assert!(matches!(warning, Some(WarningKind::UnsupportedCalendar(_))));
+
+        let (_, warning) = from_gedcom("sometime");
+        assert!(matches!(warning, Some(WarningKind::UnparsedDate(_))));
+    }
+}
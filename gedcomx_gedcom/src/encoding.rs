@@ -0,0 +1,471 @@
+use crate::{Warning, WarningKind};
+
+/// Character encodings a GEDCOM file can be written in.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum Encoding {
+    /// UTF-8. Plain ASCII files are also read as UTF-8.
+    Utf8,
+
+    /// UTF-16, little endian. Called `UNICODE` in GEDCOM 5.5.1.
+    Utf16Le,
+
+    /// UTF-16, big endian. Called `UNICODE` in GEDCOM 5.5.1.
+    Utf16Be,
+
+    /// ANSEL (ANSI Z39.47), the default encoding of GEDCOM 5.5.1.
+    Ansel,
+
+    /// Windows-1252, written as `ANSI` by many desktop programs even though
+    /// the spec doesn't allow it.
+    Windows1252,
+}
+
+impl Encoding {
+    /// The value of the header's `CHAR` line for this encoding.
+    pub(crate) const fn char_value(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le | Self::Utf16Be => "UNICODE",
+            Self::Ansel => "ANSEL",
+            Self::Windows1252 => "ANSI",
+        }
+    }
+}
+
+/// Decode a GEDCOM file into a string.
+///
+/// The encoding is taken from the byte order mark if there is one, then from
+/// the `CHAR` line in the header. Characters that can't be decoded are
+/// replaced with U+FFFD and reported as warnings.
+pub fn decode(bytes: &[u8], warnings: &mut Vec<Warning>) -> (String, Encoding) {
+    let (encoding, bytes) = detect(bytes, warnings);
+
+    let text = match encoding {
+        Encoding::Utf8 => std::str::from_utf8(bytes).map_or_else(
+            |_| {
+                warnings.push(Warning::new(None, WarningKind::InvalidCharacters));
+                String::from_utf8_lossy(bytes).into_owned()
+            },
+            ToString::to_string,
+        ),
+        Encoding::Utf16Le | Encoding::Utf16Be => {
+            let units: Vec<u16> = bytes
+                .chunks(2)
+                .map(|c| {
+                    let pair = [c[0], c.get(1).copied().unwrap_or_default()];
+                    if encoding == Encoding::Utf16Le {
+                        u16::from_le_bytes(pair)
+                    } else {
+                        u16::from_be_bytes(pair)
+                    }
+                })
+                .collect();
+            String::from_utf16(&units).unwrap_or_else(|_| {
+                warnings.push(Warning::new(None, WarningKind::InvalidCharacters));
+                String::from_utf16_lossy(&units)
+            })
+        }
+        Encoding::Ansel => decode_ansel(bytes, warnings),
+        Encoding::Windows1252 => bytes.iter().map(|&b| windows_1252(b)).collect(),
+    };
+
+    (text, encoding)
+}
+
+fn detect<'a>(bytes: &'a [u8], warnings: &mut Vec<Warning>) -> (Encoding, &'a [u8]) {
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => return (Encoding::Utf8, rest),
+        [0xFF, 0xFE, rest @ ..] => return (Encoding::Utf16Le, rest),
+        [0xFE, 0xFF, rest @ ..] => return (Encoding::Utf16Be, rest),
+        [b'0', 0, ..] => return (Encoding::Utf16Le, bytes),
+        [0, b'0', ..] => return (Encoding::Utf16Be, bytes),
+        _ => {}
+    }
+
+    // The header is always ASCII, so the CHAR line can be found before
+    // decoding anything else.
+    let charset = bytes
+        .split(|&b| b == b'\n' || b == b'\r')
+        .map(|line| String::from_utf8_lossy(line).trim().to_string())
+        .take_while(|line| line == "0 HEAD" || !line.starts_with('0') || line.is_empty())
+        .find_map(|line| line.strip_prefix("1 CHAR ").map(str::to_uppercase));
+
+    let encoding = match charset.as_deref() {
+        None | Some("UTF-8" | "UTF8" | "ASCII") => Encoding::Utf8,
+        Some("ANSEL") => Encoding::Ansel,
+        Some("ANSI" | "WINDOWS-1252" | "CP1252" | "ISO-8859-1" | "LATIN1") => Encoding::Windows1252,
+        // UNICODE files without a byte order mark are almost always UTF-16
+        // files that were re-encoded by some other tool.
+        Some("UNICODE") => {
+            if std::str::from_utf8(bytes).is_ok() {
+                Encoding::Utf8
+            } else {
+                Encoding::Utf16Le
+            }
+        }
+        Some(other) => {
+            let encoding = if std::str::from_utf8(bytes).is_ok() {
+                Encoding::Utf8
+            } else {
+                Encoding::Windows1252
+            };
+            warnings.push(Warning::new(
+                None,
+                WarningKind::UnknownEncoding(other.to_string()),
+            ));
+            encoding
+        }
+    };
+
+    (encoding, bytes)
+}
+
+// ANSEL puts combining diacritics before the character they modify, while
+// Unicode puts them after. Diacritics are moved after the base character and
+// composed with it where there is a precomposed Unicode character.
+fn decode_ansel(bytes: &[u8], warnings: &mut Vec<Warning>) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    let mut pending_marks = vec![];
+    let mut invalid = false;
+
+    for &b in bytes {
+        if let Some(mark) = ansel_combining(b) {
+            pending_marks.push(mark);
+            continue;
+        }
+
+        let c = if b < 0x80 {
+            char::from(b)
+        } else if let Some(c) = ansel_spacing(b) {
+            c
+        } else {
+            invalid = true;
+            char::REPLACEMENT_CHARACTER
+        };
+
+        let mut c = c;
+        let mut marks = std::mem::take(&mut pending_marks).into_iter();
+        // Marks are stored in the order they should be applied, nearest the
+        // base character first.
+        for mark in marks.by_ref() {
+            if let Some(composed) = compose(c, mark) {
+                c = composed;
+            } else {
+                out.push(c);
+                c = mark;
+                break;
+            }
+        }
+        out.push(c);
+        out.extend(marks);
+    }
+    out.extend(pending_marks);
+
+    if invalid {
+        warnings.push(Warning::new(None, WarningKind::InvalidCharacters));
+    }
+
+    out
+}
+
+fn ansel_spacing(b: u8) -> Option<char> {
+    Some(match b {
+        0xA1 => 'Ł',
+        0xA2 => 'Ø',
+        0xA3 => 'Đ',
+        0xA4 => 'Þ',
+        0xA5 => 'Æ',
+        0xA6 => 'Œ',
+        0xA7 => 'ʹ',
+        0xA8 => '·',
+        0xA9 => '♭',
+        0xAA => '®',
+        0xAB => '±',
+        0xAC => 'Ơ',
+        0xAD => 'Ư',
+        0xAE => 'ʼ',
+        0xB0 => 'ʻ',
+        0xB1 => 'ł',
+        0xB2 => 'ø',
+        0xB3 => 'đ',
+        0xB4 => 'þ',
+        0xB5 => 'æ',
+        0xB6 => 'œ',
+        0xB7 => 'ʺ',
+        0xB8 => 'ı',
+        0xB9 => '£',
+        0xBA => 'ð',
+        0xBC => 'ơ',
+        0xBD => 'ư',
+        0xBE => '□',
+        0xBF => '■',
+        0xC0 => '°',
+        0xC1 => 'ℓ',
+        0xC2 => '℗',
+        0xC3 => '©',
+        0xC4 => '♯',
+        0xC5 => '¿',
+        0xC6 => '¡',
+        0xC7 | 0xCF => 'ß',
+        0xC8 => '€',
+        0xCD => 'e',
+        0xCE => 'o',
+        _ => return None,
+    })
+}
+
+fn ansel_combining(b: u8) -> Option<char> {
+    Some(match b {
+        0xE0 => '\u{0309}',
+        0xE1 => '\u{0300}',
+        0xE2 => '\u{0301}',
+        0xE3 => '\u{0302}',
+        0xE4 => '\u{0303}',
+        0xE5 => '\u{0304}',
+        0xE6 => '\u{0306}',
+        0xE7 => '\u{0307}',
+        0xE8 => '\u{0308}',
+        0xE9 => '\u{030C}',
+        0xEA => '\u{030A}',
+        0xEB => '\u{FE20}',
+        0xEC => '\u{FE21}',
+        0xED => '\u{0315}',
+        0xEE => '\u{030B}',
+        0xEF => '\u{0310}',
+        0xF0 => '\u{0327}',
+        0xF1 => '\u{0328}',
+        0xF2 => '\u{0323}',
+        0xF3 => '\u{0324}',
+        0xF4 => '\u{0325}',
+        0xF5 => '\u{0333}',
+        0xF6 => '\u{0332}',
+        0xF7 => '\u{0326}',
+        0xF8 => '\u{031C}',
+        0xF9 => '\u{032E}',
+        0xFA => '\u{FE22}',
+        0xFB => '\u{FE23}',
+        0xFE => '\u{0313}',
+        _ => return None,
+    })
+}
+
+// For each combining mark, the base characters it composes with and the
+// resulting precomposed characters, in the same order.
+const COMPOSITIONS: &[(char, &str, &str)] = &[
+    ('\u{0300}', "AEIOUaeiouNnWwYy", "ÀÈÌÒÙàèìòùǸǹẀẁỲỳ"),
+    (
+        '\u{0301}',
+        "AEIOUYaeiouyCcGgNnSsZzRrLlWw",
+        "ÁÉÍÓÚÝáéíóúýĆćǴǵŃńŚśŹźŔŕĹĺẂẃ",
+    ),
+    (
+        '\u{0302}',
+        "AEIOUaeiouCcGgHhJjSsWwYy",
+        "ÂÊÎÔÛâêîôûĈĉĜĝĤĥĴĵŜŝŴŵŶŷ",
+    ),
+    ('\u{0303}', "ANOaonIiUuEeYy", "ÃÑÕãñõĨĩŨũẼẽỸỹ"),
+    ('\u{0304}', "AEIOUaeiou", "ĀĒĪŌŪāēīōū"),
+    ('\u{0306}', "AEGIOUaegiou", "ĂĔĞĬŎŬăĕğĭŏŭ"),
+    ('\u{0307}', "CEGIZcegz", "ĊĖĠİŻċėġż"),
+    ('\u{0308}', "AEIOUaeiouyYWwXxt", "ÄËÏÖÜäëïöüÿŸẄẅẌẍẗ"),
+    ('\u{030A}', "AaUuwy", "ÅåŮůẘẙ"),
+    ('\u{030B}', "OoUu", "ŐőŰű"),
+    (
+        '\u{030C}',
+        "CcDdEeNnRrSsTtZzAaIiOoUuGgKk",
+        "ČčĎďĚěŇňŘřŠšŤťŽžǍǎǏǐǑǒǓǔǦǧǨǩ",
+    ),
+    ('\u{0327}', "CcGgKkLlNnRrSsTtEe", "ÇçĢģĶķĻļŅņŖŗŞşŢţȨȩ"),
+    ('\u{0328}', "AaEeIiUuOo", "ĄąĘęĮįŲųǪǫ"),
+];
+
+fn compose(base: char, mark: char) -> Option<char> {
+    let (_, bases, composed) = COMPOSITIONS.iter().find(|(m, _, _)| *m == mark)?;
+    let index = bases.chars().position(|c| c == base)?;
+    composed.chars().nth(index)
+}
+
+fn windows_1252(b: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž',
+        '\u{8F}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}',
+        'ž', 'Ÿ',
+    ];
+
+    match b {
+        0x80..=0x9F => HIGH[usize::from(b - 0x80)],
+        _ => char::from(b),
+    }
+}
+
+/// Encode a string for a GEDCOM file. UTF-16 output starts with a byte order
+/// mark. Characters the encoding can't represent are replaced with `?` and
+/// reported as a warning.
+pub fn encode(s: &str, encoding: Encoding, warnings: &mut Vec<Warning>) -> Vec<u8> {
+    let mut invalid = false;
+    let bytes = match encoding {
+        Encoding::Utf8 => s.as_bytes().to_vec(),
+        Encoding::Utf16Le => std::iter::once('\u{FEFF}')
+            .chain(s.chars())
+            .collect::<String>()
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect(),
+        Encoding::Utf16Be => std::iter::once('\u{FEFF}')
+            .chain(s.chars())
+            .collect::<String>()
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect(),
+        Encoding::Ansel => encode_ansel(s, &mut invalid),
+        Encoding::Windows1252 => s
+            .chars()
+            .map(|c| {
+                (0..=0xFF)
+                    .find(|&b| windows_1252(b) == c)
+                    .unwrap_or_else(|| {
+                        invalid = true;
+                        b'?'
+                    })
+            })
+            .collect(),
+    };
+
+    if invalid {
+        warnings.push(Warning::new(None, WarningKind::InvalidCharacters));
+    }
+    bytes
+}
+
+fn encode_ansel(s: &str, invalid: &mut bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        if let Some(b) = u8::try_from(c).ok().filter(u8::is_ascii) {
+            out.push(b);
+        } else if let Some(b) = (0xA1..=0xCF).find(|&b| ansel_spacing(b) == Some(c)) {
+            out.push(b);
+        } else if let Some(b) = (0xE0..=0xFE).find(|&b| ansel_combining(b) == Some(c)) {
+            out.push(b);
+        } else if let Some((base, mark)) = decompose(c) {
+            // ANSEL puts combining marks before the character they modify.
+            out.extend((0xE0..=0xFE).find(|&b| ansel_combining(b) == Some(mark)));
+            out.push(u8::try_from(base).unwrap_or(b'?'));
+        } else {
+            *invalid = true;
+            out.push(b'?');
+        }
+    }
+    out
+}
+
+fn decompose(c: char) -> Option<(char, char)> {
+    COMPOSITIONS.iter().find_map(|(mark, bases, composed)| {
+        let index = composed.chars().position(|x| x == c)?;
+        bases.chars().nth(index).map(|base| (base, *mark))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn decode_str(bytes: &[u8]) -> (String, Encoding, Vec<Warning>) {
+        let mut warnings = vec![];
+        let (s, e) = decode(bytes, &mut warnings);
+        (s, e, warnings)
+    }
+
+    #[test]
+    fn composition_tables_line_up() {
+        for (mark, bases, composed) in COMPOSITIONS {
+            assert_eq!(bases.chars().count(), composed.chars().count(), "{mark:?}");
+        }
+    }
+
+    #[test]
+    fn ansel() {
+        let bytes =
+            b"0 HEAD\n1 CHAR ANSEL\n0 @I1@ INDI\n1 NAME Ren\xE2ee /M\xE8uller/ \xA1od\xB8\n";
+        let (s, encoding, warnings) = decode_str(bytes);
+        assert_eq!(encoding, Encoding::Ansel);
+        assert!(warnings.is_empty());
+        assert!(s.ends_with("1 NAME Renée /Müller/ Łodı\n"));
+
+        let mut warnings = vec![];
+        let encoded = encode("Renée /Müller/ Łodı", Encoding::Ansel, &mut warnings);
+        assert_eq!(encoded, b"Ren\xE2ee /M\xE8uller/ \xA1od\xB8");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn ansel_uncomposable_mark() {
+        let (s, _, _) = decode_str(b"0 HEAD\n1 CHAR ANSEL\n1 NAME \xF2q\n");
+        assert!(s.ends_with("1 NAME q\u{0323}\n"));
+    }
+
+    #[test]
+    fn utf16() {
+        let text = "0 HEAD\n1 CHAR UNICODE\n1 NAME Zoë\n";
+        let le: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let be: Vec<u8> = [0xFE, 0xFF]
+            .into_iter()
+            .chain(text.encode_utf16().flat_map(u16::to_be_bytes))
+            .collect();
+
+        assert_eq!(decode_str(&le).0, text);
+        assert_eq!(decode_str(&le).1, Encoding::Utf16Le);
+        assert_eq!(decode_str(&be).0, text);
+        assert_eq!(decode_str(&be).1, Encoding::Utf16Be);
+    }
+
+    #[test]
+    fn utf8_bom() {
+        let (s, encoding, _) = decode_str(b"\xEF\xBB\xBF0 HEAD\n1 CHAR UTF-8\n");
+        assert_eq!(encoding, Encoding::Utf8);
+        assert_eq!(s, "0 HEAD\n1 CHAR UTF-8\n");
+    }
+
+    #[test]
+    fn windows_1252_and_unknown() {
+        let (s, encoding, _) = decode_str(b"0 HEAD\n1 CHAR ANSI\n1 NAME Jos\xE9 \x93x\x94\n");
+        assert_eq!(encoding, Encoding::Windows1252);
+        assert!(s.ends_with("José “x”\n"));
+
+        let (_, encoding, warnings) = decode_str(b"0 HEAD\n1 CHAR IBMPC\n0 TRLR\n");
+        assert_eq!(encoding, Encoding::Utf8);
+        assert_eq!(
+            warnings[0].kind,
+            WarningKind::UnknownEncoding("IBMPC".to_string())
+        );
+    }
+
+    #[test]
+    fn encode_roundtrip() {
+        for encoding in [
+            Encoding::Utf8,
+            Encoding::Utf16Le,
+            Encoding::Utf16Be,
+            Encoding::Windows1252,
+        ] {
+            let text = format!(
+                "0 HEAD\n1 CHAR {}\n1 NAME José “x”\n",
+                encoding.char_value()
+            );
+            let mut warnings = vec![];
+            let bytes = encode(&text, encoding, &mut warnings);
+            assert!(warnings.is_empty());
+            let (s, _, _) = decode_str(&bytes);
+            assert_eq!(s, text, "{encoding:?}");
+        }
+
+        let mut warnings = vec![];
+        assert_eq!(encode("a≠b", Encoding::Windows1252, &mut warnings), b"a?b");
+        assert_eq!(
+            warnings,
+            vec![Warning::new(None, WarningKind::InvalidCharacters)]
+        );
+    }
+}
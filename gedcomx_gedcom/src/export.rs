@@ -0,0 +1,1401 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write as _,
+    io::Write,
+};
+
+use gedcomx::{
+    Address, Agent, Attribution, Date, Entity, EntityKind, EventRoleType, Fact, FactQualifier,
+    FactType, Gedcomx, GenderType, Id, Name, NameForm, NamePartType, NameType, Note, Person,
+    ReferenceIndex, Relationship, RelationshipType, ResourceReference, ResourceType,
+    SourceDescription, SourceReference, Uri,
+};
+
+use crate::{
+    Encoding, Result, Version, Warning, WarningKind, date, encoding, tags,
+    tree::{self, Node},
+};
+
+/// The result of exporting a GEDCOM file.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct Export {
+    /// The GEDCOM file, declared as UTF-8.
+    pub gedcom: String,
+
+    /// Data that couldn't be converted exactly, in document order.
+    pub warnings: Vec<Warning>,
+}
+
+/// Export a document as a GEDCOM 5.5.1 file.
+///
+/// `FAM` records are created from the `Couple` and `ParentChild`
+/// relationships in the document. Anything with no GEDCOM equivalent is
+/// dropped and reported as a warning.
+pub fn to_string(gx: &Gedcomx) -> Export {
+    let mut exporter = Exporter::new(gx, Version::Gedcom551);
+    let gedcom = exporter.export(Encoding::Utf8);
+
+    Export {
+        gedcom,
+        warnings: exporter.warnings,
+    }
+}
+
+/// Export a document as a GEDCOM 5.5.1 file in the given encoding, returning
+/// the warnings.
+///
+/// # Errors
+///
+/// Will return [`GedcomError::IoError`](crate::GedcomError::IoError) if
+/// writing fails.
+pub fn to_writer<W: Write>(
+    gx: &Gedcomx,
+    mut writer: W,
+    encoding: Encoding,
+) -> Result<Vec<Warning>> {
+    let mut exporter = Exporter::new(gx, Version::Gedcom551);
+    let gedcom = exporter.export(encoding);
+    let bytes = encoding::encode(&gedcom, encoding, &mut exporter.warnings);
+    writer.write_all(&bytes)?;
+
+    Ok(exporter.warnings)
+}
+
+/// Export a document as a GEDCOM 7.0 file.
+///
+/// Works like [`to_string`], except that types with no GEDCOM equivalent,
+/// such as `http://gedcomx.org/Stillbirth` facts, are written as extension
+/// tags whose URIs are declared in the header's `SCHMA`, so they are read back
+/// as the same type. Events are written below each of their principals, with
+/// an `ASSO` for each of the other people in the event.
+pub fn to_gedcom7_string(gx: &Gedcomx) -> Export {
+    let mut exporter = Exporter::new(gx, Version::Gedcom70);
+    let gedcom = exporter.export(Encoding::Utf8);
+
+    Export {
+        gedcom,
+        warnings: exporter.warnings,
+    }
+}
+
+/// Export a document as a GEDCOM 7.0 file, returning the warnings. GEDCOM 7.0
+/// files are always UTF-8.
+///
+/// # Errors
+///
+/// Will return [`GedcomError::IoError`](crate::GedcomError::IoError) if
+/// writing fails.
+pub fn to_gedcom7_writer<W: Write>(gx: &Gedcomx, mut writer: W) -> Result<Vec<Warning>> {
+    let export = to_gedcom7_string(gx);
+    writer.write_all(export.gedcom.as_bytes())?;
+
+    Ok(export.warnings)
+}
+
+// How a source description is exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceKind {
+    // A SOUR record.
+    Source,
+
+    // An OBJE record.
+    Media,
+
+    // A PAGE within a citation of the source it's a component of, which is
+    // at the given index.
+    Citation(usize),
+}
+
+// A parent of a child, along with the pedigree (the `PEDI` value) of the
+// link between them.
+type Parent = (usize, Option<&'static str>);
+
+// A FAM record, made from a couple relationship or from the parents of a
+// child.
+struct Family<'a> {
+    xref: String,
+    couple: Option<(usize, &'a Relationship)>,
+    spouses: Vec<usize>,
+    children: Vec<Parent>,
+}
+
+// An event written below a principal, along with the people in its roles as
+// (role, person) positions.
+type EventRoles = (usize, Vec<(usize, usize)>);
+
+struct Exporter<'a> {
+    gx: &'a Gedcomx,
+    version: Version,
+    index: ReferenceIndex<'a>,
+    warnings: Vec<Warning>,
+
+    used_xrefs: HashSet<String>,
+    generated_xrefs: usize,
+
+    // Cross-reference ids for each person, source description and agent, by
+    // position in the document.
+    person_xrefs: Vec<String>,
+    source_xrefs: Vec<String>,
+    agent_xrefs: Vec<String>,
+
+    // The position in its collection of each entity with an id.
+    positions: HashMap<(EntityKind, String), usize>,
+
+    source_kinds: Vec<SourceKind>,
+    repositories: HashSet<usize>,
+    families: Vec<Family<'a>>,
+    events: Vec<EventRoles>,
+
+    // GEDCOM 7.0 extension tags and the URIs they stand for, declared in the
+    // header.
+    schema: Vec<(String, Uri)>,
+}
+
+impl<'a> Exporter<'a> {
+    fn new(gx: &'a Gedcomx, version: Version) -> Self {
+        let mut exporter = Self {
+            gx,
+            version,
+            index: ReferenceIndex::new(gx),
+            warnings: vec![],
+            used_xrefs: HashSet::new(),
+            generated_xrefs: 0,
+            person_xrefs: vec![],
+            source_xrefs: vec![],
+            agent_xrefs: vec![],
+            positions: HashMap::new(),
+            source_kinds: vec![],
+            repositories: HashSet::new(),
+            families: vec![],
+            events: vec![],
+            schema: vec![],
+        };
+
+        let ids = [
+            (
+                EntityKind::Person,
+                gx.persons.iter().map(|p| &p.id).collect::<Vec<_>>(),
+            ),
+            (
+                EntityKind::SourceDescription,
+                gx.source_descriptions.iter().map(|s| &s.id).collect(),
+            ),
+            (EntityKind::Agent, gx.agents.iter().map(|a| &a.id).collect()),
+        ];
+        for (kind, ids) in ids {
+            for (i, id) in ids.into_iter().enumerate() {
+                if let Some(id) = id {
+                    exporter
+                        .positions
+                        .entry((kind, id.to_string()))
+                        .or_insert(i);
+                }
+            }
+        }
+
+        exporter.source_kinds = gx
+            .source_descriptions
+            .iter()
+            .map(|sd| exporter.source_kind(sd))
+            .collect();
+        exporter.repositories = gx
+            .source_descriptions
+            .iter()
+            .filter_map(|sd| sd.repository.as_ref())
+            .filter_map(|r| exporter.position(&r.resource, Entity::as_agent))
+            .collect();
+
+        // Ids from the document are kept as cross-reference ids where
+        // possible, so they are claimed before any are generated.
+        let person_ids: Vec<_> = gx
+            .persons
+            .iter()
+            .map(|p| exporter.claim(p.id.as_ref()))
+            .collect();
+        let family_ids: Vec<_> = gx
+            .relationships
+            .iter()
+            .map(|r| {
+                (r.relationship_type == Some(RelationshipType::Couple))
+                    .then(|| exporter.claim(r.id.as_ref()))
+                    .flatten()
+            })
+            .collect();
+        let source_ids: Vec<_> = gx
+            .source_descriptions
+            .iter()
+            .map(|sd| exporter.claim(sd.id.as_ref()))
+            .collect();
+        let agent_ids: Vec<_> = gx
+            .agents
+            .iter()
+            .map(|a| exporter.claim(a.id.as_ref()))
+            .collect();
+
+        exporter.person_xrefs = exporter.fill_xrefs(person_ids, "I");
+        exporter.source_xrefs = exporter.fill_xrefs(source_ids, "S");
+        exporter.agent_xrefs = exporter.fill_xrefs(agent_ids, "R");
+
+        exporter.families(&family_ids);
+        if version == Version::Gedcom70 {
+            exporter.events();
+        }
+        exporter
+    }
+
+    fn export(&mut self, encoding: Encoding) -> String {
+        let mut records = vec![];
+
+        let submitters: Vec<_> = (0..self.gx.agents.len())
+            .filter(|i| !self.repositories.contains(i))
+            .collect();
+        for i in submitters {
+            records.push(self.agent(i, "SUBM"));
+        }
+        for i in 0..self.gx.persons.len() {
+            records.push(self.person(i));
+        }
+        for i in 0..self.families.len() {
+            records.push(self.family(i));
+        }
+        for (i, kind) in self.source_kinds.clone().into_iter().enumerate() {
+            match kind {
+                SourceKind::Source => records.push(self.source(i)),
+                SourceKind::Media => records.push(self.media(i)),
+                SourceKind::Citation(_) => {}
+            }
+        }
+        let mut repositories: Vec<_> = self.repositories.iter().copied().collect();
+        repositories.sort_unstable();
+        for i in repositories {
+            records.push(self.agent(i, "REPO"));
+        }
+        records.push(Node::new("TRLR", None));
+        // The header is made last, once every extension tag is known.
+        records.insert(0, self.header(encoding));
+
+        let events = match self.version {
+            Version::Gedcom551 => self.gx.events.len(),
+            Version::Gedcom70 => 0,
+        };
+        for (collection, len) in [
+            ("events", events),
+            ("documents", self.gx.documents.len()),
+            ("places", self.gx.places.len()),
+            ("groups", self.gx.groups.len()),
+        ] {
+            for i in 0..len {
+                self.unsupported(format!("{collection}[{i}]"));
+            }
+        }
+
+        let mut out = String::new();
+        tree::write(&records, self.version, &mut out);
+        out
+    }
+
+    fn unsupported(&mut self, path: String) {
+        self.warnings
+            .push(Warning::new(None, WarningKind::UnsupportedData(path)));
+    }
+
+    fn claim(&mut self, id: Option<&Id>) -> Option<String> {
+        let id = id?.to_string();
+        (is_xref(&id, self.version) && self.used_xrefs.insert(id.clone())).then_some(id)
+    }
+
+    // The GEDCOM 7.0 extension tag for a URI, declaring it if it's new.
+    fn extension(&mut self, uri: &Uri) -> String {
+        if let Some((tag, _)) = self.schema.iter().find(|(_, u)| u == uri) {
+            return tag.clone();
+        }
+
+        let base = tags::extension_tag(uri);
+        let mut tag = base.clone();
+        let mut n = 1;
+        while self.schema.iter().any(|(t, _)| *t == tag) {
+            n += 1;
+            tag = format!("{base}{n}");
+        }
+        self.schema.push((tag.clone(), uri.clone()));
+        tag
+    }
+
+    // A GEDCOM 7.0 enumeration value for a type with no standard value:
+    // `OTHER` with a PHRASE for types made from text, otherwise an extension
+    // value.
+    fn other(&mut self, tag: &str, uri: &Uri) -> Node {
+        let mut node = Node::new(tag, None);
+        if let Some(name) = tags::custom_name(uri) {
+            node.value = Some("OTHER".to_string());
+            node.children.push(Node::new("PHRASE", Some(name)));
+        } else {
+            node.value = Some(self.extension(uri));
+        }
+        node
+    }
+
+    // GEDCOM 5.5.1 lowercases enumeration values that GEDCOM 7.0 uppercases.
+    fn enumeration(&self, value: &str) -> String {
+        match self.version {
+            Version::Gedcom551 => value.to_lowercase(),
+            Version::Gedcom70 => value.to_uppercase(),
+        }
+    }
+
+    fn fill_xrefs(&mut self, ids: Vec<Option<String>>, prefix: &str) -> Vec<String> {
+        ids.into_iter()
+            .map(|id| id.unwrap_or_else(|| self.generate_xref(prefix)))
+            .collect()
+    }
+
+    fn generate_xref(&mut self, prefix: &str) -> String {
+        loop {
+            self.generated_xrefs += 1;
+            let xref = format!("{prefix}{}", self.generated_xrefs);
+            if self.used_xrefs.insert(xref.clone()) {
+                return xref;
+            }
+        }
+    }
+
+    // The position in its collection of the entity a local reference
+    // resolves to, if it's the expected kind of entity.
+    fn position<T: 'a>(
+        &self,
+        uri: &Uri,
+        kind: impl Fn(&Entity<'a>) -> Option<&'a T>,
+    ) -> Option<usize> {
+        let entity = self.index.resolve(uri)?;
+        kind(&entity)?;
+        self.positions
+            .get(&(entity.kind(), entity.id()?.to_string()))
+            .copied()
+    }
+
+    fn source_kind(&self, sd: &SourceDescription) -> SourceKind {
+        if sd.resource_type == Some(ResourceType::DigitalArtifact) && sd.about.is_some() {
+            return SourceKind::Media;
+        }
+
+        // Citations of part of a source, like those made from `PAGE` on
+        // import, are written back as a `PAGE`.
+        let parent = sd.component_of.as_ref().and_then(|c| {
+            let i = self.position(&c.description, Entity::as_source_description)?;
+            let parent = &self.gx.source_descriptions[i];
+            (parent.component_of.is_none()
+                && parent.resource_type != Some(ResourceType::DigitalArtifact))
+            .then_some(i)
+        });
+        match parent {
+            Some(i) if sd.titles.is_empty() && sd.about.is_none() && sd.repository.is_none() => {
+                SourceKind::Citation(i)
+            }
+            _ => SourceKind::Source,
+        }
+    }
+
+    fn families(&mut self, family_ids: &[Option<String>]) {
+        let gx = self.gx;
+        let mut parents: Vec<(usize, Vec<Parent>)> = vec![];
+
+        for (i, relationship) in gx.relationships.iter().enumerate() {
+            let person1 = self.position(&relationship.person1.resource, Entity::as_person);
+            let person2 = self.position(&relationship.person2.resource, Entity::as_person);
+            let (Some(person1), Some(person2)) = (person1, person2) else {
+                self.unsupported(format!("relationships[{i}]"));
+                continue;
+            };
+
+            match relationship.relationship_type {
+                Some(RelationshipType::Couple) => {
+                    let mut spouses = vec![person1, person2];
+                    if self.is_female(person1) && !self.is_female(person2) {
+                        spouses.reverse();
+                    }
+                    let xref = family_ids[i]
+                        .clone()
+                        .unwrap_or_else(|| self.generate_xref("F"));
+                    self.families.push(Family {
+                        xref,
+                        couple: Some((i, relationship)),
+                        spouses,
+                        children: vec![],
+                    });
+                }
+                Some(RelationshipType::ParentChild) => {
+                    let mut pedigree = None;
+                    for (j, fact) in relationship.facts.iter().enumerate() {
+                        match fact.fact_type {
+                            FactType::BiologicalParent => pedigree = Some("birth"),
+                            FactType::AdoptiveParent => pedigree = Some("adopted"),
+                            FactType::FosterParent => pedigree = Some("foster"),
+                            _ => self.unsupported(format!("relationships[{i}].facts[{j}]")),
+                        }
+                    }
+
+                    match parents.iter_mut().find(|(child, _)| *child == person2) {
+                        Some((_, p)) => p.push((person1, pedigree)),
+                        None => parents.push((person2, vec![(person1, pedigree)])),
+                    }
+                }
+                _ => self.unsupported(format!("relationships[{i}]")),
+            }
+        }
+
+        // Each child goes in the family of a couple made of two of its
+        // parents. Any other parent gets a family of their own.
+        let mut single_parents = HashMap::new();
+        for (child, mut child_parents) in parents {
+            for family in &mut self.families {
+                if let [spouse1, spouse2] = family.spouses[..]
+                    && family.couple.is_some()
+                    && let Some(p1) = child_parents.iter().position(|(p, _)| *p == spouse1)
+                    && let Some(p2) = child_parents.iter().position(|(p, _)| *p == spouse2)
+                {
+                    let pedigree = child_parents[p1].1.or(child_parents[p2].1);
+                    family.children.push((child, pedigree));
+                    child_parents.retain(|(p, _)| *p != spouse1 && *p != spouse2);
+                }
+            }
+
+            for (parent, pedigree) in child_parents {
+                let family = *single_parents.entry(parent).or_insert_with(|| {
+                    let xref = self.generate_xref("F");
+                    self.families.push(Family {
+                        xref,
+                        couple: None,
+                        spouses: vec![parent],
+                        children: vec![],
+                    });
+                    self.families.len() - 1
+                });
+                self.families[family].children.push((child, pedigree));
+            }
+        }
+    }
+
+    fn events(&mut self) {
+        let gx = self.gx;
+        for (i, event) in gx.events.iter().enumerate() {
+            let mut roles = vec![];
+            for (j, role) in event.roles.iter().enumerate() {
+                match self.position(&role.person.resource, Entity::as_person) {
+                    Some(person) => roles.push((j, person)),
+                    None => self.unsupported(format!("events[{i}].roles[{j}]")),
+                }
+            }
+
+            if roles.iter().any(|(j, _)| is_principal(&event.roles[*j])) {
+                self.events.push((i, roles));
+            } else {
+                self.unsupported(format!("events[{i}]"));
+            }
+        }
+    }
+
+    fn is_female(&self, person: usize) -> bool {
+        self.gx.persons[person]
+            .gender
+            .as_ref()
+            .is_some_and(|g| g.gender_type == GenderType::Female)
+    }
+
+    fn header(&self, encoding: Encoding) -> Node {
+        let mut head = Node::new("HEAD", None);
+
+        if self.version == Version::Gedcom70 {
+            let mut gedc = Node::new("GEDC", None);
+            gedc.children
+                .push(Node::new("VERS", Some("7.0".to_string())));
+            head.children.push(gedc);
+
+            if !self.schema.is_empty() {
+                let mut schema = Node::new("SCHMA", None);
+                for (tag, uri) in &self.schema {
+                    schema
+                        .children
+                        .push(Node::new("TAG", Some(format!("{tag} {uri}"))));
+                }
+                head.children.push(schema);
+            }
+        }
+
+        let mut source = Node::new("SOUR", Some(env!("CARGO_PKG_NAME").to_string()));
+        source.children.push(Node::new(
+            "VERS",
+            Some(env!("CARGO_PKG_VERSION").to_string()),
+        ));
+        head.children.push(source);
+
+        let contributor = self
+            .gx
+            .attribution
+            .as_ref()
+            .and_then(|a| a.contributor.as_ref())
+            .and_then(|c| self.position(&c.resource, Entity::as_agent))
+            .filter(|i| !self.repositories.contains(i));
+        let submitter = contributor
+            .or_else(|| (0..self.gx.agents.len()).find(|i| !self.repositories.contains(i)));
+        if let Some(submitter) = submitter {
+            head.children
+                .push(pointer("SUBM", &self.agent_xrefs[submitter]));
+        }
+
+        if self.version == Version::Gedcom551 {
+            let mut gedc = Node::new("GEDC", None);
+            gedc.children
+                .push(Node::new("VERS", Some("5.5.1".to_string())));
+            gedc.children
+                .push(Node::new("FORM", Some("LINEAGE-LINKED".to_string())));
+            head.children.push(gedc);
+            head.children
+                .push(Node::new("CHAR", Some(encoding.char_value().to_string())));
+        }
+
+        head
+    }
+
+    fn person(&mut self, i: usize) -> Node {
+        let person: &Person = &self.gx.persons[i];
+        let path = format!("persons[{i}]");
+
+        let mut indi = Node::new("INDI", None);
+        indi.xref = Some(self.person_xrefs[i].clone());
+
+        let mut nicknames = vec![];
+        for (j, name) in person.names.iter().enumerate() {
+            if name.name_type == Some(NameType::Nickname) {
+                nicknames.push(name);
+                continue;
+            }
+            let node = self.name(name, &format!("{path}.names[{j}]"));
+            indi.children.push(node);
+        }
+        for nickname in nicknames {
+            let nick = Node::new("NICK", name_form_text(nickname.name_forms.first()));
+            if let Some(name) = indi.children.first_mut() {
+                name.children.push(nick);
+            } else {
+                let mut name = Node::new("NAME", nick.value.clone());
+                name.children.push(nick);
+                indi.children.push(name);
+            }
+        }
+
+        if let Some(gender) = &person.gender {
+            let sex = match (&gender.gender_type, self.version) {
+                (GenderType::Male, _) => "M".to_string(),
+                (GenderType::Female, _) => "F".to_string(),
+                (GenderType::Unknown, _) => "U".to_string(),
+                (GenderType::Intersex, Version::Gedcom70) => "X".to_string(),
+                (other, Version::Gedcom70) => self.extension(&Uri::from(other.to_string())),
+                (other, Version::Gedcom551) => {
+                    self.warnings.push(Warning::new(
+                        None,
+                        WarningKind::UnsupportedValue {
+                            tag: "SEX".to_string(),
+                            value: other.to_string(),
+                        },
+                    ));
+                    "U".to_string()
+                }
+            };
+            indi.children.push(Node::new("SEX", Some(sex)));
+        }
+
+        for (j, fact) in person.facts.iter().enumerate() {
+            let node = self.fact(fact, &format!("{path}.facts[{j}]"));
+            indi.children.push(node);
+        }
+        for (event, roles) in self.events.clone() {
+            let gx = self.gx;
+            if roles
+                .iter()
+                .any(|(j, p)| *p == i && is_principal(&gx.events[event].roles[*j]))
+            {
+                self.event(event, &roles, i, &mut indi);
+            }
+        }
+
+        for family in &self.families {
+            if let Some((_, pedigree)) = family.children.iter().find(|(c, _)| *c == i) {
+                let mut famc = pointer("FAMC", &family.xref);
+                if let Some(pedigree) = pedigree {
+                    famc.children
+                        .push(Node::new("PEDI", Some(self.enumeration(pedigree))));
+                }
+                indi.children.push(famc);
+            }
+        }
+        for family in &self.families {
+            if family.spouses.contains(&i) && family.couple.is_some() {
+                indi.children.push(pointer("FAMS", &family.xref));
+            }
+        }
+        // Single parents are spouses in their family too, so that the
+        // family can be found from either side.
+        for family in &self.families {
+            if family.spouses.contains(&i) && family.couple.is_none() {
+                indi.children.push(pointer("FAMS", &family.xref));
+            }
+        }
+
+        let citations = self.citations(&person.sources, &person.media);
+        indi.children.extend(citations);
+        indi.children.extend(person.notes.iter().map(note));
+        if person.private == Some(true) {
+            indi.children
+                .push(Node::new("RESN", Some(self.enumeration("privacy"))));
+        }
+        indi.children.extend(change(person.attribution.as_ref()));
+
+        indi
+    }
+
+    fn name(&mut self, name: &Name, path: &str) -> Node {
+        let mut forms = name.name_forms.iter();
+        let first = forms.next();
+        let mut node = Node::new("NAME", first.map(name_value));
+
+        if let Some(name_type) = &name.name_type {
+            let value = tags::name_type_value(name_type);
+            match (value, self.version) {
+                (Some(value), Version::Gedcom551) => {
+                    node.children.push(Node::new("TYPE", Some(value)));
+                }
+                (Some(value), Version::Gedcom70)
+                    if tags::NAME_TYPES.contains(&value.to_uppercase().as_str()) =>
+                {
+                    node.children
+                        .push(Node::new("TYPE", Some(value.to_uppercase())));
+                }
+                (_, Version::Gedcom70) => {
+                    let name_type = self.other("TYPE", &Uri::from(name_type.to_string()));
+                    node.children.push(name_type);
+                }
+                (None, Version::Gedcom551) => {}
+            }
+        }
+        if let Some(form) = first {
+            for (tag, part_type) in [
+                ("NPFX", NamePartType::Prefix),
+                ("GIVN", NamePartType::Given),
+                ("SURN", NamePartType::Surname),
+                ("NSFX", NamePartType::Suffix),
+            ] {
+                if let Some(value) = parts(form, &part_type) {
+                    node.children.push(Node::new(tag, Some(value)));
+                }
+            }
+        }
+        for form in forms {
+            // GEDCOM 7.0 translations must have a language, `und` if it
+            // isn't known.
+            let (tag, lang_tag, lang) = match self.version {
+                Version::Gedcom551 => ("ROMN", "TYPE", form.lang.as_ref().map(ToString::to_string)),
+                Version::Gedcom70 => (
+                    "TRAN",
+                    "LANG",
+                    Some(
+                        form.lang
+                            .as_ref()
+                            .map_or_else(|| "und".to_string(), ToString::to_string),
+                    ),
+                ),
+            };
+            let mut translation = Node::new(tag, Some(name_value(form)));
+            if let Some(lang) = lang {
+                translation.children.push(Node::new(lang_tag, Some(lang)));
+            }
+            node.children.push(translation);
+        }
+
+        if name.date.is_some() {
+            self.unsupported(format!("{path}.date"));
+        }
+        let citations = self.citations(&name.sources, &[]);
+        node.children.extend(citations);
+        node.children.extend(name.notes.iter().map(note));
+
+        node
+    }
+
+    fn fact(&mut self, fact: &Fact, path: &str) -> Node {
+        // GEDCOM 7.0 has extension tags for fact types that aren't made from
+        // text.
+        let tag = tags::fact_tag(&fact.fact_type).or_else(|| match &fact.fact_type {
+            FactType::Custom(uri) if tags::custom_name(uri).is_some() => None,
+            fact_type => (self.version == Version::Gedcom70)
+                .then(|| self.extension(&Uri::from(fact_type.to_string()))),
+        });
+        let period = tag.as_deref().is_some_and(tags::is_attribute);
+
+        let mut node = tag.map_or_else(
+            || {
+                // Facts without a tag are written as generic events or
+                // attributes, with the fact type as the TYPE.
+                let tag = if fact.value.is_some() { "FACT" } else { "EVEN" };
+                let mut node = Node::new(tag, fact.value.clone());
+                node.children
+                    .push(Node::new("TYPE", Some(fact_type_name(&fact.fact_type))));
+                node
+            },
+            |tag| Node::new(tag, fact.value.clone()),
+        );
+
+        if let Some(date) = fact.date.as_ref().and_then(|d| self.date(d, period)) {
+            node.children.push(date);
+        }
+        if let Some(place) = fact.place.as_ref().and_then(|p| p.original.clone()) {
+            node.children.push(Node::new("PLAC", Some(place)));
+        }
+        for (j, qualifier) in fact.qualifiers.iter().enumerate() {
+            let tag = [
+                ("AGE", FactQualifier::Age),
+                ("CAUS", FactQualifier::Cause),
+                ("RELI", FactQualifier::Religion),
+            ]
+            .into_iter()
+            .find(|(_, q)| Uri::from(q.clone()) == qualifier.name)
+            .map(|(tag, _)| tag);
+            match tag {
+                Some(tag) => node.children.push(Node::new(tag, qualifier.value.clone())),
+                None => self.unsupported(format!("{path}.qualifiers[{j}]")),
+            }
+        }
+        let citations = self.citations(&fact.sources, &[]);
+        node.children.extend(citations);
+        node.children.extend(fact.notes.iter().map(note));
+
+        // An event with nothing else known about it is asserted with `Y`.
+        if node.value.is_none()
+            && node.children.is_empty()
+            && tags::FACT_TAGS.iter().any(|(t, _)| *t == node.tag)
+            && !tags::is_attribute(&node.tag)
+        {
+            node.value = Some("Y".to_string());
+        }
+
+        node
+    }
+
+    fn date(&mut self, date: &Date, period: bool) -> Option<Node> {
+        let original = date
+            .original
+            .as_deref()
+            .map(str::trim)
+            .filter(|o| !o.is_empty());
+        let Some(formal) = &date.formal else {
+            return match original {
+                Some(original) if self.is_gedcom_date(original) => {
+                    Some(Node::new("DATE", Some(original.to_string())))
+                }
+                Some(original) => Some(self.date_phrase(None, original)),
+                None => None,
+            };
+        };
+
+        // The original is kept if it's a GEDCOM date that means the same
+        // thing, since it may be written in a way the formal date can't
+        // show.
+        let same = original.is_some_and(|o| {
+            matches!(date::from_gedcom(o), (parsed, None) if parsed.formal.as_ref() == Some(formal))
+        });
+        if let Some(original) = original
+            && same
+            && self.is_gedcom_date(original)
+        {
+            return Some(Node::new("DATE", Some(original.to_string())));
+        }
+
+        match (date::to_gedcom(formal, period, self.version), original) {
+            (Some(gedcom), Some(original)) if !same => {
+                Some(self.date_phrase(Some(gedcom), original))
+            }
+            (Some(gedcom), _) => Some(Node::new("DATE", Some(gedcom))),
+            (None, Some(original)) => Some(self.date_phrase(None, original)),
+            (None, None) => {
+                self.warnings.push(Warning::new(
+                    None,
+                    WarningKind::UnsupportedValue {
+                        tag: "DATE".to_string(),
+                        value: formal.to_string(),
+                    },
+                ));
+                None
+            }
+        }
+    }
+
+    // Whether text is a GEDCOM date in this version. GEDCOM 7.0 dropped date
+    // phrases in parentheses, dual years, `B.C.` and calendar escapes, and
+    // added calendar keywords and `BCE`.
+    fn is_gedcom_date(&self, text: &str) -> bool {
+        let upper = text.to_uppercase();
+        let mut tokens = upper.split_whitespace();
+        date::from_gedcom(text).1.is_none()
+            && match self.version {
+                Version::Gedcom551 => !tokens.any(|t| matches!(t, "GREGORIAN" | "BCE")),
+                Version::Gedcom70 => !tokens.any(|t| {
+                    t.starts_with(['(', '@'])
+                        || t.contains('/')
+                        || matches!(t, "INT" | "B.C." | "BC")
+                }),
+            }
+    }
+
+    // Text that isn't a GEDCOM date is kept as a date phrase: in parentheses
+    // in GEDCOM 5.5.1, or as a PHRASE below the date in GEDCOM 7.0. GEDCOM
+    // 5.5.1 can only give a phrase with a single date, as an INT date.
+    fn date_phrase(&self, value: Option<String>, phrase: &str) -> Node {
+        let phrase = phrase.trim_matches(['(', ')']).to_string();
+        match self.version {
+            Version::Gedcom551 => match value {
+                None => Node::new("DATE", Some(format!("({phrase})"))),
+                Some(value) if is_single_date(&value) => {
+                    Node::new("DATE", Some(format!("INT {value} ({phrase})")))
+                }
+                Some(value) => Node::new("DATE", Some(value)),
+            },
+            Version::Gedcom70 => {
+                let mut date = Node::new("DATE", value);
+                date.children.push(Node::new("PHRASE", Some(phrase)));
+                date
+            }
+        }
+    }
+
+    // An event, written below a principal like a fact of theirs. Everyone
+    // else in the event is an ASSO of it. An event that matches a fact of
+    // the principal is merged into that fact.
+    fn event(&mut self, event: usize, roles: &[(usize, usize)], person: usize, indi: &mut Node) {
+        let gx = self.gx;
+        let path = format!("events[{event}]");
+        let event = &gx.events[event];
+
+        let fact_type = event.event_type.as_ref().map_or_else(
+            || FactType::Custom(tags::custom_uri("Event")),
+            |t| FactType::from(Uri::from(t.to_string())),
+        );
+        let mut fact = Fact::builder(fact_type).build();
+        fact.date.clone_from(&event.date);
+        fact.place.clone_from(&event.place);
+        fact.sources.clone_from(&event.sources);
+        fact.notes.clone_from(&event.notes);
+        let mut node = self.fact(&fact, &path);
+
+        let mut associations = vec![];
+        for &(j, p) in roles {
+            if p == person {
+                continue;
+            }
+            let role = &event.roles[j];
+            let mut asso = pointer("ASSO", &self.person_xrefs[p]);
+            let role_type = role
+                .event_role_type
+                .clone()
+                .unwrap_or(EventRoleType::Participant);
+            let role_node = self.role(&role_type);
+            asso.children.push(role_node);
+            asso.children.extend(self.citations(&role.sources, &[]));
+            asso.children.extend(role.notes.iter().map(note));
+            associations.push(asso);
+        }
+
+        let fact = indi.children.iter_mut().find(|c| {
+            c.tag == node.tag
+                && c.child("DATE") == node.child("DATE")
+                && c.child("PLAC") == node.child("PLAC")
+        });
+        if let Some(fact) = fact {
+            fact.children.extend(associations);
+        } else {
+            node.children.extend(associations);
+            indi.children.push(node);
+        }
+    }
+
+    fn role(&mut self, role_type: &EventRoleType) -> Node {
+        tags::role_value(role_type).map_or_else(
+            || self.other("ROLE", &Uri::from(role_type.to_string())),
+            |value| Node::new("ROLE", Some(value)),
+        )
+    }
+
+    // Citations of sources and media, as SOUR and OBJE lines.
+    fn citations(&self, sources: &[SourceReference], media: &[SourceReference]) -> Vec<Node> {
+        let mut nodes = vec![];
+        for reference in sources.iter().chain(media) {
+            let Some(i) = self.position(&reference.description, Entity::as_source_description)
+            else {
+                // A source outside the document is described by its URI.
+                // GEDCOM 7.0 has no descriptions, only a PAGE without a
+                // source.
+                let uri = reference.description.to_string();
+                let node = match self.version {
+                    Version::Gedcom551 => Node::new("SOUR", Some(uri)),
+                    Version::Gedcom70 => {
+                        let mut node = pointer("SOUR", "VOID");
+                        node.children.push(Node::new("PAGE", Some(uri)));
+                        node
+                    }
+                };
+                nodes.push(node);
+                continue;
+            };
+
+            let node = match self.source_kinds[i] {
+                SourceKind::Source => pointer("SOUR", &self.source_xrefs[i]),
+                SourceKind::Media => pointer("OBJE", &self.source_xrefs[i]),
+                SourceKind::Citation(parent) => {
+                    let sd = &self.gx.source_descriptions[i];
+                    let mut node = pointer("SOUR", &self.source_xrefs[parent]);
+                    if let Some(citation) = sd.citations.first() {
+                        node.children
+                            .push(Node::new("PAGE", Some(citation.value.clone())));
+                    }
+                    if let Some(text) = sd.descriptions.first() {
+                        let mut data = Node::new("DATA", None);
+                        data.children
+                            .push(Node::new("TEXT", Some(text.value.clone())));
+                        node.children.push(data);
+                    }
+                    node.children.extend(sd.notes.iter().map(note));
+                    node
+                }
+            };
+            nodes.push(node);
+        }
+        nodes
+    }
+
+    fn family(&mut self, i: usize) -> Node {
+        let family = &self.families[i];
+        let mut fam = Node::new("FAM", None);
+        fam.xref = Some(family.xref.clone());
+
+        match family.spouses[..] {
+            [spouse] => {
+                let tag = if self.is_female(spouse) {
+                    "WIFE"
+                } else {
+                    "HUSB"
+                };
+                fam.children.push(pointer(tag, &self.person_xrefs[spouse]));
+            }
+            [husband, wife] => {
+                fam.children
+                    .push(pointer("HUSB", &self.person_xrefs[husband]));
+                fam.children.push(pointer("WIFE", &self.person_xrefs[wife]));
+            }
+            _ => {}
+        }
+        for (child, _) in &family.children {
+            fam.children
+                .push(pointer("CHIL", &self.person_xrefs[*child]));
+        }
+
+        if let Some((r, couple)) = family.couple {
+            for (j, fact) in couple.facts.iter().enumerate() {
+                let node = self.fact(fact, &format!("relationships[{r}].facts[{j}]"));
+                fam.children.push(node);
+            }
+            let citations = self.citations(&couple.sources, &couple.media);
+            fam.children.extend(citations);
+            fam.children.extend(couple.notes.iter().map(note));
+            fam.children.extend(change(couple.attribution.as_ref()));
+        }
+
+        fam
+    }
+
+    fn source(&mut self, i: usize) -> Node {
+        let sd = &self.gx.source_descriptions[i];
+        let mut sour = Node::new("SOUR", None);
+        sour.xref = Some(self.source_xrefs[i].clone());
+
+        let agent_names = |references: &[&ResourceReference]| {
+            references
+                .iter()
+                .filter_map(|r| self.position(&r.resource, Entity::as_agent))
+                .filter_map(|a| self.gx.agents[a].names.first())
+                .map(|n| n.value.clone())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut authors = agent_names(&sd.authors.iter().collect::<Vec<_>>());
+        let mut publisher = agent_names(&sd.publisher.iter().collect::<Vec<_>>());
+        let citation = sd.citations.first().map(|c| c.value.as_str());
+        let title = sd
+            .titles
+            .first()
+            .map(|t| t.value.as_str())
+            .or(citation)
+            .unwrap_or_default();
+
+        // Citations are made from the author, title and publisher on import,
+        // so the citation is split around the title to get them back.
+        if let Some((before, after)) = citation.and_then(|c| c.split_once(title)) {
+            let before = before.trim_end_matches(['.', ' ']);
+            let after = after.trim_start_matches(['.', ' ']);
+            if authors.is_empty() {
+                authors = before.to_string();
+            }
+            if publisher.is_empty() {
+                publisher = after.to_string();
+            }
+        }
+
+        for (tag, value) in [
+            ("AUTH", authors.as_str()),
+            ("TITL", title),
+            ("PUBL", publisher.as_str()),
+        ] {
+            if !value.is_empty() {
+                sour.children.push(Node::new(tag, Some(value.to_string())));
+            }
+        }
+
+        let rebuilt = [authors.as_str(), title, publisher.as_str()]
+            .into_iter()
+            .filter(|v| !v.is_empty())
+            .collect::<Vec<_>>()
+            .join(". ");
+        if citation.is_some_and(|c| c != rebuilt) {
+            self.unsupported(format!("source_descriptions[{i}].citations[0]"));
+        }
+
+        if let Some(text) = sd.descriptions.first() {
+            sour.children
+                .push(Node::new("TEXT", Some(text.value.clone())));
+        }
+        if let Some(repository) = sd
+            .repository
+            .as_ref()
+            .and_then(|r| self.position(&r.resource, Entity::as_agent))
+        {
+            sour.children
+                .push(pointer("REPO", &self.agent_xrefs[repository]));
+        }
+        sour.children.extend(sd.notes.iter().map(note));
+        sour.children.extend(change(sd.attribution.as_ref()));
+
+        sour
+    }
+
+    fn media(&self, i: usize) -> Node {
+        let sd = &self.gx.source_descriptions[i];
+        let mut obje = Node::new("OBJE", None);
+        obje.xref = Some(self.source_xrefs[i].clone());
+
+        let about = sd
+            .about
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let mut file = Node::new("FILE", Some(about.clone()));
+        let from_extension = about
+            .rsplit_once('.')
+            .and_then(|(_, extension)| tags::media_type(extension));
+        // GEDCOM 7.0 forms are media types.
+        let form = match self.version {
+            Version::Gedcom551 => sd
+                .media_type
+                .as_deref()
+                .and_then(tags::media_form)
+                .or_else(|| from_extension.and_then(tags::media_form)),
+            Version::Gedcom70 => sd.media_type.as_deref().or(from_extension),
+        };
+        if let Some(form) = form {
+            file.children
+                .push(Node::new("FORM", Some(form.to_string())));
+        }
+        let title = sd
+            .titles
+            .first()
+            .map(|t| t.value.clone())
+            .or_else(|| sd.citations.first().map(|c| c.value.clone()))
+            .filter(|t| *t != about);
+        if let Some(title) = title {
+            file.children.push(Node::new("TITL", Some(title)));
+        }
+        obje.children.push(file);
+
+        obje.children.extend(sd.notes.iter().map(note));
+        obje.children.extend(self.citations(&sd.sources, &[]));
+        obje.children.extend(change(sd.attribution.as_ref()));
+
+        obje
+    }
+
+    fn agent(&self, i: usize, tag: &str) -> Node {
+        let agent: &Agent = &self.gx.agents[i];
+        let mut node = Node::new(tag, None);
+        node.xref = Some(self.agent_xrefs[i].clone());
+
+        if let Some(name) = agent.names.first() {
+            node.children
+                .push(Node::new("NAME", Some(name.value.clone())));
+        }
+        node.children.extend(agent.addresses.first().map(address));
+        for (tag, references, scheme) in [
+            ("PHON", &agent.phones, "tel:"),
+            ("EMAIL", &agent.emails, "mailto:"),
+        ] {
+            for reference in references {
+                let uri = reference.resource.to_string();
+                let value = uri.strip_prefix(scheme).unwrap_or(&uri);
+                node.children.push(Node::new(tag, Some(value.to_string())));
+            }
+        }
+        if let Some(homepage) = &agent.homepage {
+            node.children
+                .push(Node::new("WWW", Some(homepage.resource.to_string())));
+        }
+
+        node
+    }
+}
+
+// Whether an id can be used as a cross-reference id. GEDCOM 5.5.1 allows
+// up to 20 characters, and many programs only handle letters and digits.
+// GEDCOM 7.0 only allows uppercase letters, digits and underscores, and
+// reserves `VOID`.
+fn is_xref(id: &str, version: Version) -> bool {
+    !id.is_empty()
+        && id.len() <= 20
+        && match version {
+            Version::Gedcom551 => id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+            Version::Gedcom70 => {
+                id != "VOID"
+                    && id
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            }
+        }
+}
+
+// Whether a GEDCOM date is a single date, rather than a range, period or
+// approximation.
+fn is_single_date(value: &str) -> bool {
+    !value.split_whitespace().any(|t| {
+        matches!(
+            t,
+            "ABT" | "CAL" | "EST" | "BEF" | "AFT" | "BET" | "AND" | "FROM" | "TO"
+        )
+    })
+}
+
+fn is_principal(role: &gedcomx::EventRole) -> bool {
+    role.event_role_type == Some(EventRoleType::Principal)
+}
+
+fn pointer(tag: &str, xref: &str) -> Node {
+    Node::new(tag, Some(format!("@{xref}@")))
+}
+
+fn note(note: &Note) -> Node {
+    Node::new("NOTE", Some(note.text.clone()))
+}
+
+fn change(attribution: Option<&Attribution>) -> Option<Node> {
+    let (date, time) = date::from_timestamp(attribution?.modified.as_ref()?)?;
+
+    let mut chan = Node::new("CHAN", None);
+    let mut date = Node::new("DATE", Some(date));
+    date.children.push(Node::new("TIME", Some(time)));
+    chan.children.push(date);
+    Some(chan)
+}
+
+fn address(address: &Address) -> Node {
+    let value = address
+        .value
+        .clone()
+        .or_else(|| address.street.clone())
+        .or_else(|| address.city.clone());
+    let mut node = Node::new("ADDR", value);
+
+    for (tag, value) in [
+        ("ADR1", &address.street),
+        ("ADR2", &address.street2),
+        ("ADR3", &address.street3),
+        ("CITY", &address.city),
+        ("STAE", &address.state_or_province),
+        ("POST", &address.postal_code),
+        ("CTRY", &address.country),
+    ] {
+        if let Some(value) = value {
+            node.children.push(Node::new(tag, Some(value.clone())));
+        }
+    }
+
+    node
+}
+
+// The parts of a name form of the given type, joined with spaces.
+fn parts(form: &NameForm, part_type: &NamePartType) -> Option<String> {
+    let parts: Vec<_> = form
+        .parts
+        .iter()
+        .filter(|p| p.part_type.as_ref() == Some(part_type))
+        .map(|p| p.value.as_str())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+// The value of a NAME line, with the surname between slashes, like
+// `John /Smith/ Jr.`.
+fn name_value(form: &NameForm) -> String {
+    let has_parts = [
+        NamePartType::Prefix,
+        NamePartType::Given,
+        NamePartType::Surname,
+        NamePartType::Suffix,
+    ]
+    .iter()
+    .any(|t| parts(form, t).is_some());
+    if !has_parts {
+        return form.full_text.clone().unwrap_or_default();
+    }
+
+    let mut value = [
+        parts(form, &NamePartType::Prefix),
+        parts(form, &NamePartType::Given),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+    if let Some(surname) = parts(form, &NamePartType::Surname) {
+        if !value.is_empty() {
+            value.push(' ');
+        }
+        let _ = write!(value, "/{surname}/");
+    }
+    if let Some(suffix) = parts(form, &NamePartType::Suffix) {
+        value.push(' ');
+        value.push_str(&suffix);
+    }
+
+    value
+}
+
+fn name_form_text(form: Option<&NameForm>) -> Option<String> {
+    form.and_then(|f| {
+        f.full_text.clone().or_else(|| {
+            Some(
+                f.parts
+                    .iter()
+                    .map(|p| p.value.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            )
+        })
+    })
+    .filter(|t| !t.is_empty())
+}
+
+// The TYPE of an EVEN or FACT line for a fact type without a tag.
+fn fact_type_name(fact_type: &FactType) -> String {
+    match fact_type {
+        FactType::Custom(uri) => tags::custom_name(uri).unwrap_or_else(|| uri.to_string()),
+        // Standard fact types are named by the end of their URI, as in
+        // `http://gedcomx.org/Stillbirth`.
+        fact_type => {
+            let uri = fact_type.to_string();
+            uri.rsplit('/').next().unwrap_or(&uri).to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use gedcomx::NamePart;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn name_values() {
+        let form = NameForm::builder()
+            .full_text("Dr. John Quincy Smith Jr.")
+            .part(
+                NamePart::builder("Dr.")
+                    .part_type(NamePartType::Prefix)
+                    .build(),
+            )
+            .part(
+                NamePart::builder("John")
+                    .part_type(NamePartType::Given)
+                    .build(),
+            )
+            .part(
+                NamePart::builder("Quincy")
+                    .part_type(NamePartType::Given)
+                    .build(),
+            )
+            .part(
+                NamePart::builder("Smith")
+                    .part_type(NamePartType::Surname)
+                    .build(),
+            )
+            .part(
+                NamePart::builder("Jr.")
+                    .part_type(NamePartType::Suffix)
+                    .build(),
+            )
+            .build();
+        assert_eq!(name_value(&form), "Dr. John Quincy /Smith/ Jr.");
+        assert_eq!(
+            parts(&form, &NamePartType::Given).as_deref(),
+            Some("John Quincy")
+        );
+
+        let form = NameForm::builder().full_text("Madonna").build();
+        assert_eq!(name_value(&form), "Madonna");
+    }
+
+    #[test]
+    fn xrefs() {
+        assert!(is_xref("I1", Version::Gedcom551));
+        assert!(is_xref("P-12_a", Version::Gedcom551));
+        assert!(!is_xref("", Version::Gedcom551));
+        assert!(!is_xref("has space", Version::Gedcom551));
+        assert!(!is_xref("a@b", Version::Gedcom551));
+        assert!(!is_xref("abcdefghijklmnopqrstuvwxyz", Version::Gedcom551));
+
+        assert!(is_xref("I_1", Version::Gedcom70));
+        assert!(!is_xref("P-12_a", Version::Gedcom70));
+        assert!(!is_xref("VOID", Version::Gedcom70));
+    }
+
+    #[test]
+    fn fact_type_names() {
+        assert_eq!(fact_type_name(&FactType::Stillbirth), "Stillbirth");
+        assert_eq!(
+            fact_type_name(&FactType::Custom(tags::custom_uri("Eagle Scout"))),
+            "Eagle Scout"
+        );
+        assert_eq!(
+            fact_type_name(&FactType::Custom("http://example.com/Knighthood".into())),
+            "http://example.com/Knighthood"
+        );
+    }
+
+    #[test]
+    fn generated_xrefs() {
+        let gx = Gedcomx::builder()
+            .person(Person::builder().id("I1").build())
+            .person(Person::builder().build())
+            .person(Person::builder().id("not an xref").build())
+            .build();
+
+        let exporter = Exporter::new(&gx, Version::Gedcom551);
+        assert_eq!(exporter.person_xrefs, vec!["I1", "I2", "I3"]);
+    }
+}
@@ -0,0 +1,178 @@
+use std::io::{Read, Seek, Write};
+
+use gedcomx::Gedcomx;
+use gedcomx_file::{GedcomxFile, GedcomxFileEntry, GedcomxFileWriter, GedcomxFormat, ImageEntry};
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
+
+use crate::{GedcomError, Import, Resources, Result, Warning, from_slice, tags, to_gedcom7_string};
+
+// The name of the GEDCOM file in a GEDZIP file.
+const GEDZIP_DOCUMENT: &str = "gedcom.ged";
+
+// The name of the document in GEDCOM X files written by this crate.
+const GEDX_DOCUMENT: &str = "tree.json";
+
+/// Import a GEDZIP file: a zip file holding a GEDCOM 7.0 file named
+/// `gedcom.ged`, along with the files it references.
+///
+/// Every other file becomes one of the import's
+/// [`resources`](Import::resources).
+///
+/// # Errors
+///
+/// Will return [`GedcomError::ZipError`] if the file isn't a zip file,
+/// [`GedcomError::MissingDocument`] if it has no `gedcom.ged`, or any of the
+/// errors of [`from_slice`].
+pub fn from_gedzip<R: Read + Seek>(reader: R) -> Result<Import> {
+    let mut archive = ZipArchive::new(reader)?;
+    let mut gedcom = None;
+    let mut resources = Resources::new();
+
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if file.is_dir() {
+            continue;
+        }
+        let mut bytes = vec![];
+        file.read_to_end(&mut bytes)?;
+        if file.name() == GEDZIP_DOCUMENT {
+            gedcom = Some(bytes);
+        } else {
+            resources.insert(file.name().to_string(), bytes);
+        }
+    }
+
+    let mut import = from_slice(&gedcom.ok_or(GedcomError::MissingDocument)?)?;
+    import.resources = resources;
+    Ok(import)
+}
+
+/// Export a document as a GEDZIP file, with the document written as GEDCOM
+/// 7.0 and packaged along with the resources. Returns the warnings from
+/// [`to_gedcom7_string`].
+///
+/// # Errors
+///
+/// Will return [`GedcomError::ZipError`] or [`GedcomError::IoError`] if
+/// writing fails.
+pub fn to_gedzip<W: Write + Seek>(
+    gx: &Gedcomx,
+    resources: &Resources,
+    writer: W,
+) -> Result<Vec<Warning>> {
+    let export = to_gedcom7_string(gx);
+
+    let mut zip = ZipWriter::new(writer);
+    zip.start_file(GEDZIP_DOCUMENT, SimpleFileOptions::default())?;
+    zip.write_all(export.gedcom.as_bytes())?;
+    for (name, bytes) in resources {
+        zip.start_file(name, SimpleFileOptions::default())?;
+        zip.write_all(bytes)?;
+    }
+    zip.finish()?;
+
+    Ok(export.warnings)
+}
+
+/// Read a GEDCOM X file, returning its document and the other files in it.
+///
+/// If the file has more than one document, their contents are combined.
+///
+/// # Errors
+///
+/// Will return [`GedcomError::GedcomxFileError`] if the file can't be read,
+/// or [`GedcomError::MissingDocument`] if it has no GEDCOM X document.
+pub fn from_gedx<R: Read + Seek>(reader: R) -> Result<(Gedcomx, Resources)> {
+    let mut file = GedcomxFile::from_reader(reader)?;
+    let names: Vec<_> = file.file_names().map(ToString::to_string).collect();
+    let mut document: Option<Gedcomx> = None;
+    let mut resources = Resources::new();
+
+    for name in names {
+        match file.by_name(&name)? {
+            GedcomxFileEntry::Gedcomx(gx) => match &mut document {
+                Some(document) => combine(document, *gx),
+                None => document = Some(*gx),
+            },
+            GedcomxFileEntry::Image(ImageEntry { mut reader, .. })
+            | GedcomxFileEntry::Pdf(mut reader)
+            | GedcomxFileEntry::Reader(mut reader) => {
+                let mut bytes = vec![];
+                reader.read_to_end(&mut bytes)?;
+                resources.insert(name, bytes);
+            }
+            GedcomxFileEntry::Text(text) => {
+                resources.insert(name, text.into_bytes());
+            }
+            _ => {}
+        }
+    }
+
+    Ok((document.ok_or(GedcomError::MissingDocument)?, resources))
+}
+
+/// Write a document and its resources as a GEDCOM X file. The document is
+/// written as JSON.
+///
+/// # Errors
+///
+/// Will return [`GedcomError::GedcomxError`] if the document can't be
+/// serialized, or [`GedcomError::ZipError`] or [`GedcomError::IoError`] if
+/// writing fails.
+pub fn to_gedx<W: Write + Seek>(gx: &Gedcomx, resources: &Resources, writer: W) -> Result<()> {
+    let mut file = GedcomxFileWriter::new(writer);
+    file.created_by(format!(
+        "{} {}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    ));
+    file.add_gedcomx(GEDX_DOCUMENT, gx, GedcomxFormat::Json, &[])?;
+    for (name, bytes) in resources {
+        file.add_resource(name, &content_type(gx, name), &[], bytes.as_slice())?;
+    }
+    file.finish()?;
+
+    Ok(())
+}
+
+// The media type of a resource, from the media source description that
+// refers to it or else from its extension.
+fn content_type(gx: &Gedcomx, name: &str) -> String {
+    gx.source_descriptions
+        .iter()
+        .filter(|sd| sd.about.as_ref().is_some_and(|a| a.to_string() == name))
+        .find_map(|sd| sd.media_type.clone())
+        .or_else(|| {
+            name.rsplit_once('.')
+                .and_then(|(_, extension)| tags::media_type(extension))
+                .map(ToString::to_string)
+        })
+        .unwrap_or_else(|| "application/octet-stream".to_string())
+}
+
+fn combine(document: &mut Gedcomx, other: Gedcomx) {
+    document.persons.extend(other.persons);
+    document.relationships.extend(other.relationships);
+    document
+        .source_descriptions
+        .extend(other.source_descriptions);
+    document.agents.extend(other.agents);
+    document.events.extend(other.events);
+    document.documents.extend(other.documents);
+    document.places.extend(other.places);
+    document.groups.extend(other.groups);
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn content_types() {
+        let gx = Gedcomx::default();
+        assert_eq!(content_type(&gx, "photo.JPG"), "image/jpeg");
+        assert_eq!(content_type(&gx, "data.bin"), "application/octet-stream");
+    }
+}
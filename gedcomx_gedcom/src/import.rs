@@ -0,0 +1,1040 @@
+use std::{collections::HashMap, io::Read};
+
+use gedcomx::{
+    Address, Agent, Attribution, Date, Event, EventRole, EventRoleType, EventType, Fact,
+    FactQualifier, FactType, Gedcomx, Gender, GenderType, Id, Lang, Name, NameForm, NamePart,
+    NamePartType, NameType, Note, Person, PlaceReference, Qualifier, Relationship,
+    RelationshipType, ResourceReference, ResourceType, SourceCitation, SourceDescription,
+    SourceReference, TextValue, Uri,
+};
+
+use crate::{
+    Encoding, GedcomError, Resources, Result, Version, Warning, WarningKind, date, encoding, tags,
+    tree::{self, Node},
+};
+
+/// The result of importing a GEDCOM file.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub struct Import {
+    /// The imported document.
+    pub gedcomx: Gedcomx,
+
+    /// The GEDCOM version of the file.
+    pub version: Version,
+
+    /// The character encoding the file was read with.
+    pub encoding: Encoding,
+
+    /// The files packaged with the document. Only GEDZIP files have any.
+    pub resources: Resources,
+
+    /// Data that couldn't be converted exactly, in the order it was found.
+    pub warnings: Vec<Warning>,
+}
+
+/// Import a GEDCOM 5.5.1 or 7.0 file from a reader.
+///
+/// # Errors
+///
+/// Will return [`GedcomError::IoError`] if reading fails, or
+/// [`GedcomError::MissingHeader`] if the file doesn't look like a GEDCOM
+/// file. Everything else that goes wrong during the import is reported as a
+/// warning.
+pub fn from_reader<R: Read>(mut reader: R) -> Result<Import> {
+    let mut bytes = vec![];
+    reader.read_to_end(&mut bytes)?;
+    from_slice(&bytes)
+}
+
+/// Import a GEDCOM 5.5.1 or 7.0 file from bytes. The character encoding is
+/// detected from the byte order mark or the `CHAR` line of the header, and the
+/// version from the `GEDC` structure of the header.
+///
+/// # Errors
+///
+/// Will return [`GedcomError::MissingHeader`] if the file doesn't look like a
+/// GEDCOM file.
+pub fn from_slice(bytes: &[u8]) -> Result<Import> {
+    let mut warnings = vec![];
+    let (text, encoding) = encoding::decode(bytes, &mut warnings);
+    import(&text, encoding, warnings)
+}
+
+/// Import a GEDCOM 5.5.1 or 7.0 file that has already been decoded.
+///
+/// # Errors
+///
+/// Will return [`GedcomError::MissingHeader`] if the file doesn't look like a
+/// GEDCOM file.
+pub fn from_str(s: &str) -> Result<Import> {
+    import(s, Encoding::Utf8, vec![])
+}
+
+fn import(text: &str, encoding: Encoding, mut warnings: Vec<Warning>) -> Result<Import> {
+    let version = tree::version(text);
+    let records = tree::parse(text, version, &mut warnings);
+    if records.first().is_none_or(|r| r.tag != "HEAD") {
+        return Err(GedcomError::MissingHeader);
+    }
+
+    let mut importer = Importer::new(&records, warnings);
+    for record in &records {
+        importer.record(record);
+    }
+
+    Ok(Import {
+        gedcomx: importer.gx,
+        version,
+        encoding,
+        resources: Resources::new(),
+        warnings: importer.warnings,
+    })
+}
+
+struct Importer {
+    gx: Gedcomx,
+    warnings: Vec<Warning>,
+
+    // The tag of every record with an xref, used to check pointers.
+    xrefs: HashMap<String, String>,
+
+    // The text of each NOTE (or GEDCOM 7.0 SNOTE) record.
+    notes: HashMap<String, String>,
+
+    // The URI of each extension tag declared in the header's SCHMA.
+    schema: HashMap<String, Uri>,
+
+    // The PEDI value of each FAMC link, keyed by (child, family).
+    pedigrees: HashMap<(String, String), (usize, String)>,
+
+    // Used to create ids for source descriptions that don't have a record of
+    // their own, such as citations with a PAGE.
+    generated_ids: usize,
+}
+
+impl Importer {
+    fn new(records: &[Node], warnings: Vec<Warning>) -> Self {
+        let mut importer = Self {
+            gx: Gedcomx::default(),
+            warnings,
+            xrefs: HashMap::new(),
+            notes: HashMap::new(),
+            schema: HashMap::new(),
+            pedigrees: HashMap::new(),
+            generated_ids: 0,
+        };
+
+        for record in records {
+            if record.tag == "HEAD"
+                && let Some(schema) = record.child("SCHMA")
+            {
+                for tag in schema.children.iter().filter(|c| c.tag == "TAG") {
+                    if let Some((tag, uri)) = tag.value.as_deref().and_then(|v| v.split_once(' ')) {
+                        importer
+                            .schema
+                            .insert(tag.to_string(), Uri::from(uri.trim()));
+                    }
+                }
+            }
+
+            let Some(xref) = &record.xref else {
+                continue;
+            };
+            importer.xrefs.insert(xref.clone(), record.tag.clone());
+
+            match record.tag.as_str() {
+                "NOTE" | "SNOTE" => {
+                    importer
+                        .notes
+                        .insert(xref.clone(), record.value.clone().unwrap_or_default());
+                }
+                "INDI" => {
+                    for famc in record.children.iter().filter(|c| c.tag == "FAMC") {
+                        if let (Some(family), Some(pedi)) = (famc.pointer(), famc.child("PEDI")) {
+                            importer.pedigrees.insert(
+                                (xref.clone(), family.to_string()),
+                                (pedi.line, pedi.value.clone().unwrap_or_default()),
+                            );
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        importer
+    }
+
+    fn warn(&mut self, node: &Node, kind: WarningKind) {
+        self.warnings.push(Warning::new(Some(node.line), kind));
+    }
+
+    fn unsupported(&mut self, path: &str, node: &Node) {
+        self.warn(
+            node,
+            WarningKind::UnsupportedTag(format!("{path}.{}", node.tag)),
+        );
+    }
+
+    // A local reference to the record a pointer points at, if it exists and
+    // is one of the expected kinds of record. GEDCOM 7.0 uses `@VOID@` for a
+    // pointer to nothing.
+    fn pointer(&mut self, node: &Node, expected: &[&str]) -> Option<Uri> {
+        let pointer = node.pointer()?;
+        if pointer == "VOID" {
+            return None;
+        }
+        if self
+            .xrefs
+            .get(pointer)
+            .is_some_and(|tag| expected.contains(&tag.as_str()))
+        {
+            Some(Uri::from(format!("#{pointer}")))
+        } else {
+            self.warn(node, WarningKind::UnresolvedPointer(format!("@{pointer}@")));
+            None
+        }
+    }
+
+    fn generate_id(&mut self, prefix: &str) -> String {
+        loop {
+            self.generated_ids += 1;
+            let id = format!("{prefix}-{}", self.generated_ids);
+            if !self.xrefs.contains_key(&id) {
+                return id;
+            }
+        }
+    }
+
+    // The URI an extension tag or enumeration value like `_STILLBIRTH` was
+    // declared with.
+    fn extension(&self, value: &str) -> Option<Uri> {
+        value
+            .starts_with('_')
+            .then(|| self.schema.get(value).cloned())
+            .flatten()
+    }
+
+    fn fact_type(&self, tag: &str) -> FactType {
+        self.extension(tag)
+            .map_or_else(|| tags::fact_type(tag), FactType::from)
+    }
+
+    fn record(&mut self, record: &Node) {
+        match record.tag.as_str() {
+            "HEAD" => self.header(record),
+            "INDI" => {
+                let person = self.person(record);
+                self.gx.persons.push(person);
+            }
+            "FAM" => self.family(record),
+            "SOUR" => {
+                let sd = self.source_description(record);
+                self.gx.source_descriptions.push(sd);
+            }
+            "OBJE" => {
+                let sd = self.media_description(record, record.xref.as_deref());
+                self.gx.source_descriptions.push(sd);
+            }
+            "REPO" | "SUBM" => {
+                let agent = self.agent(record);
+                self.gx.agents.push(agent);
+            }
+            // Notes are converted where they're referenced.
+            "NOTE" | "SNOTE" | "TRLR" => {}
+            _ => self.warn(record, WarningKind::UnsupportedTag(record.tag.clone())),
+        }
+    }
+
+    fn header(&mut self, head: &Node) {
+        if let Some(submitter) = head.child("SUBM")
+            && let Some(uri) = self.pointer(submitter, &["SUBM"])
+        {
+            let mut attribution = Attribution::builder().build();
+            attribution.contributor = Some(ResourceReference::from(uri.to_string()));
+            self.gx.attribution = Some(attribution);
+        }
+    }
+
+    fn person(&mut self, record: &Node) -> Person {
+        let mut person = Person::builder().build();
+        person.id = record.xref.as_deref().map(Id::from);
+
+        for child in &record.children {
+            match child.tag.as_str() {
+                "NAME" => {
+                    let names = self.names(child);
+                    person.names.extend(names);
+                }
+                "SEX" => person.gender = self.gender(child),
+                "SOUR" => {
+                    if let Some(source) = self.source_reference(child) {
+                        person.sources.push(source);
+                    }
+                }
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = self.note(child) {
+                        person.notes.push(note);
+                    }
+                }
+                "OBJE" => {
+                    if let Some(media) = self.media_reference(child) {
+                        person.media.push(media);
+                    }
+                }
+                "CHAN" => person.attribution = self.change(child),
+                "RESN" => person.private = Some(true),
+                // Families are converted from the FAM records.
+                "FAMC" | "FAMS" => {}
+                "ASSO" | "ALIA" | "ANCI" | "DESI" | "SUBM" | "RIN" | "REFN" | "RFN" | "AFN"
+                | "UID" | "EXID" | "NO" => {
+                    self.unsupported("INDI", child);
+                }
+                tag => {
+                    let fact = self.fact(child, "INDI", self.fact_type(tag));
+                    if let Some(id) = &person.id {
+                        self.event(child, id, &fact);
+                    }
+                    person.facts.push(fact);
+                }
+            }
+        }
+
+        person
+    }
+
+    fn names(&mut self, node: &Node) -> Vec<Name> {
+        let mut name_forms = vec![name_form(node.value.as_deref().unwrap_or_default(), node)];
+        let mut name = Name::builder(NameForm::default()).build();
+        let mut nicknames = vec![];
+
+        for child in &node.children {
+            match child.tag.as_str() {
+                "TYPE" => name.name_type = self.name_type(child),
+                "FONE" | "ROMN" => {
+                    name_forms.push(name_form(child.value.as_deref().unwrap_or_default(), child));
+                }
+                // GEDCOM 7.0 translations of the name, in the language
+                // given by LANG.
+                "TRAN" => {
+                    let mut form = name_form(child.value.as_deref().unwrap_or_default(), child);
+                    form.lang = child
+                        .child_value("LANG")
+                        .filter(|l| *l != "und")
+                        .map(Lang::from);
+                    name_forms.push(form);
+                }
+                "NICK" => {
+                    if let Some(nick) = &child.value {
+                        let mut nickname = Name::builder(
+                            NameForm::builder()
+                                .full_text(nick.clone())
+                                .part(
+                                    NamePart::builder(nick.clone())
+                                        .part_type(NamePartType::Given)
+                                        .build(),
+                                )
+                                .build(),
+                        )
+                        .name_type(NameType::Nickname)
+                        .build();
+                        nickname.sources.clone_from(&name.sources);
+                        nicknames.push(nickname);
+                    }
+                }
+                "SOUR" => {
+                    if let Some(source) = self.source_reference(child) {
+                        name.sources.push(source);
+                    }
+                }
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = self.note(child) {
+                        name.notes.push(note);
+                    }
+                }
+                // Name pieces are part of the name form.
+                "NPFX" | "GIVN" | "SPFX" | "SURN" | "NSFX" => {}
+                _ => self.unsupported("NAME", child),
+            }
+        }
+
+        name.name_forms = name_forms;
+        let mut names = vec![name];
+        names.extend(nicknames);
+        names
+    }
+
+    fn name_type(&self, node: &Node) -> Option<NameType> {
+        let value = node.value.as_deref()?.trim();
+        if let Some(uri) = self.extension(value) {
+            return Some(NameType::from(uri));
+        }
+        match node.child_value("PHRASE") {
+            Some(phrase) if value == "OTHER" => Some(NameType::Custom(tags::custom_uri(phrase))),
+            _ => Some(tags::name_type(value)),
+        }
+    }
+
+    fn gender(&mut self, node: &Node) -> Option<Gender> {
+        let value = node.value.as_deref().unwrap_or_default();
+        if let Some(uri) = self.extension(value.trim()) {
+            return Some(Gender::from(GenderType::from(uri)));
+        }
+        let gender_type = match value.trim().to_uppercase().as_str() {
+            "M" => GenderType::Male,
+            "F" => GenderType::Female,
+            "U" => GenderType::Unknown,
+            "X" => GenderType::Intersex,
+            _ => {
+                self.warn(
+                    node,
+                    WarningKind::UnsupportedValue {
+                        tag: "SEX".to_string(),
+                        value: value.to_string(),
+                    },
+                );
+                return None;
+            }
+        };
+
+        Some(Gender::from(gender_type))
+    }
+
+    fn fact(&mut self, node: &Node, path: &str, fact_type: FactType) -> Fact {
+        // EVEN and FACT are generic, and get their type from a TYPE line.
+        let fact_type = match (node.tag.as_str(), node.child_value("TYPE")) {
+            ("EVEN" | "FACT", Some(t)) => FactType::Custom(tags::custom_uri(t)),
+            _ => fact_type,
+        };
+        let mut fact = Fact::builder(fact_type).build();
+        fact.value = node.value.clone().filter(|v| !v.is_empty() && v != "Y");
+
+        let path = format!("{path}.{}", node.tag);
+        for child in &node.children {
+            match child.tag.as_str() {
+                "DATE" => fact.date = Some(self.date(child)),
+                "PLAC" => {
+                    fact.place = child
+                        .value
+                        .as_deref()
+                        .map(|p| PlaceReference::new(Some(p), None));
+                    for place_child in &child.children {
+                        self.unsupported(&format!("{path}.PLAC"), place_child);
+                    }
+                }
+                "TYPE" if matches!(node.tag.as_str(), "EVEN" | "FACT") => {}
+                // Associated people become roles in an event.
+                "ASSO" if path.starts_with("INDI.") => {}
+                "AGE" => fact.qualifiers.push(qualifier(FactQualifier::Age, child)),
+                "CAUS" => fact.qualifiers.push(qualifier(FactQualifier::Cause, child)),
+                "RELI" => fact
+                    .qualifiers
+                    .push(qualifier(FactQualifier::Religion, child)),
+                "SOUR" => {
+                    if let Some(source) = self.source_reference(child) {
+                        fact.sources.push(source);
+                    }
+                }
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = self.note(child) {
+                        fact.notes.push(note);
+                    }
+                }
+                _ => self.unsupported(&path, child),
+            }
+        }
+
+        fact
+    }
+
+    // A DATE line. GEDCOM 7.0 dates can have a PHRASE, which is kept as the
+    // original in place of the date value.
+    fn date(&mut self, node: &Node) -> Date {
+        let value = node.value.as_deref().unwrap_or_default();
+        let (mut date, warning) = date::from_gedcom(value);
+        if let Some(warning) = warning
+            && !value.trim().is_empty()
+        {
+            self.warn(node, warning);
+        }
+        if let Some(phrase) = node.child_value("PHRASE") {
+            date.original = Some(phrase.to_string());
+        }
+        for child in node.children.iter().filter(|c| c.tag != "PHRASE") {
+            self.unsupported("DATE", child);
+        }
+        date
+    }
+
+    // GEDCOM 7.0 events can list the people associated with them, which is
+    // an event with roles in GEDCOM X. The person is the principal.
+    fn event(&mut self, node: &Node, person: &Id, fact: &Fact) {
+        let mut roles = vec![];
+        for asso in node.children.iter().filter(|c| c.tag == "ASSO") {
+            let Some(uri) = self.pointer(asso, &["INDI"]) else {
+                continue;
+            };
+            let mut role = event_role(&uri.to_string(), None);
+            for child in &asso.children {
+                match child.tag.as_str() {
+                    "ROLE" => {
+                        let value = child.value.as_deref().unwrap_or_default().trim();
+                        role.event_role_type = Some(self.extension(value).map_or_else(
+                            || match child.child_value("PHRASE") {
+                                Some(phrase) if value == "OTHER" => {
+                                    EventRoleType::Custom(tags::custom_uri(phrase))
+                                }
+                                _ => tags::role_type(value),
+                            },
+                            EventRoleType::from,
+                        ));
+                    }
+                    "NOTE" | "SNOTE" => {
+                        if let Some(note) = self.note(child) {
+                            role.notes.push(note);
+                        }
+                    }
+                    "SOUR" => {
+                        if let Some(source) = self.source_reference(child) {
+                            role.sources.push(source);
+                        }
+                    }
+                    _ => self.unsupported("ASSO", child),
+                }
+            }
+            roles.push(role);
+        }
+        if roles.is_empty() {
+            return;
+        }
+
+        let event_type = EventType::from(Uri::from(fact.fact_type.to_string()));
+        let mut event = Event::builder().event_type(event_type).build();
+        event.id = Some(Id::from(self.generate_id("EVEN").as_str()));
+        event.date.clone_from(&fact.date);
+        event.place.clone_from(&fact.place);
+        event.roles.push(event_role(
+            &format!("#{person}"),
+            Some(EventRoleType::Principal),
+        ));
+        event.roles.extend(roles);
+        self.gx.events.push(event);
+    }
+
+    fn family(&mut self, record: &Node) {
+        let mut partners = vec![];
+        let mut children = vec![];
+        let mut couple = relationship(RelationshipType::Couple, "", "");
+        couple.id = record.xref.as_deref().map(Id::from);
+
+        for child in &record.children {
+            match child.tag.as_str() {
+                "HUSB" | "WIFE" => {
+                    if let Some(uri) = self.pointer(child, &["INDI"]) {
+                        partners.push(uri);
+                    }
+                }
+                "CHIL" => {
+                    if let Some(uri) = self.pointer(child, &["INDI"]) {
+                        children.push((uri, child.pointer().unwrap_or_default().to_string()));
+                    }
+                }
+                "SOUR" => {
+                    if let Some(source) = self.source_reference(child) {
+                        couple.sources.push(source);
+                    }
+                }
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = self.note(child) {
+                        couple.notes.push(note);
+                    }
+                }
+                "OBJE" => {
+                    if let Some(media) = self.media_reference(child) {
+                        couple.media.push(media);
+                    }
+                }
+                "CHAN" => couple.attribution = self.change(child),
+                "SUBM" | "RIN" | "REFN" | "RESN" => self.unsupported("FAM", child),
+                tag => {
+                    let fact = self.fact(child, "FAM", self.fact_type(tag));
+                    couple.facts.push(fact);
+                }
+            }
+        }
+
+        // Only families with both partners are a couple. Anything attached to
+        // the family itself is lost otherwise.
+        if let [person1, person2] = partners.as_slice() {
+            couple.person1 = ResourceReference::from(person1.to_string());
+            couple.person2 = ResourceReference::from(person2.to_string());
+            self.gx.relationships.push(couple);
+        } else if !couple.facts.is_empty() || !couple.sources.is_empty() || !couple.notes.is_empty()
+        {
+            self.warn(record, WarningKind::UnsupportedTag("FAM".to_string()));
+        }
+
+        let family = record.xref.clone().unwrap_or_default();
+        for (child, child_xref) in children {
+            let lineage = self
+                .pedigrees
+                .get(&(child_xref, family.clone()))
+                .cloned()
+                .and_then(|(line, pedi)| self.lineage(line, &pedi));
+
+            for parent in &partners {
+                let mut parent_child = relationship(
+                    RelationshipType::ParentChild,
+                    &parent.to_string(),
+                    &child.to_string(),
+                );
+                if let Some(lineage) = &lineage {
+                    parent_child
+                        .facts
+                        .push(Fact::builder(lineage.clone()).build());
+                }
+                self.gx.relationships.push(parent_child);
+            }
+        }
+    }
+
+    fn lineage(&mut self, line: usize, pedi: &str) -> Option<FactType> {
+        match pedi.to_lowercase().as_str() {
+            "birth" => Some(FactType::BiologicalParent),
+            "adopted" => Some(FactType::AdoptiveParent),
+            "foster" => Some(FactType::FosterParent),
+            _ => {
+                self.warnings.push(Warning::new(
+                    Some(line),
+                    WarningKind::UnsupportedValue {
+                        tag: "PEDI".to_string(),
+                        value: pedi.to_string(),
+                    },
+                ));
+                None
+            }
+        }
+    }
+
+    fn source_description(&mut self, record: &Node) -> SourceDescription {
+        let citation = ["AUTH", "TITL", "PUBL"]
+            .iter()
+            .filter_map(|t| record.child_value(t))
+            .collect::<Vec<_>>()
+            .join(". ");
+        let citation = Some(citation)
+            .filter(|c| !c.is_empty())
+            .or_else(|| record.child_value("ABBR").map(ToString::to_string))
+            .or_else(|| record.value.clone())
+            .or_else(|| record.xref.clone())
+            .unwrap_or_default();
+
+        let mut sd = SourceDescription::builder(SourceCitation::new(citation, None)).build();
+        sd.id = record.xref.as_deref().map(Id::from);
+
+        for child in &record.children {
+            match child.tag.as_str() {
+                "TITL" | "ABBR" => {
+                    if let Some(title) = &child.value {
+                        sd.titles.push(TextValue::from(title.as_str()));
+                    }
+                }
+                "TEXT" => {
+                    if let Some(text) = &child.value {
+                        sd.descriptions.push(TextValue::from(text.as_str()));
+                    }
+                }
+                "REPO" => {
+                    if let Some(uri) = self.pointer(child, &["REPO"]) {
+                        sd.repository = Some(ResourceReference::from(uri.to_string()));
+                    }
+                    for repo_child in &child.children {
+                        self.unsupported("SOUR.REPO", repo_child);
+                    }
+                }
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = self.note(child) {
+                        sd.notes.push(note);
+                    }
+                }
+                "CHAN" => sd.attribution = self.change(child),
+                "AUTH" | "PUBL" => {}
+                _ => self.unsupported("SOUR", child),
+            }
+        }
+
+        sd
+    }
+
+    // A SOUR line below another record. Either a pointer to a SOUR record,
+    // possibly with a PAGE, or a description of the source.
+    fn source_reference(&mut self, node: &Node) -> Option<SourceReference> {
+        let mut sd = if node.pointer() == Some("VOID") {
+            // A GEDCOM 7.0 citation without a source record, which is
+            // described by its PAGE.
+            SourceDescription::builder(SourceCitation::new(
+                node.child_value("PAGE").unwrap_or_default().to_string(),
+                None,
+            ))
+            .build()
+        } else if node.pointer().is_some() {
+            let uri = self.pointer(node, &["SOUR"])?;
+            let reference = SourceReference::new(uri, None, None, vec![]);
+
+            let Some(page) = node.child_value("PAGE") else {
+                for child in &node.children {
+                    self.unsupported("SOUR", child);
+                }
+                return Some(reference);
+            };
+
+            // GEDCOM X has no equivalent of PAGE, so a citation with a PAGE
+            // becomes a source description that is a component of the
+            // referenced source.
+            let mut sd =
+                SourceDescription::builder(SourceCitation::new(page.to_string(), None)).build();
+            sd.component_of = Some(reference);
+            sd
+        } else {
+            SourceDescription::builder(SourceCitation::new(
+                node.value.clone().unwrap_or_default(),
+                None,
+            ))
+            .build()
+        };
+
+        let id = self.generate_id("SOUR");
+        sd.id = Some(Id::from(id.as_str()));
+
+        for child in &node.children {
+            match child.tag.as_str() {
+                "PAGE" => {}
+                "TEXT" => {
+                    if let Some(text) = &child.value {
+                        sd.descriptions.push(TextValue::from(text.as_str()));
+                    }
+                }
+                "DATA" => {
+                    for data in &child.children {
+                        if data.tag == "TEXT" {
+                            if let Some(text) = &data.value {
+                                sd.descriptions.push(TextValue::from(text.as_str()));
+                            }
+                        } else {
+                            self.unsupported("SOUR.DATA", data);
+                        }
+                    }
+                }
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = self.note(child) {
+                        sd.notes.push(note);
+                    }
+                }
+                _ => self.unsupported("SOUR", child),
+            }
+        }
+
+        self.gx.source_descriptions.push(sd);
+        Some(SourceReference::new(
+            Uri::from(format!("#{id}")),
+            None,
+            None,
+            vec![],
+        ))
+    }
+
+    fn note(&mut self, node: &Node) -> Option<Note> {
+        let text = if node.pointer().is_some() {
+            let uri = self.pointer(node, &["NOTE", "SNOTE"])?;
+            let xref = uri.to_string();
+            self.notes.get(&xref[1..]).cloned()
+        } else {
+            node.value.clone()
+        }?;
+
+        for child in &node.children {
+            self.unsupported("NOTE", child);
+        }
+
+        Some(Note::builder(text).build())
+    }
+
+    fn media_reference(&mut self, node: &Node) -> Option<SourceReference> {
+        let uri = if node.pointer().is_some() {
+            self.pointer(node, &["OBJE"])?
+        } else {
+            let id = self.generate_id("OBJE");
+            let sd = self.media_description(node, Some(&id));
+            self.gx.source_descriptions.push(sd);
+            Uri::from(format!("#{id}"))
+        };
+
+        Some(SourceReference::new(uri, None, None, vec![]))
+    }
+
+    fn media_description(&mut self, node: &Node, id: Option<&str>) -> SourceDescription {
+        let file = node.child("FILE");
+        let title = file
+            .and_then(|f| f.child_value("TITL"))
+            .or_else(|| node.child_value("TITL"));
+        let form = file
+            .and_then(|f| f.child_value("FORM"))
+            .or_else(|| node.child_value("FORM"));
+        let path = file.and_then(|f| f.value.clone()).unwrap_or_default();
+
+        let citation = title.map_or_else(|| path.clone(), ToString::to_string);
+        let mut sd = SourceDescription::builder(SourceCitation::new(citation, None))
+            .resource_type(ResourceType::DigitalArtifact)
+            .build();
+        sd.id = id.map(Id::from);
+        if !path.is_empty() {
+            sd.about = Some(Uri::from(path));
+        }
+        if let Some(title) = title {
+            sd.titles.push(TextValue::from(title));
+        }
+        // GEDCOM 7.0 forms are already media types.
+        sd.media_type = form
+            .and_then(|f| {
+                if f.contains('/') {
+                    Some(f)
+                } else {
+                    tags::media_type(f)
+                }
+            })
+            .map(ToString::to_string);
+
+        for child in &node.children {
+            match child.tag.as_str() {
+                "FILE" | "FORM" | "TITL" => {}
+                "NOTE" | "SNOTE" => {
+                    if let Some(note) = self.note(child) {
+                        sd.notes.push(note);
+                    }
+                }
+                "SOUR" => {
+                    if let Some(source) = self.source_reference(child) {
+                        sd.sources.push(source);
+                    }
+                }
+                "CHAN" => sd.attribution = self.change(child),
+                _ => self.unsupported("OBJE", child),
+            }
+        }
+
+        sd
+    }
+
+    fn agent(&mut self, record: &Node) -> Agent {
+        let mut agent = Agent::builder().build();
+        agent.id = record.xref.as_deref().map(Id::from);
+
+        for child in &record.children {
+            let Some(value) = child.value.as_deref() else {
+                if child.tag == "ADDR" {
+                    agent.addresses.push(address(child));
+                } else {
+                    self.unsupported(&record.tag, child);
+                }
+                continue;
+            };
+
+            match child.tag.as_str() {
+                "NAME" => agent.names.push(TextValue::from(value)),
+                "ADDR" => agent.addresses.push(address(child)),
+                "PHON" => agent.phones.push(ResourceReference::from(format!(
+                    "tel:{}",
+                    value.replace(' ', "")
+                ))),
+                "EMAIL" => agent
+                    .emails
+                    .push(ResourceReference::from(format!("mailto:{value}"))),
+                "WWW" => agent.homepage = Some(ResourceReference::from(value)),
+                _ => self.unsupported(&record.tag, child),
+            }
+        }
+
+        agent
+    }
+
+    fn change(&mut self, node: &Node) -> Option<Attribution> {
+        let date = node.child("DATE")?;
+        let modified = date::timestamp(
+            date.value.as_deref().unwrap_or_default(),
+            date.child_value("TIME"),
+        );
+        if modified.is_none() {
+            self.warn(
+                date,
+                WarningKind::UnparsedDate(date.value.clone().unwrap_or_default()),
+            );
+        }
+
+        modified.map(|modified| Attribution::builder().modified(modified).build())
+    }
+}
+
+fn name_form(value: &str, node: &Node) -> NameForm {
+    // The surname is delimited by slashes, as in `John /Smith/ Jr.`.
+    let mut pieces = value.splitn(3, '/');
+    let given = pieces.next().unwrap_or_default().trim();
+    let surname = pieces.next().unwrap_or_default().trim();
+    let suffix = pieces.next().unwrap_or_default().trim();
+
+    let full_text = [given, surname, suffix]
+        .into_iter()
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let parts = [
+        (NamePartType::Prefix, node.child_value("NPFX"), ""),
+        (NamePartType::Given, node.child_value("GIVN"), given),
+        (NamePartType::Surname, node.child_value("SURN"), surname),
+        (NamePartType::Suffix, node.child_value("NSFX"), suffix),
+    ];
+
+    let mut name_form = NameForm::builder().build();
+    if !full_text.is_empty() {
+        name_form.full_text = Some(full_text);
+    }
+    for (part_type, explicit, from_value) in parts {
+        let value = explicit.unwrap_or(from_value).trim();
+        if !value.is_empty() {
+            name_form
+                .parts
+                .push(NamePart::builder(value).part_type(part_type).build());
+        }
+    }
+
+    name_form
+}
+
+fn event_role(person: &str, event_role_type: Option<EventRoleType>) -> EventRole {
+    EventRole::new(
+        None,
+        None,
+        vec![],
+        None,
+        vec![],
+        None,
+        None,
+        ResourceReference::from(person),
+        event_role_type,
+        None,
+    )
+}
+
+fn qualifier(name: FactQualifier, node: &Node) -> Qualifier {
+    Qualifier::new(name, node.value.clone())
+}
+
+fn relationship(relationship_type: RelationshipType, person1: &str, person2: &str) -> Relationship {
+    Relationship::new(
+        None,
+        None,
+        vec![],
+        None,
+        vec![],
+        None,
+        None,
+        None,
+        vec![],
+        vec![],
+        vec![],
+        Some(relationship_type),
+        ResourceReference::from(person1),
+        ResourceReference::from(person2),
+        vec![],
+    )
+}
+
+fn address(node: &Node) -> Address {
+    let mut address = Address::builder().build();
+    address.value.clone_from(&node.value);
+    for child in &node.children {
+        let value = child.value.clone();
+        match child.tag.as_str() {
+            "ADR1" => address.street = value,
+            "ADR2" => address.street2 = value,
+            "ADR3" => address.street3 = value,
+            "CITY" => address.city = value,
+            "STAE" => address.state_or_province = value,
+            "POST" => address.postal_code = value,
+            "CTRY" => address.country = value,
+            _ => {}
+        }
+    }
+    address
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn missing_header() {
+        assert!(matches!(
+            from_str("0 @I1@ INDI\n0 TRLR\n"),
+            Err(GedcomError::MissingHeader)
+        ));
+        assert!(matches!(from_str(""), Err(GedcomError::MissingHeader)));
+    }
+
+    #[test]
+    fn name_forms() {
+        let node = Node {
+            tag: "NAME".to_string(),
+            value: Some("John Q /van Smith/ Jr.".to_string()),
+            ..Node::default()
+        };
+        let form = name_form(node.value.as_deref().unwrap(), &node);
+        assert_eq!(form.full_text.as_deref(), Some("John Q van Smith Jr."));
+        assert_eq!(
+            form.parts,
+            vec![
+                NamePart::builder("John Q")
+                    .part_type(NamePartType::Given)
+                    .build(),
+                NamePart::builder("van Smith")
+                    .part_type(NamePartType::Surname)
+                    .build(),
+                NamePart::builder("Jr.")
+                    .part_type(NamePartType::Suffix)
+                    .build(),
+            ]
+        );
+
+        let mut node = Node {
+            tag: "NAME".to_string(),
+            value: Some("/Smith/".to_string()),
+            ..Node::default()
+        };
+        node.children.push(Node {
+            tag: "GIVN".to_string(),
+            value: Some("Jonathan".to_string()),
+            ..Node::default()
+        });
+        let form = name_form(node.value.as_deref().unwrap(), &node);
+        assert_eq!(form.full_text.as_deref(), Some("Smith"));
+        assert_eq!(form.parts[0].value, "Jonathan");
+    }
+
+    #[test]
+    fn unresolved_pointers() {
+        let ged =
+            "0 HEAD\n0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @X9@\n0 @I1@ INDI\n1 SOUR @I1@\n0 TRLR\n";
+        let import = from_str(ged).unwrap();
+        assert_eq!(
+            import.warnings,
+            vec![
+                Warning::new(Some(4), WarningKind::UnresolvedPointer("@X9@".to_string())),
+                Warning::new(Some(6), WarningKind::UnresolvedPointer("@I1@".to_string())),
+            ]
+        );
+        assert!(import.gedcomx.relationships.is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+//! # GEDCOM
+//! Conversion between GEDCOM files and the GEDCOM X data model.
+//!
+//! GEDCOM 5.5.1 is the format most genealogical data is still exchanged in,
+//! and GEDCOM 7.0 is its successor. This crate reads either version into a
+//! [`gedcomx::Gedcomx`] document, and writes documents back out as either
+//! version. GEDCOM 7.0 files can also be packaged with the media they
+//! reference as a GEDZIP file, which can be converted to and from a GEDCOM X
+//! file along with its media.
+//! GEDCOM has structures that have no direct equivalent in GEDCOM X (and the
+//! other way around), so conversions can lose data. Anything that couldn't be
+//! converted is reported as a [`Warning`] rather than failing the whole
+//! conversion.
+//!
+//! # Examples
+//! ## Import a GEDCOM 5.5.1 file
+//!
+//! ```
+//! let ged = "0 HEAD\n1 CHAR UTF-8\n0 @I1@ INDI\n1 NAME John /Smith/\n1 BIRT\n2 DATE ABT 1850\n0 TRLR\n";
+//! let import = gedcomx_gedcom::from_str(ged).unwrap();
+//!
+//! let person = &import.gedcomx.persons[0];
+//! assert_eq!(
+//!     person.names[0].name_forms[0].full_text.as_deref(),
+//!     Some("John Smith")
+//! );
+//! assert_eq!(
+//!     person.facts[0].date.as_ref().unwrap().formal.as_ref().unwrap().to_string(),
+//!     "A+1850"
+//! );
+//! assert!(import.warnings.is_empty());
+//! ```
+//!
+//! ## Export a GEDCOM 5.5.1 file
+//!
+//! ```
+//! use gedcomx::{Gedcomx, Name, NameForm, Person};
+//!
+//! let gx = Gedcomx::builder()
+//!     .person(
+//!         Person::builder()
+//!             .id("I1")
+//!             .name(Name::builder(NameForm::builder().full_text("John Smith").build()).build())
+//!             .build(),
+//!     )
+//!     .build();
+//!
+//! let export = gedcomx_gedcom::to_string(&gx);
+//! assert!(export.gedcom.contains("0 @I1@ INDI\n1 NAME John Smith\n"));
+//! ```
+//!
+//! ## Import a GEDCOM 7.0 file with extension tags
+//!
+//! ```
+//! use gedcomx::FactType;
+//!
+//! let ged = "0 HEAD\n1 GEDC\n2 VERS 7.0\n1 SCHMA\n2 TAG _STIL http://gedcomx.org/Stillbirth\n0 @I1@ INDI\n1 _STIL\n0 TRLR\n";
+//! let import = gedcomx_gedcom::from_str(ged).unwrap();
+//!
+//! assert_eq!(import.version, gedcomx_gedcom::Version::Gedcom70);
+//! assert_eq!(import.gedcomx.persons[0].facts[0].fact_type, FactType::Stillbirth);
+//! ```
+
+#![deny(clippy::all)]
+#![deny(clippy::pedantic)]
+#![deny(clippy::nursery)]
+#![allow(clippy::module_name_repetitions)]
+#![allow(clippy::must_use_candidate)]
+#![allow(clippy::missing_const_for_fn)]
+
+use std::{collections::BTreeMap, fmt, io};
+
+use thiserror::Error;
+
+mod date;
+
+mod encoding;
+pub use encoding::Encoding;
+
+mod export;
+pub use export::{Export, to_gedcom7_string, to_gedcom7_writer, to_string, to_writer};
+
+mod gedzip;
+pub use gedzip::{from_gedx, from_gedzip, to_gedx, to_gedzip};
+
+mod import;
+pub use import::{Import, from_reader, from_slice, from_str};
+
+mod tags;
+
+mod tree;
+
+pub type Result<T> = std::result::Result<T, GedcomError>;
+
+/// Files packaged along with a document, such as photos, keyed by their path.
+///
+/// Media source descriptions refer to them by that path (as their `about`
+/// URI, or the `FILE` of an `OBJE` record), so the same resources can be
+/// written to either a GEDZIP file or a GEDCOM X file.
+pub type Resources = BTreeMap<String, Vec<u8>>;
+
+/// Versions of GEDCOM.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum Version {
+    /// GEDCOM 5.5.1, the version most programs read and write.
+    Gedcom551,
+
+    /// GEDCOM 7.0. Always UTF-8, and declares the URIs of its extension tags
+    /// in the header.
+    Gedcom70,
+}
+
+/// Errors produced by the crate.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum GedcomError {
+    /// Error while reading or writing a file.
+    #[error("io error")]
+    IoError(#[from] io::Error),
+
+    /// The file doesn't start with a `HEAD` record, so probably isn't a
+    /// GEDCOM file at all.
+    #[error("missing GEDCOM header")]
+    MissingHeader,
+
+    /// Error while zipping or unzipping a GEDZIP or GEDCOM X file.
+    #[error("zip error")]
+    ZipError(#[from] zip::result::ZipError),
+
+    /// Error while reading a GEDCOM X file.
+    #[error("gedcomx file error")]
+    GedcomxFileError(#[from] gedcomx_file::GedcomxFileError),
+
+    /// Error while writing a GEDCOM X document.
+    #[error("gedcomx error")]
+    GedcomxError(#[from] gedcomx::GedcomxError),
+
+    /// A GEDZIP or GEDCOM X file doesn't contain a document.
+    #[error("no document in file")]
+    MissingDocument,
+}
+
+/// Something that couldn't be converted exactly.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct Warning {
+    /// The 1-based line of the GEDCOM file the warning applies to, if it
+    /// applies to a single line.
+    pub line: Option<usize>,
+
+    /// What couldn't be converted.
+    pub kind: WarningKind,
+}
+
+impl Warning {
+    pub(crate) const fn new(line: Option<usize>, kind: WarningKind) -> Self {
+        Self { line, kind }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.kind),
+            None => write!(f, "{}", self.kind),
+        }
+    }
+}
+
+/// The kinds of data that can't be converted exactly.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum WarningKind {
+    /// The file declares a character set that isn't supported. It was read as
+    /// UTF-8 if possible, otherwise as Windows-1252.
+    UnknownEncoding(String),
+
+    /// Some characters couldn't be decoded or encoded, and were replaced with
+    /// U+FFFD when reading or `?` when writing.
+    InvalidCharacters,
+
+    /// A line that isn't valid GEDCOM was skipped.
+    MalformedLine(String),
+
+    /// A tag has no equivalent in the target format and was dropped, along
+    /// with any lines below it. The path is made of the tags from the record
+    /// down, such as `INDI.ASSO`.
+    UnsupportedTag(String),
+
+    /// A value has no equivalent in the target format.
+    UnsupportedValue { tag: String, value: String },
+
+    /// A pointer to a record that doesn't exist, or is the wrong kind of
+    /// record.
+    UnresolvedPointer(String),
+
+    /// A date that couldn't be parsed. Only the original text was kept.
+    UnparsedDate(String),
+
+    /// A date in a calendar other than the Gregorian calendar. Only the
+    /// original text was kept.
+    UnsupportedCalendar(String),
+
+    /// Data in a GEDCOM X document has no equivalent in GEDCOM and was
+    /// dropped. The path is to the data in the document, such as
+    /// `persons[0].facts[1].qualifiers[0]`.
+    UnsupportedData(String),
+}
+
+impl fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownEncoding(e) => write!(f, "unknown character set {e}"),
+            Self::InvalidCharacters => write!(f, "invalid characters were replaced"),
+            Self::MalformedLine(l) => write!(f, "malformed line {l:?} was skipped"),
+            Self::UnsupportedTag(t) => write!(f, "unsupported tag {t} was dropped"),
+            Self::UnsupportedValue { tag, value } => {
+                write!(f, "unsupported value {value:?} for {tag} was dropped")
+            }
+            Self::UnresolvedPointer(p) => write!(f, "pointer {p} does not resolve"),
+            Self::UnparsedDate(d) => write!(f, "date {d:?} could not be parsed"),
+            Self::UnsupportedCalendar(d) => write!(f, "date {d:?} is not a Gregorian date"),
+            Self::UnsupportedData(p) => write!(f, "unsupported {p} was dropped"),
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use std::fmt::Write;
+
+use gedcomx::{EventRoleType, FactType, NameType, Uri};
+
+/// GEDCOM event and attribute tags along with the GEDCOM X fact type they
+/// map to. When more than one tag maps to the same fact type, the first one is
+/// used for export.
+pub const FACT_TAGS: &[(&str, FactType)] = &[
+    // Individual events.
+    ("BIRT", FactType::Birth),
+    ("CHR", FactType::Christening),
+    ("DEAT", FactType::Death),
+    ("BURI", FactType::Burial),
+    ("CREM", FactType::Cremation),
+    ("ADOP", FactType::Adoption),
+    ("BAPM", FactType::Baptism),
+    ("BARM", FactType::BarMitzvah),
+    ("BASM", FactType::BatMitzvah),
+    ("BLES", FactType::Blessing),
+    ("CHRA", FactType::AdultChristening),
+    ("CONF", FactType::Confirmation),
+    ("FCOM", FactType::FirstCommunion),
+    ("ORDN", FactType::Ordination),
+    ("NATU", FactType::Naturalization),
+    ("EMIG", FactType::Emigration),
+    ("IMMI", FactType::Immigration),
+    ("CENS", FactType::Census),
+    ("PROB", FactType::Probate),
+    ("WILL", FactType::Will),
+    ("GRAD", FactType::Graduation),
+    ("RETI", FactType::Retirement),
+    // Individual attributes.
+    ("CAST", FactType::Caste),
+    ("DSCR", FactType::PhysicalDescription),
+    ("EDUC", FactType::Education),
+    ("IDNO", FactType::NationalId),
+    ("SSN", FactType::NationalId),
+    ("NATI", FactType::Nationality),
+    ("NCHI", FactType::NumberOfChildren),
+    ("NMR", FactType::NumberOfMarriages),
+    ("OCCU", FactType::Occupation),
+    ("PROP", FactType::Property),
+    ("RELI", FactType::Religion),
+    ("RESI", FactType::Residence),
+    // Family events.
+    ("ANUL", FactType::Annulment),
+    ("DIV", FactType::Divorce),
+    ("DIVF", FactType::DivorceFiling),
+    ("ENGA", FactType::Engagement),
+    ("MARB", FactType::MarriageBanns),
+    ("MARC", FactType::MarriageContract),
+    ("MARR", FactType::Marriage),
+    ("MARL", FactType::MarriageLicense),
+];
+
+/// The fact type for a GEDCOM event or attribute tag. Tags without a standard
+/// fact type map to a custom fact type.
+pub fn fact_type(tag: &str) -> FactType {
+    FACT_TAGS
+        .iter()
+        .find(|(t, _)| *t == tag)
+        .map_or_else(|| FactType::Custom(custom_uri(tag)), |(_, f)| f.clone())
+}
+
+/// Whether a tag is an attribute rather than an event. Attributes describe a
+/// state, so their date ranges are periods (`FROM .. TO ..`).
+pub fn is_attribute(tag: &str) -> bool {
+    matches!(
+        tag,
+        "CAST"
+            | "DSCR"
+            | "EDUC"
+            | "IDNO"
+            | "SSN"
+            | "NATI"
+            | "NCHI"
+            | "NMR"
+            | "OCCU"
+            | "PROP"
+            | "RELI"
+            | "RESI"
+    )
+}
+
+/// The GEDCOM tag for a fact type, if there is one. Custom fact types created
+/// from a GEDCOM tag map back to that tag.
+pub fn fact_tag(fact_type: &FactType) -> Option<String> {
+    if let Some((tag, _)) = FACT_TAGS.iter().find(|(_, f)| f == fact_type) {
+        return Some((*tag).to_string());
+    }
+
+    match fact_type {
+        FactType::Custom(uri) => custom_name(uri).filter(|n| is_tag(n)),
+        _ => None,
+    }
+}
+
+/// Whether a string can be written as a GEDCOM tag.
+pub fn is_tag(s: &str) -> bool {
+    !s.is_empty()
+        && s.len() <= 31
+        && s.chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && !s.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// The GEDCOM name type (the value of `NAME.TYPE`) for a name type.
+pub fn name_type_value(name_type: &NameType) -> Option<String> {
+    match name_type {
+        NameType::BirthName => Some("birth".to_string()),
+        NameType::MarriedName => Some("married".to_string()),
+        NameType::AlsoKnownAs => Some("aka".to_string()),
+        NameType::Custom(uri) => custom_name(uri),
+        _ => None,
+    }
+}
+
+/// The name type for a GEDCOM `NAME.TYPE` value.
+pub fn name_type(value: &str) -> NameType {
+    match value.to_lowercase().as_str() {
+        "birth" | "maiden" => NameType::BirthName,
+        "married" => NameType::MarriedName,
+        "aka" => NameType::AlsoKnownAs,
+        _ => NameType::Custom(custom_uri(value)),
+    }
+}
+
+/// GEDCOM 7.0 name types (the value of `NAME.TYPE`), other than `OTHER`.
+pub const NAME_TYPES: &[&str] = &[
+    "AKA",
+    "BIRTH",
+    "IMMIGRANT",
+    "MAIDEN",
+    "MARRIED",
+    "PROFESSIONAL",
+];
+
+/// GEDCOM 7.0 roles (the value of `ASSO.ROLE`), other than `OTHER`.
+const ROLES: &[&str] = &[
+    "CHIL",
+    "CLERGY",
+    "FATH",
+    "FRIEND",
+    "GODP",
+    "HUSB",
+    "MOTH",
+    "MULTIPLE",
+    "NGHBR",
+    "OFFICIATOR",
+    "PARENT",
+    "SPOU",
+    "WIFE",
+    "WITN",
+];
+
+/// The event role type for a GEDCOM 7.0 `ROLE` value.
+pub fn role_type(value: &str) -> EventRoleType {
+    match value.to_uppercase().as_str() {
+        "WITN" => EventRoleType::Witness,
+        "OFFICIATOR" => EventRoleType::Official,
+        "OTHER" => EventRoleType::Participant,
+        _ => EventRoleType::Custom(custom_uri(value)),
+    }
+}
+
+/// The GEDCOM 7.0 `ROLE` value for an event role type, if there is one.
+pub fn role_value(role_type: &EventRoleType) -> Option<String> {
+    match role_type {
+        EventRoleType::Witness => Some("WITN".to_string()),
+        EventRoleType::Official => Some("OFFICIATOR".to_string()),
+        EventRoleType::Participant => Some("OTHER".to_string()),
+        EventRoleType::Custom(uri) => custom_name(uri).filter(|n| ROLES.contains(&n.as_str())),
+        _ => None,
+    }
+}
+
+/// A GEDCOM 7.0 extension tag for a URI, made from the end of the URI, like
+/// `_STILLBIRTH` for `http://gedcomx.org/Stillbirth`. The header's `SCHMA`
+/// structure maps the tag back to the URI.
+pub fn extension_tag(uri: &Uri) -> String {
+    let uri = uri.to_string();
+    let name = uri
+        .trim_end_matches(['/', '#'])
+        .rsplit(['/', '#', ':'])
+        .next()
+        .unwrap_or_default();
+
+    let mut tag = String::from("_");
+    tag.extend(
+        name.chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_uppercase())
+            .take(30),
+    );
+    if tag.len() == 1 {
+        tag.push_str("EXT");
+    }
+    tag
+}
+
+/// Multimedia formats (the value of `OBJE.FILE.FORM`) along with their media
+/// type. When more than one format has the same media type, the first one is
+/// used for export.
+const MEDIA_FORMS: &[(&str, &str)] = &[
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("png", "image/png"),
+    ("gif", "image/gif"),
+    ("bmp", "image/bmp"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("pdf", "application/pdf"),
+    ("txt", "text/plain"),
+    ("wav", "audio/wav"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+];
+
+/// The media type for a multimedia format.
+pub fn media_type(form: &str) -> Option<&'static str> {
+    let form = form.to_lowercase();
+    MEDIA_FORMS
+        .iter()
+        .find(|(f, _)| *f == form)
+        .map(|(_, media_type)| *media_type)
+}
+
+/// The multimedia format for a media type.
+pub fn media_form(media_type: &str) -> Option<&'static str> {
+    MEDIA_FORMS
+        .iter()
+        .find(|(_, m)| m.eq_ignore_ascii_case(media_type))
+        .map(|(form, _)| *form)
+}
+
+/// A custom type URI made from some text, such as a GEDCOM tag or an `EVEN`
+/// type. These are `data` URIs, like `data:,Eagle%20Scout`.
+pub fn custom_uri(text: &str) -> Uri {
+    let mut uri = String::from("data:,");
+    for b in text.bytes() {
+        if b.is_ascii_alphanumeric() || b"-_.~".contains(&b) {
+            uri.push(char::from(b));
+        } else {
+            let _ = write!(uri, "%{b:02X}");
+        }
+    }
+    Uri::from(uri)
+}
+
+/// The text a custom type URI was made from, if it's a `data` URI.
+pub fn custom_name(uri: &Uri) -> Option<String> {
+    let uri = uri.to_string();
+    let encoded = uri.strip_prefix("data:,")?;
+
+    let mut bytes = vec![];
+    let mut rest = encoded.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'%'
+            && let Some(decoded) = tail
+                .get(..2)
+                .and_then(|h| std::str::from_utf8(h).ok())
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+        {
+            bytes.push(decoded);
+            rest = &tail[2..];
+        } else {
+            bytes.push(b);
+            rest = tail;
+        }
+    }
+
+    String::from_utf8(bytes).ok().filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn custom_uris() {
+        assert_eq!(custom_uri("Eagle Scout").to_string(), "data:,Eagle%20Scout");
+        assert_eq!(custom_uri("_MILT").to_string(), "data:,_MILT");
+        assert_eq!(
+            custom_uri("Ørsted's prize").to_string(),
+            "data:,%C3%98rsted%27s%20prize"
+        );
+        assert_eq!(
+            custom_name(&custom_uri("Ørsted's prize")).as_deref(),
+            Some("Ørsted's prize")
+        );
+        assert_eq!(custom_name(&"http://example.com/x".into()), None);
+    }
+
+    #[test]
+    fn fact_types() {
+        assert_eq!(fact_type("BIRT"), FactType::Birth);
+        assert_eq!(fact_type("_MILT"), FactType::Custom("data:,_MILT".into()));
+        assert_eq!(fact_type("SSN"), FactType::NationalId);
+    }
+
+    #[test]
+    fn media_forms() {
+        assert_eq!(media_type("JPEG"), Some("image/jpeg"));
+        assert_eq!(media_type("ole"), None);
+        assert_eq!(media_form("image/jpeg"), Some("jpg"));
+        assert_eq!(media_form("image/x-unknown"), None);
+    }
+
+    #[test]
+    fn fact_tags() {
+        assert_eq!(fact_tag(&FactType::NationalId).as_deref(), Some("IDNO"));
+        assert_eq!(fact_tag(&fact_type("_MILT")).as_deref(), Some("_MILT"));
+        assert_eq!(fact_tag(&FactType::Custom(custom_uri("Eagle Scout"))), None);
+        assert_eq!(fact_tag(&FactType::Stillbirth), None);
+    }
+
+    #[test]
+    fn extension_tags() {
+        assert_eq!(
+            extension_tag(&FactType::Stillbirth.to_string().into()),
+            "_STILLBIRTH"
+        );
+        assert_eq!(
+            extension_tag(&"http://example.com/types#eagle-scout".into()),
+            "_EAGLESCOUT"
+        );
+        assert_eq!(extension_tag(&"urn:example:role/".into()), "_ROLE");
+        assert_eq!(extension_tag(&"".into()), "_EXT");
+    }
+
+    #[test]
+    fn roles() {
+        assert_eq!(role_type("witn"), EventRoleType::Witness);
+        assert_eq!(role_value(&role_type("GODP")).as_deref(), Some("GODP"));
+        assert_eq!(role_value(&role_type("OTHER")).as_deref(), Some("OTHER"));
+        assert_eq!(role_value(&EventRoleType::Principal), None);
+        assert_eq!(
+            role_value(&EventRoleType::Custom(custom_uri("Best man"))),
+            None
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use crate::{Version, Warning, WarningKind};
+
+/// A GEDCOM line along with its subordinate lines.
+///
+/// `CONT` and `CONC` lines have already been folded into `value`, and the
+/// `@@` escape in values has been replaced with a single `@`. GEDCOM 7.0 only
+/// escapes an `@` at the start of a value.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Node {
+    /// The 1-based line number of the line in the file.
+    pub line: usize,
+    pub xref: Option<String>,
+    pub tag: String,
+    pub value: Option<String>,
+    pub children: Vec<Self>,
+}
+
+impl Node {
+    pub fn new<S: Into<String>>(tag: S, value: Option<String>) -> Self {
+        Self {
+            tag: tag.into(),
+            value,
+            ..Self::default()
+        }
+    }
+
+    /// The first child with the given tag.
+    pub fn child(&self, tag: &str) -> Option<&Self> {
+        self.children.iter().find(|c| c.tag == tag)
+    }
+
+    /// The value of the first child with the given tag.
+    pub fn child_value(&self, tag: &str) -> Option<&str> {
+        self.child(tag).and_then(|c| c.value.as_deref())
+    }
+
+    /// The value of this line, if it's a pointer (`@X1@`) to another record.
+    pub fn pointer(&self) -> Option<&str> {
+        let value = self.value.as_deref()?;
+        value
+            .strip_prefix('@')
+            .and_then(|v| v.strip_suffix('@'))
+            .filter(|v| !v.is_empty() && !v.contains('@') && !v.starts_with('#'))
+    }
+}
+
+/// The GEDCOM version of decoded text, from the `GEDC.VERS` line of the
+/// header. Anything that isn't GEDCOM 7 is read as GEDCOM 5.5.1.
+pub fn version(text: &str) -> Version {
+    let mut in_gedc = false;
+    for line in text.lines() {
+        let mut tokens = line.trim_start_matches('\u{FEFF}').split_whitespace();
+        match (tokens.next(), tokens.next(), tokens.next()) {
+            (Some("0"), Some(tag), _) if tag != "HEAD" => break,
+            (Some("1"), Some(tag), _) => in_gedc = tag == "GEDC",
+            (Some("2"), Some("VERS"), Some(vers)) if in_gedc => {
+                return if vers.starts_with("7.") {
+                    Version::Gedcom70
+                } else {
+                    Version::Gedcom551
+                };
+            }
+            _ => {}
+        }
+    }
+    Version::Gedcom551
+}
+
+/// Parse decoded GEDCOM text into a list of level 0 records.
+///
+/// Malformed lines are skipped and reported as warnings.
+pub fn parse(text: &str, version: Version, warnings: &mut Vec<Warning>) -> Vec<Node> {
+    // The stack holds the chain of open lines, from the level 0 record down.
+    let mut records = vec![];
+    let mut stack: Vec<Node> = vec![];
+
+    for (i, raw) in text.lines().enumerate() {
+        let line_number = i + 1;
+        let raw = raw.trim_start_matches('\u{FEFF}').trim_start();
+        if raw.trim().is_empty() {
+            continue;
+        }
+
+        let Some((level, mut node)) = parse_line(raw, version) else {
+            warnings.push(Warning::new(
+                Some(line_number),
+                WarningKind::MalformedLine(raw.to_string()),
+            ));
+            continue;
+        };
+        node.line = line_number;
+
+        if level > stack.len() {
+            warnings.push(Warning::new(
+                Some(line_number),
+                WarningKind::MalformedLine(raw.to_string()),
+            ));
+            continue;
+        }
+
+        if matches!(node.tag.as_str(), "CONT" | "CONC") && level > 0 {
+            close(&mut stack, &mut records, level);
+            let parent = stack.last_mut().expect("level > 0");
+            let value = parent.value.get_or_insert_with(String::new);
+            if node.tag == "CONT" {
+                value.push('\n');
+            }
+            value.push_str(node.value.as_deref().unwrap_or_default());
+            continue;
+        }
+
+        close(&mut stack, &mut records, level);
+        stack.push(node);
+    }
+    close(&mut stack, &mut records, 0);
+
+    records
+}
+
+// Close every open line at `level` or deeper, attaching each to its parent.
+fn close(stack: &mut Vec<Node>, records: &mut Vec<Node>, level: usize) {
+    while stack.len() > level {
+        let node = stack.pop().expect("stack is not empty");
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(node),
+            None => records.push(node),
+        }
+    }
+}
+
+fn parse_line(line: &str, version: Version) -> Option<(usize, Node)> {
+    let (level, rest) = line.split_once(' ')?;
+    let level: usize = level.parse().ok()?;
+    let rest = rest.trim_start_matches(' ');
+
+    let (xref, rest) = if rest.starts_with('@') {
+        let (xref, rest) = rest.split_once(' ')?;
+        let xref = xref.strip_prefix('@')?.strip_suffix('@')?;
+        (Some(xref.to_string()), rest.trim_start_matches(' '))
+    } else {
+        (None, rest)
+    };
+
+    let (tag, value) = match rest.split_once(' ') {
+        Some((tag, value)) => (tag, Some(value)),
+        None => (rest, None),
+    };
+    if tag.is_empty() || !tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    let value = value.map(|v| {
+        let v = v.trim_end_matches(['\r', '\n']);
+        match version {
+            Version::Gedcom551 => v.replace("@@", "@"),
+            Version::Gedcom70 if v.starts_with("@@") => v[1..].to_string(),
+            Version::Gedcom70 => v.to_string(),
+        }
+    });
+    Some((
+        level,
+        Node {
+            line: 0,
+            xref,
+            tag: tag.to_string(),
+            value,
+            children: vec![],
+        },
+    ))
+}
+
+/// Write records as GEDCOM lines, splitting multi-line values with `CONT`.
+/// GEDCOM 5.5.1 also splits long values with `CONC`, which GEDCOM 7.0 doesn't
+/// have.
+pub fn write(records: &[Node], version: Version, out: &mut String) {
+    for record in records {
+        write_node(record, 0, version, out);
+    }
+}
+
+// GEDCOM 5.5.1 limits lines to 255 characters. Values are split well before
+// that so there is room for the level, tag and xref.
+const MAX_VALUE_LENGTH: usize = 200;
+
+fn write_node(node: &Node, level: usize, version: Version, out: &mut String) {
+    let mut value_lines = node.value.as_deref().map(|v| v.split('\n'));
+    let first = value_lines.as_mut().and_then(Iterator::next);
+
+    write_line(level, node.xref.as_deref(), &node.tag, first, version, out);
+    for line in value_lines.into_iter().flatten() {
+        write_line(level + 1, None, "CONT", Some(line), version, out);
+    }
+    for child in &node.children {
+        write_node(child, level + 1, version, out);
+    }
+}
+
+// Long values are split onto CONC lines below the line. CONT lines are
+// already below the line they continue, so their CONC lines are siblings.
+fn write_line(
+    level: usize,
+    xref: Option<&str>,
+    tag: &str,
+    value: Option<&str>,
+    version: Version,
+    out: &mut String,
+) {
+    use std::fmt::Write;
+
+    let _ = write!(out, "{level}");
+    if let Some(xref) = xref {
+        let _ = write!(out, " @{xref}@");
+    }
+    let _ = write!(out, " {tag}");
+
+    let Some(value) = value.filter(|v| !v.is_empty()) else {
+        out.push('\n');
+        return;
+    };
+
+    // Pointers are written as they are, any other @ is escaped (only a
+    // leading one in GEDCOM 7.0).
+    let is_pointer = value.len() > 2
+        && value.starts_with('@')
+        && value.ends_with('@')
+        && !value[1..value.len() - 1].contains('@');
+    let escaped = match version {
+        _ if is_pointer => value.to_string(),
+        Version::Gedcom551 => value.replace('@', "@@"),
+        Version::Gedcom70 if value.starts_with('@') => format!("@{value}"),
+        Version::Gedcom70 => value.to_string(),
+    };
+    // GEDCOM 7.0 has no line length limit.
+    if version == Version::Gedcom70 {
+        let _ = writeln!(out, " {escaped}");
+        return;
+    }
+
+    let conc_level = if tag == "CONT" { level } else { level + 1 };
+    let mut chunks = split_chunks(&escaped).into_iter();
+    let _ = writeln!(out, " {}", chunks.next().unwrap_or_default());
+    for chunk in chunks {
+        let _ = writeln!(out, "{conc_level} CONC {chunk}");
+    }
+}
+
+// Split a value into chunks of at most MAX_VALUE_LENGTH characters. Splits
+// never leave a space at the start or end of a chunk, since many readers trim
+// them.
+fn split_chunks(value: &str) -> Vec<&str> {
+    let mut chunks = vec![];
+    let mut rest = value;
+    while rest.chars().count() > MAX_VALUE_LENGTH {
+        let mut split = rest
+            .char_indices()
+            .nth(MAX_VALUE_LENGTH)
+            .map_or(rest.len(), |(i, _)| i);
+        while split > 1 && (rest[..split].ends_with(' ') || rest[split..].starts_with(' ')) {
+            split = rest[..split]
+                .char_indices()
+                .next_back()
+                .map_or(0, |(i, _)| i);
+        }
+        if split <= 1 {
+            break;
+        }
+        chunks.push(&rest[..split]);
+        rest = &rest[split..];
+    }
+    chunks.push(rest);
+    chunks
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn continuation_lines() {
+        let text =
+            "0 @N1@ NOTE First line\n1 CONT second\n1 CONC  line\n1 CONT\n1 CONT a@@b\n0 TRLR";
+        let mut warnings = vec![];
+        let records = parse(text, Version::Gedcom551, &mut warnings);
+
+        assert!(warnings.is_empty());
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].xref.as_deref(), Some("N1"));
+        assert_eq!(
+            records[0].value.as_deref(),
+            Some("First line\nsecond line\n\na@b")
+        );
+        assert!(records[0].children.is_empty());
+    }
+
+    #[test]
+    fn nesting_and_pointers() {
+        let text = "0 @F1@ FAM\n1 HUSB @I1@\n1 MARR\n2 DATE 1 JAN 1900\n2 PLAC Here\n1 CHIL @I2@\n";
+        let records = parse(text, Version::Gedcom551, &mut vec![]);
+
+        let fam = &records[0];
+        assert_eq!(fam.tag, "FAM");
+        assert_eq!(fam.children.len(), 3);
+        assert_eq!(fam.child("HUSB").unwrap().pointer(), Some("I1"));
+        assert_eq!(
+            fam.child("MARR").unwrap().child_value("DATE"),
+            Some("1 JAN 1900")
+        );
+        assert_eq!(fam.children[2].line, 6);
+    }
+
+    #[test]
+    fn malformed_lines() {
+        let text = "0 HEAD\nnot a line\n3 DATE skipped\n1 CHAR UTF-8\n";
+        let mut warnings = vec![];
+        let records = parse(text, Version::Gedcom551, &mut warnings);
+
+        assert_eq!(records[0].children.len(), 1);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].line, Some(2));
+        assert_eq!(warnings[1].line, Some(3));
+    }
+
+    #[test]
+    fn write_roundtrip() {
+        let long = "word ".repeat(100).trim_end().to_string();
+        let mut note = Node::new("NOTE", Some(format!("a@b\n{long}")));
+        note.xref = Some("N1".to_string());
+        let mut out = String::new();
+        write(&[note.clone()], Version::Gedcom551, &mut out);
+
+        assert!(out.starts_with("0 @N1@ NOTE a@@b\n1 CONT word word"));
+        assert!(out.lines().all(|l| l.len() < 255));
+        assert!(out.contains("1 CONC "));
+
+        let records = parse(&out, Version::Gedcom551, &mut vec![]);
+        assert_eq!(records[0].value, note.value);
+    }
+
+    #[test]
+    fn gedcom7() {
+        let text = "0 HEAD\n1 GEDC\n2 VERS 7.0\n0 @N1@ SNOTE @@me and a@@b\n";
+        assert_eq!(version(text), Version::Gedcom70);
+        assert_eq!(
+            version("0 HEAD\n1 GEDC\n2 VERS 5.5.1\n"),
+            Version::Gedcom551
+        );
+        assert_eq!(
+            version("0 HEAD\n0 @I1@ INDI\n1 GEDC\n2 VERS 7.0\n"),
+            Version::Gedcom551
+        );
+
+        let records = parse(text, Version::Gedcom70, &mut vec![]);
+        assert_eq!(records[1].value.as_deref(), Some("@me and a@@b"));
+
+        let long = "word ".repeat(100);
+        let note = Node::new("SNOTE", Some(format!("@me\n{long}")));
+        let mut out = String::new();
+        write(std::slice::from_ref(&note), Version::Gedcom70, &mut out);
+        assert_eq!(out, format!("0 SNOTE @@me\n1 CONT {long}\n"));
+        assert_eq!(
+            parse(&out, Version::Gedcom70, &mut vec![])[0].value,
+            note.value
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use gedcomx::{
+    Date, Event, EventType, Fact, FactQualifier, FactType, Gedcomx, Gender, GenderType, Name,
+    NameForm, NamePart, NamePartType, Person, PlaceReference, Qualifier, Relationship,
+    RelationshipType, SourceCitation, SourceDescription, SourceReference, Uri,
+};
+use gedcomx_gedcom::{Encoding, WarningKind};
+use pretty_assertions::assert_eq;
+
+fn person(id: &str, given: &str, surname: &str, gender: GenderType) -> Person {
+    Person::builder()
+        .id(id)
+        .name(
+            Name::builder(
+                NameForm::builder()
+                    .full_text(format!("{given} {surname}"))
+                    .part(
+                        NamePart::builder(given)
+                            .part_type(NamePartType::Given)
+                            .build(),
+                    )
+                    .part(
+                        NamePart::builder(surname)
+                            .part_type(NamePartType::Surname)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build(),
+        )
+        .gender(Gender::from(gender))
+        .build()
+}
+
+fn test_struct() -> Gedcomx {
+    let source = SourceDescription::builder(SourceCitation::new("Parish register", None))
+        .id("S1")
+        .build();
+
+    let mut john = person("P1", "John", "Smith", GenderType::Male);
+    john.facts.push(
+        Fact::builder(FactType::Birth)
+            .date(Date::new(None::<String>, Some("A+1850".parse().unwrap())))
+            .place(PlaceReference::new(Some("Springfield"), None))
+            .source_ref(SourceReference::new(Uri::from("#S1"), None, None, vec![]))
+            .build(),
+    );
+    john.facts.push(
+        Fact::builder(FactType::Occupation)
+            .value("Farmer")
+            .date(Date::new(
+                None::<String>,
+                Some("+1870/+1880".parse().unwrap()),
+            ))
+            .build(),
+    );
+    john.facts.push(Fact::builder(FactType::Death).build());
+    john.facts.push(
+        Fact::builder(FactType::Custom(Uri::from("data:,Eagle%20Scout")))
+            .date(Date::new(Some("spring 1866"), None))
+            .build(),
+    );
+    john.facts.push(
+        Fact::builder(FactType::Immigration)
+            .date(Date::new(
+                None::<String>,
+                Some("/+1849-06".parse().unwrap()),
+            ))
+            .qualifier(Qualifier::new(FactQualifier::Transport, Some("ship")))
+            .build(),
+    );
+
+    let mary = person("P2", "Mary", "Jones", GenderType::Female);
+    let william = person("P3", "William", "Smith", GenderType::Male);
+    let ann = person("P4", "Ann", "Smith", GenderType::Female);
+
+    let mut couple = Relationship::builder(&mary, &john)
+        .unwrap()
+        .id("R1")
+        .relationship_type(RelationshipType::Couple)
+        .build();
+    couple.facts.push(
+        Fact::builder(FactType::Marriage)
+            .date(Date::new(
+                None::<String>,
+                Some("+1875/+1876".parse().unwrap()),
+            ))
+            .build(),
+    );
+
+    let mut relationships = vec![couple];
+    for parent in [&john, &mary] {
+        relationships.push(
+            Relationship::builder(parent, &william)
+                .unwrap()
+                .relationship_type(RelationshipType::ParentChild)
+                .fact(Fact::builder(FactType::AdoptiveParent).build())
+                .build(),
+        );
+    }
+    relationships.push(
+        Relationship::builder(&mary, &ann)
+            .unwrap()
+            .relationship_type(RelationshipType::ParentChild)
+            .build(),
+    );
+
+    Gedcomx::builder()
+        .persons(vec![john, mary, william, ann])
+        .relationships(relationships)
+        .source_description(source)
+        .event(Event::builder().event_type(EventType::Census).build())
+        .build()
+}
+
+#[test]
+fn export() {
+    let export = gedcomx_gedcom::to_string(&test_struct());
+
+    let expected = format!(
+        "0 HEAD
+1 SOUR gedcomx_gedcom
+2 VERS {}
+1 GEDC
+2 VERS 5.5.1
+2 FORM LINEAGE-LINKED
+1 CHAR UTF-8
+0 @P1@ INDI
+1 NAME John /Smith/
+2 GIVN John
+2 SURN Smith
+1 SEX M
+1 BIRT
+2 DATE ABT 1850
+2 PLAC Springfield
+2 SOUR @S1@
+1 OCCU Farmer
+2 DATE FROM 1870 TO 1880
+1 DEAT Y
+1 EVEN
+2 TYPE Eagle Scout
+2 DATE (spring 1866)
+1 IMMI
+2 DATE BEF JUN 1849
+1 FAMS @R1@
+0 @P2@ INDI
+1 NAME Mary /Jones/
+2 GIVN Mary
+2 SURN Jones
+1 SEX F
+1 FAMS @R1@
+1 FAMS @F1@
+0 @P3@ INDI
+1 NAME William /Smith/
+2 GIVN William
+2 SURN Smith
+1 SEX M
+1 FAMC @R1@
+2 PEDI adopted
+0 @P4@ INDI
+1 NAME Ann /Smith/
+2 GIVN Ann
+2 SURN Smith
+1 SEX F
+1 FAMC @F1@
+0 @R1@ FAM
+1 HUSB @P1@
+1 WIFE @P2@
+1 CHIL @P3@
+1 MARR
+2 DATE BET 1875 AND 1876
+0 @F1@ FAM
+1 WIFE @P2@
+1 CHIL @P4@
+0 @S1@ SOUR
+1 TITL Parish register
+0 TRLR
+",
+        env!("CARGO_PKG_VERSION")
+    );
+    assert_eq!(export.gedcom, expected);
+
+    let warnings: Vec<_> = export.warnings.into_iter().map(|w| w.kind).collect();
+    assert_eq!(
+        warnings,
+        vec![
+            WarningKind::UnsupportedData("persons[0].facts[4].qualifiers[0]".to_string()),
+            WarningKind::UnsupportedData("events[0]".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn import_roundtrip() {
+    let ged = std::fs::read("data/sample551.ged").unwrap();
+    let import = gedcomx_gedcom::from_slice(&ged).unwrap();
+    let export = gedcomx_gedcom::to_string(&import.gedcomx);
+
+    for line in [
+        "0 @I1@ INDI\n1 NAME John Quincy /Smith/ Jr.\n2 GIVN John Quincy\n2 SURN Smith\n2 NSFX Jr.\n",
+        "2 SOUR @S1@\n3 PAGE Page 12, entry 4\n2 NICK Jack\n",
+        "1 NAME John /Smyth/\n2 TYPE aka\n",
+        "1 OCCU Farmer\n2 DATE FROM 1870 TO 1880\n",
+        "1 EVEN\n2 TYPE Eagle Scout\n2 DATE 1866\n",
+        "1 _MILT Union Army\n",
+        "2 AGE 69y\n2 CAUS Pneumonia\n",
+        "1 FAMC @F1@\n2 PEDI adopted\n",
+        "1 NOTE John was known for his temper.\n2 CONT He farmed all his life.\n",
+        "1 CHAN\n2 DATE 12 JAN 2020\n3 TIME 10:20:30\n",
+        "0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n1 CHIL @I3@\n1 MARR\n2 DATE BET 1875 AND 1876\n",
+        "0 @O1@ OBJE\n1 FILE photos/john.jpg\n2 FORM jpg\n2 TITL Portrait of John\n",
+        "0 @S1@ SOUR\n1 AUTH Sangamon County Clerk\n1 TITL Sangamon County Records\n",
+        "0 @R1@ REPO\n1 NAME Sangamon County Courthouse\n1 ADDR 200 S 9th St\n2 CITY Springfield\n",
+    ] {
+        assert!(export.gedcom.contains(line), "{line}\n{}", export.gedcom);
+    }
+
+    // A second trip through GEDCOM X doesn't change anything.
+    let reimport = gedcomx_gedcom::from_str(&export.gedcom).unwrap();
+    assert_eq!(reimport.warnings, vec![]);
+    assert_eq!(gedcomx_gedcom::to_string(&reimport.gedcomx), export);
+}
+
+#[test]
+fn to_writer() {
+    let mut gx = test_struct();
+    gx.persons[0].names[0].name_forms[0].parts[0].value = "Renée".to_string();
+    gx.events.clear();
+
+    let mut bytes = vec![];
+    let warnings = gedcomx_gedcom::to_writer(&gx, &mut bytes, Encoding::Ansel).unwrap();
+    assert_eq!(warnings.len(), 1);
+
+    let import = gedcomx_gedcom::from_slice(&bytes).unwrap();
+    assert_eq!(import.encoding, Encoding::Ansel);
+    assert_eq!(
+        import.gedcomx.persons[0].names[0].name_forms[0]
+            .full_text
+            .as_deref(),
+        Some("Renée Smith")
+    );
+}
@@ -0,0 +1,290 @@
+use std::io::Cursor;
+
+use gedcomx::{
+    Date, Event, EventRole, EventRoleType, EventType, Fact, FactType, Gedcomx, Gender, GenderType,
+    Name, NameForm, NameType, Person, ResourceReference, Uri,
+};
+use gedcomx_gedcom::{Resources, Version};
+use pretty_assertions::assert_eq;
+
+fn import() -> gedcomx_gedcom::Import {
+    let file = std::fs::File::open("data/sample70.ged").unwrap();
+    gedcomx_gedcom::from_reader(file).unwrap()
+}
+
+#[test]
+fn persons() {
+    let import = import();
+    assert_eq!(import.version, Version::Gedcom70);
+    assert!(import.warnings.is_empty(), "{:?}", import.warnings);
+
+    let gx = &import.gedcomx;
+    let john = &gx.persons[0];
+    assert_eq!(john.names.len(), 3);
+    assert_eq!(john.names[0].name_forms.len(), 2);
+    assert_eq!(
+        john.names[0].name_forms[1].full_text.as_deref(),
+        Some("Джон Смит")
+    );
+    assert_eq!(
+        john.names[0].name_forms[1]
+            .lang
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "ru"
+    );
+    assert_eq!(john.names[1].name_type, Some(NameType::FormalName));
+    assert_eq!(
+        john.names[2].name_type,
+        Some(NameType::Custom(Uri::from("data:,Pen%20name")))
+    );
+
+    let fact_types: Vec<_> = john.facts.iter().map(|f| f.fact_type.clone()).collect();
+    assert_eq!(
+        fact_types,
+        vec![
+            FactType::Birth,
+            FactType::Baptism,
+            FactType::Custom(Uri::from("http://example.com/Knighthood")),
+        ]
+    );
+
+    let birth_date = john.facts[0].date.as_ref().unwrap();
+    assert_eq!(birth_date.original.as_deref(), Some("Spring of 1850"));
+    assert_eq!(birth_date.formal.as_ref().unwrap().to_string(), "+1850");
+
+    assert_eq!(
+        john.notes[0].text,
+        "A note shared between records.\nIt has two lines."
+    );
+
+    assert_eq!(
+        gx.persons[2].gender.as_ref().unwrap().gender_type,
+        GenderType::Intersex
+    );
+    assert_eq!(gx.persons[4].facts[0].fact_type, FactType::Stillbirth);
+    assert_eq!(
+        gx.persons[4].facts[0]
+            .date
+            .as_ref()
+            .unwrap()
+            .formal
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "-0043"
+    );
+}
+
+#[test]
+fn events() {
+    let gx = import().gedcomx;
+    assert_eq!(gx.events.len(), 1);
+
+    let baptism = &gx.events[0];
+    assert_eq!(baptism.event_type, Some(EventType::Baptism));
+    let roles: Vec<_> = baptism
+        .roles
+        .iter()
+        .map(|r| (r.person.resource.to_string(), r.event_role_type.clone()))
+        .collect();
+    assert_eq!(
+        roles,
+        vec![
+            ("#I1".to_string(), Some(EventRoleType::Principal)),
+            (
+                "#I3".to_string(),
+                Some(EventRoleType::Custom(Uri::from("data:,GODP")))
+            ),
+            (
+                "#I4".to_string(),
+                Some(EventRoleType::Custom(Uri::from("data:,Organist")))
+            ),
+        ]
+    );
+}
+
+#[test]
+fn media() {
+    let gx = import().gedcomx;
+    let media = gx
+        .source_descriptions
+        .iter()
+        .find(|sd| sd.id.as_ref().is_some_and(|id| id.to_string() == "O1"))
+        .unwrap();
+    assert_eq!(media.media_type.as_deref(), Some("image/jpeg"));
+    assert_eq!(media.about.as_ref().unwrap().to_string(), "media/john.jpg");
+}
+
+#[test]
+fn round_trip() {
+    let export = gedcomx_gedcom::to_gedcom7_string(&import().gedcomx);
+    assert!(export.warnings.is_empty(), "{:?}", export.warnings);
+    assert!(export.gedcom.starts_with("0 HEAD\n1 GEDC\n2 VERS 7.0\n"));
+
+    let reimport = gedcomx_gedcom::from_str(&export.gedcom).unwrap();
+    assert_eq!(reimport.version, Version::Gedcom70);
+    assert_eq!(
+        gedcomx_gedcom::to_gedcom7_string(&reimport.gedcomx).gedcom,
+        export.gedcom
+    );
+}
+
+#[test]
+fn gedcom551_dates() {
+    let export = gedcomx_gedcom::to_string(&import().gedcomx);
+    assert!(export.gedcom.contains("2 DATE 44 B.C.\n"));
+    assert!(export.gedcom.contains("2 DATE 1 JUN 1875\n"));
+    assert!(export.gedcom.contains("2 DATE INT 1850 (Spring of 1850)\n"));
+}
+
+#[test]
+fn extension_tags() {
+    let mut person = Person::builder()
+        .id("P1")
+        .name(
+            Name::builder(NameForm::builder().full_text("John Smith").build())
+                .name_type(NameType::Custom(Uri::from("http://example.com/Regnal")))
+                .build(),
+        )
+        .gender(Gender::from(GenderType::Intersex))
+        .fact(Fact::builder(FactType::Stillbirth).build())
+        .build();
+    person.facts.push(
+        Fact::builder(FactType::Custom(Uri::from("http://example.com/Knighthood")))
+            .date(Date::new(None::<String>, Some("+1890".parse().unwrap())))
+            .build(),
+    );
+    let witness = Person::builder().id("P2").build();
+
+    let mut event = Event::builder().event_type(EventType::Christening).build();
+    event.roles = vec![
+        role("#P1", EventRoleType::Principal),
+        role("#P2", EventRoleType::Witness),
+    ];
+
+    let gx = Gedcomx::builder()
+        .person(person)
+        .person(witness)
+        .event(event)
+        .build();
+    let export = gedcomx_gedcom::to_gedcom7_string(&gx);
+    assert!(export.warnings.is_empty(), "{:?}", export.warnings);
+
+    let ged = &export.gedcom;
+    assert!(ged.contains(
+        "1 SCHMA\n2 TAG _REGNAL http://example.com/Regnal\n2 TAG _STILLBIRTH http://gedcomx.org/Stillbirth\n2 TAG _KNIGHTHOOD http://example.com/Knighthood\n"
+    ));
+    assert!(ged.contains("1 NAME John Smith\n2 TYPE _REGNAL\n"));
+    assert!(ged.contains("1 SEX X\n"));
+    assert!(ged.contains("1 _KNIGHTHOOD\n2 DATE 1890\n"));
+    assert!(ged.contains("1 CHR Y\n2 ASSO @P2@\n3 ROLE WITN\n"));
+
+    let reimport = gedcomx_gedcom::from_str(ged).unwrap();
+    let fact_types: Vec<_> = reimport.gedcomx.persons[0]
+        .facts
+        .iter()
+        .map(|f| f.fact_type.clone())
+        .collect();
+    assert_eq!(
+        fact_types,
+        vec![
+            FactType::Stillbirth,
+            FactType::Custom(Uri::from("http://example.com/Knighthood")),
+            FactType::Christening,
+        ]
+    );
+    assert_eq!(
+        reimport.gedcomx.events[0].roles[1].event_role_type,
+        Some(EventRoleType::Witness)
+    );
+}
+
+#[test]
+fn family_extension_tags() {
+    let ged = "0 HEAD\n1 GEDC\n2 VERS 7.0\n1 SCHMA\n2 TAG _CIVIL http://example.com/CivilUnion\n\
+               0 @I1@ INDI\n1 FAMS @F1@\n0 @I2@ INDI\n1 FAMS @F1@\n\
+               0 @F1@ FAM\n1 HUSB @I1@\n1 WIFE @I2@\n1 _CIVIL\n2 DATE 2001\n1 _UNDECLARED\n0 TRLR\n";
+
+    let import = gedcomx_gedcom::from_str(ged).unwrap();
+    let fact_types: Vec<_> = import.gedcomx.relationships[0]
+        .facts
+        .iter()
+        .map(|f| f.fact_type.clone())
+        .collect();
+    assert_eq!(
+        fact_types,
+        vec![
+            FactType::Custom(Uri::from("http://example.com/CivilUnion")),
+            FactType::Custom(Uri::from("data:,_UNDECLARED")),
+        ]
+    );
+}
+
+fn role(person: &str, role_type: EventRoleType) -> EventRole {
+    EventRole::new(
+        None,
+        None,
+        vec![],
+        None,
+        vec![],
+        None,
+        None,
+        ResourceReference::new(Uri::from(person)),
+        Some(role_type),
+        None,
+    )
+}
+
+#[test]
+fn gedzip() {
+    let import = import();
+    let mut resources = Resources::new();
+    resources.insert("media/john.jpg".to_string(), vec![0xff, 0xd8, 0xff]);
+
+    let mut zip = Cursor::new(vec![]);
+    gedcomx_gedcom::to_gedzip(&import.gedcomx, &resources, &mut zip).unwrap();
+
+    zip.set_position(0);
+    let reimport = gedcomx_gedcom::from_gedzip(zip).unwrap();
+    assert_eq!(reimport.version, Version::Gedcom70);
+    assert_eq!(reimport.resources, resources);
+    assert_eq!(reimport.gedcomx.persons.len(), import.gedcomx.persons.len());
+}
+
+#[test]
+fn gedzip_missing_document() {
+    let mut zip = zip::ZipWriter::new(Cursor::new(vec![]));
+    zip.start_file("photo.jpg", zip::write::SimpleFileOptions::default())
+        .unwrap();
+    let zip = zip.finish().unwrap();
+
+    assert!(matches!(
+        gedcomx_gedcom::from_gedzip(zip),
+        Err(gedcomx_gedcom::GedcomError::MissingDocument)
+    ));
+}
+
+#[test]
+fn gedx() {
+    let file = std::fs::File::open("../gedcomx_file/data/sample.gedx").unwrap();
+    let (gx, resources) = gedcomx_gedcom::from_gedx(file).unwrap();
+    assert_eq!(
+        resources.keys().collect::<Vec<_>>(),
+        vec!["person1.png", "person2.png"]
+    );
+
+    let mut zip = Cursor::new(vec![]);
+    gedcomx_gedcom::to_gedzip(&gx, &resources, &mut zip).unwrap();
+    zip.set_position(0);
+    let import = gedcomx_gedcom::from_gedzip(zip).unwrap();
+    assert_eq!(import.resources, resources);
+
+    let mut gedx = Cursor::new(vec![]);
+    gedcomx_gedcom::to_gedx(&import.gedcomx, &import.resources, &mut gedx).unwrap();
+    gedx.set_position(0);
+    let (regx, reresources) = gedcomx_gedcom::from_gedx(gedx).unwrap();
+    assert_eq!(regx, import.gedcomx);
+    assert_eq!(reresources, resources);
+}
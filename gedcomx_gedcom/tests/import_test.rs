@@ -0,0 +1,200 @@
+use gedcomx::{
+    FactQualifier, FactType, GenderType, NamePartType, NameType, Qualifier, RelationshipType,
+    ResourceReference, Timestamp, Uri,
+};
+use gedcomx_gedcom::{Encoding, WarningKind};
+use pretty_assertions::assert_eq;
+
+fn import() -> gedcomx_gedcom::Import {
+    let file = std::fs::File::open("data/sample551.ged").unwrap();
+    gedcomx_gedcom::from_reader(file).unwrap()
+}
+
+#[test]
+fn persons() {
+    let import = import();
+    assert_eq!(import.encoding, Encoding::Utf8);
+
+    let gx = &import.gedcomx;
+    assert_eq!(gx.persons.len(), 3);
+
+    let john = &gx.persons[0];
+    assert_eq!(john.id.as_ref().unwrap().to_string(), "I1");
+    assert_eq!(john.gender.as_ref().unwrap().gender_type, GenderType::Male);
+    assert_eq!(
+        john.attribution.as_ref().unwrap().modified,
+        Some("2020-01-12T10:20:30".parse::<Timestamp>().unwrap())
+    );
+
+    assert_eq!(john.names.len(), 3);
+    let name = &john.names[0].name_forms[0];
+    assert_eq!(name.full_text.as_deref(), Some("John Quincy Smith Jr."));
+    assert_eq!(name.parts[1].part_type, Some(NamePartType::Surname));
+    assert_eq!(john.names[0].sources.len(), 1);
+    assert_eq!(john.names[1].name_type, Some(NameType::Nickname));
+    assert_eq!(
+        john.names[1].name_forms[0].full_text.as_deref(),
+        Some("Jack")
+    );
+    assert_eq!(john.names[2].name_type, Some(NameType::AlsoKnownAs));
+
+    let fact_types: Vec<_> = john.facts.iter().map(|f| f.fact_type.clone()).collect();
+    assert_eq!(
+        fact_types,
+        vec![
+            FactType::Birth,
+            FactType::Occupation,
+            FactType::Custom(Uri::from("data:,Eagle%20Scout")),
+            FactType::Custom(Uri::from("data:,_MILT")),
+            FactType::Death,
+        ]
+    );
+
+    let birth = &john.facts[0];
+    assert_eq!(
+        birth
+            .date
+            .as_ref()
+            .unwrap()
+            .formal
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "A+1850"
+    );
+    assert_eq!(
+        birth.place.as_ref().unwrap().original.as_deref(),
+        Some("Springfield, Sangamon, Illinois, USA")
+    );
+    assert_eq!(birth.sources[0].description, Uri::from("#S1"));
+
+    assert_eq!(john.facts[1].value.as_deref(), Some("Farmer"));
+    assert_eq!(john.facts[3].value.as_deref(), Some("Union Army"));
+    assert_eq!(
+        john.facts[4].qualifiers,
+        vec![
+            Qualifier::new(FactQualifier::Age, Some("69y")),
+            Qualifier::new(FactQualifier::Cause, Some("Pneumonia")),
+        ]
+    );
+
+    assert_eq!(
+        john.notes[0].text,
+        "John was known for his temper.\nHe farmed all his life."
+    );
+    assert_eq!(john.media[0].description, Uri::from("#O1"));
+
+    let mary = &gx.persons[1];
+    assert_eq!(mary.names[0].name_forms[0].parts[0].value, "Mary Ann");
+}
+
+#[test]
+fn relationships() {
+    let gx = import().gedcomx;
+    assert_eq!(gx.relationships.len(), 3);
+
+    let couple = &gx.relationships[0];
+    assert_eq!(couple.id.as_ref().unwrap().to_string(), "F1");
+    assert_eq!(couple.relationship_type, Some(RelationshipType::Couple));
+    assert_eq!(couple.person1, ResourceReference::from("#I1"));
+    assert_eq!(couple.person2, ResourceReference::from("#I2"));
+    assert_eq!(
+        couple.facts[0]
+            .date
+            .as_ref()
+            .unwrap()
+            .formal
+            .as_ref()
+            .unwrap()
+            .to_string(),
+        "+1875/+1876"
+    );
+
+    for (parent_child, parent) in gx.relationships[1..].iter().zip(["#I1", "#I2"]) {
+        assert_eq!(
+            parent_child.relationship_type,
+            Some(RelationshipType::ParentChild)
+        );
+        assert_eq!(parent_child.person1, ResourceReference::from(parent));
+        assert_eq!(parent_child.person2, ResourceReference::from("#I3"));
+        assert_eq!(parent_child.facts[0].fact_type, FactType::AdoptiveParent);
+    }
+}
+
+#[test]
+fn sources_and_agents() {
+    let gx = import().gedcomx;
+
+    let ids: Vec<_> = gx
+        .source_descriptions
+        .iter()
+        .map(|sd| sd.id.as_ref().unwrap().to_string())
+        .collect();
+    assert_eq!(ids, vec!["SOUR-1", "S1", "O1"]);
+
+    let page = &gx.source_descriptions[0];
+    assert_eq!(page.citations[0].value, "Page 12, entry 4");
+    assert_eq!(
+        page.component_of.as_ref().unwrap().description,
+        Uri::from("#S1")
+    );
+
+    let source = &gx.source_descriptions[1];
+    assert_eq!(
+        source.citations[0].value,
+        "Sangamon County Clerk. Sangamon County Records"
+    );
+    assert_eq!(source.repository, Some(ResourceReference::from("#R1")));
+    assert_eq!(source.notes[0].text, "Transcribed from microfilm.");
+
+    let photo = &gx.source_descriptions[2];
+    assert_eq!(photo.about, Some(Uri::from("photos/john.jpg")));
+    assert_eq!(photo.media_type.as_deref(), Some("image/jpeg"));
+
+    assert_eq!(gx.agents.len(), 2);
+    let repository = &gx.agents[1];
+    assert_eq!(repository.addresses[0].city.as_deref(), Some("Springfield"));
+    assert_eq!(
+        repository.phones,
+        vec![ResourceReference::from("tel:2175550100")]
+    );
+    assert_eq!(
+        gx.attribution.as_ref().unwrap().contributor,
+        Some(ResourceReference::from("#U1"))
+    );
+}
+
+#[test]
+fn warnings() {
+    let import = import();
+    let warnings: Vec<_> = import
+        .warnings
+        .iter()
+        .map(|w| (w.line, w.kind.clone()))
+        .collect();
+
+    assert_eq!(
+        warnings,
+        vec![
+            (
+                Some(34),
+                WarningKind::UnsupportedTag("INDI.DEAT.ADDR".to_string())
+            ),
+            (
+                Some(51),
+                WarningKind::UnsupportedTag("INDI.ASSO".to_string())
+            ),
+            (Some(78), WarningKind::UnsupportedTag("_CUSTOM".to_string())),
+        ]
+    );
+}
+
+#[test]
+fn result_is_valid() {
+    let report = import().gedcomx.validate();
+    assert!(
+        report.is_valid(),
+        "{:?}",
+        report.errors().collect::<Vec<_>>()
+    );
+}